@@ -0,0 +1,63 @@
+//! Compares the scalar, simd128 (with the `simd` feature) and simd128 + threads (with the `parallel` feature, under atomics)
+//! variants of the batch kernels.
+//!
+//! Run with `wasm-pack test --headless --chrome --release -- --bench batch`, adding the desired features and target features.
+
+use std::time::Duration;
+use rustww::{math::batch, time::now};
+use wasm_bindgen_test::{wasm_bindgen_test};
+
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+const LEN: usize = 1 << 22;
+const ITERS: u32 = 20;
+
+/// Returns the average time it takes to run `f`
+fn measure<T> (mut f: impl FnMut() -> T) -> Duration {
+    core::hint::black_box(f());
+    let start = now();
+    for _ in 0..ITERS {
+        core::hint::black_box(f());
+    }
+    return (now() - start) / ITERS
+}
+
+fn inputs () -> (Vec<f32>, Vec<f32>) {
+    let a = (0..LEN).map(|i| (i % 97) as f32 * 0.01).collect();
+    let b = (0..LEN).map(|i| (i % 89) as f32 * 0.02).collect();
+    return (a, b)
+}
+
+#[wasm_bindgen_test]
+fn bench_dot () {
+    let (a, b) = inputs();
+
+    let scalar = measure(|| a.iter().zip(&b).map(|(a, b)| a * b).sum::<f32>());
+    rustww::println!("dot/scalar: {scalar:?}");
+    let batched = measure(|| batch::dot(&a, &b));
+    rustww::println!("dot/{}: {batched:?}", if cfg!(feature = "simd") { "simd128" } else { "batch" });
+
+    #[cfg(feature = "parallel")] {
+        #[cfg(target_feature = "atomics")]
+        let _ = batch::set_pool(rustww::thread::pool::ThreadPool::new(4).unwrap());
+        let parallel = measure(|| batch::par_dot(&a, &b));
+        rustww::println!("dot/{}+threads: {parallel:?}", if cfg!(feature = "simd") { "simd128" } else { "batch" });
+    }
+}
+
+#[wasm_bindgen_test]
+fn bench_axpy () {
+    let (a, mut b) = inputs();
+
+    let scalar = measure(|| b.iter_mut().zip(&a).for_each(|(y, x)| *y += 0.5 * x));
+    rustww::println!("axpy/scalar: {scalar:?}");
+    let batched = measure(|| batch::axpy(0.5, &a, &mut b));
+    rustww::println!("axpy/{}: {batched:?}", if cfg!(feature = "simd") { "simd128" } else { "batch" });
+
+    #[cfg(feature = "parallel")] {
+        #[cfg(target_feature = "atomics")]
+        let _ = batch::set_pool(rustww::thread::pool::ThreadPool::new(4).unwrap());
+        let parallel = measure(|| batch::par_axpy(0.5, &a, &mut b));
+        rustww::println!("axpy/{}+threads: {parallel:?}", if cfg!(feature = "simd") { "simd128" } else { "batch" });
+    }
+}
@@ -0,0 +1,32 @@
+//! Compares the per-tick overhead of [`Interval::ticks`] against the closure-based [`Interval::new`].
+//! Both are bounded by the browser's timer clamping, so only the difference between them is meaningful.
+//!
+//! Run with `wasm-pack test --headless --chrome --release -- --bench time`.
+
+use std::time::Duration;
+use futures::StreamExt;
+use rustww::time::{Interval, Stopwatch};
+use wasm_bindgen_test::{wasm_bindgen_test};
+
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+const TICKS: usize = 200;
+
+#[wasm_bindgen_test]
+async fn bench_ticks_vs_closure () {
+    let watch = Stopwatch::start();
+    let count = Interval::new(Duration::ZERO, || ()).unwrap().take(TICKS).count().await;
+    let closure = watch.elapsed();
+    assert_eq!(count, TICKS);
+
+    let watch = Stopwatch::start();
+    let count = Interval::ticks(Duration::ZERO).unwrap().take(TICKS).count().await;
+    let ticks = watch.elapsed();
+    assert_eq!(count, TICKS);
+
+    rustww::println!(
+        "{TICKS} ticks: closure-based {:?} ({:?}/tick), ticks {:?} ({:?}/tick)",
+        closure, closure / TICKS as u32,
+        ticks, ticks / TICKS as u32
+    );
+}
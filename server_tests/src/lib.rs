@@ -20,7 +20,7 @@ pub fn main () {
 }
 
 #[wasm_bindgen]
-pub fn runner () -> Result<()> {
+pub fn runner () -> Result<(), JsValue> {
     test_interval()?;
     Ok(())
 }
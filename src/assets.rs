@@ -0,0 +1,345 @@
+use std::{rc::Rc, cell::{Cell, RefCell}, collections::HashMap, task::Poll};
+use futures::{Stream, StreamExt, FutureExt, TryStreamExt, future::LocalBoxFuture};
+use js_sys::Uint8Array;
+use serde::{Serialize, Deserialize};
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{Cache, CacheStorage, ResponseInit};
+use crate::{Result, Error, ErrorKind, io::{FetchClient, Response}, utils::{LocalSender, LocalReceiver, Overflow, bounded_local_channel}};
+
+/// An asset of a [`Preloader`]'s manifest
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct AssetEntry {
+    /// Url of the asset
+    pub url: String,
+    /// Expected size of the asset, in bytes. Used to report progress before the asset starts downloading.
+    #[serde(default)]
+    pub size: Option<u64>,
+    /// Assets with higher priority start downloading first
+    #[serde(default)]
+    pub priority: i32
+}
+
+impl AssetEntry {
+    /// Creates a new entry with unknown size and default priority
+    #[inline]
+    pub fn new (url: impl Into<String>) -> Self {
+        return Self { url: url.into(), size: None, priority: 0 }
+    }
+}
+
+/// Aggregate progress of a [`Preload`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Progress {
+    /// Number of assets that have finished downloading
+    pub completed: usize,
+    /// Total number of assets
+    pub total: usize,
+    /// Number of bytes downloaded
+    pub loaded_bytes: u64,
+    /// Expected number of bytes to download, from the sizes of the manifest and the `Content-Length` of the started downloads
+    pub total_bytes: u64
+}
+
+impl Progress {
+    /// Returns the progress as a fraction between `0` and `1`, by bytes if their total is known, and by assets otherwise
+    #[inline]
+    pub fn fraction (&self) -> f64 {
+        if self.total_bytes > 0 {
+            return (self.loaded_bytes as f64 / self.total_bytes as f64).min(1.)
+        } else if self.total > 0 {
+            return self.completed as f64 / self.total as f64
+        }
+        return 1.
+    }
+}
+
+/// Downloads a manifest of assets concurrently, reporting their aggregate progress.
+///
+/// Downloaded assets are kept in memory (for synchronous retrieval through [`Assets::get`]) and/or stored in the
+/// [Cache API](https://developer.mozilla.org/en-US/docs/Web/API/Cache), so they survive reloads.
+/// ```ignore
+/// let mut preload = Preloader::new()
+///     .entry(AssetEntry { url: "/music.ogg".into(), size: Some(4_000_000), priority: -1 })
+///     .entry(AssetEntry::new("/atlas.png"))
+///     .parallelism(4)
+///     .start();
+///
+/// while let Some(progress) = preload.next().await {
+///     loading_bar.set(progress?.fraction());
+/// }
+/// let atlas = preload.assets().get("/atlas.png").unwrap();
+/// ```
+pub struct Preloader {
+    entries: Vec<AssetEntry>,
+    client: FetchClient,
+    parallelism: usize,
+    memory: bool,
+    cache: Option<String>
+}
+
+impl Preloader {
+    /// Creates a new preloader without entries, that downloads up to 6 assets at a time and keeps them in memory
+    #[inline]
+    pub fn new () -> Self {
+        return Self { entries: Vec::new(), client: FetchClient::new(), parallelism: 6, memory: true, cache: None }
+    }
+
+    /// Adds an asset to the manifest
+    #[inline]
+    pub fn entry (mut self, entry: AssetEntry) -> Self {
+        self.entries.push(entry);
+        self
+    }
+
+    /// Adds the assets to the manifest
+    #[inline]
+    pub fn manifest (mut self, entries: impl IntoIterator<Item = AssetEntry>) -> Self {
+        self.entries.extend(entries);
+        self
+    }
+
+    /// Sets the client the assets are downloaded with
+    #[inline]
+    pub fn client (mut self, client: FetchClient) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// Sets the maximum number of assets downloaded at the same time
+    ///
+    /// # Panics
+    /// This method panics if `parallelism` is zero.
+    #[inline]
+    pub fn parallelism (mut self, parallelism: usize) -> Self {
+        assert!(parallelism > 0, "parallelism must be greater than zero");
+        self.parallelism = parallelism;
+        self
+    }
+
+    /// If `true` (the default), downloaded assets are kept in memory, and can be retrieved synchronously
+    #[inline]
+    pub fn in_memory (mut self, memory: bool) -> Self {
+        self.memory = memory;
+        self
+    }
+
+    /// Stores the downloaded assets in the cache with the specified name, of the Cache API
+    #[inline]
+    pub fn cache (mut self, name: impl Into<String>) -> Self {
+        self.cache = Some(name.into());
+        self
+    }
+
+    /// Starts downloading the assets, in order of priority
+    pub fn start (mut self) -> Preload {
+        self.entries.sort_by_key(|entry| core::cmp::Reverse(entry.priority));
+
+        let assets = Assets {
+            memory: Default::default(),
+            cache: self.cache.map(Rc::from)
+        };
+
+        let (send, progress) = bounded_local_channel(1, Overflow::DropOldest);
+        let state = State {
+            progress: Cell::new(Progress {
+                total: self.entries.len(),
+                total_bytes: self.entries.iter().filter_map(|x| x.size).sum(),
+                ..Default::default()
+            }),
+            send
+        };
+
+        let my_assets = assets.clone();
+        let entries = self.entries;
+        let client = self.client;
+        let memory = self.memory;
+        let parallelism = self.parallelism;
+
+        let job = async move {
+            let cache = match my_assets.cache {
+                Some(ref name) => Some(open_cache(name).await?),
+                None => None
+            };
+
+            futures::stream::iter(entries.into_iter().map(Ok))
+                .try_for_each_concurrent(parallelism, |entry| {
+                    load(&client, entry, &state, &my_assets, memory, cache.as_ref())
+                })
+                .await
+        }.boxed_local();
+
+        return Preload { job: Some(job), progress, assets, failed: false }
+    }
+}
+
+impl Default for Preloader {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+struct State {
+    progress: Cell<Progress>,
+    send: LocalSender<Progress>
+}
+
+impl State {
+    #[inline]
+    fn update (&self, f: impl FnOnce(&mut Progress)) {
+        let mut progress = self.progress.get();
+        f(&mut progress);
+        self.progress.set(progress);
+        let _ = self.send.try_send(progress);
+    }
+}
+
+async fn load (client: &FetchClient, entry: AssetEntry, state: &State, assets: &Assets, memory: bool, cache: Option<&Cache>) -> Result<()> {
+    let resp = client.get(&entry.url).await?.error_for_status().await?.into_raw();
+
+    let expected = entry.size.unwrap_or_default();
+    if let Some(len) = resp.headers().get("content-length")?.and_then(|x| x.parse::<u64>().ok()) && len != expected {
+        state.update(|x| x.total_bytes = (x.total_bytes + len).saturating_sub(expected));
+    }
+
+    let mut bytes = Vec::with_capacity(expected as usize);
+    if let Some(mut body) = Response::from(resp.clone()).body()? {
+        while let Some(chunk) = body.read_chunk().await? {
+            let len = chunk.length();
+            bytes.extend_from_slice(&chunk.to_vec());
+            state.update(|x| x.loaded_bytes += len as u64);
+        }
+    }
+    let bytes = Uint8Array::from(bytes.as_slice());
+
+    if let Some(cache) = cache {
+        let mut init = ResponseInit::new();
+        init.status(resp.status()).headers(&resp.headers());
+        let cached = web_sys::Response::new_with_opt_buffer_source_and_init(Some(&bytes), &init)?;
+        JsFuture::from(cache.put_with_str(&entry.url, &cached)).await?;
+    }
+
+    if memory {
+        assets.memory.borrow_mut().insert(entry.url, bytes);
+    }
+    state.update(|x| x.completed += 1);
+    return Ok(())
+}
+
+async fn open_cache (name: &str) -> Result<Cache> {
+    let caches = js_sys::Reflect::get(&js_sys::global(), &JsValue::from_str("caches"))?;
+    if caches.is_undefined() {
+        return Err(Error::new(ErrorKind::NotSupported, "Cache API is not supported"))
+    }
+    let cache = JsFuture::from(caches.unchecked_into::<CacheStorage>().open(name)).await?;
+    return Ok(cache.unchecked_into())
+}
+
+/// A running download of the assets of a [`Preloader`].
+///
+/// As a [`Stream`], it yields the aggregate progress of the download (skipping intermediate updates if it isn't polled often enough),
+/// ending once every asset has been downloaded, or after yielding the first error. When droped, the download is cancelled.
+pub struct Preload {
+    job: Option<LocalBoxFuture<'static, Result<()>>>,
+    progress: LocalReceiver<Progress>,
+    assets: Assets,
+    failed: bool
+}
+
+impl Preload {
+    /// Returns the assets, which are filled in as they're downloaded
+    #[inline]
+    pub fn assets (&self) -> &Assets {
+        return &self.assets
+    }
+
+    /// Waits for every asset to be downloaded, ignoring the progress
+    pub async fn finish (mut self) -> Result<Assets> {
+        if let Some(job) = self.job.take() {
+            job.await?;
+        }
+        return Ok(self.assets)
+    }
+}
+
+impl Stream for Preload {
+    type Item = Result<Progress>;
+
+    fn poll_next(mut self: std::pin::Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.failed {
+            return Poll::Ready(None)
+        }
+
+        if let Some(ref mut job) = self.job && let Poll::Ready(result) = job.poll_unpin(cx) {
+            self.job = None;
+            if let Err(e) = result {
+                self.failed = true;
+                return Poll::Ready(Some(Err(e)))
+            }
+        }
+
+        return self.progress.poll_next_unpin(cx).map(|x| x.map(Ok))
+    }
+}
+
+/// Assets downloaded by a [`Preloader`]
+#[derive(Debug, Clone)]
+pub struct Assets {
+    memory: Rc<RefCell<HashMap<String, Uint8Array>>>,
+    cache: Option<Rc<str>>
+}
+
+impl Assets {
+    /// Returns the contents of the asset, if it has been downloaded and kept in memory
+    #[inline]
+    pub fn get (&self, url: &str) -> Option<Uint8Array> {
+        return self.memory.borrow().get(url).cloned()
+    }
+
+    /// Returns a copy of the contents of the asset, if it has been downloaded and kept in memory
+    #[inline]
+    pub fn bytes (&self, url: &str) -> Option<Vec<u8>> {
+        return self.get(url).map(|x| x.to_vec())
+    }
+
+    /// Returns `true` if the asset has been downloaded and kept in memory
+    #[inline]
+    pub fn contains (&self, url: &str) -> bool {
+        return self.memory.borrow().contains_key(url)
+    }
+
+    /// Returns the number of assets kept in memory
+    #[inline]
+    pub fn len (&self) -> usize {
+        return self.memory.borrow().len()
+    }
+
+    /// Returns `true` if there are no assets kept in memory
+    #[inline]
+    pub fn is_empty (&self) -> bool {
+        return self.len() == 0
+    }
+
+    /// Returns the asset as a [`Response`], from memory or from the Cache API, if it has been downloaded
+    pub async fn response (&self, url: &str) -> Result<Option<Response>> {
+        if let Some(bytes) = self.get(url) {
+            return Response::new(200, bytes).map(Some)
+        }
+
+        if let Some(ref name) = self.cache {
+            let cached = JsFuture::from(open_cache(name).await?.match_with_str(url)).await?;
+            if !cached.is_undefined() {
+                return Ok(Some(Response::from(cached.unchecked_into::<web_sys::Response>())))
+            }
+        }
+
+        return Ok(None)
+    }
+
+    /// Removes the asset from memory
+    #[inline]
+    pub fn evict (&self, url: &str) -> Option<Uint8Array> {
+        return self.memory.borrow_mut().remove(url)
+    }
+}
@@ -0,0 +1,7 @@
+flat_mod! { jwt, store, pkce }
+
+/// Returns the current time, as seconds since the UNIX epoch
+#[inline]
+fn unix_now () -> f64 {
+    return js_sys::Date::now() / 1000.
+}
@@ -0,0 +1,193 @@
+use std::time::Duration;
+use serde::{Serialize, Deserialize, de::DeserializeOwned};
+use serde_json::{Map, Value};
+use wasm_bindgen::JsValue;
+use crate::{Result, Error, ErrorKind, crypto::{self, algorithm_params}, encoding::decode_base64_url};
+use super::unix_now;
+
+/// Header of a JSON Web Token
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Header {
+    /// Signing algorithm
+    pub alg: String,
+    /// Media type of the token
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub typ: Option<String>,
+    /// Identifier of the signing key
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kid: Option<String>
+}
+
+/// Claims of a JSON Web Token, with the registered claims alongside the custom ones
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(bound(deserialize = "T: DeserializeOwned", serialize = "T: Serialize"))]
+pub struct Claims<T = Map<String, Value>> {
+    /// Issuer
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub iss: Option<String>,
+    /// Subject
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sub: Option<String>,
+    /// Audience, either a single string or an array of strings
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub aud: Option<Value>,
+    /// Expiration time, as seconds since the UNIX epoch
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exp: Option<i64>,
+    /// Time before which the token must not be accepted, as seconds since the UNIX epoch
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nbf: Option<i64>,
+    /// Issuing time, as seconds since the UNIX epoch
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub iat: Option<i64>,
+    /// Unique identifier of the token
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jti: Option<String>,
+    /// Custom claims
+    #[serde(flatten)]
+    pub custom: T
+}
+
+/// Signature algorithm of a JSON Web Token
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Algorithm {
+    /// HMAC with SHA-256
+    HS256,
+    /// RSASSA-PKCS1-v1_5 with SHA-256
+    RS256,
+    /// ECDSA with the P-256 curve and SHA-256
+    ES256
+}
+
+impl Algorithm {
+    /// Parses the algorithm from it's name, as found in the token's header
+    #[inline]
+    pub fn from_name (name: &str) -> Option<Self> {
+        return match name {
+            "HS256" => Some(Self::HS256),
+            "RS256" => Some(Self::RS256),
+            "ES256" => Some(Self::ES256),
+            _ => None
+        }
+    }
+
+    fn import_params (self) -> Result<JsValue> {
+        return match self {
+            Self::HS256 => algorithm_params(&[("name", "HMAC"), ("hash", "SHA-256")]),
+            Self::RS256 => algorithm_params(&[("name", "RSASSA-PKCS1-v1_5"), ("hash", "SHA-256")]),
+            Self::ES256 => algorithm_params(&[("name", "ECDSA"), ("namedCurve", "P-256")])
+        }
+    }
+
+    fn verify_params (self) -> Result<JsValue> {
+        return match self {
+            Self::ES256 => algorithm_params(&[("name", "ECDSA"), ("hash", "SHA-256")]),
+            other => other.import_params()
+        }
+    }
+}
+
+/// Key used to verify the signature of a JSON Web Token
+#[derive(Debug, Clone, Copy)]
+pub enum VerifyingKey<'a> {
+    /// Shared secret, for HMAC algorithms
+    Secret (&'a [u8]),
+    /// DER-encoded public key (`SubjectPublicKeyInfo`), for RSA and ECDSA algorithms
+    Spki (&'a [u8]),
+    /// Public key in JSON Web Key format, for RSA and ECDSA algorithms
+    Jwk (&'a Value)
+}
+
+/// A decoded JSON Web Token.
+///
+/// Decoding a token doesn't verify it, so it's claims mustn't be trusted until [`verify`](Jwt::verify) succeeds.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Jwt<T = Map<String, Value>> {
+    pub header: Header,
+    pub claims: Claims<T>,
+    signing_input: String,
+    signature: Vec<u8>
+}
+
+impl<T: DeserializeOwned> Jwt<T> {
+    /// Decodes the token's header and claims, without verifying it
+    pub fn decode (token: &str) -> Result<Self> {
+        let mut parts = token.trim().split('.');
+        let (header, claims, signature) = match (parts.next(), parts.next(), parts.next(), parts.next()) {
+            (Some(header), Some(claims), Some(signature), None) => (header, claims, signature),
+            _ => return Err(Error::new(ErrorKind::Serde, "malformed JSON Web Token"))
+        };
+
+        return Ok(Self {
+            header: serde_json::from_slice(&decode_base64_url(header)?)?,
+            claims: serde_json::from_slice(&decode_base64_url(claims)?)?,
+            signing_input: format!("{header}.{claims}"),
+            signature: decode_base64_url(signature)?
+        })
+    }
+}
+
+impl<T> Jwt<T> {
+    /// Returns the signature algorithm of the token, if supported
+    #[inline]
+    pub fn algorithm (&self) -> Option<Algorithm> {
+        return Algorithm::from_name(&self.header.alg)
+    }
+
+    /// Returns `true` if the token has expired, allowing for the specified clock skew
+    #[inline]
+    pub fn is_expired (&self, leeway: Duration) -> bool {
+        return self.claims.exp.is_some_and(|exp| unix_now() >= exp as f64 + leeway.as_secs_f64())
+    }
+
+    /// Returns the time left until the token expires, if it has an expiration time.
+    /// Expired tokens return [`Duration::ZERO`]
+    #[inline]
+    pub fn expires_in (&self) -> Option<Duration> {
+        let exp = self.claims.exp?;
+        return Some(Duration::from_secs_f64(f64::max(exp as f64 - unix_now(), 0.)))
+    }
+
+    /// Checks the token's `exp` and `nbf` claims against the current time, allowing for the specified clock skew
+    pub fn validate (&self, leeway: Duration) -> Result<()> {
+        let now = unix_now();
+        let leeway = leeway.as_secs_f64();
+
+        if let Some(exp) = self.claims.exp && now >= exp as f64 + leeway {
+            return Err(Error::new(ErrorKind::Permission, "the token has expired"))
+        }
+        if let Some(nbf) = self.claims.nbf && now + leeway < nbf as f64 {
+            return Err(Error::new(ErrorKind::Permission, "the token isn't valid yet"))
+        }
+        return Ok(())
+    }
+
+    /// Verifies the token's signature with the specified key, via the Web Crypto API.
+    ///
+    /// Returns `Ok(false)` if the signature is invalid, and an error if the token's algorithm isn't supported
+    /// or doesn't match the kind of key (HMAC secrets can only verify `HS256` tokens, and public keys can't verify them).
+    pub async fn verify (&self, key: VerifyingKey<'_>) -> Result<bool> {
+        let algorithm = match self.algorithm() {
+            Some(x) => x,
+            None => return Err(Error::new(ErrorKind::NotSupported, &format!("unsupported JWT algorithm '{}'", self.header.alg)))
+        };
+
+        let (format, key) = match (algorithm, key) {
+            (Algorithm::HS256, VerifyingKey::Secret(secret)) => ("raw", js_sys::Uint8Array::from(secret).into()),
+            (Algorithm::RS256 | Algorithm::ES256, VerifyingKey::Spki(der)) => ("spki", js_sys::Uint8Array::from(der).into()),
+            (Algorithm::RS256 | Algorithm::ES256, VerifyingKey::Jwk(jwk)) => ("jwk", js_sys::JSON::parse(&jwk.to_string())?),
+            _ => return Err(Error::new(ErrorKind::Permission, "the key doesn't match the token's algorithm"))
+        };
+
+        let key = crypto::import_key(format, &key, &algorithm.import_params()?, &["verify"]).await?;
+        return crypto::verify(&algorithm.verify_params()?, &key, &self.signature, self.signing_input.as_bytes()).await
+    }
+
+    /// Verifies the token's signature and validates it's `exp` and `nbf` claims, failing if either is invalid
+    pub async fn verify_and_validate (&self, key: VerifyingKey<'_>, leeway: Duration) -> Result<()> {
+        if !self.verify(key).await? {
+            return Err(Error::new(ErrorKind::Permission, "invalid token signature"))
+        }
+        return self.validate(leeway)
+    }
+}
@@ -0,0 +1,290 @@
+use std::time::Duration;
+use serde::{Serialize, Deserialize};
+use wasm_bindgen::JsValue;
+use web_sys::UrlSearchParams;
+use crate::{Result, Error, ErrorKind, crypto::{sha256, random_bytes}, encoding::encode_base64_url, scope::window, storage::Storage, time::sleep, windowing::{self, WindowFeatures}, io::{Request, Method, FetchError}};
+use super::{Token, unix_now};
+
+/// Interval at which the login popup is checked for the authorization callback
+const POPUP_POLL: Duration = Duration::from_millis(100);
+/// Session storage key of the pending redirect flow
+const PENDING_KEY: &str = "rustww:pkce";
+
+/// How the authorization page is shown to the user
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum LoginMode {
+    /// Opens the authorization page in a popup window, with the specified features
+    Popup (WindowFeatures),
+    /// Navigates the current page to the authorization page, which redirects back when done
+    Redirect
+}
+
+impl Default for LoginMode {
+    #[inline]
+    fn default() -> Self {
+        let mut features = WindowFeatures::new();
+        features.popup(true).width(500).height(650);
+        return Self::Popup(features)
+    }
+}
+
+/// Configuration of an OAuth 2.0 authorization code flow with PKCE
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PkceConfig {
+    pub authorization_endpoint: String,
+    pub token_endpoint: String,
+    pub client_id: String,
+    /// Url the authorization server redirects to. It must have the same origin as the current page.
+    pub redirect_uri: String,
+    pub scopes: Vec<String>,
+    /// Additional parameters of the authorization url (`prompt`, `audience`, ...)
+    pub extra_params: Vec<(String, String)>,
+    pub mode: LoginMode
+}
+
+impl PkceConfig {
+    /// Creates a new configuration, without scopes, that logs in through a popup
+    #[inline]
+    pub fn new (authorization_endpoint: impl Into<String>, token_endpoint: impl Into<String>, client_id: impl Into<String>, redirect_uri: impl Into<String>) -> Self {
+        return Self {
+            authorization_endpoint: authorization_endpoint.into(),
+            token_endpoint: token_endpoint.into(),
+            client_id: client_id.into(),
+            redirect_uri: redirect_uri.into(),
+            scopes: Vec::new(),
+            extra_params: Vec::new(),
+            mode: LoginMode::default()
+        }
+    }
+
+    /// Adds a scope to the authorization request
+    #[inline]
+    pub fn scope (mut self, scope: impl Into<String>) -> Self {
+        self.scopes.push(scope.into());
+        self
+    }
+
+    /// Adds a parameter to the authorization url
+    #[inline]
+    pub fn param (mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.extra_params.push((key.into(), value.into()));
+        self
+    }
+
+    /// Sets how the authorization page is shown
+    #[inline]
+    pub fn mode (mut self, mode: LoginMode) -> Self {
+        self.mode = mode;
+        self
+    }
+}
+
+/// Successful response of a token endpoint
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenResponse {
+    pub access_token: String,
+    pub token_type: String,
+    /// Lifetime of the access token, in seconds
+    #[serde(default)]
+    pub expires_in: Option<u64>,
+    #[serde(default)]
+    pub refresh_token: Option<String>,
+    /// OpenID Connect ID token
+    #[serde(default)]
+    pub id_token: Option<String>,
+    /// Space-separated scopes granted, if they differ from the requested ones
+    #[serde(default)]
+    pub scope: Option<String>
+}
+
+impl TokenResponse {
+    /// Converts the response into a [`Token`], ready to be persisted in a [`TokenStore`](super::TokenStore)
+    pub fn into_token (self) -> Token {
+        let mut token = Token::new(self.access_token);
+        token.refresh_token = self.refresh_token;
+        token.id_token = self.id_token;
+        if let Some(expires_in) = self.expires_in {
+            token.expires_at = Some(unix_now() as i64 + expires_in as i64);
+        }
+        return token
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct Pending {
+    state: String,
+    verifier: String
+}
+
+/// Runs an OAuth 2.0 authorization code flow with PKCE, returning the tokens issued.
+///
+/// With [`LoginMode::Popup`], the flow completes within this call. With [`LoginMode::Redirect`], the first call navigates
+/// away from the page and returns `None`, and the call made after being redirected back completes the flow
+/// (removing the callback parameters from the url). Calls made without a pending flow start a new one.
+///
+/// With [`LoginMode::Popup`], it must be called from within a user gesture (like a click handler), or the browser may block the popup.
+pub async fn pkce_flow (config: &PkceConfig) -> ::core::result::Result<Option<TokenResponse>, FetchError> {
+    // browsers only allow opening popups synchronously within a user gesture, so it's opened before any await point,
+    // and navigated to the authorization page once it's url is ready
+    let popup = match config.mode {
+        LoginMode::Popup(ref features) => match windowing::open("about:blank", "_blank", features)? {
+            Some(popup) => Some(popup),
+            None => return Err(Error::new(ErrorKind::NotSupported, "the login popup isn't accessible").into())
+        },
+        LoginMode::Redirect => None
+    };
+
+    if config.mode == LoginMode::Redirect && let Some(response) = complete_redirect(config).await? {
+        return Ok(Some(response))
+    }
+
+    let (pending, url) = match start_flow(config).await {
+        Ok(flow) => flow,
+        Err(e) => {
+            if let Some(ref popup) = popup {
+                let _ = popup.close();
+            }
+            return Err(e.into())
+        }
+    };
+
+    return match popup {
+        Some(popup) => {
+            if let Err(e) = popup.raw().location().set_href(&url) {
+                let _ = popup.close();
+                return Err(e.into())
+            }
+
+            let code = loop {
+                if popup.is_closed() {
+                    return Err(FetchError::Aborted(JsValue::from_str("the login popup was closed")))
+                }
+
+                // reading the location fails while the popup is on another origin
+                if let Ok(href) = popup.raw().location().href() && href.starts_with(&config.redirect_uri) {
+                    let _ = popup.close();
+                    break callback_code(&href, &pending.state)?
+                }
+
+                sleep(POPUP_POLL)?.await;
+            };
+
+            exchange(config, &code, &pending.verifier).await.map(Some)
+        },
+
+        None => {
+            session()?.set(PENDING_KEY, &pending)?;
+            window()?.location().set_href(&url)?;
+            Ok(None)
+        }
+    }
+}
+
+/// Generates the state and verifier of a new flow, returning them alongside the url of it's authorization page
+async fn start_flow (config: &PkceConfig) -> Result<(Pending, String)> {
+    let pending = Pending { state: random_string(16)?, verifier: random_string(32)? };
+    let challenge = encode_base64_url(&sha256(pending.verifier.as_bytes()).await?)?;
+    let url = authorization_url(config, &pending.state, &challenge)?;
+    return Ok((pending, url))
+}
+
+/// Exchanges a refresh token for new tokens
+pub async fn refresh_token (token_endpoint: &str, client_id: &str, refresh_token: &str) -> ::core::result::Result<TokenResponse, FetchError> {
+    let body = UrlSearchParams::new()?;
+    body.append("grant_type", "refresh_token");
+    body.append("refresh_token", refresh_token);
+    body.append("client_id", client_id);
+    return token_request(token_endpoint, body).await
+}
+
+/// Completes a pending redirect flow, if the current page is it's callback
+async fn complete_redirect (config: &PkceConfig) -> ::core::result::Result<Option<TokenResponse>, FetchError> {
+    let session = session()?;
+    let pending = match session.get::<Pending>(PENDING_KEY)? {
+        Some(pending) => pending,
+        None => return Ok(None)
+    };
+
+    let window = window()?;
+    let href = window.location().href()?;
+    if !href.starts_with(&config.redirect_uri) {
+        return Ok(None)
+    }
+
+    session.remove(PENDING_KEY)?;
+    let code = callback_code(&href, &pending.state);
+
+    // remove the callback parameters, so they aren't reused on reload
+    let url = web_sys::Url::new(&href)?;
+    for key in ["code", "state", "error", "error_description", "session_state", "iss"] {
+        url.search_params().delete(key);
+    }
+    window.history()?.replace_state_with_url(&JsValue::NULL, "", Some(&url.href()))?;
+
+    return exchange(config, &code?, &pending.verifier).await.map(Some)
+}
+
+/// Returns the authorization code of the callback url, checking it's state
+fn callback_code (href: &str, state: &str) -> Result<String> {
+    let params = web_sys::Url::new(href)?.search_params();
+    if let Some(error) = params.get("error") {
+        let msg = match params.get("error_description") {
+            Some(description) => format!("authorization failed ({error}): {description}"),
+            None => format!("authorization failed ({error})")
+        };
+        return Err(Error::new(ErrorKind::Permission, &msg))
+    }
+
+    if params.get("state").as_deref() != Some(state) {
+        return Err(Error::new(ErrorKind::Permission, "authorization state mismatch"))
+    }
+
+    return params.get("code").ok_or_else(|| Error::new(ErrorKind::Permission, "authorization callback has no code"))
+}
+
+fn authorization_url (config: &PkceConfig, state: &str, challenge: &str) -> Result<String> {
+    let url = web_sys::Url::new(&config.authorization_endpoint)?;
+    let params = url.search_params();
+    params.append("response_type", "code");
+    params.append("client_id", &config.client_id);
+    params.append("redirect_uri", &config.redirect_uri);
+    params.append("state", state);
+    params.append("code_challenge", challenge);
+    params.append("code_challenge_method", "S256");
+    if !config.scopes.is_empty() {
+        params.append("scope", &config.scopes.join(" "));
+    }
+    for (key, value) in config.extra_params.iter() {
+        params.append(key, value);
+    }
+    return Ok(url.href())
+}
+
+async fn exchange (config: &PkceConfig, code: &str, verifier: &str) -> ::core::result::Result<TokenResponse, FetchError> {
+    let body = UrlSearchParams::new()?;
+    body.append("grant_type", "authorization_code");
+    body.append("code", code);
+    body.append("redirect_uri", &config.redirect_uri);
+    body.append("client_id", &config.client_id);
+    body.append("code_verifier", verifier);
+    return token_request(&config.token_endpoint, body).await
+}
+
+async fn token_request (token_endpoint: &str, body: UrlSearchParams) -> ::core::result::Result<TokenResponse, FetchError> {
+    let mut req = Request::new();
+    req.method(Method::Post).body(body).header("Accept", "application/json")?;
+    return req.fetch(token_endpoint).await?.error_for_status().await?.json().await
+}
+
+#[inline]
+fn session () -> Result<Storage> {
+    return Storage::session()?.ok_or_else(|| Error::new(ErrorKind::NotSupported, "session storage is not available"))
+}
+
+/// Returns `len` random bytes, encoded as URL-safe base64
+#[inline]
+fn random_string (len: usize) -> Result<String> {
+    let mut bytes = vec![0; len];
+    random_bytes(&mut bytes)?;
+    return encode_base64_url(&bytes)
+}
@@ -0,0 +1,173 @@
+use std::{time::Duration, future::Future, pin::Pin, task::{Context, Poll}};
+use futures::{FutureExt, future::LocalBoxFuture};
+use serde::{Serialize, Deserialize};
+use crate::{Result, Error, ErrorKind, storage::Storage, time::Timeout};
+use super::{Jwt, unix_now};
+
+/// Longest delay of a single refresh timeout, below the `setTimeout` limit
+const MAX_DELAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Set of tokens persisted by a [`TokenStore`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Token {
+    pub access_token: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refresh_token: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id_token: Option<String>,
+    /// Expiration time of the access token, as seconds since the UNIX epoch
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<i64>
+}
+
+impl Token {
+    /// Creates a new token, without a refresh token.
+    ///
+    /// If the access token is a JWT with an `exp` claim, it's used as the expiration time.
+    #[inline]
+    pub fn new (access_token: impl Into<String>) -> Self {
+        let access_token = access_token.into();
+        let expires_at = Jwt::<serde_json::Value>::decode(&access_token).ok().and_then(|jwt| jwt.claims.exp);
+        return Self { access_token, refresh_token: None, id_token: None, expires_at }
+    }
+
+    /// Sets the expiration time of the token as a delay from now
+    #[inline]
+    pub fn expires_in (mut self, delay: Duration) -> Self {
+        self.expires_at = Some((unix_now() + delay.as_secs_f64()) as i64);
+        self
+    }
+
+    /// Sets the refresh token
+    #[inline]
+    pub fn refresh_token (mut self, refresh_token: impl Into<String>) -> Self {
+        self.refresh_token = Some(refresh_token.into());
+        self
+    }
+
+    /// Returns `true` if the access token has expired, or will expire within the specified margin
+    #[inline]
+    pub fn is_expired (&self, margin: Duration) -> bool {
+        return self.expires_at.is_some_and(|exp| unix_now() + margin.as_secs_f64() >= exp as f64)
+    }
+}
+
+/// Persists a [`Token`] in a [`Storage`], refreshing it ahead of it's expiration
+#[derive(Clone)]
+pub struct TokenStore {
+    storage: Storage,
+    key: String,
+    ahead: Duration
+}
+
+impl TokenStore {
+    /// Creates a new store that persists it's token under the specified key of the storage
+    #[inline]
+    pub fn new (storage: Storage, key: impl Into<String>) -> Self {
+        return Self { storage, key: key.into(), ahead: Duration::from_secs(60) }
+    }
+
+    /// Creates a new store over local storage
+    #[inline]
+    pub fn local (key: impl Into<String>) -> Result<Self> {
+        return match Storage::local()? {
+            Some(storage) => Ok(Self::new(storage, key)),
+            None => Err(Error::new(ErrorKind::NotSupported, "local storage is not available"))
+        }
+    }
+
+    /// Sets how long before the token's expiration it's refreshed. By default, tokens are refreshed a minute ahead.
+    #[inline]
+    pub fn refresh_ahead (mut self, ahead: Duration) -> Self {
+        self.ahead = ahead;
+        self
+    }
+
+    /// Returns the stored token, if any
+    #[inline]
+    pub fn get (&self) -> Result<Option<Token>> {
+        return self.storage.get(&self.key)
+    }
+
+    /// Returns the stored token if it isn't about to expire
+    #[inline]
+    pub fn get_valid (&self) -> Result<Option<Token>> {
+        return Ok(self.get()?.filter(|token| !token.is_expired(self.ahead)))
+    }
+
+    /// Stores the token, replacing the previous one
+    #[inline]
+    pub fn set (&self, token: &Token) -> Result<()> {
+        return self.storage.set(&self.key, token)
+    }
+
+    /// Removes the stored token
+    #[inline]
+    pub fn clear (&self) -> Result<()> {
+        return self.storage.remove(&self.key)
+    }
+
+    /// Returns a future that refreshes the stored token ahead of it's expiration, storing the refreshed token.
+    ///
+    /// The future completes when there's no token to refresh (it's missing, has no expiration time or no refresh token),
+    /// and fails if the refresh fails. It may be spawned with [`spawn_local`](wasm_bindgen_futures::spawn_local),
+    /// and dropping it stops the refreshes.
+    pub fn auto_refresh<F, Fut> (&self, mut refresh: F) -> AutoRefresh where
+        F: 'static + FnMut(Token) -> Fut,
+        Fut: 'static + Future<Output = Result<Token>>
+    {
+        let this = self.clone();
+        let inner = async move {
+            loop {
+                let token = match this.get()? {
+                    Some(token) if token.refresh_token.is_some() => token,
+                    _ => return Ok(())
+                };
+
+                let expires_at = match token.expires_at {
+                    Some(x) => x as f64,
+                    None => return Ok(())
+                };
+
+                let delay = f64::max(expires_at - this.ahead.as_secs_f64() - unix_now(), 0.);
+                let delay = Duration::from_secs_f64(delay);
+                if delay > MAX_DELAY {
+                    Timeout::new(MAX_DELAY, || ())?.await;
+                    continue
+                }
+
+                Timeout::new(delay, || ())?.await;
+                // the token may have been replaced while waiting
+                if this.get()?.as_ref() != Some(&token) {
+                    continue
+                }
+
+                let mut refreshed = refresh(token.clone()).await?;
+                if refreshed.refresh_token.is_none() {
+                    refreshed.refresh_token = token.refresh_token;
+                }
+                this.set(&refreshed)?;
+
+                if refreshed.is_expired(this.ahead) {
+                    return Err(Error::new(ErrorKind::Io, "the refreshed token expires within the refresh-ahead margin"))
+                }
+            }
+        };
+
+        return AutoRefresh { inner: inner.boxed_local() }
+    }
+}
+
+/// Future returned by [`TokenStore::auto_refresh`]
+pub struct AutoRefresh {
+    inner: LocalBoxFuture<'static, Result<()>>
+}
+
+impl Future for AutoRefresh {
+    type Output = Result<()>;
+
+    #[inline]
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.inner.poll_unpin(cx)
+    }
+}
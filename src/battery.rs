@@ -1,19 +1,28 @@
 use std::time::Duration;
 use futures::Future;
-use wasm_bindgen::{prelude::wasm_bindgen, JsValue, JsCast};
+use wasm_bindgen::{JsValue, JsCast};
 use web_sys::BatteryManager;
-use crate::scope::window;
-use crate::{Result};
+use crate::scope::{navigator, is_worker};
+use crate::{Result, Error, ErrorKind};
 use wasm_bindgen::closure::Closure;
 use std::task::*;
 use futures::Stream;
 use futures::StreamExt;
 use crate::utils::*;
 
-#[wasm_bindgen]
-extern {
-    #[wasm_bindgen(catch, js_namespace = navigator, js_name = getBattery)]
-    async fn get_battery (this: &web_sys::Navigator) -> Result<JsValue>;
+/// Calls `getBattery` on the navigator of the current global scope, if available
+async fn get_battery () -> Result<JsValue> {
+    let nav = navigator();
+    let get_battery = js_sys::Reflect::get(&nav, &JsValue::from_str("getBattery"))?;
+    if get_battery.is_undefined() {
+        return Err(Error::new(ErrorKind::NotSupported, match is_worker() {
+            true => "Battery Status API is not supported inside web workers",
+            false => "Battery Status API is not supported"
+        }))
+    }
+
+    let promise = get_battery.dyn_into::<js_sys::Function>().map_err(Error::from)?.call0(&nav)?;
+    return Ok(wasm_bindgen_futures::JsFuture::from(js_sys::Promise::from(promise)).await?)
 }
 
 /// Amount of battery remaining until the battery hits a battery stage fully
@@ -28,14 +37,13 @@ pub enum BatteryTime {
 /// Dynamic battery information.
 #[derive(Clone)]
 pub struct Battery {
-    inner: web_sys::BatteryManager
+    pub(crate) inner: web_sys::BatteryManager
 }
 
 impl Battery {
     /// Creates a new battery information watcher
     pub async fn new () -> Result<Self> {
-        let nav = window()?.navigator();
-        let value = get_battery(&nav).await?;
+        let value = get_battery().await?;
 
         let inner: web_sys::BatteryManager;
         cfg_if::cfg_if! {
@@ -90,6 +98,7 @@ macro_rules! impl_watch {
         impl Battery {
             $(
                 #[doc = concat!("Returns a watcher of the battery's `", stringify!($name), "`.\nEvery time this value is updated, the watcher will be notified.")]
+                #[doc = "\nDevices without a battery never produce any item, which can be detected with [`first_event_timeout`](crate::time::first_event_timeout)."]
                 pub fn $fn (&self) -> Result<$watch> {
                     let (send, recv) = local_channel();
                     let resolve = Closure::<dyn FnMut(web_sys::Event)>::new(move |evt: web_sys::Event| {
@@ -162,6 +171,8 @@ impl_watch! {
 impl Battery {
     /// Returns a watcher of the battery's `battery_time`.
     /// Every time this value is updated, the watcher will be notified.
+    /// 
+    /// Devices without a battery never produce any item, which can be detected with [`first_event_timeout`](crate::time::first_event_timeout).
     pub fn watch_battery_time (&self) -> Result<BatteryTimeWatcher> {
         let (send, recv) = local_channel();
 
@@ -267,8 +278,7 @@ impl Snapshot {
     /// Creates a new snapshot of the current battery information
     #[inline]
     pub async fn new () -> Result<Self> {
-        let nav = window()?.navigator();
-        let value = get_battery(&nav).await?;
+        let value = get_battery().await?;
 
         let manager: web_sys::BatteryManager;
         cfg_if::cfg_if! {
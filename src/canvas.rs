@@ -0,0 +1,457 @@
+use std::fmt::Display;
+use docfg::docfg;
+use js_sys::{Promise, Uint8Array};
+use wasm_bindgen::{JsCast, JsValue, prelude::wasm_bindgen};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{OffscreenCanvas, HtmlCanvasElement, Blob};
+use crate::{Result, Error, ErrorKind, math::{Vec2d, Mat3f}};
+#[cfg(target_feature = "atomics")]
+use crate::utils::Transfer;
+
+#[wasm_bindgen]
+extern "C" {
+    /// Methods shared by `CanvasRenderingContext2D` and `OffscreenCanvasRenderingContext2D`
+    #[derive(Debug, Clone)]
+    type RawContext2d;
+
+    #[wasm_bindgen(structural, method, js_name = beginPath)]
+    fn begin_path (this: &RawContext2d);
+    #[wasm_bindgen(structural, method, js_name = closePath)]
+    fn close_path (this: &RawContext2d);
+    #[wasm_bindgen(structural, method, js_name = moveTo)]
+    fn move_to (this: &RawContext2d, x: f64, y: f64);
+    #[wasm_bindgen(structural, method, js_name = lineTo)]
+    fn line_to (this: &RawContext2d, x: f64, y: f64);
+    #[wasm_bindgen(structural, method, js_name = quadraticCurveTo)]
+    fn quadratic_curve_to (this: &RawContext2d, cpx: f64, cpy: f64, x: f64, y: f64);
+    #[wasm_bindgen(structural, method, js_name = bezierCurveTo)]
+    fn bezier_curve_to (this: &RawContext2d, cp1x: f64, cp1y: f64, cp2x: f64, cp2y: f64, x: f64, y: f64);
+    #[wasm_bindgen(structural, method, catch)]
+    fn arc (this: &RawContext2d, x: f64, y: f64, radius: f64, start: f64, end: f64) -> ::core::result::Result<(), JsValue>;
+    #[wasm_bindgen(structural, method)]
+    fn rect (this: &RawContext2d, x: f64, y: f64, w: f64, h: f64);
+    #[wasm_bindgen(structural, method)]
+    fn fill (this: &RawContext2d);
+    #[wasm_bindgen(structural, method)]
+    fn stroke (this: &RawContext2d);
+    #[wasm_bindgen(structural, method)]
+    fn clip (this: &RawContext2d);
+
+    #[wasm_bindgen(structural, method, js_name = fillRect)]
+    fn fill_rect (this: &RawContext2d, x: f64, y: f64, w: f64, h: f64);
+    #[wasm_bindgen(structural, method, js_name = strokeRect)]
+    fn stroke_rect (this: &RawContext2d, x: f64, y: f64, w: f64, h: f64);
+    #[wasm_bindgen(structural, method, js_name = clearRect)]
+    fn clear_rect (this: &RawContext2d, x: f64, y: f64, w: f64, h: f64);
+
+    #[wasm_bindgen(structural, method, setter, js_name = fillStyle)]
+    fn set_fill_style (this: &RawContext2d, style: &str);
+    #[wasm_bindgen(structural, method, setter, js_name = strokeStyle)]
+    fn set_stroke_style (this: &RawContext2d, style: &str);
+    #[wasm_bindgen(structural, method, setter, js_name = lineWidth)]
+    fn set_line_width (this: &RawContext2d, width: f64);
+    #[wasm_bindgen(structural, method, setter, js_name = globalAlpha)]
+    fn set_global_alpha (this: &RawContext2d, alpha: f64);
+
+    #[wasm_bindgen(structural, method, setter)]
+    fn set_font (this: &RawContext2d, font: &str);
+    #[wasm_bindgen(structural, method, catch, js_name = fillText)]
+    fn fill_text (this: &RawContext2d, text: &str, x: f64, y: f64) -> ::core::result::Result<(), JsValue>;
+    #[wasm_bindgen(structural, method, catch, js_name = strokeText)]
+    fn stroke_text (this: &RawContext2d, text: &str, x: f64, y: f64) -> ::core::result::Result<(), JsValue>;
+    #[wasm_bindgen(structural, method, catch, js_name = measureText)]
+    fn measure_text (this: &RawContext2d, text: &str) -> ::core::result::Result<js_sys::Object, JsValue>;
+
+    #[wasm_bindgen(structural, method)]
+    fn save (this: &RawContext2d);
+    #[wasm_bindgen(structural, method)]
+    fn restore (this: &RawContext2d);
+    #[wasm_bindgen(structural, method, catch, js_name = setTransform)]
+    fn set_transform (this: &RawContext2d, a: f64, b: f64, c: f64, d: f64, e: f64, f: f64) -> ::core::result::Result<(), JsValue>;
+    #[wasm_bindgen(structural, method, catch)]
+    fn transform (this: &RawContext2d, a: f64, b: f64, c: f64, d: f64, e: f64, f: f64) -> ::core::result::Result<(), JsValue>;
+    #[wasm_bindgen(structural, method, catch, js_name = resetTransform)]
+    fn reset_transform (this: &RawContext2d) -> ::core::result::Result<(), JsValue>;
+
+    #[wasm_bindgen(structural, method, catch, js_name = drawImage)]
+    fn draw_image (this: &RawContext2d, image: &JsValue, x: f64, y: f64) -> ::core::result::Result<(), JsValue>;
+}
+
+/// A color with 8-bit RGB channels and a floating point alpha channel
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    /// Alpha channel, between `0` (transparent) and `1` (opaque)
+    pub a: f32
+}
+
+impl Color {
+    pub const BLACK: Self = Self::rgb(0, 0, 0);
+    pub const WHITE: Self = Self::rgb(255, 255, 255);
+    pub const TRANSPARENT: Self = Self::rgba(0, 0, 0, 0.);
+
+    /// Creates a new opaque color
+    #[inline]
+    pub const fn rgb (r: u8, g: u8, b: u8) -> Self {
+        return Self { r, g, b, a: 1. }
+    }
+
+    /// Creates a new color
+    #[inline]
+    pub const fn rgba (r: u8, g: u8, b: u8, a: f32) -> Self {
+        return Self { r, g, b, a }
+    }
+}
+
+impl Display for Color {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "rgba({}, {}, {}, {})", self.r, self.g, self.b, self.a)
+    }
+}
+
+#[derive(Debug, Clone)]
+enum CanvasInner {
+    Offscreen (OffscreenCanvas),
+    Element (HtmlCanvasElement)
+}
+
+/// A canvas, either an [`OffscreenCanvas`] or an [`HtmlCanvasElement`]
+#[derive(Debug, Clone)]
+pub struct Canvas {
+    inner: CanvasInner
+}
+
+impl Canvas {
+    /// Creates a new [`OffscreenCanvas`] with the specified size
+    #[inline]
+    pub fn offscreen (width: u32, height: u32) -> Result<Self> {
+        return Ok(Self { inner: CanvasInner::Offscreen(OffscreenCanvas::new(width, height)?) })
+    }
+
+    /// Returns the width of the canvas, in pixels
+    #[inline]
+    pub fn width (&self) -> u32 {
+        return match self.inner {
+            CanvasInner::Offscreen(ref canvas) => canvas.width(),
+            CanvasInner::Element(ref canvas) => canvas.width()
+        }
+    }
+
+    /// Returns the height of the canvas, in pixels
+    #[inline]
+    pub fn height (&self) -> u32 {
+        return match self.inner {
+            CanvasInner::Offscreen(ref canvas) => canvas.height(),
+            CanvasInner::Element(ref canvas) => canvas.height()
+        }
+    }
+
+    /// Resizes the canvas, clearing it's contents
+    #[inline]
+    pub fn set_size (&self, width: u32, height: u32) {
+        match self.inner {
+            CanvasInner::Offscreen(ref canvas) => {
+                canvas.set_width(width);
+                canvas.set_height(height);
+            },
+            CanvasInner::Element(ref canvas) => {
+                canvas.set_width(width);
+                canvas.set_height(height);
+            }
+        }
+    }
+
+    /// Returns the canvas' 2D rendering context
+    pub fn context_2d (&self) -> Result<Context2d> {
+        return match self.get_context("2d")? {
+            Some(context) => Ok(Context2d { inner: context.unchecked_into() }),
+            None => Err(Error::new(ErrorKind::NotSupported, "2D context not supported"))
+        }
+    }
+
+    #[inline]
+    pub(crate) fn get_context (&self, id: &str) -> Result<Option<js_sys::Object>> {
+        return Ok(match self.inner {
+            CanvasInner::Offscreen(ref canvas) => canvas.get_context(id)?,
+            CanvasInner::Element(ref canvas) => canvas.get_context(id)?
+        })
+    }
+
+    /// Encodes the canvas' contents as a PNG image
+    pub async fn to_blob (&self) -> Result<Blob> {
+        let promise = match self.inner {
+            CanvasInner::Offscreen(ref canvas) => canvas.convert_to_blob()?,
+            CanvasInner::Element(ref canvas) => {
+                let mut result = Ok(());
+                let promise = Promise::new(&mut |resolve, _| result = canvas.to_blob(&resolve));
+                result?;
+                promise
+            }
+        };
+
+        let blob = JsFuture::from(promise).await?;
+        if blob.is_null() {
+            return Err(Error::new(ErrorKind::Io, "the canvas couldn't be encoded"))
+        }
+        return blob.dyn_into().map_err(Into::into)
+    }
+
+    /// Encodes the canvas' contents as PNG image bytes
+    #[inline]
+    pub async fn to_bytes (&self) -> Result<Vec<u8>> {
+        let buffer = JsFuture::from(self.to_blob().await?.array_buffer()).await?;
+        return Ok(Uint8Array::new(&buffer).to_vec())
+    }
+
+    /// Returns the underlying canvas
+    #[inline]
+    pub fn as_js (&self) -> &JsValue {
+        return match self.inner {
+            CanvasInner::Offscreen(ref canvas) => canvas.as_ref(),
+            CanvasInner::Element(ref canvas) => canvas.as_ref()
+        }
+    }
+
+    /// Transfers the canvas to another context (typically a worker), through a `postMessage` call on `target`.
+    ///
+    /// Canvas elements transfer their control to an [`OffscreenCanvas`], so they can be drawn from the other context.
+    #[docfg(target_feature = "atomics")]
+    pub fn transfer_to (self, target: &js_sys::Object) -> Result<()> {
+        let canvas = match self.inner {
+            CanvasInner::Offscreen(canvas) => canvas,
+            CanvasInner::Element(canvas) => canvas.transfer_control_to_offscreen()?
+        };
+
+        return Transfer::object(canvas).post(target)
+    }
+}
+
+impl From<OffscreenCanvas> for Canvas {
+    #[inline]
+    fn from(value: OffscreenCanvas) -> Self {
+        Self { inner: CanvasInner::Offscreen(value) }
+    }
+}
+
+impl From<HtmlCanvasElement> for Canvas {
+    #[inline]
+    fn from(value: HtmlCanvasElement) -> Self {
+        Self { inner: CanvasInner::Element(value) }
+    }
+}
+
+/// A typed wrapper arround a canvas' 2D rendering context
+#[derive(Debug, Clone)]
+pub struct Context2d {
+    inner: RawContext2d
+}
+
+impl Context2d {
+    /// Starts a new path
+    #[inline]
+    pub fn begin_path (&self) -> &Self {
+        self.inner.begin_path();
+        self
+    }
+
+    /// Adds a straight line from the current point to the start of the current sub-path
+    #[inline]
+    pub fn close_path (&self) -> &Self {
+        self.inner.close_path();
+        self
+    }
+
+    /// Begins a new sub-path at the specified point
+    #[inline]
+    pub fn move_to (&self, p: Vec2d) -> &Self {
+        self.inner.move_to(p.x(), p.y());
+        self
+    }
+
+    /// Adds a straight line to the current sub-path
+    #[inline]
+    pub fn line_to (&self, p: Vec2d) -> &Self {
+        self.inner.line_to(p.x(), p.y());
+        self
+    }
+
+    /// Adds a quadratic Bézier curve to the current sub-path
+    #[inline]
+    pub fn quadratic_curve_to (&self, cp: Vec2d, p: Vec2d) -> &Self {
+        self.inner.quadratic_curve_to(cp.x(), cp.y(), p.x(), p.y());
+        self
+    }
+
+    /// Adds a cubic Bézier curve to the current sub-path
+    #[inline]
+    pub fn bezier_curve_to (&self, cp1: Vec2d, cp2: Vec2d, p: Vec2d) -> &Self {
+        self.inner.bezier_curve_to(cp1.x(), cp1.y(), cp2.x(), cp2.y(), p.x(), p.y());
+        self
+    }
+
+    /// Adds a circular arc to the current sub-path, with the angles in radians
+    #[inline]
+    pub fn arc (&self, center: Vec2d, radius: f64, start: f64, end: f64) -> Result<&Self> {
+        self.inner.arc(center.x(), center.y(), radius, start, end)?;
+        return Ok(self)
+    }
+
+    /// Adds a rectangle to the current path
+    #[inline]
+    pub fn rect (&self, origin: Vec2d, size: Vec2d) -> &Self {
+        self.inner.rect(origin.x(), origin.y(), size.x(), size.y());
+        self
+    }
+
+    /// Fills the current path with the fill color
+    #[inline]
+    pub fn fill (&self) -> &Self {
+        self.inner.fill();
+        self
+    }
+
+    /// Strokes the current path with the stroke color
+    #[inline]
+    pub fn stroke (&self) -> &Self {
+        self.inner.stroke();
+        self
+    }
+
+    /// Turns the current path into the clipping region
+    #[inline]
+    pub fn clip (&self) -> &Self {
+        self.inner.clip();
+        self
+    }
+
+    /// Fills the rectangle with the fill color
+    #[inline]
+    pub fn fill_rect (&self, origin: Vec2d, size: Vec2d) -> &Self {
+        self.inner.fill_rect(origin.x(), origin.y(), size.x(), size.y());
+        self
+    }
+
+    /// Strokes the rectangle with the stroke color
+    #[inline]
+    pub fn stroke_rect (&self, origin: Vec2d, size: Vec2d) -> &Self {
+        self.inner.stroke_rect(origin.x(), origin.y(), size.x(), size.y());
+        self
+    }
+
+    /// Makes the pixels inside the rectangle transparent
+    #[inline]
+    pub fn clear_rect (&self, origin: Vec2d, size: Vec2d) -> &Self {
+        self.inner.clear_rect(origin.x(), origin.y(), size.x(), size.y());
+        self
+    }
+
+    /// Sets the color used to fill shapes
+    #[inline]
+    pub fn set_fill_color (&self, color: Color) -> &Self {
+        self.inner.set_fill_style(&color.to_string());
+        self
+    }
+
+    /// Sets the color used to stroke shapes
+    #[inline]
+    pub fn set_stroke_color (&self, color: Color) -> &Self {
+        self.inner.set_stroke_style(&color.to_string());
+        self
+    }
+
+    /// Sets the width of stroked lines
+    #[inline]
+    pub fn set_line_width (&self, width: f64) -> &Self {
+        self.inner.set_line_width(width);
+        self
+    }
+
+    /// Sets the alpha value applied to every drawing
+    #[inline]
+    pub fn set_global_alpha (&self, alpha: f64) -> &Self {
+        self.inner.set_global_alpha(alpha);
+        self
+    }
+
+    /// Sets the font used to draw text, as a CSS font (`"16px sans-serif"`, ...)
+    #[inline]
+    pub fn set_font (&self, font: &str) -> &Self {
+        self.inner.set_font(font);
+        self
+    }
+
+    /// Fills the text at the specified position
+    #[inline]
+    pub fn fill_text (&self, text: &str, p: Vec2d) -> Result<&Self> {
+        self.inner.fill_text(text, p.x(), p.y())?;
+        return Ok(self)
+    }
+
+    /// Strokes the text at the specified position
+    #[inline]
+    pub fn stroke_text (&self, text: &str, p: Vec2d) -> Result<&Self> {
+        self.inner.stroke_text(text, p.x(), p.y())?;
+        return Ok(self)
+    }
+
+    /// Returns the width the text would have if drawn with the current font
+    pub fn measure_text (&self, text: &str) -> Result<f64> {
+        let metrics = self.inner.measure_text(text)?;
+        let width = js_sys::Reflect::get(&metrics, &JsValue::from_str("width"))?;
+        return Ok(width.as_f64().unwrap_or_default())
+    }
+
+    /// Saves the current drawing state into a stack
+    #[inline]
+    pub fn save (&self) -> &Self {
+        self.inner.save();
+        self
+    }
+
+    /// Restores the last drawing state saved with [`save`](Context2d::save)
+    #[inline]
+    pub fn restore (&self) -> &Self {
+        self.inner.restore();
+        self
+    }
+
+    /// Replaces the current transformation with the specified 2D affine transformation
+    #[inline]
+    pub fn set_transform (&self, m: Mat3f) -> Result<&Self> {
+        let [a, b, c, d, e, f] = affine(m);
+        self.inner.set_transform(a, b, c, d, e, f)?;
+        return Ok(self)
+    }
+
+    /// Multiplies the current transformation by the specified 2D affine transformation
+    #[inline]
+    pub fn transform (&self, m: Mat3f) -> Result<&Self> {
+        let [a, b, c, d, e, f] = affine(m);
+        self.inner.transform(a, b, c, d, e, f)?;
+        return Ok(self)
+    }
+
+    /// Resets the current transformation to the identity
+    #[inline]
+    pub fn reset_transform (&self) -> Result<&Self> {
+        self.inner.reset_transform()?;
+        return Ok(self)
+    }
+
+    /// Draws an image (a [`ImageBitmap`](web_sys::ImageBitmap), another canvas, ...) at the specified position
+    #[inline]
+    pub fn draw_image (&self, image: &JsValue, p: Vec2d) -> Result<&Self> {
+        self.inner.draw_image(image, p.x(), p.y())?;
+        return Ok(self)
+    }
+}
+
+/// Extracts the `[a, b, c, d, e, f]` components of an affine transformation
+#[inline]
+fn affine (m: Mat3f) -> [f64; 6] {
+    let [c0, c1, c2] = m.to_cols_array();
+    return [c0[0] as f64, c0[1] as f64, c1[0] as f64, c1[1] as f64, c2[0] as f64, c2[1] as f64]
+}
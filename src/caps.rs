@@ -0,0 +1,127 @@
+use wasm_bindgen::JsValue;
+use crate::scope::navigator;
+
+/// Minimal module using a SIMD instruction (`i8x16.splat` + `i8x16.popcnt`)
+const SIMD_PROBE: &[u8] = &[0, 97, 115, 109, 1, 0, 0, 0, 1, 5, 1, 96, 0, 1, 123, 3, 2, 1, 0, 10, 10, 1, 8, 0, 65, 0, 253, 15, 253, 98, 11];
+/// Minimal module using an atomic instruction (`i32.atomic.load`) on a shared memory
+const ATOMICS_PROBE: &[u8] = &[0, 97, 115, 109, 1, 0, 0, 0, 1, 4, 1, 96, 0, 0, 3, 2, 1, 0, 5, 4, 1, 3, 1, 1, 10, 11, 1, 9, 0, 65, 0, 254, 16, 2, 0, 26, 11];
+
+#[inline]
+fn has (target: &JsValue, key: &str) -> bool {
+    return js_sys::Reflect::get(target, &JsValue::from_str(key))
+        .map(|x| !x.is_undefined() && !x.is_null())
+        .unwrap_or_default()
+}
+
+#[inline]
+fn validate (module: &[u8]) -> bool {
+    let bytes = js_sys::Uint8Array::from(module);
+    return js_sys::WebAssembly::validate(&bytes).unwrap_or_default()
+}
+
+/// Returns `true` if the runtime supports WebAssembly SIMD instructions
+#[inline]
+pub fn has_simd () -> bool {
+    return validate(SIMD_PROBE)
+}
+
+/// Returns `true` if the runtime supports WebAssembly atomics and shared memory can be created
+/// (which requires the page to be [cross-origin isolated](https://developer.mozilla.org/en-US/docs/Web/API/crossOriginIsolated))
+pub fn has_atomics () -> bool {
+    let global = js_sys::global();
+    if !has(&global, "SharedArrayBuffer") { return false }
+    if js_sys::Reflect::get(&global, &JsValue::from_str("crossOriginIsolated")).ok().and_then(|x| x.as_bool()) == Some(false) {
+        return false
+    }
+    return validate(ATOMICS_PROBE)
+}
+
+/// Returns `true` if the current scope can spawn web workers
+#[inline]
+pub fn has_workers () -> bool {
+    return has(&js_sys::global(), "Worker")
+}
+
+/// Returns `true` if the [File System Access API](https://developer.mozilla.org/en-US/docs/Web/API/File_System_Access_API) file pickers are available
+#[inline]
+pub fn has_file_system_access () -> bool {
+    return has(&js_sys::global(), "showOpenFilePicker")
+}
+
+/// Returns `true` if the [Origin Private File System](https://developer.mozilla.org/en-US/docs/Web/API/File_System_Access_API#origin_private_file_system) is available
+#[inline]
+pub fn has_origin_private_fs () -> bool {
+    let storage = match js_sys::Reflect::get(&navigator(), &JsValue::from_str("storage")) {
+        Ok(storage) => storage,
+        Err(_) => return false
+    };
+    return has(&storage, "getDirectory")
+}
+
+/// Returns `true` if the [Web Share API](https://developer.mozilla.org/en-US/docs/Web/API/Web_Share_API) is available
+#[inline]
+pub fn has_web_share () -> bool {
+    return has(&navigator(), "share")
+}
+
+/// Returns `true` if the [Clipboard API](https://developer.mozilla.org/en-US/docs/Web/API/Clipboard_API) is available
+#[inline]
+pub fn has_clipboard () -> bool {
+    return has(&navigator(), "clipboard")
+}
+
+/// Returns `true` if the [Web Locks API](https://developer.mozilla.org/en-US/docs/Web/API/Web_Locks_API) is available
+#[inline]
+pub fn has_web_locks () -> bool {
+    return has(&navigator(), "locks")
+}
+
+/// Returns `true` if the [Screen Wake Lock API](https://developer.mozilla.org/en-US/docs/Web/API/Screen_Wake_Lock_API) is available
+#[inline]
+pub fn has_wake_lock () -> bool {
+    return has(&navigator(), "wakeLock")
+}
+
+/// Returns `true` if the [Geolocation API](https://developer.mozilla.org/en-US/docs/Web/API/Geolocation_API) is available
+#[inline]
+pub fn has_geolocation () -> bool {
+    return has(&navigator(), "geolocation")
+}
+
+/// Returns `true` if the [Battery Status API](https://developer.mozilla.org/en-US/docs/Web/API/Battery_Status_API) is available
+#[inline]
+pub fn has_battery () -> bool {
+    return has(&navigator(), "getBattery")
+}
+
+/// Returns `true` if the [Web MIDI API](https://developer.mozilla.org/en-US/docs/Web/API/Web_MIDI_API) is available
+#[inline]
+pub fn has_midi () -> bool {
+    return has(&navigator(), "requestMIDIAccess")
+}
+
+/// Returns `true` if service workers can be registered from the current scope
+#[inline]
+pub fn has_service_worker () -> bool {
+    return has(&navigator(), "serviceWorker")
+}
+
+/// Returns `true` if the [Notifications API](https://developer.mozilla.org/en-US/docs/Web/API/Notifications_API) is available
+#[inline]
+pub fn has_notifications () -> bool {
+    return has(&js_sys::global(), "Notification")
+}
+
+/// Returns `true` if [`OffscreenCanvas`](web_sys::OffscreenCanvas) is available
+#[inline]
+pub fn has_offscreen_canvas () -> bool {
+    return has(&js_sys::global(), "OffscreenCanvas")
+}
+
+/// Returns `true` if [`AbortSignal.timeout`](https://developer.mozilla.org/en-US/docs/Web/API/AbortSignal/timeout) is available natively
+#[inline]
+pub fn has_abort_timeout () -> bool {
+    return js_sys::Reflect::get(&js_sys::global(), &JsValue::from_str("AbortSignal"))
+        .map(|signal| has(&signal, "timeout"))
+        .unwrap_or_default()
+}
@@ -0,0 +1,120 @@
+use std::{rc::Rc, cell::{RefCell, Cell}, fmt::Arguments};
+use wasm_bindgen::{closure::Closure, JsCast};
+use crate::{ArgsExt, scope::set_timeout};
+
+thread_local! {
+    static GLOBAL: Buffered = Buffered::new();
+}
+
+/// Returns the buffered logger of the current thread, used by [`buffered_println`](crate::buffered_println)
+#[inline]
+pub fn global () -> Buffered {
+    return GLOBAL.with(Clone::clone)
+}
+
+/// A logger that accumulates log lines and prints them with a single `console.log` call.
+///
+/// The lines are flushed at the end of the current macrotask (or as soon as the buffer is full),
+/// so hot loops only pay for a string push per line. Clones of the logger share the same buffer,
+/// and the remaining lines are flushed when the last clone is dropped.
+#[derive(Debug, Clone)]
+pub struct Buffered {
+    inner: Rc<Inner>
+}
+
+#[derive(Debug)]
+struct Inner {
+    lines: RefCell<Vec<String>>,
+    capacity: usize,
+    scheduled: Cell<bool>
+}
+
+impl Buffered {
+    /// Creates a new logger that buffers up to 1024 lines before flushing them
+    #[inline]
+    pub fn new () -> Self {
+        return Self::with_capacity(1024)
+    }
+
+    /// Creates a new logger that buffers up to `capacity` lines before flushing them
+    #[inline]
+    pub fn with_capacity (capacity: usize) -> Self {
+        let inner = Inner { lines: RefCell::new(Vec::new()), capacity: capacity.max(1), scheduled: Cell::new(false) };
+        return Self { inner: Rc::new(inner) }
+    }
+
+    /// Adds a line to the buffer
+    pub fn log (&self, line: impl Into<String>) {
+        let len = {
+            let mut lines = self.inner.lines.borrow_mut();
+            lines.push(line.into());
+            lines.len()
+        };
+
+        if len >= self.inner.capacity {
+            self.flush();
+        } else if !self.inner.scheduled.replace(true) {
+            self.schedule();
+        }
+    }
+
+    /// Adds the formatted arguments to the buffer as a line
+    #[inline]
+    pub fn log_fmt (&self, args: Arguments<'_>) {
+        self.log(args.into_str())
+    }
+
+    /// Returns the number of lines waiting to be flushed
+    #[inline]
+    pub fn len (&self) -> usize {
+        return self.inner.lines.borrow().len()
+    }
+
+    /// Returns `true` if there are no lines waiting to be flushed
+    #[inline]
+    pub fn is_empty (&self) -> bool {
+        return self.inner.lines.borrow().is_empty()
+    }
+
+    /// Prints the buffered lines right away, with a single `console.log` call
+    #[inline]
+    pub fn flush (&self) {
+        self.inner.flush()
+    }
+
+    fn schedule (&self) {
+        let weak = Rc::downgrade(&self.inner);
+        let f = Closure::once_into_js(move || {
+            if let Some(inner) = weak.upgrade() {
+                inner.flush()
+            }
+        });
+        if set_timeout(f.unchecked_ref(), 0).is_err() {
+            self.flush()
+        }
+    }
+}
+
+impl Default for Buffered {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Inner {
+    fn flush (&self) {
+        self.scheduled.set(false);
+        let lines = core::mem::take(&mut *self.lines.borrow_mut());
+        if !lines.is_empty() {
+            crate::_log(&lines.join("\n"))
+        }
+    }
+}
+
+impl Drop for Inner {
+    #[inline]
+    fn drop(&mut self) {
+        self.flush()
+    }
+}
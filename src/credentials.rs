@@ -0,0 +1,472 @@
+use std::time::Duration;
+use js_sys::{ArrayBuffer, Uint8Array, Object, Array};
+use wasm_bindgen::{prelude::wasm_bindgen, JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use crate::{Result, Error, ErrorKind, scope::window_only};
+
+#[wasm_bindgen]
+extern "C" {
+    #[derive(Debug, Clone)]
+    type CredentialsContainer;
+    #[derive(Debug, Clone)]
+    #[wasm_bindgen(js_name = PasswordCredential)]
+    type JsPasswordCredential;
+    #[derive(Debug, Clone)]
+    #[wasm_bindgen(js_name = PublicKeyCredential)]
+    type JsPublicKeyCredential;
+    #[derive(Debug, Clone)]
+    type AuthenticatorResponse;
+
+    #[wasm_bindgen(method, catch)]
+    fn get (this: &CredentialsContainer, ops: &JsValue) -> Result<js_sys::Promise, JsValue>;
+    #[wasm_bindgen(method, catch)]
+    fn store (this: &CredentialsContainer, cred: &JsValue) -> Result<js_sys::Promise, JsValue>;
+    #[wasm_bindgen(method, catch)]
+    fn create (this: &CredentialsContainer, ops: &JsValue) -> Result<js_sys::Promise, JsValue>;
+
+    #[wasm_bindgen(constructor, catch, js_class = PasswordCredential)]
+    fn new (data: &JsValue) -> Result<JsPasswordCredential, JsValue>;
+    #[wasm_bindgen(method, getter)]
+    fn id (this: &JsPasswordCredential) -> String;
+    #[wasm_bindgen(method, getter)]
+    fn password (this: &JsPasswordCredential) -> String;
+    #[wasm_bindgen(method, getter)]
+    fn name (this: &JsPasswordCredential) -> Option<String>;
+    #[wasm_bindgen(method, getter, js_name = iconURL)]
+    fn icon_url (this: &JsPasswordCredential) -> Option<String>;
+
+    #[wasm_bindgen(method, getter, js_name = id)]
+    fn id (this: &JsPublicKeyCredential) -> String;
+    #[wasm_bindgen(method, getter, js_name = rawId)]
+    fn raw_id (this: &JsPublicKeyCredential) -> ArrayBuffer;
+    #[wasm_bindgen(method, getter)]
+    fn response (this: &JsPublicKeyCredential) -> AuthenticatorResponse;
+    #[wasm_bindgen(method, getter, js_name = authenticatorAttachment)]
+    fn authenticator_attachment (this: &JsPublicKeyCredential) -> Option<String>;
+
+    #[wasm_bindgen(method, getter, js_name = clientDataJSON)]
+    fn client_data_json (this: &AuthenticatorResponse) -> ArrayBuffer;
+    #[wasm_bindgen(method, getter, js_name = attestationObject)]
+    fn attestation_object (this: &AuthenticatorResponse) -> ArrayBuffer;
+    #[wasm_bindgen(method, getter, js_name = authenticatorData)]
+    fn authenticator_data (this: &AuthenticatorResponse) -> ArrayBuffer;
+    #[wasm_bindgen(method, getter)]
+    fn signature (this: &AuthenticatorResponse) -> ArrayBuffer;
+    #[wasm_bindgen(method, getter, js_name = userHandle)]
+    fn user_handle (this: &AuthenticatorResponse) -> Option<ArrayBuffer>;
+}
+
+/// How the user should be asked to participate in a credential request
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Mediation {
+    /// The user will not be asked
+    Silent = "silent",
+    /// The user will be asked if a credential can't be handed over without interaction
+    #[default]
+    Optional = "optional",
+    /// The user will be asked if the browser considers it appropriate
+    Conditional = "conditional",
+    /// The user will always be asked
+    Required = "required"
+}
+
+/// Requirement level of a WebAuthn option
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Requirement {
+    Discouraged = "discouraged",
+    Preferred = "preferred",
+    Required = "required"
+}
+
+/// Attestation conveyance preference of a WebAuthn credential creation
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Attestation {
+    None = "none",
+    Indirect = "indirect",
+    Direct = "direct",
+    Enterprise = "enterprise"
+}
+
+/// A username/password pair stored by the browser
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PasswordCredential {
+    /// Identifier of the credential, usually the username
+    pub id: String,
+    pub password: String,
+    /// Human-readable name of the credential
+    pub name: Option<String>,
+    /// URL of an image representing the credential
+    pub icon_url: Option<String>
+}
+
+impl PasswordCredential {
+    /// Creates a new password credential
+    #[inline]
+    pub fn new (id: impl Into<String>, password: impl Into<String>) -> Self {
+        return Self { id: id.into(), password: password.into(), name: None, icon_url: None }
+    }
+
+    /// Stores the credential in the browser's credential manager
+    pub async fn store (&self) -> Result<()> {
+        let data = Object::new();
+        set(&data, "id", &JsValue::from_str(&self.id))?;
+        set(&data, "password", &JsValue::from_str(&self.password))?;
+        if let Some(ref name) = self.name {
+            set(&data, "name", &JsValue::from_str(name))?;
+        }
+        if let Some(ref icon_url) = self.icon_url {
+            set(&data, "iconURL", &JsValue::from_str(icon_url))?;
+        }
+
+        let cred = JsPasswordCredential::new(&data)?;
+        JsFuture::from(container()?.store(&cred)?).await?;
+        return Ok(())
+    }
+
+    /// Retreives a password credential from the browser's credential manager, if available
+    pub async fn get (mediation: Mediation) -> Result<Option<Self>> {
+        let ops = Object::new();
+        set(&ops, "password", &JsValue::TRUE)?;
+        set(&ops, "mediation", &mediation.into())?;
+
+        let cred = JsFuture::from(container()?.get(&ops)?).await?;
+        if cred.is_null() || cred.is_undefined() {
+            return Ok(None)
+        }
+
+        let cred = cred.unchecked_into::<JsPasswordCredential>();
+        return Ok(Some(Self {
+            id: cred.id(),
+            password: cred.password(),
+            name: cred.name(),
+            icon_url: cred.icon_url()
+        }))
+    }
+}
+
+/// Builder of a WebAuthn credential creation ceremony
+#[derive(Debug, Clone)]
+pub struct PublicKeyCreation {
+    rp_id: Option<String>,
+    rp_name: String,
+    user_id: Vec<u8>,
+    user_name: String,
+    display_name: Option<String>,
+    challenge: Vec<u8>,
+    algorithms: Vec<i32>,
+    exclude: Vec<Vec<u8>>,
+    timeout: Option<Duration>,
+    attestation: Option<Attestation>,
+    resident_key: Option<Requirement>,
+    user_verification: Option<Requirement>
+}
+
+impl PublicKeyCreation {
+    /// Creates a new credential creation builder for the specified relying party, user and server-generated challenge.
+    ///
+    /// By default, the ES256 (`-7`) and RS256 (`-257`) algorithms are accepted.
+    #[inline]
+    pub fn new (rp_name: impl Into<String>, user_id: impl Into<Vec<u8>>, user_name: impl Into<String>, challenge: impl Into<Vec<u8>>) -> Self {
+        return Self {
+            rp_id: None,
+            rp_name: rp_name.into(),
+            user_id: user_id.into(),
+            user_name: user_name.into(),
+            display_name: None,
+            challenge: challenge.into(),
+            algorithms: vec![-7, -257],
+            exclude: Vec::new(),
+            timeout: None,
+            attestation: None,
+            resident_key: None,
+            user_verification: None
+        }
+    }
+
+    /// Sets the relying party's id. By default, the current origin's effective domain is used.
+    #[inline]
+    pub fn rp_id (mut self, rp_id: impl Into<String>) -> Self {
+        self.rp_id = Some(rp_id.into());
+        self
+    }
+
+    /// Sets the user's display name. By default, the user name is used.
+    #[inline]
+    pub fn display_name (mut self, display_name: impl Into<String>) -> Self {
+        self.display_name = Some(display_name.into());
+        self
+    }
+
+    /// Sets the accepted [COSE algorithms](https://www.iana.org/assignments/cose/cose.xhtml#algorithms), in order of preference
+    #[inline]
+    pub fn algorithms (mut self, algorithms: impl IntoIterator<Item = i32>) -> Self {
+        self.algorithms = algorithms.into_iter().collect();
+        self
+    }
+
+    /// Excludes a credential already registered for the user
+    #[inline]
+    pub fn exclude (mut self, id: impl Into<Vec<u8>>) -> Self {
+        self.exclude.push(id.into());
+        self
+    }
+
+    /// Sets the time the user has to complete the ceremony
+    #[inline]
+    pub fn timeout (mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the attestation conveyance preference
+    #[inline]
+    pub fn attestation (mut self, attestation: Attestation) -> Self {
+        self.attestation = Some(attestation);
+        self
+    }
+
+    /// Sets the requirement for the credential to be discoverable (a passkey)
+    #[inline]
+    pub fn resident_key (mut self, resident_key: Requirement) -> Self {
+        self.resident_key = Some(resident_key);
+        self
+    }
+
+    /// Sets the requirement for user verification
+    #[inline]
+    pub fn user_verification (mut self, user_verification: Requirement) -> Self {
+        self.user_verification = Some(user_verification);
+        self
+    }
+
+    /// Performs the creation ceremony, returning the new credential's attestation
+    pub async fn create (self) -> Result<AttestationResponse> {
+        let rp = Object::new();
+        set(&rp, "name", &JsValue::from_str(&self.rp_name))?;
+        if let Some(ref rp_id) = self.rp_id {
+            set(&rp, "id", &JsValue::from_str(rp_id))?;
+        }
+
+        let user = Object::new();
+        set(&user, "id", &Uint8Array::from(&self.user_id as &[u8]))?;
+        set(&user, "name", &JsValue::from_str(&self.user_name))?;
+        set(&user, "displayName", &JsValue::from_str(self.display_name.as_ref().unwrap_or(&self.user_name)))?;
+
+        let params = Array::new();
+        for alg in self.algorithms {
+            let param = Object::new();
+            set(&param, "type", &JsValue::from_str("public-key"))?;
+            set(&param, "alg", &JsValue::from(alg))?;
+            params.push(&param);
+        }
+
+        let public_key = Object::new();
+        set(&public_key, "rp", &rp)?;
+        set(&public_key, "user", &user)?;
+        set(&public_key, "challenge", &Uint8Array::from(&self.challenge as &[u8]))?;
+        set(&public_key, "pubKeyCredParams", &params)?;
+        set(&public_key, "excludeCredentials", &descriptors(&self.exclude)?)?;
+        if let Some(timeout) = self.timeout {
+            set(&public_key, "timeout", &JsValue::from_f64(timeout.as_millis() as f64))?;
+        }
+        if let Some(attestation) = self.attestation {
+            set(&public_key, "attestation", &attestation.into())?;
+        }
+
+        if self.resident_key.is_some() || self.user_verification.is_some() {
+            let selection = Object::new();
+            if let Some(resident_key) = self.resident_key {
+                set(&selection, "residentKey", &resident_key.into())?;
+                set(&selection, "requireResidentKey", &JsValue::from_bool(resident_key == Requirement::Required))?;
+            }
+            if let Some(user_verification) = self.user_verification {
+                set(&selection, "userVerification", &user_verification.into())?;
+            }
+            set(&public_key, "authenticatorSelection", &selection)?;
+        }
+
+        let ops = Object::new();
+        set(&ops, "publicKey", &public_key)?;
+
+        let cred = JsFuture::from(container()?.create(&ops)?).await?;
+        if cred.is_null() || cred.is_undefined() {
+            return Err(Error::new(ErrorKind::Abort, "no credential was created"))
+        }
+
+        let cred = cred.unchecked_into::<JsPublicKeyCredential>();
+        let response = cred.response();
+        return Ok(AttestationResponse {
+            id: cred.id(),
+            raw_id: buffer_to_vec(&cred.raw_id()),
+            authenticator_attachment: cred.authenticator_attachment(),
+            client_data_json: buffer_to_vec(&response.client_data_json()),
+            attestation_object: buffer_to_vec(&response.attestation_object())
+        })
+    }
+}
+
+/// Builder of a WebAuthn credential assertion ceremony
+#[derive(Debug, Clone)]
+pub struct PublicKeyRequest {
+    challenge: Vec<u8>,
+    rp_id: Option<String>,
+    allow: Vec<Vec<u8>>,
+    timeout: Option<Duration>,
+    user_verification: Option<Requirement>,
+    mediation: Mediation
+}
+
+impl PublicKeyRequest {
+    /// Creates a new credential assertion builder with the specified server-generated challenge
+    #[inline]
+    pub fn new (challenge: impl Into<Vec<u8>>) -> Self {
+        return Self {
+            challenge: challenge.into(),
+            rp_id: None,
+            allow: Vec::new(),
+            timeout: None,
+            user_verification: None,
+            mediation: Mediation::default()
+        }
+    }
+
+    /// Sets the relying party's id. By default, the current origin's effective domain is used.
+    #[inline]
+    pub fn rp_id (mut self, rp_id: impl Into<String>) -> Self {
+        self.rp_id = Some(rp_id.into());
+        self
+    }
+
+    /// Allows a specific credential to be used. By default, any discoverable credential may be used.
+    #[inline]
+    pub fn allow (mut self, id: impl Into<Vec<u8>>) -> Self {
+        self.allow.push(id.into());
+        self
+    }
+
+    /// Sets the time the user has to complete the ceremony
+    #[inline]
+    pub fn timeout (mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the requirement for user verification
+    #[inline]
+    pub fn user_verification (mut self, user_verification: Requirement) -> Self {
+        self.user_verification = Some(user_verification);
+        self
+    }
+
+    /// Sets how the user should be asked to participate
+    #[inline]
+    pub fn mediation (mut self, mediation: Mediation) -> Self {
+        self.mediation = mediation;
+        self
+    }
+
+    /// Performs the assertion ceremony, returning the credential's assertion
+    pub async fn get (self) -> Result<AssertionResponse> {
+        let public_key = Object::new();
+        set(&public_key, "challenge", &Uint8Array::from(&self.challenge as &[u8]))?;
+        set(&public_key, "allowCredentials", &descriptors(&self.allow)?)?;
+        if let Some(ref rp_id) = self.rp_id {
+            set(&public_key, "rpId", &JsValue::from_str(rp_id))?;
+        }
+        if let Some(timeout) = self.timeout {
+            set(&public_key, "timeout", &JsValue::from_f64(timeout.as_millis() as f64))?;
+        }
+        if let Some(user_verification) = self.user_verification {
+            set(&public_key, "userVerification", &user_verification.into())?;
+        }
+
+        let ops = Object::new();
+        set(&ops, "publicKey", &public_key)?;
+        set(&ops, "mediation", &self.mediation.into())?;
+
+        let cred = JsFuture::from(container()?.get(&ops)?).await?;
+        if cred.is_null() || cred.is_undefined() {
+            return Err(Error::new(ErrorKind::Abort, "no credential was selected"))
+        }
+
+        let cred = cred.unchecked_into::<JsPublicKeyCredential>();
+        let response = cred.response();
+        return Ok(AssertionResponse {
+            id: cred.id(),
+            raw_id: buffer_to_vec(&cred.raw_id()),
+            client_data_json: buffer_to_vec(&response.client_data_json()),
+            authenticator_data: buffer_to_vec(&response.authenticator_data()),
+            signature: buffer_to_vec(&response.signature()),
+            user_handle: response.user_handle().as_ref().map(buffer_to_vec)
+        })
+    }
+}
+
+/// Result of a [`PublicKeyCreation`] ceremony, to be verified by the relying party's server
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct AttestationResponse {
+    /// Base64url-encoded id of the new credential
+    pub id: String,
+    /// Raw id of the new credential
+    pub raw_id: Vec<u8>,
+    /// Attachment modality of the authenticator (`platform` or `cross-platform`), if available
+    pub authenticator_attachment: Option<String>,
+    /// JSON-encoded client data passed to the authenticator
+    pub client_data_json: Vec<u8>,
+    /// CBOR-encoded attestation object
+    pub attestation_object: Vec<u8>
+}
+
+/// Result of a [`PublicKeyRequest`] ceremony, to be verified by the relying party's server
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct AssertionResponse {
+    /// Base64url-encoded id of the credential
+    pub id: String,
+    /// Raw id of the credential
+    pub raw_id: Vec<u8>,
+    /// JSON-encoded client data passed to the authenticator
+    pub client_data_json: Vec<u8>,
+    /// Data returned by the authenticator
+    pub authenticator_data: Vec<u8>,
+    /// Signature over the authenticator and client data
+    pub signature: Vec<u8>,
+    /// Id of the user the credential was created for, if available
+    pub user_handle: Option<Vec<u8>>
+}
+
+#[inline]
+fn container () -> Result<CredentialsContainer> {
+    let navigator = window_only("Credential Management API")?.navigator();
+    let container = js_sys::Reflect::get(&navigator, &JsValue::from_str("credentials"))?;
+    if container.is_undefined() {
+        return Err(Error::new(ErrorKind::NotSupported, "Credential Management API is not supported"))
+    }
+    return Ok(container.unchecked_into())
+}
+
+#[inline]
+fn set (target: &Object, key: &str, value: &JsValue) -> Result<()> {
+    js_sys::Reflect::set(target, &JsValue::from_str(key), value)?;
+    return Ok(())
+}
+
+fn descriptors (ids: &[Vec<u8>]) -> Result<Array> {
+    let result = Array::new();
+    for id in ids {
+        let desc = Object::new();
+        set(&desc, "type", &JsValue::from_str("public-key"))?;
+        set(&desc, "id", &Uint8Array::from(id as &[u8]))?;
+        result.push(&desc);
+    }
+    return Ok(result)
+}
+
+#[inline]
+fn buffer_to_vec (buffer: &ArrayBuffer) -> Vec<u8> {
+    return Uint8Array::new(buffer).to_vec()
+}
@@ -0,0 +1,118 @@
+use js_sys::{Array, Uint8Array};
+use wasm_bindgen::{prelude::wasm_bindgen, JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use crate::{Result, Error, ErrorKind};
+
+#[wasm_bindgen]
+extern "C" {
+    #[derive(Debug, Clone)]
+    type SubtleCrypto;
+
+    #[wasm_bindgen(method, catch)]
+    fn digest (this: &SubtleCrypto, algorithm: &str, data: &Uint8Array) -> ::core::result::Result<js_sys::Promise, JsValue>;
+    #[wasm_bindgen(method, catch, js_name = importKey)]
+    fn import_key (this: &SubtleCrypto, format: &str, key: &JsValue, algorithm: &JsValue, extractable: bool, usages: &Array) -> ::core::result::Result<js_sys::Promise, JsValue>;
+    #[wasm_bindgen(method, catch)]
+    fn sign (this: &SubtleCrypto, algorithm: &JsValue, key: &JsValue, data: &Uint8Array) -> ::core::result::Result<js_sys::Promise, JsValue>;
+    #[wasm_bindgen(method, catch)]
+    fn verify (this: &SubtleCrypto, algorithm: &JsValue, key: &JsValue, signature: &Uint8Array, data: &Uint8Array) -> ::core::result::Result<js_sys::Promise, JsValue>;
+}
+
+/// Returns the [`SubtleCrypto`](https://developer.mozilla.org/en-US/docs/Web/API/SubtleCrypto) instance of the current global scope
+fn subtle () -> Result<SubtleCrypto> {
+    let crypto = js_sys::Reflect::get(&js_sys::global(), &JsValue::from_str("crypto"))?;
+    let subtle = match crypto.is_undefined() {
+        true => JsValue::UNDEFINED,
+        false => js_sys::Reflect::get(&crypto, &JsValue::from_str("subtle"))?
+    };
+
+    if subtle.is_undefined() {
+        return Err(Error::new(ErrorKind::NotSupported, "Web Crypto API is not supported (it's only available in secure contexts)"))
+    }
+    return Ok(subtle.unchecked_into())
+}
+
+/// Hash algorithm supported by the Web Crypto API
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HashAlgorithm {
+    Sha1,
+    Sha256,
+    Sha384,
+    Sha512
+}
+
+impl HashAlgorithm {
+    /// Returns the algorithm's name, as expected by JavaScript
+    #[inline]
+    pub fn as_str (self) -> &'static str {
+        return match self {
+            Self::Sha1 => "SHA-1",
+            Self::Sha256 => "SHA-256",
+            Self::Sha384 => "SHA-384",
+            Self::Sha512 => "SHA-512"
+        }
+    }
+}
+
+/// Computes the digest of `data` with the specified hash algorithm
+pub async fn digest (algorithm: HashAlgorithm, data: &[u8]) -> Result<Vec<u8>> {
+    // data is copied, since the Web Crypto API rejects views of shared memory
+    let hash = JsFuture::from(subtle()?.digest(algorithm.as_str(), &Uint8Array::from(data))?).await?;
+    return Ok(Uint8Array::new(&hash).to_vec())
+}
+
+/// Computes the SHA-256 digest of `data`
+#[inline]
+pub async fn sha256 (data: &[u8]) -> Result<[u8; 32]> {
+    let hash = digest(HashAlgorithm::Sha256, data).await?;
+    return hash.try_into().map_err(|_| Error::new(ErrorKind::Js, "unexpected SHA-256 digest length"))
+}
+
+/// Computes the HMAC of `data` with the specified key and hash algorithm
+pub async fn hmac (algorithm: HashAlgorithm, key: &[u8], data: &[u8]) -> Result<Vec<u8>> {
+    let subtle = subtle()?;
+    let params = hmac_params(algorithm)?;
+
+    let key = JsFuture::from(subtle.import_key("raw", &Uint8Array::from(key), &params, false, &Array::of1(&JsValue::from_str("sign")))?).await?;
+    let signature = JsFuture::from(subtle.sign(&params, &key, &Uint8Array::from(data))?).await?;
+    return Ok(Uint8Array::new(&signature).to_vec())
+}
+
+/// Computes the HMAC-SHA256 of `data` with the specified key
+#[inline]
+pub async fn hmac_sha256 (key: &[u8], data: &[u8]) -> Result<[u8; 32]> {
+    let signature = hmac(HashAlgorithm::Sha256, key, data).await?;
+    return signature.try_into().map_err(|_| Error::new(ErrorKind::Js, "unexpected HMAC-SHA256 length"))
+}
+
+/// Fills `buf` with cryptographically secure random bytes
+#[inline]
+pub fn random_bytes (buf: &mut [u8]) -> Result<()> {
+    return crate::random::fill(buf)
+}
+
+#[inline]
+fn hmac_params (algorithm: HashAlgorithm) -> Result<JsValue> {
+    return algorithm_params(&[("name", "HMAC"), ("hash", algorithm.as_str())])
+}
+
+/// Builds an algorithm parameters object from it's key-value pairs
+pub(crate) fn algorithm_params (entries: &[(&str, &str)]) -> Result<JsValue> {
+    let params = js_sys::Object::new();
+    for (key, value) in entries {
+        js_sys::Reflect::set(&params, &JsValue::from_str(key), &JsValue::from_str(value))?;
+    }
+    return Ok(params.into())
+}
+
+/// Imports a key for the specified usages, returning the resulting `CryptoKey`
+pub(crate) async fn import_key (format: &str, key: &JsValue, algorithm: &JsValue, usages: &[&str]) -> Result<JsValue> {
+    let usages = usages.iter().map(|x| JsValue::from_str(x)).collect::<Array>();
+    return Ok(JsFuture::from(subtle()?.import_key(format, key, algorithm, false, &usages)?).await?)
+}
+
+/// Verifies the signature of `data` with the specified algorithm and `CryptoKey`
+pub(crate) async fn verify (algorithm: &JsValue, key: &JsValue, signature: &[u8], data: &[u8]) -> Result<bool> {
+    let valid = JsFuture::from(subtle()?.verify(algorithm, key, &Uint8Array::from(signature), &Uint8Array::from(data))?).await?;
+    return Ok(valid.is_truthy())
+}
@@ -0,0 +1,144 @@
+use js_sys::{Promise, Array};
+use wasm_bindgen::{JsCast, JsValue, prelude::wasm_bindgen};
+use wasm_bindgen_futures::JsFuture;
+use crate::{Result, Error, ErrorKind, math::Vec2d, image::Image};
+
+#[wasm_bindgen]
+extern "C" {
+    #[derive(Debug, Clone)]
+    #[wasm_bindgen(js_name = BarcodeDetector)]
+    type RawBarcodeDetector;
+
+    #[wasm_bindgen(constructor, catch)]
+    fn new (options: &js_sys::Object) -> ::core::result::Result<RawBarcodeDetector, JsValue>;
+    #[wasm_bindgen(static_method_of = RawBarcodeDetector, js_name = getSupportedFormats, catch)]
+    fn get_supported_formats () -> ::core::result::Result<Promise, JsValue>;
+    #[wasm_bindgen(method, catch)]
+    fn detect (this: &RawBarcodeDetector, source: &JsValue) -> ::core::result::Result<Promise, JsValue>;
+
+    type DetectedBarcode;
+
+    #[wasm_bindgen(method, getter, js_name = rawValue)]
+    fn raw_value (this: &DetectedBarcode) -> String;
+    #[wasm_bindgen(method, getter)]
+    fn format (this: &DetectedBarcode) -> String;
+    #[wasm_bindgen(method, getter, js_name = boundingBox)]
+    fn bounding_box (this: &DetectedBarcode) -> DomRect;
+    #[wasm_bindgen(method, getter, js_name = cornerPoints)]
+    fn corner_points (this: &DetectedBarcode) -> Array;
+
+    type DomRect;
+
+    #[wasm_bindgen(method, getter)]
+    fn x (this: &DomRect) -> f64;
+    #[wasm_bindgen(method, getter)]
+    fn y (this: &DomRect) -> f64;
+    #[wasm_bindgen(method, getter)]
+    fn width (this: &DomRect) -> f64;
+    #[wasm_bindgen(method, getter)]
+    fn height (this: &DomRect) -> f64;
+}
+
+/// An axis-aligned rectangle
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct BoundingBox {
+    /// Top-left corner of the rectangle
+    pub origin: Vec2d,
+    /// Width and height of the rectangle
+    pub size: Vec2d
+}
+
+/// A barcode detected in an image
+#[derive(Debug, Clone, PartialEq)]
+pub struct Barcode {
+    /// The decoded value of the barcode
+    pub raw_value: String,
+    /// Format of the barcode (`"qr_code"`, `"ean_13"`, ...)
+    pub format: String,
+    /// Rectangle containing the barcode
+    pub bounding_box: BoundingBox,
+    /// Corners of the barcode, clockwise from the top-left one
+    pub corners: Vec<Vec2d>
+}
+
+impl From<DetectedBarcode> for Barcode {
+    fn from(value: DetectedBarcode) -> Self {
+        let rect = value.bounding_box();
+        let corners = value.corner_points().iter()
+            .map(|point| {
+                let get = |key: &str| js_sys::Reflect::get(&point, &JsValue::from_str(key)).ok().and_then(|x| x.as_f64()).unwrap_or_default();
+                Vec2d::new(get("x"), get("y"))
+            })
+            .collect();
+
+        return Self {
+            raw_value: value.raw_value(),
+            format: value.format(),
+            bounding_box: BoundingBox {
+                origin: Vec2d::new(rect.x(), rect.y()),
+                size: Vec2d::new(rect.width(), rect.height())
+            },
+            corners
+        }
+    }
+}
+
+/// Detects barcodes in images, via the [Barcode Detection API](https://developer.mozilla.org/en-US/docs/Web/API/Barcode_Detection_API)
+#[derive(Debug, Clone)]
+pub struct BarcodeDetector {
+    inner: RawBarcodeDetector
+}
+
+impl BarcodeDetector {
+    /// Returns `true` if the Barcode Detection API is available, `false` otherwise
+    #[inline]
+    pub fn is_supported () -> bool {
+        return js_sys::Reflect::has(&js_sys::global(), &JsValue::from_str("BarcodeDetector")).unwrap_or_default()
+    }
+
+    /// Returns the barcode formats supported by the current platform
+    pub async fn supported_formats () -> Result<Vec<String>> {
+        if !Self::is_supported() {
+            return Err(Error::new(ErrorKind::NotSupported, "Barcode Detection API is not supported"))
+        }
+
+        let formats = JsFuture::from(get_supported_formats()?).await?;
+        return Ok(formats.unchecked_into::<Array>().iter().filter_map(|x| x.as_string()).collect())
+    }
+
+    /// Creates a new detector for every supported format
+    #[inline]
+    pub fn new () -> Result<Self> {
+        return Self::with_formats(&[])
+    }
+
+    /// Creates a new detector for the specified formats. If empty, every supported format is detected.
+    pub fn with_formats (formats: &[&str]) -> Result<Self> {
+        if !Self::is_supported() {
+            return Err(Error::new(ErrorKind::NotSupported, "Barcode Detection API is not supported"))
+        }
+
+        let options = js_sys::Object::new();
+        if !formats.is_empty() {
+            let formats = formats.iter().map(|x| JsValue::from_str(x)).collect::<Array>();
+            js_sys::Reflect::set(&options, &JsValue::from_str("formats"), &formats)?;
+        }
+
+        return Ok(Self { inner: RawBarcodeDetector::new(&options)? })
+    }
+
+    /// Detects the barcodes in the image source (an [`ImageBitmap`](web_sys::ImageBitmap), a video element, a [`Blob`](web_sys::Blob), ...)
+    pub async fn detect (&self, source: &JsValue) -> Result<Vec<Barcode>> {
+        let barcodes = JsFuture::from(self.inner.detect(source)?).await?;
+        return Ok(barcodes.unchecked_into::<Array>().iter()
+            .map(|x| Barcode::from(x.unchecked_into::<DetectedBarcode>()))
+            .collect()
+        )
+    }
+
+    /// Detects the barcodes in the image
+    #[inline]
+    pub async fn detect_image (&self, image: &Image) -> Result<Vec<Barcode>> {
+        return self.detect(image.bitmap()).await
+    }
+}
@@ -0,0 +1,260 @@
+use std::{rc::Rc, cell::Cell, pin::Pin, task::{Context, Poll}};
+use futures::{Stream, StreamExt};
+use js_sys::Array;
+use serde::Deserialize;
+use wasm_bindgen::{prelude::wasm_bindgen, JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::EventTarget;
+use crate::{Result, ErrorKind, scope::{navigator, is_worker}, battery::Battery, dom::{document, Listener}, utils::{LocalReceiver, local_channel, from_js}};
+
+#[wasm_bindgen]
+extern "C" {
+    #[derive(Debug, Clone)]
+    type NavigatorExt;
+    #[derive(Debug, Clone)]
+    type NavigatorUAData;
+
+    #[wasm_bindgen(method, getter, js_name = deviceMemory)]
+    fn device_memory (this: &NavigatorExt) -> Option<f64>;
+    #[wasm_bindgen(method, getter, js_name = hardwareConcurrency)]
+    fn hardware_concurrency (this: &NavigatorExt) -> Option<f64>;
+    #[wasm_bindgen(method, getter, js_name = maxTouchPoints)]
+    fn max_touch_points (this: &NavigatorExt) -> Option<u32>;
+    #[wasm_bindgen(method, getter, js_name = userAgentData)]
+    fn user_agent_data (this: &NavigatorExt) -> Option<NavigatorUAData>;
+
+    #[wasm_bindgen(method, getter)]
+    fn brands (this: &NavigatorUAData) -> JsValue;
+    #[wasm_bindgen(method, getter)]
+    fn mobile (this: &NavigatorUAData) -> bool;
+    #[wasm_bindgen(method, getter)]
+    fn platform (this: &NavigatorUAData) -> String;
+    #[wasm_bindgen(method, js_name = getHighEntropyValues)]
+    fn get_high_entropy_values (this: &NavigatorUAData, hints: &Array) -> js_sys::Promise;
+}
+
+/// Returns the hardware capabilities of the device.
+///
+/// These values are available both on windows and on workers.
+pub fn capabilities () -> Result<Capabilities> {
+    let nav = navigator().unchecked_into::<NavigatorExt>();
+    let user_agent = match nav.user_agent_data() {
+        Some(inner) => Some(UserAgent {
+            brands: from_js(inner.brands())?,
+            mobile: inner.mobile(),
+            platform: inner.platform(),
+            inner
+        }),
+        None => None
+    };
+
+    return Ok(Capabilities {
+        device_memory: nav.device_memory(),
+        hardware_concurrency: nav.hardware_concurrency().map(|x| x as usize),
+        max_touch_points: nav.max_touch_points(),
+        user_agent
+    })
+}
+
+/// Hardware capabilities of the device
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct Capabilities {
+    /// Approximate amount of device memory in gigabytes, if available.
+    ///
+    /// The value is rounded down to the nearest power of 2, and clamped between `0.25` and `8`
+    pub device_memory: Option<f64>,
+    /// Number of logical processors available to run threads on the device, if available.
+    pub hardware_concurrency: Option<usize>,
+    /// Maximum number of simultaneous touch contact points supported by the device, if available.
+    pub max_touch_points: Option<u32>,
+    /// Information about the browser and operating system, if the [User-Agent Client Hints API](https://developer.mozilla.org/en-US/docs/Web/API/User-Agent_Client_Hints_API) is available.
+    pub user_agent: Option<UserAgent>
+}
+
+impl Capabilities {
+    /// Returns the number of threads that can be run in parallel, defaulting to `1` if it isn't available.
+    #[inline]
+    pub fn available_parallelism (&self) -> usize {
+        return self.hardware_concurrency.unwrap_or(1).max(1)
+    }
+}
+
+/// A browser brand and it's version
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize)]
+pub struct Brand {
+    pub brand: String,
+    pub version: String
+}
+
+/// Low-entropy information about the browser and operating system
+#[derive(Debug, Clone)]
+pub struct UserAgent {
+    /// Brands of the browser
+    pub brands: Vec<Brand>,
+    /// Indicates if the browser is on a mobile device
+    pub mobile: bool,
+    /// Operating system of the device
+    pub platform: String,
+    inner: NavigatorUAData
+}
+
+impl UserAgent {
+    /// Returns the high-entropy information about the browser and operating system.
+    ///
+    /// The browser may ask the user for permission, or decide to not return some of the values.
+    pub async fn high_entropy_values (&self) -> Result<HighEntropyValues> {
+        let hints = Array::of5(
+            &JsValue::from_str("architecture"),
+            &JsValue::from_str("bitness"),
+            &JsValue::from_str("model"),
+            &JsValue::from_str("platformVersion"),
+            &JsValue::from_str("fullVersionList")
+        );
+
+        let values = JsFuture::from(self.inner.get_high_entropy_values(&hints)).await?;
+        return from_js(values)
+    }
+}
+
+/// High-entropy information about the browser and operating system
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+#[non_exhaustive]
+pub struct HighEntropyValues {
+    /// Platform architecture (`"x86"`, `"arm"`, ...)
+    pub architecture: Option<String>,
+    /// Architecture bitness (`"32"` or `"64"`)
+    pub bitness: Option<String>,
+    /// Device model
+    pub model: Option<String>,
+    /// Operating system version
+    pub platform_version: Option<String>,
+    /// Brands of the browser, with their full version
+    pub full_version_list: Vec<Brand>
+}
+
+/// Battery level at or below which, while discharging, [`PowerHint::Conserve`] is advised
+const CONSERVE_LEVEL: f64 = 0.2;
+/// Battery level at or below which, while discharging, [`PowerHint::Critical`] is advised
+const CRITICAL_LEVEL: f64 = 0.1;
+
+/// Advice on how much work the application should perform, based on the device's power state
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub enum PowerHint {
+    /// No restrictions are advised
+    #[default]
+    Normal,
+    /// The user asked for reduced data usage, the page is hidden or the battery is low.
+    /// Intervals and animation loops should be throttled.
+    Conserve,
+    /// The battery is almost depleted. Only essential work should be performed.
+    Critical
+}
+
+/// Returns a stream of [`PowerHint`]s, assembled from the battery level and charging state, the network's save-data preference
+/// and the page's visibility.
+///
+/// The current hint is yielded first, and a new one every time it changes. Sources that aren't available on the current
+/// platform (like the battery inside web workers) are ignored.
+pub async fn power_advisor () -> Result<PowerAdvisor> {
+    let battery = match Battery::new().await {
+        Ok(battery) => Some(battery.inner),
+        Err(e) if e.kind() == ErrorKind::NotSupported => None,
+        Err(e) => return Err(e)
+    };
+
+    let connection = js_sys::Reflect::get(&navigator(), &JsValue::from_str("connection"))?
+        .dyn_into::<EventTarget>()
+        .ok();
+
+    let document = match is_worker() {
+        true => None,
+        false => Some(document()?)
+    };
+
+    let sources = Rc::new(PowerSources { battery, connection, document });
+    let last = Rc::new(Cell::new(sources.hint()));
+    let (send, recv) = local_channel();
+    let _ = send.try_send(last.get());
+
+    let mut listeners = Vec::with_capacity(4);
+    let mut listen = |target: &EventTarget, ty: &'static str| -> Result<()> {
+        let (sources, last, send) = (sources.clone(), last.clone(), send.clone());
+        listeners.push(Listener::new(target, ty, move |_| {
+            let hint = sources.hint();
+            if last.replace(hint) != hint {
+                let _ = send.try_send(hint);
+            }
+        })?);
+        return Ok(())
+    };
+
+    if let Some(ref battery) = sources.battery {
+        listen(battery, "levelchange")?;
+        listen(battery, "chargingchange")?;
+    }
+    if let Some(ref connection) = sources.connection {
+        listen(connection, "change")?;
+    }
+    if let Some(ref document) = sources.document {
+        listen(document, "visibilitychange")?;
+    }
+
+    return Ok(PowerAdvisor { sources, _listeners: listeners, recv })
+}
+
+struct PowerSources {
+    battery: Option<web_sys::BatteryManager>,
+    connection: Option<EventTarget>,
+    document: Option<web_sys::Document>
+}
+
+impl PowerSources {
+    fn hint (&self) -> PowerHint {
+        let mut hint = PowerHint::Normal;
+
+        if let Some(ref battery) = self.battery && !battery.charging() {
+            let level = battery.level();
+            if level <= CRITICAL_LEVEL {
+                return PowerHint::Critical
+            } else if level <= CONSERVE_LEVEL {
+                hint = PowerHint::Conserve
+            }
+        }
+
+        let save_data = self.connection.as_ref()
+            .and_then(|x| js_sys::Reflect::get(x, &JsValue::from_str("saveData")).ok())
+            .is_some_and(|x| x.is_truthy());
+
+        if save_data || self.document.as_ref().is_some_and(|x| x.hidden()) {
+            hint = PowerHint::Conserve
+        }
+
+        return hint
+    }
+}
+
+/// Stream of [`PowerHint`]s, created by [`power_advisor`]
+pub struct PowerAdvisor {
+    sources: Rc<PowerSources>,
+    _listeners: Vec<Listener>,
+    recv: LocalReceiver<PowerHint>
+}
+
+impl PowerAdvisor {
+    /// Returns the current power hint
+    #[inline]
+    pub fn hint (&self) -> PowerHint {
+        return self.sources.hint()
+    }
+}
+
+impl Stream for PowerAdvisor {
+    type Item = PowerHint;
+
+    #[inline]
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.recv.poll_next_unpin(cx)
+    }
+}
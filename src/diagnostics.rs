@@ -0,0 +1,137 @@
+use docfg::docfg;
+#[cfg(debug_assertions)]
+use std::{cell::RefCell, collections::BTreeMap, fmt::Display};
+
+#[cfg(debug_assertions)]
+thread_local! {
+    static REGISTRY: RefCell<BTreeMap<&'static str, Counts>> = RefCell::new(BTreeMap::new());
+}
+
+#[cfg(debug_assertions)]
+#[derive(Debug, Clone, Default)]
+struct Counts {
+    live: usize,
+    total: usize,
+    failures: usize,
+    last_failure: Option<String>
+}
+
+/// Registration of a closure created by rustww, tracked while it's alive.
+///
+/// On release builds, it's a zero-sized type that tracks nothing.
+#[derive(Debug)]
+pub(crate) struct Tracked {
+    #[cfg(debug_assertions)]
+    name: &'static str
+}
+
+impl Tracked {
+    /// Tracks a new registration of the specified kind, until the returned value is dropped
+    #[inline]
+    pub(crate) fn new (name: &'static str) -> Self {
+        #[cfg(debug_assertions)]
+        REGISTRY.with(|registry| {
+            let mut registry = registry.borrow_mut();
+            let counts = registry.entry(name).or_default();
+            counts.live += 1;
+            counts.total += 1;
+        });
+
+        #[cfg(not(debug_assertions))]
+        let _ = name;
+
+        return Self {
+            #[cfg(debug_assertions)]
+            name
+        }
+    }
+}
+
+#[cfg(debug_assertions)]
+impl Drop for Tracked {
+    #[inline]
+    fn drop(&mut self) {
+        let _ = REGISTRY.try_with(|registry| {
+            if let Some(counts) = registry.borrow_mut().get_mut(self.name) {
+                counts.live = counts.live.saturating_sub(1);
+            }
+        });
+    }
+}
+
+/// Records a failure of a background operation whose result nobody awaits (like the cancellation of a droped stream).
+///
+/// On release builds, failures aren't recorded.
+#[inline]
+pub(crate) fn failure (name: &'static str, error: &wasm_bindgen::JsValue) {
+    #[cfg(debug_assertions)]
+    let _ = REGISTRY.try_with(|registry| {
+        let mut registry = registry.borrow_mut();
+        let counts = registry.entry(name).or_default();
+        counts.failures += 1;
+        counts.last_failure = Some(crate::Error::from(error.clone()).to_string());
+    });
+
+    #[cfg(not(debug_assertions))]
+    let _ = (name, error);
+}
+
+/// Closure registrations of a single kind, as returned by [`dump`]
+#[docfg(debug_assertions)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Registration {
+    /// Kind of the registration (`"geo::watch"`, `"time::Interval"`, ...)
+    pub name: &'static str,
+    /// Registrations that are still alive (either in use or leaked)
+    pub live: usize,
+    /// Registrations created since the start of the program
+    pub total: usize,
+    /// Background operations of this kind that failed since the start of the program
+    pub failures: usize,
+    /// Message of the last failure, if any
+    pub last_failure: Option<String>
+}
+
+#[docfg(debug_assertions)]
+impl Display for Registration {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {} live ({} total)", self.name, self.live, self.total)?;
+        if let Some(ref last_failure) = self.last_failure {
+            write!(f, ", {} failures (last: {last_failure})", self.failures)?;
+        }
+        return Ok(())
+    }
+}
+
+/// Returns the closure registrations created by rustww on the current thread (watchers, intervals, listeners, custom streams, ...),
+/// and the failures of it's background operations, sorted by name.
+///
+/// A live count that keeps growing usually means that handles are being leaked (with `leak` or [`forget`](core::mem::forget))
+/// instead of dropped.
+/// ```ignore
+/// for registration in diagnostics::dump() {
+///     println!("{registration}");
+/// }
+/// ```
+#[docfg(debug_assertions)]
+pub fn dump () -> Vec<Registration> {
+    return REGISTRY.with(|registry| {
+        registry.borrow().iter()
+            .map(|(&name, counts)| Registration {
+                name,
+                live: counts.live,
+                total: counts.total,
+                failures: counts.failures,
+                last_failure: counts.last_failure.clone()
+            })
+            .collect()
+    })
+}
+
+/// Returns the number of live closure registrations created by rustww on the current thread
+#[docfg(debug_assertions)]
+#[inline]
+pub fn live () -> usize {
+    return REGISTRY.with(|registry| registry.borrow().values().map(|counts| counts.live).sum())
+}
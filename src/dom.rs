@@ -0,0 +1,198 @@
+use wasm_bindgen::{JsCast, prelude::Closure};
+use web_sys::{Document, Element, HtmlElement, Node, EventTarget, MouseEvent};
+use crate::{Result, Error, ErrorKind, scope::window, input::{EventStream, ListenOptions}, diagnostics::Tracked};
+
+flat_mod! { form, bind }
+
+/// Scrolling and element geometry
+pub mod scroll;
+
+/// Text selection and caret
+pub mod selection;
+
+/// Returns the current document
+#[inline]
+pub fn document () -> Result<Document> {
+    return match window()?.document() {
+        Some(document) => Ok(document),
+        None => Err(Error::new(ErrorKind::NotSupported, "current window doesn't have a document"))
+    }
+}
+
+/// Returns the document's `<body>` element
+#[inline]
+pub fn body () -> Result<HtmlElement> {
+    return match document()?.body() {
+        Some(body) => Ok(body),
+        None => Err(Error::new(ErrorKind::NotSupported, "current document doesn't have a body"))
+    }
+}
+
+/// Returns the first element that matches the CSS selector, if any
+#[inline]
+pub fn query (selector: &str) -> Result<Option<Element>> {
+    return Ok(document()?.query_selector(selector)?)
+}
+
+/// Creates a new element with the specified tag name
+#[inline]
+pub fn el (tag: &str) -> Result<El> {
+    return Ok(El { inner: document()?.create_element(tag)? })
+}
+
+/// Appends `child` as the last child of `parent`
+#[inline]
+pub fn append (parent: &Node, child: &Node) -> Result<()> {
+    let _ = parent.append_child(child)?;
+    return Ok(())
+}
+
+/// Removes the element from it's parent, if it has one
+#[inline]
+pub fn remove (element: &Element) {
+    element.remove()
+}
+
+/// A builder-like wrapper arround an [`Element`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct El {
+    inner: Element
+}
+
+impl El {
+    /// Replaces the element's contents with the text
+    #[inline]
+    pub fn text (self, text: &str) -> Self {
+        self.inner.set_text_content(Some(text));
+        self
+    }
+
+    /// Sets the value of an attribute
+    #[inline]
+    pub fn attr (self, name: &str, value: &str) -> Result<Self> {
+        self.inner.set_attribute(name, value)?;
+        return Ok(self)
+    }
+
+    /// Adds a class to the element
+    #[inline]
+    pub fn class (self, name: &str) -> Result<Self> {
+        self.inner.class_list().add_1(name)?;
+        return Ok(self)
+    }
+
+    /// Appends `child` as the last child of the element
+    #[inline]
+    pub fn child (self, child: impl AsRef<Node>) -> Result<Self> {
+        append(&self.inner, child.as_ref())?;
+        return Ok(self)
+    }
+
+    /// Appends the element as the last child of `parent`
+    #[inline]
+    pub fn append_to (self, parent: &Node) -> Result<Self> {
+        append(parent, &self.inner)?;
+        return Ok(self)
+    }
+
+    /// Removes the element from it's parent, if it has one
+    #[inline]
+    pub fn remove (&self) {
+        self.inner.remove()
+    }
+
+    /// Returns a stream of the element's click events
+    #[inline]
+    pub fn on_click (&self) -> Result<EventStream<MouseEvent>> {
+        return EventStream::new::<MouseEvent>(&self.inner, "click", ListenOptions::default())
+    }
+
+    /// Calls `f` every time an event of type `ty` is dispatched on the element, for as long as the returned guard is alive
+    #[inline]
+    pub fn listen<F: 'static + FnMut(web_sys::Event)> (&self, ty: &'static str, f: F) -> Result<Listener> {
+        return Listener::new(&self.inner, ty, f)
+    }
+
+    /// Returns the underlying [`Element`]
+    #[inline]
+    pub fn raw (&self) -> &Element {
+        return &self.inner
+    }
+
+    /// Returns the underlying [`Element`]
+    #[inline]
+    pub fn into_inner (self) -> Element {
+        return self.inner
+    }
+}
+
+impl AsRef<Node> for El {
+    #[inline]
+    fn as_ref(&self) -> &Node {
+        &self.inner
+    }
+}
+
+impl AsRef<Element> for El {
+    #[inline]
+    fn as_ref(&self) -> &Element {
+        &self.inner
+    }
+}
+
+impl From<Element> for El {
+    #[inline]
+    fn from(inner: Element) -> Self {
+        Self { inner }
+    }
+}
+
+/// Guard of an event listener.
+///
+/// When droped, the listener will be removed, releasing all the memory of it's closure.
+pub struct Listener {
+    target: EventTarget,
+    ty: &'static str,
+    closure: Closure<dyn FnMut(web_sys::Event)>,
+    _tracked: Tracked
+}
+
+impl Listener {
+    /// Calls `f` every time an event of type `ty` is dispatched on `target`, for as long as the listener is alive
+    pub fn new<F: 'static + FnMut(web_sys::Event)> (target: &EventTarget, ty: &'static str, f: F) -> Result<Self> {
+        let closure = Closure::<dyn FnMut(web_sys::Event)>::new(f);
+
+        let listener: &js_sys::Function;
+        cfg_if::cfg_if! {
+            if #[cfg(debug_assertions)] {
+                listener = closure.as_ref().dyn_ref().unwrap();
+            } else {
+                listener = closure.as_ref().unchecked_ref();
+            }
+        }
+
+        target.add_event_listener_with_callback(ty, listener)?;
+        return Ok(Self { target: target.clone(), ty, closure, _tracked: Tracked::new("dom::Listener") })
+    }
+
+    /// Leaks the listener, keeping it registered for the rest of the target's lifetime.
+    #[inline]
+    pub fn leak (self) {
+        core::mem::forget(self)
+    }
+}
+
+impl Drop for Listener {
+    fn drop(&mut self) {
+        let listener: &js_sys::Function;
+        cfg_if::cfg_if! {
+            if #[cfg(debug_assertions)] {
+                listener = self.closure.as_ref().dyn_ref().unwrap();
+            } else {
+                listener = self.closure.as_ref().unchecked_ref();
+            }
+        }
+
+        let _ = self.target.remove_event_listener_with_callback(self.ty, listener);
+    }
+}
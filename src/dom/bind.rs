@@ -0,0 +1,83 @@
+use std::{rc::Rc, cell::Cell};
+use wasm_bindgen::JsCast;
+use web_sys::{HtmlElement, HtmlInputElement, HtmlTextAreaElement};
+use crate::{Result, reactive::{Signal, Effect}};
+use super::Listener;
+
+/// Guard of a binding created by [`bind_input`].
+///
+/// When droped, the element and the signal stop being synced.
+pub struct InputBinding {
+    _listeners: [Listener; 3],
+    _effect: Effect
+}
+
+impl InputBinding {
+    /// Leaks the binding, keeping the element and the signal synced for the rest of the program
+    #[inline]
+    pub fn leak (self) {
+        core::mem::forget(self)
+    }
+}
+
+/// Syncs the value of an `<input>`, a `<textarea>` or a `contenteditable` element with `signal`, in both directions.
+///
+/// The element is initialized with the signal's value. While an IME composition is in progress (typing CJK characters, for example),
+/// the signal isn't updated, and it receives the composed text once the composition ends.
+/// ```ignore
+/// let name = Signal::new(String::new());
+/// let _binding = bind_input(&input, name.clone())?;
+/// let _greeting = Effect::new(&name, |name| log!("Hello, {name}!"));
+/// ```
+pub fn bind_input (element: &HtmlElement, signal: Signal<String>) -> Result<InputBinding> {
+    let composing = Rc::new(Cell::new(false));
+
+    let my_composing = composing.clone();
+    let start = Listener::new(element, "compositionstart", move |_| my_composing.set(true))?;
+
+    let my_element = element.clone();
+    let my_signal = signal.clone();
+    let my_composing = composing.clone();
+    let end = Listener::new(element, "compositionend", move |_| {
+        my_composing.set(false);
+        my_signal.set(read_value(&my_element))
+    })?;
+
+    let my_element = element.clone();
+    let my_signal = signal.clone();
+    let input = Listener::new(element, "input", move |evt| {
+        let is_composing = evt.dyn_ref::<web_sys::InputEvent>().is_some_and(web_sys::InputEvent::is_composing);
+        if !is_composing && !composing.get() {
+            my_signal.set(read_value(&my_element))
+        }
+    })?;
+
+    let my_element = element.clone();
+    let effect = Effect::new(&signal, move |value: &String| {
+        // writing the same value would move the caret to the end
+        if read_value(&my_element) != *value {
+            write_value(&my_element, value)
+        }
+    });
+
+    return Ok(InputBinding { _listeners: [start, end, input], _effect: effect })
+}
+
+fn read_value (element: &HtmlElement) -> String {
+    if let Some(input) = element.dyn_ref::<HtmlInputElement>() {
+        return input.value()
+    } else if let Some(textarea) = element.dyn_ref::<HtmlTextAreaElement>() {
+        return textarea.value()
+    }
+    return element.inner_text()
+}
+
+fn write_value (element: &HtmlElement, value: &str) {
+    if let Some(input) = element.dyn_ref::<HtmlInputElement>() {
+        input.set_value(value)
+    } else if let Some(textarea) = element.dyn_ref::<HtmlTextAreaElement>() {
+        textarea.set_value(value)
+    } else {
+        element.set_inner_text(value)
+    }
+}
@@ -0,0 +1,224 @@
+use std::{marker::PhantomData, fmt::Display};
+use serde::{de::{self, DeserializeOwned, IntoDeserializer, value::{MapDeserializer, SeqDeserializer}}, forward_to_deserialize_any};
+use wasm_bindgen::JsCast;
+use web_sys::HtmlFormElement;
+use crate::{Result, Error, ErrorKind, input::{EventStream, ListenOptions}, io::{Request, Response, FetchError, Method}};
+use super::query;
+
+/// A `<form>` element, whose fields are read into a `T`.
+/// 
+/// Field values are parsed into the type expected by `T` (numbers, booleans, ...). Fields that appear multiple times
+/// (like checkboxes sharing a name) can be read into sequences, and empty fields into `None`. File fields are skipped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Form<T> {
+    inner: HtmlFormElement,
+    _phtm: PhantomData<fn() -> T>
+}
+
+impl<T: DeserializeOwned> Form<T> {
+    /// Creates a new [`Form`] from a `<form>` element
+    #[inline]
+    pub fn new (inner: HtmlFormElement) -> Self {
+        return Self { inner, _phtm: PhantomData }
+    }
+
+    /// Returns the first `<form>` element that matches the CSS selector
+    pub fn query (selector: &str) -> Result<Self> {
+        return match query(selector)? {
+            Some(element) => Ok(Self::new(element.dyn_into().map_err(Error::from)?)),
+            None => Err(Error::new(ErrorKind::NotSupported, "no form element matches the selector"))
+        }
+    }
+
+    /// Returns the underlying [`HtmlFormElement`]
+    #[inline]
+    pub fn raw (&self) -> &HtmlFormElement {
+        return &self.inner
+    }
+
+    /// Returns the current fields of the form, as a [`FormData`](web_sys::FormData)
+    #[inline]
+    pub fn form_data (&self) -> Result<web_sys::FormData> {
+        return Ok(web_sys::FormData::new_with_form(&self.inner)?)
+    }
+
+    /// Reads the current fields of the form into a `T`
+    #[inline]
+    pub fn read (&self) -> Result<T> {
+        return read_form(&self.inner)
+    }
+
+    /// Returns a stream of the form's submissions, with their fields read into a `T`.
+    /// 
+    /// The default action of the submissions (navigating to the form's action) is prevented.
+    pub fn submissions (&self) -> Result<EventStream<Result<T>>> where T: 'static {
+        let form = self.inner.clone();
+        return EventStream::with_map(&self.inner, "submit", ListenOptions::default(), move |evt: web_sys::Event| {
+            evt.prevent_default();
+            read_form::<T>(&form)
+        })
+    }
+
+    /// Submits the current fields of the form to it's action url with it's method, via a fetch [`Request`].
+    ///
+    /// As with native submissions, the fields of `GET` forms replace the query of the action url.
+    /// Forms with the `dialog` method don't submit to the network, so they return an error.
+    pub async fn submit (&self) -> ::core::result::Result<Response, FetchError> {
+        let method = self.inner.method();
+        if method.eq_ignore_ascii_case("dialog") {
+            return Err(Error::new(ErrorKind::NotSupported, "dialog forms can't be submitted via fetch").into())
+        }
+
+        let data = self.form_data()?;
+        let method = Method::from(method.as_str());
+        let action = self.inner.action();
+
+        if method == Method::Get {
+            let params = web_sys::UrlSearchParams::new_with_str_sequence_sequence(&data)?;
+            let url = web_sys::Url::new(&action)?;
+            url.set_search(&String::from(params.to_string()));
+            return Request::get(&url.href()).await
+        }
+
+        let mut req = Request::new();
+        req.method(method).body(data);
+        return req.fetch(&action).await
+    }
+}
+
+fn read_form<T: DeserializeOwned> (form: &HtmlFormElement) -> Result<T> {
+    let data = web_sys::FormData::new_with_form(form)?;
+    let mut fields = Vec::<(String, Vec<String>)>::new();
+
+    if let Some(iter) = js_sys::try_iter(&data)? {
+        for entry in iter {
+            let entry = entry?.unchecked_into::<js_sys::Array>();
+            let (key, value) = match (entry.get(0).as_string(), entry.get(1).as_string()) {
+                (Some(key), Some(value)) => (key, value),
+                _ => continue
+            };
+
+            match fields.iter_mut().find(|(x, _)| x == &key) {
+                Some((_, values)) => values.push(value),
+                None => fields.push((key, vec![value]))
+            }
+        }
+    }
+
+    let de = MapDeserializer::new(fields.into_iter().map(|(key, values)| (key, Field(values))));
+    return T::deserialize(de).map_err(|e: FieldError| Error::with_source(ErrorKind::Serde, e))
+}
+
+/// Error deserializing the fields of a form
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct FieldError (String);
+
+impl Display for FieldError {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for FieldError {}
+
+impl de::Error for FieldError {
+    #[inline]
+    fn custom<E: Display> (msg: E) -> Self {
+        return Self(msg.to_string())
+    }
+}
+
+/// Values of a form field
+struct Field (Vec<String>);
+
+impl Field {
+    #[inline]
+    fn single (&self) -> ::core::result::Result<&str, FieldError> {
+        return match self.0.last() {
+            Some(x) => Ok(x),
+            None => Err(de::Error::custom("missing field value"))
+        }
+    }
+}
+
+impl<'de> IntoDeserializer<'de, FieldError> for Field {
+    type Deserializer = Self;
+
+    #[inline]
+    fn into_deserializer(self) -> Self::Deserializer {
+        self
+    }
+}
+
+macro_rules! impl_parse {
+    ($($f:ident => $visit:ident as $ty:ty),+) => {
+        $(
+            fn $f<V: de::Visitor<'de>> (self, visitor: V) -> ::core::result::Result<V::Value, Self::Error> {
+                let value = self.single()?;
+                return match value.trim().parse::<$ty>() {
+                    Ok(x) => visitor.$visit(x),
+                    Err(e) => Err(de::Error::custom(format!("invalid value `{value}`: {e}")))
+                }
+            }
+        )+
+    };
+}
+
+impl<'de> de::Deserializer<'de> for Field {
+    type Error = FieldError;
+
+    fn deserialize_any<V: de::Visitor<'de>> (mut self, visitor: V) -> ::core::result::Result<V::Value, Self::Error> {
+        if self.0.len() == 1 {
+            return visitor.visit_string(self.0.pop().unwrap())
+        }
+        return self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_seq<V: de::Visitor<'de>> (self, visitor: V) -> ::core::result::Result<V::Value, Self::Error> {
+        return visitor.visit_seq(SeqDeserializer::new(self.0.into_iter()))
+    }
+
+    fn deserialize_option<V: de::Visitor<'de>> (self, visitor: V) -> ::core::result::Result<V::Value, Self::Error> {
+        if self.0.iter().all(String::is_empty) {
+            return visitor.visit_none()
+        }
+        return visitor.visit_some(self)
+    }
+
+    fn deserialize_bool<V: de::Visitor<'de>> (self, visitor: V) -> ::core::result::Result<V::Value, Self::Error> {
+        // Checked checkboxes are submitted as `on` by default
+        return match self.single()? {
+            "on" | "true" | "1" => visitor.visit_bool(true),
+            "off" | "false" | "0" | "" => visitor.visit_bool(false),
+            other => Err(de::Error::custom(format!("invalid boolean `{other}`")))
+        }
+    }
+
+    fn deserialize_enum<V: de::Visitor<'de>> (self, _name: &'static str, _variants: &'static [&'static str], visitor: V) -> ::core::result::Result<V::Value, Self::Error> {
+        return visitor.visit_enum(self.single()?.to_string().into_deserializer())
+    }
+
+    fn deserialize_newtype_struct<V: de::Visitor<'de>> (self, _name: &'static str, visitor: V) -> ::core::result::Result<V::Value, Self::Error> {
+        return visitor.visit_newtype_struct(self)
+    }
+
+    impl_parse! {
+        deserialize_i8 => visit_i8 as i8,
+        deserialize_i16 => visit_i16 as i16,
+        deserialize_i32 => visit_i32 as i32,
+        deserialize_i64 => visit_i64 as i64,
+        deserialize_u8 => visit_u8 as u8,
+        deserialize_u16 => visit_u16 as u16,
+        deserialize_u32 => visit_u32 as u32,
+        deserialize_u64 => visit_u64 as u64,
+        deserialize_f32 => visit_f32 as f32,
+        deserialize_f64 => visit_f64 as f64,
+        deserialize_char => visit_char as char
+    }
+
+    forward_to_deserialize_any! {
+        i128 u128 str string bytes byte_buf unit unit_struct
+        tuple tuple_struct map struct identifier ignored_any
+    }
+}
@@ -0,0 +1,215 @@
+use std::{rc::Rc, cell::Cell, pin::Pin, task::{Context, Poll}};
+use futures::{Stream, StreamExt};
+use wasm_bindgen::{JsCast, prelude::Closure};
+use web_sys::{Element, Window, ScrollToOptions, ScrollIntoViewOptions, ScrollLogicalPosition};
+use crate::{Result, scope::{window, request_animation_frame, cancel_animation_frame}, math::Vec2d, utils::{LocalSender, LocalReceiver, local_channel}};
+use super::Listener;
+
+pub use web_sys::ScrollBehavior;
+
+/// Alignment of an element scrolled into view, along one axis
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ScrollAlign {
+    /// Aligns the start of the element with the start of the scrollable area
+    #[default]
+    Start,
+    /// Centers the element within the scrollable area
+    Center,
+    /// Aligns the end of the element with the end of the scrollable area
+    End,
+    /// Scrolls the minimum amount necessary for the element to become visible
+    Nearest
+}
+
+impl From<ScrollAlign> for ScrollLogicalPosition {
+    #[inline]
+    fn from(value: ScrollAlign) -> Self {
+        return match value {
+            ScrollAlign::Start => Self::Start,
+            ScrollAlign::Center => Self::Center,
+            ScrollAlign::End => Self::End,
+            ScrollAlign::Nearest => Self::Nearest
+        }
+    }
+}
+
+/// An axis-aligned rectangle, in CSS pixels
+#[derive(Debug, Clone, Copy)]
+pub struct Rect {
+    /// Top-left corner of the rectangle
+    pub origin: Vec2d,
+    /// Width and height of the rectangle
+    pub size: Vec2d
+}
+
+impl Rect {
+    /// Returns the bottom-right corner of the rectangle
+    #[inline]
+    pub fn end (&self) -> Vec2d {
+        return self.origin + self.size
+    }
+
+    /// Returns the center of the rectangle
+    #[inline]
+    pub fn center (&self) -> Vec2d {
+        return self.origin + self.size * 0.5
+    }
+}
+
+impl From<web_sys::DomRect> for Rect {
+    #[inline]
+    fn from(rect: web_sys::DomRect) -> Self {
+        return Self {
+            origin: Vec2d::new(rect.x(), rect.y()),
+            size: Vec2d::new(rect.width(), rect.height())
+        }
+    }
+}
+
+/// Returns the size of the window's viewport, in CSS pixels
+pub fn viewport_size () -> Result<Vec2d> {
+    let window = window()?;
+    let width = window.inner_width()?.as_f64().unwrap_or_default();
+    let height = window.inner_height()?.as_f64().unwrap_or_default();
+    return Ok(Vec2d::new(width, height))
+}
+
+/// Returns the bounding rectangle of the element, relative to the viewport
+#[inline]
+pub fn bounding_rect (element: &Element) -> Rect {
+    return Rect::from(element.get_bounding_client_rect())
+}
+
+/// Returns the current scroll position of the window
+#[inline]
+pub fn scroll_position () -> Result<Vec2d> {
+    return ScrollTarget::Window(window()?).position()
+}
+
+/// Scrolls the window to the specified position
+#[inline]
+pub fn scroll_to (position: Vec2d, behavior: ScrollBehavior) -> Result<()> {
+    window()?.scroll_to_with_scroll_to_options(&scroll_options(position, behavior));
+    return Ok(())
+}
+
+/// Scrolls the window by the specified offset
+#[inline]
+pub fn scroll_by (offset: Vec2d, behavior: ScrollBehavior) -> Result<()> {
+    window()?.scroll_by_with_scroll_to_options(&scroll_options(offset, behavior));
+    return Ok(())
+}
+
+/// Scrolls the element's contents to the specified position
+#[inline]
+pub fn scroll_element_to (element: &Element, position: Vec2d, behavior: ScrollBehavior) {
+    element.scroll_to_with_scroll_to_options(&scroll_options(position, behavior));
+}
+
+/// Scrolls the element's ancestors so that the element becomes visible, aligned as specified
+#[inline]
+pub fn scroll_into_view (element: &Element, block: ScrollAlign, inline: ScrollAlign, behavior: ScrollBehavior) {
+    let mut options = ScrollIntoViewOptions::new();
+    options.block(block.into()).inline(inline.into()).behavior(behavior);
+    element.scroll_into_view_with_scroll_into_view_options(&options);
+}
+
+#[inline]
+fn scroll_options (position: Vec2d, behavior: ScrollBehavior) -> ScrollToOptions {
+    let mut options = ScrollToOptions::new();
+    options.left(position.x()).top(position.y()).behavior(behavior);
+    return options
+}
+
+enum ScrollTarget {
+    Window (Window),
+    Element (Element)
+}
+
+impl ScrollTarget {
+    fn position (&self) -> Result<Vec2d> {
+        return match self {
+            Self::Window(window) => Ok(Vec2d::new(window.scroll_x()?, window.scroll_y()?)),
+            Self::Element(element) => Ok(Vec2d::new(element.scroll_left() as f64, element.scroll_top() as f64))
+        }
+    }
+}
+
+struct WatchState {
+    target: ScrollTarget,
+    pending: Cell<Option<i32>>,
+    send: LocalSender<Vec2d>
+}
+
+/// Returns a stream of the window's scroll positions.
+///
+/// Scroll events are throttled, yielding at most one position per animation frame.
+#[inline]
+pub fn watch_scroll () -> Result<ScrollWatcher> {
+    return ScrollWatcher::new(ScrollTarget::Window(window()?))
+}
+
+/// Returns a stream of the element's scroll positions.
+///
+/// Scroll events are throttled, yielding at most one position per animation frame.
+#[inline]
+pub fn watch_element_scroll (element: &Element) -> Result<ScrollWatcher> {
+    return ScrollWatcher::new(ScrollTarget::Element(element.clone()))
+}
+
+/// Watcher of scroll positions, created by [`watch_scroll`] or [`watch_element_scroll`]
+pub struct ScrollWatcher {
+    _listener: Listener,
+    state: Rc<WatchState>,
+    _frame: Closure<dyn FnMut(f64)>,
+    recv: LocalReceiver<Vec2d>
+}
+
+impl ScrollWatcher {
+    fn new (target: ScrollTarget) -> Result<Self> {
+        let event_target = match target {
+            ScrollTarget::Window(ref window) => window.clone().unchecked_into::<web_sys::EventTarget>(),
+            ScrollTarget::Element(ref element) => element.clone().unchecked_into::<web_sys::EventTarget>()
+        };
+
+        let (send, recv) = local_channel();
+        let state = Rc::new(WatchState { target, pending: Cell::new(None), send });
+
+        let frame_state = state.clone();
+        let frame = Closure::<dyn FnMut(f64)>::new(move |_| {
+            frame_state.pending.set(None);
+            if let Ok(position) = frame_state.target.position() {
+                let _ = frame_state.send.try_send(position);
+            }
+        });
+
+        let listener_state = state.clone();
+        let frame_fn = frame.as_ref().unchecked_ref::<js_sys::Function>().clone();
+        let listener = Listener::new(&event_target, "scroll", move |_| {
+            if listener_state.pending.get().is_some() { return }
+            if let Ok(handle) = request_animation_frame(&frame_fn) {
+                listener_state.pending.set(Some(handle));
+            }
+        })?;
+
+        return Ok(Self { _listener: listener, state, _frame: frame, recv })
+    }
+}
+
+impl Stream for ScrollWatcher {
+    type Item = Vec2d;
+
+    #[inline]
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.recv.poll_next_unpin(cx)
+    }
+}
+
+impl Drop for ScrollWatcher {
+    #[inline]
+    fn drop(&mut self) {
+        if let Some(handle) = self.state.pending.take() {
+            cancel_animation_frame(handle);
+        }
+    }
+}
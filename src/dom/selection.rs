@@ -0,0 +1,126 @@
+use web_sys::{Node, Range, Selection};
+use crate::{Result, Error, ErrorKind, input::{EventStream, ListenOptions}, math::Vec2d};
+use super::{document, scroll::Rect};
+
+/// Snapshot of the document's text selection
+#[derive(Debug, Clone)]
+pub struct TextSelection {
+    /// Selected text
+    pub text: String,
+    /// `true` if the selection is a caret (it's start and end are at the same position)
+    pub collapsed: bool,
+    /// Bounding rectangle of the selection, relative to the viewport. If it's collapsed, the rectangle has zero width.
+    pub rect: Option<Rect>,
+    /// Selected ranges. Most browsers only support a single range.
+    pub ranges: Vec<Range>
+}
+
+impl From<Selection> for TextSelection {
+    fn from(selection: Selection) -> Self {
+        let ranges = (0..selection.range_count())
+            .filter_map(|i| selection.get_range_at(i).ok())
+            .collect::<Vec<_>>();
+
+        let rect = ranges.iter()
+            .map(|range| Rect::from(range.get_bounding_client_rect()))
+            .reduce(|a, b| {
+                let origin = Vec2d::new(a.origin.x().min(b.origin.x()), a.origin.y().min(b.origin.y()));
+                let end = Vec2d::new(a.end().x().max(b.end().x()), a.end().y().max(b.end().y()));
+                Rect { origin, size: end - origin }
+            });
+
+        return Self {
+            text: String::from(selection.to_string()),
+            collapsed: selection.is_collapsed(),
+            rect,
+            ranges
+        }
+    }
+}
+
+/// Returns the document's [`Selection`]
+#[inline]
+pub fn raw () -> Result<Selection> {
+    return match document()?.get_selection()? {
+        Some(selection) => Ok(selection),
+        None => Err(Error::new(ErrorKind::NotSupported, "current document doesn't have a selection"))
+    }
+}
+
+/// Returns a snapshot of the current text selection
+#[inline]
+pub fn current () -> Result<TextSelection> {
+    return raw().map(TextSelection::from)
+}
+
+/// Returns the selected text
+#[inline]
+pub fn text () -> Result<String> {
+    return raw().map(|selection| String::from(selection.to_string()))
+}
+
+/// Returns the bounding rectangle of the selection's first range, relative to the viewport, if there's a selection
+#[inline]
+pub fn bounding_rect () -> Result<Option<Rect>> {
+    let selection = raw()?;
+    if selection.range_count() == 0 {
+        return Ok(None)
+    }
+    return Ok(Some(Rect::from(selection.get_range_at(0)?.get_bounding_client_rect())))
+}
+
+/// Replaces the selection with the range
+#[inline]
+pub fn select (range: &Range) -> Result<()> {
+    let selection = raw()?;
+    selection.remove_all_ranges()?;
+    return Ok(selection.add_range(range)?)
+}
+
+/// Selects the contents of `node`
+#[inline]
+pub fn select_contents (node: &Node) -> Result<()> {
+    return Ok(raw()?.select_all_children(node)?)
+}
+
+/// Selects from `offset_start` in `start` to `offset_end` in `end`.
+///
+/// Offsets are in characters for text nodes, and in child nodes for every other node.
+#[inline]
+pub fn select_between (start: &Node, offset_start: u32, end: &Node, offset_end: u32) -> Result<()> {
+    return Ok(raw()?.set_base_and_extent(start, offset_start, end, offset_end)?)
+}
+
+/// Collapses the selection into a caret at `offset` in `node`
+#[inline]
+pub fn set_caret (node: &Node, offset: u32) -> Result<()> {
+    return Ok(raw()?.collapse_with_offset(Some(node), offset)?)
+}
+
+/// Removes the selection
+#[inline]
+pub fn clear () -> Result<()> {
+    return Ok(raw()?.remove_all_ranges()?)
+}
+
+/// Returns a new range of the current document, which can be selected with [`select`]
+#[inline]
+pub fn range (start: &Node, offset_start: u32, end: &Node, offset_end: u32) -> Result<Range> {
+    let range = document()?.create_range()?;
+    range.set_start(start, offset_start)?;
+    range.set_end(end, offset_end)?;
+    return Ok(range)
+}
+
+/// Returns a stream of the document's selection changes (including caret movements), with a snapshot of the new selection
+pub fn watch () -> Result<EventStream<TextSelection>> {
+    let document = document()?;
+    let my_document = document.clone();
+
+    return EventStream::with_map(&document, "selectionchange", ListenOptions::default(), move |_: web_sys::Event| {
+        return match my_document.get_selection() {
+            Ok(Some(selection)) => TextSelection::from(selection),
+            _ => TextSelection { text: String::new(), collapsed: true, rect: None, ranges: Vec::new() }
+        }
+    })
+}
@@ -0,0 +1,200 @@
+use js_sys::Uint8Array;
+use wasm_bindgen::{JsValue, prelude::wasm_bindgen};
+use web_sys::{TextDecodeOptions, TextDecoderOptions};
+use crate::{Result, Error, ErrorKind, utils::TypedArrayExt};
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_name = btoa, catch)]
+    fn js_btoa (data: &str) -> ::core::result::Result<String, JsValue>;
+    #[wasm_bindgen(js_name = atob, catch)]
+    fn js_atob (data: &str) -> ::core::result::Result<String, JsValue>;
+}
+
+/// Encodes strings into UTF-8 bytes
+#[derive(Debug, Clone)]
+pub struct TextEncoder {
+    inner: web_sys::TextEncoder
+}
+
+impl TextEncoder {
+    /// Creates a new [`TextEncoder`]
+    #[inline]
+    pub fn new () -> Result<Self> {
+        return Ok(Self { inner: web_sys::TextEncoder::new()? })
+    }
+
+    /// Encodes the string into a JavaScript [`Uint8Array`]
+    #[inline]
+    pub fn encode (&self, input: &str) -> Uint8Array {
+        return Uint8Array::from(self.inner.encode_with_input(input).as_slice())
+    }
+
+    /// Encodes the string into a vector of bytes
+    #[inline]
+    pub fn encode_to_vec (&self, input: &str) -> Vec<u8> {
+        return self.inner.encode_with_input(input)
+    }
+}
+
+/// Options of a [`TextDecoder`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct DecoderOptions {
+    /// If `true`, decoding fails on invalid data, instead of replacing it with `U+FFFD`
+    pub fatal: bool,
+    /// If `true`, the byte order mark is kept in the output, instead of being skipped
+    pub ignore_bom: bool
+}
+
+/// Decodes bytes of a specific encoding into strings
+#[derive(Debug, Clone)]
+pub struct TextDecoder {
+    inner: web_sys::TextDecoder
+}
+
+impl TextDecoder {
+    /// Creates a new UTF-8 [`TextDecoder`]
+    #[inline]
+    pub fn new () -> Result<Self> {
+        return Ok(Self { inner: web_sys::TextDecoder::new()? })
+    }
+
+    /// Creates a new [`TextDecoder`] for the specified encoding label (`"utf-8"`, `"utf-16le"`, `"iso-8859-2"`, ...)
+    #[inline]
+    pub fn with_label (label: &str) -> Result<Self> {
+        return Self::with_options(label, DecoderOptions::default())
+    }
+
+    /// Creates a new [`TextDecoder`] for the specified encoding label and options
+    pub fn with_options (label: &str, options: DecoderOptions) -> Result<Self> {
+        let mut ops = TextDecoderOptions::new();
+        ops.fatal(options.fatal);
+        ops.ignore_bom(options.ignore_bom);
+
+        return Ok(Self { inner: web_sys::TextDecoder::new_with_label_and_options(label, &ops)? })
+    }
+
+    /// Returns the name of the decoder's encoding
+    #[inline]
+    pub fn encoding (&self) -> String {
+        return self.inner.encoding()
+    }
+
+    /// Returns `true` if the decoder fails on invalid data
+    #[inline]
+    pub fn fatal (&self) -> bool {
+        return self.inner.fatal()
+    }
+
+    /// Returns `true` if the decoder keeps the byte order mark
+    #[inline]
+    pub fn ignore_bom (&self) -> bool {
+        return self.inner.ignore_bom()
+    }
+
+    /// Decodes the full contents of `input`
+    #[inline]
+    pub fn decode (&self, input: &[u8]) -> Result<String> {
+        let input = unsafe { Uint8Array::view(input) };
+        return Ok(self.inner.decode_with_buffer_source(&input)?)
+    }
+
+    /// Decodes a chunk of a larger input. Incomplete characters at the end of the chunk
+    /// are kept until the next call, or until [`finish`](TextDecoder::finish) is called.
+    pub fn decode_chunk (&self, input: &[u8]) -> Result<String> {
+        let mut ops = TextDecodeOptions::new();
+        ops.stream(true);
+
+        let input = unsafe { Uint8Array::view(input) };
+        return Ok(self.inner.decode_with_buffer_source_and_options(&input, &ops)?)
+    }
+
+    /// Flushes the characters kept by [`decode_chunk`](TextDecoder::decode_chunk)
+    #[inline]
+    pub fn finish (&self) -> Result<String> {
+        return Ok(self.inner.decode()?)
+    }
+}
+
+/// Encodes a string of Latin-1 characters into base64, via JavaScript's `btoa`.
+///
+/// Fails if the string contains characters outside of the Latin-1 range. To encode arbitrary data, use [`encode_base64`]
+#[inline]
+pub fn btoa (data: &str) -> Result<String> {
+    return Ok(js_btoa(data)?)
+}
+
+/// Decodes a base64 string into a string of Latin-1 characters, via JavaScript's `atob`.
+///
+/// To decode arbitrary data, use [`decode_base64`]
+#[inline]
+pub fn atob (data: &str) -> Result<String> {
+    return Ok(js_atob(data)?)
+}
+
+/// Encodes the bytes into a base64 string
+#[inline]
+pub fn encode_base64 (data: &[u8]) -> Result<String> {
+    let binary = data.iter().map(|&x| x as char).collect::<String>();
+    return btoa(&binary)
+}
+
+/// Decodes a base64 string into bytes
+#[inline]
+pub fn decode_base64 (data: &str) -> Result<Vec<u8>> {
+    let binary = atob(data)?;
+    return Ok(binary.chars().map(|x| x as u8).collect())
+}
+
+/// Encodes the bytes into an unpadded, URL-safe base64 string (as used by JWTs and PKCE)
+#[inline]
+pub fn encode_base64_url (data: &[u8]) -> Result<String> {
+    let result = encode_base64(data)?
+        .trim_end_matches('=')
+        .replace('+', "-")
+        .replace('/', "_");
+    return Ok(result)
+}
+
+/// Decodes a URL-safe base64 string, with or without padding, into bytes
+pub fn decode_base64_url (data: &str) -> Result<Vec<u8>> {
+    let mut data = data.replace('-', "+").replace('_', "/");
+    while data.len() % 4 != 0 {
+        data.push('=');
+    }
+    return decode_base64(&data)
+}
+
+/// Encodes the bytes into a lowercase hexadecimal string
+pub fn encode_hex (data: &[u8]) -> String {
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+    let mut result = String::with_capacity(2 * data.len());
+    for byte in data {
+        result.push(DIGITS[(byte >> 4) as usize] as char);
+        result.push(DIGITS[(byte & 0xf) as usize] as char);
+    }
+    return result
+}
+
+/// Decodes a hexadecimal string (either lowercase or uppercase) into bytes
+pub fn decode_hex (data: &str) -> Result<Vec<u8>> {
+    #[inline]
+    fn digit (c: u8) -> Result<u8> {
+        return match c {
+            b'0'..=b'9' => Ok(c - b'0'),
+            b'a'..=b'f' => Ok(c - b'a' + 10),
+            b'A'..=b'F' => Ok(c - b'A' + 10),
+            _ => Err(Error::new(ErrorKind::Serde, "invalid hexadecimal digit"))
+        }
+    }
+
+    let data = data.as_bytes();
+    if data.len() % 2 != 0 {
+        return Err(Error::new(ErrorKind::Serde, "hexadecimal string has an odd length"))
+    }
+
+    return data.chunks_exact(2)
+        .map(|x| Ok((digit(x[0])? << 4) | digit(x[1])?))
+        .collect()
+}
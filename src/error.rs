@@ -0,0 +1,186 @@
+use std::{fmt::{Debug, Display}, rc::Rc};
+use wasm_bindgen::{JsValue, JsCast};
+
+/// Kind of an [`Error`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// The user or the browser denied access to the requested resource
+    Permission,
+    /// The requested functionality isn't supported by the browser or the current context
+    NotSupported,
+    /// The operation was aborted or timed out
+    Abort,
+    /// A generic JavaScript error
+    Js,
+    /// An input/output error
+    Io,
+    /// An error serializing or deserializing a value
+    Serde
+}
+
+/// Error returned by the functions of this crate.
+///
+/// Errors wrap the underlying JavaScript value (usually an `Error` or `DOMException`), classified into an [`ErrorKind`],
+/// and optionally the Rust error that caused them.
+#[derive(Clone)]
+pub struct Error {
+    kind: ErrorKind,
+    value: JsValue,
+    source: Option<Rc<dyn std::error::Error>>
+}
+
+impl Error {
+    /// Creates a new error of the specified kind, with a JavaScript `Error` with the specified message as it's value.
+    #[inline]
+    pub fn new (kind: ErrorKind, msg: &str) -> Self {
+        return Self::from_js(kind, js_sys::Error::new(msg).into())
+    }
+
+    /// Creates a new error of the specified kind from a JavaScript value
+    #[inline]
+    pub fn from_js (kind: ErrorKind, value: JsValue) -> Self {
+        return Self { kind, value, source: None }
+    }
+
+    /// Creates a new error of the specified kind, caused by a Rust error.
+    #[inline]
+    pub fn with_source<E: 'static + std::error::Error> (kind: ErrorKind, source: E) -> Self {
+        let mut this = Self::new(kind, &source.to_string());
+        this.source = Some(Rc::new(source));
+        return this
+    }
+
+    /// Returns the kind of the error
+    #[inline]
+    pub fn kind (&self) -> ErrorKind {
+        return self.kind
+    }
+
+    /// Returns the underlying JavaScript value of the error
+    #[inline]
+    pub fn as_js (&self) -> &JsValue {
+        return &self.value
+    }
+
+    /// Returns the underlying JavaScript value of the error
+    #[inline]
+    pub fn into_js (self) -> JsValue {
+        return self.value
+    }
+
+    /// Returns the name of the underlying JavaScript error (`"TypeError"`, `"NotAllowedError"`, ...), if available.
+    #[inline]
+    pub fn name (&self) -> Option<String> {
+        return js_name(&self.value)
+    }
+}
+
+impl From<JsValue> for Error {
+    fn from(value: JsValue) -> Self {
+        let kind = match js_name(&value).as_deref() {
+            Some("NotAllowedError" | "SecurityError") => ErrorKind::Permission,
+            Some("NotSupportedError" | "NotFoundError") => ErrorKind::NotSupported,
+            Some("AbortError" | "TimeoutError") => ErrorKind::Abort,
+            Some("NetworkError" | "NotReadableError" | "InvalidStateError" | "QuotaExceededError") => ErrorKind::Io,
+            Some("DataCloneError") => ErrorKind::Serde,
+            _ => ErrorKind::Js
+        };
+
+        return Self::from_js(kind, value)
+    }
+}
+
+impl From<Error> for JsValue {
+    #[inline]
+    fn from(value: Error) -> Self {
+        return value.value
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    #[inline]
+    fn from(value: serde_json::Error) -> Self {
+        return Self::with_source(ErrorKind::Serde, value)
+    }
+}
+
+impl From<serde_wasm_bindgen::Error> for Error {
+    #[inline]
+    fn from(value: serde_wasm_bindgen::Error) -> Self {
+        return Self::from_js(ErrorKind::Serde, value.into())
+    }
+}
+
+impl From<std::io::Error> for Error {
+    #[inline]
+    fn from(value: std::io::Error) -> Self {
+        return Self::with_source(ErrorKind::Io, value)
+    }
+}
+
+impl From<std::string::FromUtf8Error> for Error {
+    #[inline]
+    fn from(value: std::string::FromUtf8Error) -> Self {
+        return Self::with_source(ErrorKind::Serde, value)
+    }
+}
+
+impl From<crate::io::FetchError> for Error {
+    #[inline]
+    fn from(value: crate::io::FetchError) -> Self {
+        use crate::io::FetchError;
+
+        let kind = match value {
+            FetchError::Aborted(_) | FetchError::Timeout => ErrorKind::Abort,
+            FetchError::Decode(_) => ErrorKind::Serde,
+            FetchError::Other(_) => ErrorKind::Js,
+            _ => ErrorKind::Io
+        };
+
+        return Self::from_js(kind, value.into())
+    }
+}
+
+impl Debug for Error {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Error")
+            .field("kind", &self.kind)
+            .field("value", &self.value)
+            .finish()
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(msg) = self.value.as_string() {
+            return f.write_str(&msg)
+        }
+
+        if let Some(err) = self.value.dyn_ref::<js_sys::Error>() {
+            let msg = String::from(err.message());
+            return match js_name(&self.value) {
+                Some(name) => write!(f, "{name}: {msg}"),
+                None => f.write_str(&msg)
+            }
+        }
+
+        return write!(f, "{:?}", self.value)
+    }
+}
+
+impl std::error::Error for Error {
+    #[inline]
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        return self.source.as_deref()
+    }
+}
+
+#[inline]
+fn js_name (value: &JsValue) -> Option<String> {
+    if !value.is_object() { return None }
+    return js_sys::Reflect::get(value, &JsValue::from_str("name"))
+        .ok()
+        .and_then(|x| x.as_string())
+}
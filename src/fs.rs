@@ -1,13 +1,16 @@
-use std::{time::{SystemTime, Duration}, fmt::Debug, ops::{Deref, RangeInclusive}, io::SeekFrom};
+use std::{time::{SystemTime, Duration}, fmt::Debug, ops::{Deref, Range, RangeInclusive}, io::SeekFrom};
 use chrono::{DateTime, Utc, NaiveDateTime};
+use futures::future::LocalBoxFuture;
 use js_sys::{Array, Uint8Array};
 use wasm_bindgen::{prelude::wasm_bindgen, JsCast, JsValue};
 use wasm_bindgen_futures::JsFuture;
 use web_sys::{Window, HtmlInputElement};
-use crate::{Result, io::{JsReadStream, JsWriteStream}};
+use crate::{Result, Error, ErrorKind, io::{JsReadStream, JsWriteStream}};
 
 type JsFile = web_sys::File;
 
+flat_mod! { opfs, http }
+
 /// File reading/writing permissions granted by the user
 #[wasm_bindgen]
 #[non_exhaustive]
@@ -29,9 +32,9 @@ extern "C" {
     type FileSystemWritableFileStream;
 
     #[wasm_bindgen(method, catch, js_name = queryPermission)]
-    fn query_permission (this: &FileSystemHandle, ops: &JsValue) -> Result<PermisionStatus>;
+    fn query_permission (this: &FileSystemHandle, ops: &JsValue) -> Result<PermisionStatus, JsValue>;
     #[wasm_bindgen(method, catch, js_name = requestPermission)]
-    fn request_permission (this: &FileSystemHandle, ops: &JsValue) -> Result<js_sys::Promise>;
+    fn request_permission (this: &FileSystemHandle, ops: &JsValue) -> Result<js_sys::Promise, JsValue>;
 
     #[wasm_bindgen(method, js_name = getFile)]
     fn get_file (this: &FileSystemFileHandle) -> js_sys::Promise;
@@ -65,7 +68,7 @@ impl File {
     /// # Compatibility
     /// Check the [compatibility table](https://developer.mozilla.org/en-US/docs/Web/API/window/showOpenFilePicker#browser_compatibility)
     pub async fn from_picker () -> Result<impl Iterator<Item = File>> {
-        let picker = JsFuture::from(show_open_file_picker(&crate::scope::window()?)).await?;
+        let picker = JsFuture::from(show_open_file_picker(&crate::scope::window_only("File System Access API")?)).await?;
         let array = picker.unchecked_into::<Array>();
 
         let iter = (0..array.length()).into_iter()
@@ -103,6 +106,15 @@ impl File {
         return JsReadStream::new(read.stream());
     }
 
+    /// Returns a [`Blob`](web_sys::Blob) with the contents of the file within the specified byte range.
+    /// 
+    /// Slicing doesn't copy the file's contents, so it's cheap even for big files.
+    #[inline]
+    pub async fn slice (&mut self, range: Range<u64>) -> Result<web_sys::Blob> {
+        let read = self.get_read().await?;
+        return Ok(read.slice_with_f64_and_f64(range.start as f64, range.end as f64)?)
+    }
+
     /// Returns a [`FileWrite`] that can be used to write contents to the file.
     /// 
     /// If [`File`] was created via [`from_input`](File::from_input), the result will always be `Ok(None)`
@@ -128,7 +140,7 @@ impl File {
     }
 
     #[inline]
-    async fn get_read (&mut self) -> Result<JsFile> {
+    pub(crate) async fn get_read (&mut self) -> Result<JsFile> {
         match &mut self.inner {
             FileInner::File(file) => return Ok(file.clone()),
             FileInner::Handle(inner) => {
@@ -167,6 +179,13 @@ impl File {
     }
 }
 
+impl From<JsFile> for File {
+    #[inline]
+    fn from(file: JsFile) -> Self {
+        Self { inner: FileInner::File(file) }
+    }
+}
+
 /// A [`File`]'s metadata
 #[derive(Debug, Clone)]
 pub struct Metadata {
@@ -200,7 +219,7 @@ impl Metadata {
             x if x.is_sign_negative() => Duration::try_from_secs_f64(-1000f64 * x).ok().and_then(|x| SystemTime::UNIX_EPOCH.checked_sub(x)),
             x => Duration::try_from_secs_f64(1000f64 * x).ok().and_then(|x| SystemTime::UNIX_EPOCH.checked_add(x))
         };
-        return time.ok_or_else(|| JsValue::from_str("unsupported timestamp"))
+        return time.ok_or_else(|| Error::new(ErrorKind::NotSupported, "unsupported timestamp"))
     }
 
     /// Returns the last time the file was modified in a JavaScript [`Date`](js_sys::Date)
@@ -222,14 +241,29 @@ impl Metadata {
         
         if RANGE.contains(&self.last_modified) {
             let naive = NaiveDateTime::from_timestamp_millis(self.last_modified as i64)
-                .ok_or_else(|| JsValue::from_str("unsupported timestamp"))?;
+                .ok_or_else(|| Error::new(ErrorKind::NotSupported, "unsupported timestamp"))?;
             return Ok(DateTime::from_utc(naive, Utc));
         }
 
-        return Err(JsValue::from_str("unsupported timestamp"))
+        return Err(Error::new(ErrorKind::NotSupported, "unsupported timestamp"))
     }
 }
 
+/// A read-only file system, implemented by both [`Directory`] (for the origin private file system) and [`HttpFs`] (for network assets),
+/// so that code reading files can be generic over where they're stored.
+///
+/// Paths are separated by `/`, and are relative to the root of the file system.
+pub trait ReadFs {
+    /// Opens the file at `path`, returning a stream of it's contents
+    fn open<'a> (&'a self, path: &'a str) -> LocalBoxFuture<'a, Result<JsReadStream<'static, Uint8Array>>>;
+    /// Reads the entire contents of the file at `path`
+    fn read<'a> (&'a self, path: &'a str) -> LocalBoxFuture<'a, Result<Vec<u8>>>;
+    /// Returns the metadata of the file at `path`
+    fn metadata<'a> (&'a self, path: &'a str) -> LocalBoxFuture<'a, Result<Metadata>>;
+    /// Returns `true` if the file at `path` exists
+    fn exists<'a> (&'a self, path: &'a str) -> LocalBoxFuture<'a, Result<bool>>;
+}
+
 /// A writer to a [`File`] instance
 pub struct FileWrite {
     file: JsFile,
@@ -252,6 +286,22 @@ impl FileWrite {
         return Ok(())
     }
 
+    /// Closes the writer, commiting the written contents into the file
+    #[inline]
+    pub async fn close (&mut self) -> Result<()> {
+        return self.inner.close().await
+    }
+
+    #[inline]
+    pub(crate) fn write_stream (&mut self) -> &mut JsWriteStream<'static, Uint8Array> {
+        return &mut self.inner
+    }
+
+    #[inline]
+    pub(crate) fn advance (&mut self, len: u64) {
+        self.pos += len;
+    }
+
     /// Moves the file cursor to the specified position
     pub async fn seek (&mut self, pos: SeekFrom) -> Result<()> {
         let offset = match pos {
@@ -259,12 +309,12 @@ impl FileWrite {
 
             SeekFrom::End(offset) => match (self.file.size() as u64).checked_add_signed(offset) {
                 Some(x) => x,
-                None => return Err(JsValue::from_str("arithmetic overflow"))
+                None => return Err(Error::new(ErrorKind::Io, "arithmetic overflow"))
             },
 
             SeekFrom::Current(offset) => match self.pos.checked_add_signed(offset) {
                 Some(x) => x,
-                None => return Err(JsValue::from_str("arithmetic overflow"))
+                None => return Err(Error::new(ErrorKind::Io, "arithmetic overflow"))
             }
         };
 
@@ -0,0 +1,185 @@
+use std::{rc::Rc, cell::RefCell, collections::HashMap};
+use futures::{FutureExt, future::{try_join_all, LocalBoxFuture}};
+use js_sys::Uint8Array;
+use wasm_bindgen_futures::JsFuture;
+use crate::{Result, Error, ErrorKind, io::{JsReadStream, FetchClient, Request, Response, Method}};
+use super::{Metadata, ReadFs};
+
+/// A read-only file system whose files are fetched from a base url.
+///
+/// Paths are resolved relative to the base url, so `/textures/grass.png` and `textures/grass.png` refer to the same file.
+/// Files listed in a [manifest](HttpFs::load_manifest) can be [preloaded](HttpFs::preload) into memory, after which they're
+/// served without hitting the network.
+/// ```ignore
+/// let fs = HttpFs::new("/assets/");
+/// fs.load_manifest("manifest.json").await?;
+/// fs.preload().await?;
+/// let grass = fs.read("textures/grass.png").await?;
+/// ```
+#[derive(Clone)]
+pub struct HttpFs {
+    client: FetchClient,
+    manifest: Rc<RefCell<Vec<String>>>,
+    preloaded: Rc<RefCell<HashMap<String, Uint8Array>>>
+}
+
+impl HttpFs {
+    /// Creates a new file system whose files are fetched from `base_url`
+    #[inline]
+    pub fn new (base_url: impl Into<String>) -> Self {
+        let mut base_url = base_url.into();
+        if !base_url.ends_with('/') {
+            base_url.push('/')
+        }
+        return Self::with_client(FetchClient::new().base_url(base_url))
+    }
+
+    /// Creates a new file system whose files are fetched through `client`, relative to it's base url.
+    ///
+    /// The base url of the client should end with a `/`, otherwise it's last segment is replaced by the paths.
+    #[inline]
+    pub fn with_client (client: FetchClient) -> Self {
+        return Self { client, manifest: Default::default(), preloaded: Default::default() }
+    }
+
+    /// Opens the file at `path`, returning a stream of it's contents
+    pub async fn open (&self, path: &str) -> Result<JsReadStream<'static, Uint8Array>> {
+        let resp = match self.preloaded(path) {
+            Some(bytes) => Response::new(200, bytes)?,
+            None => self.get(path).await?
+        };
+
+        return resp.body()?.ok_or_else(|| Error::new(ErrorKind::Io, "file doesn't have a body"))
+    }
+
+    /// Reads the entire contents of the file at `path`
+    pub async fn read (&self, path: &str) -> Result<Vec<u8>> {
+        if let Some(bytes) = self.preloaded(path) {
+            return Ok(bytes.to_vec())
+        }
+        return Ok(self.get(path).await?.bytes().await?)
+    }
+
+    /// Reads the entire contents of the file at `path` into a string
+    pub async fn read_to_string (&self, path: &str) -> Result<String> {
+        return Ok(String::from_utf8(self.read(path).await?)?)
+    }
+
+    /// Returns the metadata of the file at `path`, from the headers of a `HEAD` request.
+    ///
+    /// If the server doesn't send a `Last-Modified` header, or if the file has been preloaded (in which case no request is made),
+    /// the modification time isn't meaningful.
+    pub async fn metadata (&self, path: &str) -> Result<Metadata> {
+        if let Some(bytes) = self.preloaded(path) {
+            return Ok(Metadata { len: bytes.length() as u64, last_modified: f64::NAN })
+        }
+
+        let resp = self.head(path).await?.error_for_status().await?;
+        let len = resp.header("content-length").and_then(|x| x.parse().ok()).unwrap_or_default();
+        let last_modified = resp.header("last-modified").map_or(f64::NAN, |x| js_sys::Date::parse(&x));
+
+        return Ok(Metadata { len, last_modified })
+    }
+
+    /// Returns `true` if the file at `path` exists
+    pub async fn exists (&self, path: &str) -> Result<bool> {
+        if self.preloaded(path).is_some() {
+            return Ok(true)
+        }
+
+        let resp = self.head(path).await?;
+        return match resp.status() {
+            404 | 410 => Ok(false),
+            _ => resp.error_for_status().await.map(|_| true).map_err(Error::from)
+        }
+    }
+
+    /// Loads a manifest with the paths of the file system's files, as a JSON array of strings (`["index.html", "textures/grass.png"]`)
+    pub async fn load_manifest (&self, path: &str) -> Result<()> {
+        let manifest = self.get(path).await?.json::<Vec<String>>().await?;
+        *self.manifest.borrow_mut() = manifest;
+        return Ok(())
+    }
+
+    /// Returns the paths listed in the manifest
+    #[inline]
+    pub fn manifest (&self) -> Vec<String> {
+        return self.manifest.borrow().clone()
+    }
+
+    /// Fetches every file listed in the manifest that hasn't been preloaded yet, concurrently, and keeps their contents in memory
+    pub async fn preload (&self) -> Result<()> {
+        let pending = self.manifest.borrow().iter()
+            .filter(|path| self.preloaded(path).is_none())
+            .cloned()
+            .collect::<Vec<_>>();
+
+        let files = try_join_all(pending.iter().map(|path| async move {
+            let blob = self.get(path).await?.blob().await?;
+            let buffer = JsFuture::from(blob.array_buffer()).await?;
+            Ok::<_, Error>(Uint8Array::new(&buffer))
+        })).await?;
+
+        let mut preloaded = self.preloaded.borrow_mut();
+        for (path, bytes) in pending.into_iter().zip(files) {
+            preloaded.insert(normalize(&path).to_string(), bytes);
+        }
+        return Ok(())
+    }
+
+    /// Returns `true` if the file at `path` has been preloaded into memory
+    #[inline]
+    pub fn is_preloaded (&self, path: &str) -> bool {
+        return self.preloaded(path).is_some()
+    }
+
+    /// Releases the preloaded contents of every file
+    #[inline]
+    pub fn clear_preloaded (&self) {
+        self.preloaded.borrow_mut().clear()
+    }
+
+    #[inline]
+    fn preloaded (&self, path: &str) -> Option<Uint8Array> {
+        return self.preloaded.borrow().get(normalize(path)).cloned()
+    }
+
+    #[inline]
+    async fn get (&self, path: &str) -> Result<Response> {
+        return Ok(self.client.get(normalize(path)).await?.error_for_status().await?)
+    }
+
+    #[inline]
+    async fn head (&self, path: &str) -> Result<Response> {
+        let mut req = Request::new();
+        req.method(Method::Head);
+        return Ok(self.client.fetch(normalize(path), req).await?)
+    }
+}
+
+impl ReadFs for HttpFs {
+    #[inline]
+    fn open<'a> (&'a self, path: &'a str) -> LocalBoxFuture<'a, Result<JsReadStream<'static, Uint8Array>>> {
+        return HttpFs::open(self, path).boxed_local()
+    }
+
+    #[inline]
+    fn read<'a> (&'a self, path: &'a str) -> LocalBoxFuture<'a, Result<Vec<u8>>> {
+        return HttpFs::read(self, path).boxed_local()
+    }
+
+    #[inline]
+    fn metadata<'a> (&'a self, path: &'a str) -> LocalBoxFuture<'a, Result<Metadata>> {
+        return HttpFs::metadata(self, path).boxed_local()
+    }
+
+    #[inline]
+    fn exists<'a> (&'a self, path: &'a str) -> LocalBoxFuture<'a, Result<bool>> {
+        return HttpFs::exists(self, path).boxed_local()
+    }
+}
+
+#[inline]
+fn normalize (path: &str) -> &str {
+    return path.trim_start_matches('/')
+}
@@ -0,0 +1,223 @@
+use futures::{FutureExt, future::LocalBoxFuture};
+use js_sys::Uint8Array;
+use wasm_bindgen::{prelude::wasm_bindgen, JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use crate::{Result, Error, ErrorKind, scope::navigator, io::JsReadStream};
+use super::{File, FileInner, FileSystemHandle, FileSystemFileHandle, Metadata, ReadFs};
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(extends = FileSystemHandle)]
+    #[derive(Debug, Clone, PartialEq)]
+    type FileSystemDirectoryHandle;
+    #[derive(Debug, Clone, PartialEq)]
+    type FileSystemSyncAccessHandle;
+    type StorageManager;
+
+    #[wasm_bindgen(method, js_name = getDirectory)]
+    fn get_directory (this: &StorageManager) -> js_sys::Promise;
+
+    #[wasm_bindgen(method, js_name = getFileHandle)]
+    fn get_file_handle (this: &FileSystemDirectoryHandle, name: &str, options: &js_sys::Object) -> js_sys::Promise;
+    #[wasm_bindgen(method, js_name = getDirectoryHandle)]
+    fn get_directory_handle (this: &FileSystemDirectoryHandle, name: &str, options: &js_sys::Object) -> js_sys::Promise;
+    #[wasm_bindgen(method, js_name = removeEntry)]
+    fn remove_entry (this: &FileSystemDirectoryHandle, name: &str, options: &js_sys::Object) -> js_sys::Promise;
+    #[wasm_bindgen(method, getter)]
+    fn name (this: &FileSystemHandle) -> String;
+
+    #[wasm_bindgen(method, js_name = createSyncAccessHandle)]
+    fn create_sync_access_handle (this: &FileSystemFileHandle) -> js_sys::Promise;
+
+    #[wasm_bindgen(method, catch)]
+    fn read (this: &FileSystemSyncAccessHandle, buf: &mut [u8], options: &js_sys::Object) -> ::core::result::Result<f64, JsValue>;
+    #[wasm_bindgen(method, catch)]
+    fn write (this: &FileSystemSyncAccessHandle, buf: &[u8], options: &js_sys::Object) -> ::core::result::Result<f64, JsValue>;
+    #[wasm_bindgen(method, catch)]
+    fn flush (this: &FileSystemSyncAccessHandle) -> ::core::result::Result<(), JsValue>;
+    #[wasm_bindgen(method, catch)]
+    fn truncate (this: &FileSystemSyncAccessHandle, size: f64) -> ::core::result::Result<(), JsValue>;
+    #[wasm_bindgen(method, catch, js_name = getSize)]
+    fn get_size (this: &FileSystemSyncAccessHandle) -> ::core::result::Result<f64, JsValue>;
+    #[wasm_bindgen(method)]
+    fn close (this: &FileSystemSyncAccessHandle);
+}
+
+/// A directory of the [Origin Private File System](https://developer.mozilla.org/en-US/docs/Web/API/File_System_Access_API#origin_private_file_system)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Directory {
+    inner: FileSystemDirectoryHandle
+}
+
+impl Directory {
+    /// Returns the root directory of the origin private file system.
+    ///
+    /// # Compatibility
+    /// Check the [compatibility table](https://developer.mozilla.org/en-US/docs/Web/API/StorageManager/getDirectory#browser_compatibility)
+    pub async fn origin_private () -> Result<Self> {
+        let storage = js_sys::Reflect::get(&navigator(), &JsValue::from_str("storage"))?;
+        if storage.is_undefined() || !js_sys::Reflect::has(&storage, &JsValue::from_str("getDirectory"))? {
+            return Err(Error::new(ErrorKind::NotSupported, "Origin Private File System is not supported"))
+        }
+
+        let inner = JsFuture::from(storage.unchecked_ref::<StorageManager>().get_directory()).await?;
+        return Ok(Self { inner: inner.unchecked_into() })
+    }
+
+    /// Returns the name of the directory
+    #[inline]
+    pub fn name (&self) -> String {
+        return self.inner.name()
+    }
+
+    /// Returns the file with the specified name inside this directory, creating it first if `create` is `true`
+    pub async fn file (&self, name: &str, create: bool) -> Result<File> {
+        let handle = JsFuture::from(self.inner.get_file_handle(name, &create_options(create)?)).await?;
+        return Ok(File { inner: FileInner::Handle(handle.unchecked_into()) })
+    }
+
+    /// Returns the subdirectory with the specified name, creating it first if `create` is `true`
+    pub async fn directory (&self, name: &str, create: bool) -> Result<Directory> {
+        let inner = JsFuture::from(self.inner.get_directory_handle(name, &create_options(create)?)).await?;
+        return Ok(Self { inner: inner.unchecked_into() })
+    }
+
+    /// Removes the entry with the specified name from this directory. Subdirectories with contents are only removed if `recursive` is `true`
+    pub async fn remove (&self, name: &str, recursive: bool) -> Result<()> {
+        let options = js_sys::Object::new();
+        js_sys::Reflect::set(&options, &JsValue::from_str("recursive"), &JsValue::from_bool(recursive))?;
+        JsFuture::from(self.inner.remove_entry(name, &options)).await?;
+        return Ok(())
+    }
+
+    /// Returns the file at `path`, relative to this directory, or `None` if it (or any of it's parent directories) doesn't exist
+    async fn lookup (&self, path: &str) -> Result<Option<File>> {
+        let path = path.trim_matches('/');
+        let (dirs, name) = path.rsplit_once('/').unwrap_or(("", path));
+        let options = create_options(false)?;
+
+        let mut dir = self.inner.clone();
+        for segment in dirs.split('/').filter(|x| !x.is_empty()) {
+            match JsFuture::from(dir.get_directory_handle(segment, &options)).await {
+                Ok(handle) => dir = handle.unchecked_into(),
+                Err(e) if is_not_found(&e) => return Ok(None),
+                Err(e) => return Err(e.into())
+            }
+        }
+
+        return match JsFuture::from(dir.get_file_handle(name, &options)).await {
+            Ok(handle) => Ok(Some(File { inner: FileInner::Handle(handle.unchecked_into()) })),
+            Err(e) if is_not_found(&e) => Ok(None),
+            Err(e) => Err(e.into())
+        }
+    }
+
+    #[inline]
+    async fn lookup_existing (&self, path: &str) -> Result<File> {
+        return self.lookup(path).await?.ok_or_else(|| Error::new(ErrorKind::Io, "file not found"))
+    }
+}
+
+impl ReadFs for Directory {
+    fn open<'a> (&'a self, path: &'a str) -> LocalBoxFuture<'a, Result<JsReadStream<'static, Uint8Array>>> {
+        return async move { self.lookup_existing(path).await?.reader().await }.boxed_local()
+    }
+
+    fn read<'a> (&'a self, path: &'a str) -> LocalBoxFuture<'a, Result<Vec<u8>>> {
+        return async move { self.lookup_existing(path).await?.reader().await?.read_remaining_bytes().await }.boxed_local()
+    }
+
+    fn metadata<'a> (&'a self, path: &'a str) -> LocalBoxFuture<'a, Result<Metadata>> {
+        return async move { self.lookup_existing(path).await?.metadata().await }.boxed_local()
+    }
+
+    fn exists<'a> (&'a self, path: &'a str) -> LocalBoxFuture<'a, Result<bool>> {
+        return async move { Ok(self.lookup(path).await?.is_some()) }.boxed_local()
+    }
+}
+
+/// Returns `true` if the error is thrown by a lookup of an entry that doesn't exist, or that isn't of the expected kind
+#[inline]
+fn is_not_found (e: &JsValue) -> bool {
+    return e.dyn_ref::<web_sys::DomException>().is_some_and(|e| matches!(e.name().as_str(), "NotFoundError" | "TypeMismatchError"))
+}
+
+impl File {
+    /// Opens a [`SyncAccessHandle`] to the file, which allows synchronous reads and writes.
+    ///
+    /// Only files of the [origin private file system](Directory::origin_private) support sync access handles, and only inside dedicated workers.
+    /// While the handle is open, no other handle or writer can be opened to the file.
+    ///
+    /// # Compatibility
+    /// Check the [compatibility table](https://developer.mozilla.org/en-US/docs/Web/API/FileSystemFileHandle/createSyncAccessHandle#browser_compatibility)
+    pub async fn sync_access_handle (&self) -> Result<SyncAccessHandle> {
+        return match self.inner {
+            FileInner::Handle(ref inner) if js_sys::Reflect::has(inner, &JsValue::from_str("createSyncAccessHandle"))? => {
+                let inner = JsFuture::from(inner.create_sync_access_handle()).await?;
+                Ok(SyncAccessHandle { inner: inner.unchecked_into() })
+            },
+            _ => Err(Error::new(ErrorKind::NotSupported, "file doesn't support sync access handles"))
+        }
+    }
+}
+
+/// A synchronous handle to a file of the origin private file system, suitable as a backend for databases like SQLite.
+///
+/// The handle is closed when dropped.
+#[derive(Debug)]
+pub struct SyncAccessHandle {
+    inner: FileSystemSyncAccessHandle
+}
+
+impl SyncAccessHandle {
+    /// Reads bytes from the file at the specified offset into `buf`, returning the number of bytes read
+    #[inline]
+    pub fn read_at (&self, buf: &mut [u8], at: u64) -> Result<usize> {
+        return Ok(self.inner.read(buf, &at_options(at)?)? as usize)
+    }
+
+    /// Writes `buf` into the file at the specified offset, returning the number of bytes written
+    #[inline]
+    pub fn write_at (&self, buf: &[u8], at: u64) -> Result<usize> {
+        return Ok(self.inner.write(buf, &at_options(at)?)? as usize)
+    }
+
+    /// Persists the changes written to the file into disk
+    #[inline]
+    pub fn flush (&self) -> Result<()> {
+        return Ok(self.inner.flush()?)
+    }
+
+    /// Resizes the file to the specified size, in bytes
+    #[inline]
+    pub fn truncate (&self, size: u64) -> Result<()> {
+        return Ok(self.inner.truncate(size as f64)?)
+    }
+
+    /// Returns the size of the file, in bytes
+    #[inline]
+    pub fn size (&self) -> Result<u64> {
+        return Ok(self.inner.get_size()? as u64)
+    }
+}
+
+impl Drop for SyncAccessHandle {
+    #[inline]
+    fn drop(&mut self) {
+        self.inner.close()
+    }
+}
+
+#[inline]
+fn create_options (create: bool) -> Result<js_sys::Object> {
+    let options = js_sys::Object::new();
+    js_sys::Reflect::set(&options, &JsValue::from_str("create"), &JsValue::from_bool(create))?;
+    return Ok(options)
+}
+
+#[inline]
+fn at_options (at: u64) -> Result<js_sys::Object> {
+    let options = js_sys::Object::new();
+    js_sys::Reflect::set(&options, &JsValue::from_str("at"), &JsValue::from_f64(at as f64))?;
+    return Ok(options)
+}
@@ -1,8 +1,11 @@
-use std::{task::{Poll}, future::Future};
+use std::{task::{Poll}, future::Future, rc::Rc, cell::RefCell, time::Duration};
 use futures::{Stream, FutureExt};
 use wasm_bindgen::{prelude::{wasm_bindgen, Closure}, JsCast};
-use crate::{Result, utils::{LocalReceiver, local_channel}, scope::window, sync::{one_shot, ShotReceiver}};
+use crate::{Result, Error, ErrorKind, utils::{LocalReceiver, local_channel}, scope::window_only, sync::{one_shot, ShotReceiver, ShotSender}, diagnostics::Tracked};
 use futures::StreamExt;
+use docfg::docfg;
+#[cfg(target_feature = "atomics")]
+use crate::sync::{DropHandle, drop_local};
 
 #[wasm_bindgen]
 extern {
@@ -28,6 +31,13 @@ extern {
     pub fn heading(this: &GeolocationCoordinates) -> Option<f64>;
     #[wasm_bindgen(method, getter)]
     pub fn speed(this: &GeolocationCoordinates) -> Option<f64>;
+
+    type GeolocationPositionError;
+
+    #[wasm_bindgen(method, getter)]
+    fn code(this: &GeolocationPositionError) -> u16;
+    #[wasm_bindgen(method, getter)]
+    fn message(this: &GeolocationPositionError) -> String;
 }
 
 /// Information abut a specific geolocation
@@ -51,38 +61,55 @@ pub struct Geolocation {
 }
 
 impl Geolocation {
-    /// Returns a [`Future`] that resolves to the current geolocation of the device
+    /// Returns a [`Future`] that resolves to the current geolocation of the device, or to an error if it can't be determined.
+    ///
+    /// The callbacks registered with the browser are released as soon as it answers. If the future is dropped
+    /// before that, they're kept alive until the browser answers, so that it never calls a released closure.
     pub fn current () -> Result<CurrentGeolocation> {
+        let geo = window_only("Geolocation API")?.navigator().geolocation()?;
         let (send, inner) = one_shot();
-        let resolve_closure = Closure::once(move |loc: GeolocationPosition| {
-            let _ = send.try_send(loc);
-        });
+        let pending = Rc::new(RefCell::new(None::<PendingPosition>));
 
-        let resolve: &js_sys::Function;
-        cfg_if::cfg_if! {
-            if #[cfg(debug_assertions)] {
-                resolve = resolve_closure.as_ref().dyn_ref().unwrap();
-            } else {
-                resolve = resolve_closure.as_ref().unchecked_ref();
+        let my_pending = pending.clone();
+        let success = Closure::<dyn FnMut(GeolocationPosition)>::new(move |loc: GeolocationPosition| {
+            // the closures are dropped after this call returns
+            let pending = my_pending.borrow_mut().take();
+            if let Some(pending) = pending {
+                let _ = pending.send.try_send(Ok(loc));
             }
-        }
+        });
 
-        let geo = window()?.navigator().geolocation()?;
-        match geo.get_current_position(resolve) {
-            Ok(_) => {
-                resolve_closure.forget();
-            },
-            Err(e) => return Err(e)
-        }
+        let my_pending = pending.clone();
+        let error = Closure::<dyn FnMut(GeolocationPositionError)>::new(move |err: GeolocationPositionError| {
+            let pending = my_pending.borrow_mut().take();
+            if let Some(pending) = pending {
+                let _ = pending.send.try_send(Err(Error::from(err)));
+            }
+        });
 
+        geo.get_current_position_with_error_callback(success.as_ref().unchecked_ref(), Some(error.as_ref().unchecked_ref()))?;
+        *pending.borrow_mut() = Some(PendingPosition { send, _success: success, _error: error, _tracked: Tracked::new("geo::current") });
         return Ok(CurrentGeolocation { inner })
     }
 
+    /// Calculates the great-circle distance to `other`, in meters, with the haversine formula
+    #[inline]
+    pub fn distance (&self, other: &Geolocation) -> f64 {
+        return haversine(self.latitude, self.longitude, other.latitude, other.longitude)
+    }
+
     /// Returns a watcher for the device's geolocation
     #[inline]
     pub fn watch () -> Result<GeolocationWatcher> {
         return GeolocationWatcher::new()
     }
+
+    /// Returns a thread-safe watcher for the device's geolocation, that can be sent to other threads
+    #[docfg(target_feature = "atomics")]
+    #[inline]
+    pub fn watch_send () -> Result<SendGeolocationWatcher> {
+        return SendGeolocationWatcher::new()
+    }
 }
 
 /// A watcher for a device's [`Geolocation`].
@@ -91,8 +118,7 @@ impl Geolocation {
 /// 
 /// When droped, the watcher will be closed, releasing all the memory of it's closure, avoiding a memory leak.
 pub struct GeolocationWatcher {
-    id: i32,
-    _success: Closure<dyn FnMut(GeolocationPosition)>,
+    _watch: RawWatch,
     recv: LocalReceiver<Geolocation>
 }
 
@@ -101,8 +127,140 @@ impl GeolocationWatcher {
     #[inline]
     pub fn new () -> Result<Self> {
         let (send, recv) = local_channel();
+        let watch = RawWatch::new(move |loc| {
+            let _ = send.try_send(loc);
+        })?;
+
+        return Ok(Self {
+            _watch: watch,
+            recv,
+        })
+    }
+}
+
+impl GeolocationWatcher {
+    /// Filters the updates of the watcher, to reduce the noise of tracking the device.
+    ///
+    /// Updates whose accuracy radius is larger than `min_accuracy` meters are dropped. Once an update has been yielded,
+    /// the next ones are dropped until the device has moved at least `min_distance` meters from it (by [haversine distance](Geolocation::distance)),
+    /// and at least `min_interval` has elapsed since it was received.
+    /// ```ignore
+    /// let mut watcher = Geolocation::watch()?.filtered(25., 10., Duration::from_secs(2));
+    /// while let Some(loc) = watcher.next().await {
+    ///     map.move_marker(loc.latitude, loc.longitude);
+    /// }
+    /// ```
+    #[inline]
+    pub fn filtered (self, min_accuracy: f64, min_distance: f64, min_interval: Duration) -> FilteredGeolocationWatcher {
+        return FilteredGeolocationWatcher {
+            inner: self,
+            min_accuracy,
+            min_distance,
+            min_interval: min_interval.as_secs_f64() * 1000.,
+            last: None
+        }
+    }
+}
+
+impl Stream for GeolocationWatcher {
+    type Item = Geolocation;
+
+    #[inline]
+    fn poll_next(mut self: std::pin::Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Option<Self::Item>> {
+        self.recv.poll_next_unpin(cx)
+    }
+}
+
+/// A [`GeolocationWatcher`] that drops inaccurate and jittery updates, created by [`filtered`](GeolocationWatcher::filtered)
+pub struct FilteredGeolocationWatcher {
+    inner: GeolocationWatcher,
+    min_accuracy: f64,
+    min_distance: f64,
+    min_interval: f64,
+    last: Option<(Geolocation, f64)>
+}
+
+impl FilteredGeolocationWatcher {
+    #[inline]
+    fn accepts (&self, loc: &Geolocation, now: f64) -> bool {
+        if loc.accuracy.is_nan() || loc.accuracy > self.min_accuracy || !loc.latitude.is_finite() || !loc.longitude.is_finite() {
+            return false
+        }
+
+        return match self.last {
+            Some((ref last, time)) => now - time >= self.min_interval && last.distance(loc) >= self.min_distance,
+            None => true
+        }
+    }
+}
+
+impl Stream for FilteredGeolocationWatcher {
+    type Item = Geolocation;
+
+    fn poll_next(mut self: std::pin::Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Option<Self::Item>> {
+        while let Poll::Ready(loc) = self.inner.poll_next_unpin(cx) {
+            let Some(loc) = loc else { return Poll::Ready(None) };
+            let now = js_sys::Date::now();
+            if self.accepts(&loc, now) {
+                self.last = Some((loc.clone(), now));
+                return Poll::Ready(Some(loc))
+            }
+        }
+        return Poll::Pending
+    }
+}
+
+/// A thread-safe watcher for a device's [`Geolocation`].
+/// 
+/// The geolocation is watched from the thread that created the watcher, but the watcher itself can be sent to other threads
+/// (like [`thread`](crate::thread) workers), where it will be notified every time the geolocation of the device changes.
+/// 
+/// When droped, the watcher will be closed from it's original thread, releasing all the memory of it's closure.
+#[docfg(target_feature = "atomics")]
+pub struct SendGeolocationWatcher {
+    _watch: DropHandle,
+    recv: async_channel::Receiver<Geolocation>
+}
+
+#[docfg(target_feature = "atomics")]
+impl SendGeolocationWatcher {
+    /// Creates a new [`SendGeolocationWatcher`]
+    #[inline]
+    pub fn new () -> Result<Self> {
+        let (send, recv) = async_channel::unbounded();
+        let watch = RawWatch::new(move |loc| {
+            let _ = send.try_send(loc);
+        })?;
+
+        return Ok(Self {
+            _watch: drop_local(watch),
+            recv,
+        })
+    }
+}
+
+#[docfg(target_feature = "atomics")]
+impl Stream for SendGeolocationWatcher {
+    type Item = Geolocation;
+
+    #[inline]
+    fn poll_next(mut self: std::pin::Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Option<Self::Item>> {
+        self.recv.poll_next_unpin(cx)
+    }
+}
+
+/// Registered `watchPosition` callback, cleared when droped
+struct RawWatch {
+    id: i32,
+    geo: web_sys::Geolocation,
+    _success: Closure<dyn FnMut(GeolocationPosition)>,
+    _tracked: Tracked
+}
+
+impl RawWatch {
+    fn new<F: 'static + FnMut(Geolocation)> (mut f: F) -> Result<Self> {
         let success = Closure::<dyn FnMut(GeolocationPosition)>::new(move |loc: GeolocationPosition| {
-            let _ = send.try_send(Geolocation::from(loc));
+            f(Geolocation::from(loc))
         });
 
         let resolve: &js_sys::Function;
@@ -114,31 +272,35 @@ impl GeolocationWatcher {
             }
         }
 
-        let geo = window()?.navigator().geolocation()?;
+        let geo = window_only("Geolocation API")?.navigator().geolocation()?;
         let id = geo.watch_position(resolve)?;
         return Ok(Self {
             id,
+            geo,
             _success: success,
-            recv,
+            _tracked: Tracked::new("geo::watch")
         })
     }
 }
 
-impl Stream for GeolocationWatcher {
-    type Item = Geolocation;
-
+impl Drop for RawWatch {
     #[inline]
-    fn poll_next(mut self: std::pin::Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Option<Self::Item>> {
-        self.recv.poll_next_unpin(cx)
+    fn drop(&mut self) {
+        self.geo.clear_watch(self.id);
     }
 }
 
-impl Drop for GeolocationWatcher {
-    #[inline]
-    fn drop(&mut self) {
-        let geo = window().unwrap().navigator().geolocation().unwrap();
-        geo.clear_watch(self.id);
-    }
+/// Mean radius of the Earth, in meters
+const EARTH_RADIUS: f64 = 6_371_008.8;
+
+/// Great-circle distance between two points, in meters, with their coordinates in decimal degrees
+fn haversine (lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64 {
+    let (lat1, lat2) = (lat1.to_radians(), lat2.to_radians());
+    let d_lat = lat2 - lat1;
+    let d_lon = (lon2 - lon1).to_radians();
+
+    let a = f64::powi(f64::sin(0.5 * d_lat), 2) + lat1.cos() * lat2.cos() * f64::powi(f64::sin(0.5 * d_lon), 2);
+    return 2. * EARTH_RADIUS * f64::asin(f64::sqrt(a.min(1.)))
 }
 
 impl From<&GeolocationCoordinates> for Geolocation {
@@ -177,20 +339,39 @@ impl From<GeolocationPosition> for Geolocation {
     }
 }
 
+/// Callbacks of a [`current`](Geolocation::current) request, released once the browser calls either of them
+struct PendingPosition {
+    send: ShotSender<Result<GeolocationPosition>>,
+    _success: Closure<dyn FnMut(GeolocationPosition)>,
+    _error: Closure<dyn FnMut(GeolocationPositionError)>,
+    _tracked: Tracked
+}
+
 /// Future for [`current`](Geolocation::current)
 pub struct CurrentGeolocation {
-    inner: ShotReceiver<GeolocationPosition>
+    inner: ShotReceiver<Result<GeolocationPosition>>
 }
 
 impl Future for CurrentGeolocation {
-    type Output = Geolocation;
+    type Output = Result<Geolocation>;
 
     #[inline]
     fn poll(mut self: std::pin::Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> std::task::Poll<Self::Output> {
         return match self.inner.poll_unpin(cx) {
-            Poll::Ready(Some(x)) => Poll::Ready(Geolocation::from(x)),
-            Poll::Ready(None) => unreachable!(),
+            Poll::Ready(Some(x)) => Poll::Ready(x.map(Geolocation::from)),
+            Poll::Ready(None) => Poll::Ready(Err(Error::new(ErrorKind::Abort, "the geolocation request was dropped"))),
             Poll::Pending => Poll::Pending
         }
     }
+}
+
+impl From<GeolocationPositionError> for Error {
+    fn from(value: GeolocationPositionError) -> Self {
+        let kind = match value.code() {
+            1 => ErrorKind::Permission,
+            3 => ErrorKind::Abort,
+            _ => ErrorKind::NotSupported
+        };
+        return Error::new(kind, &value.message())
+    }
 }
\ No newline at end of file
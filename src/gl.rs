@@ -0,0 +1,210 @@
+use std::{rc::{Rc, Weak}, cell::{Cell, RefCell}};
+use js_sys::Float32Array;
+use wasm_bindgen::{JsCast, prelude::Closure};
+use web_sys::{WebGl2RenderingContext, WebGlShader, WebGlProgram, WebGlBuffer};
+use crate::{Result, Error, ErrorKind, canvas::Canvas, math::{Vec2f, Vec3f, Vec4f}, scope::{request_animation_frame, cancel_animation_frame}};
+
+mod sealed { pub trait Sealed {} }
+
+/// Types that can be uploaded into WebGL buffers as vertex attributes
+pub trait GlVector: sealed::Sealed + Copy {
+    /// Number of `f32` components of the type
+    const COMPONENTS: i32;
+}
+
+macro_rules! impl_vector {
+    ($($ty:ty => $len:literal),+) => {
+        $(
+            impl sealed::Sealed for $ty {}
+            impl GlVector for $ty {
+                const COMPONENTS: i32 = $len;
+            }
+        )+
+    };
+}
+
+impl_vector! {
+    f32 => 1,
+    Vec2f => 2,
+    Vec3f => 3,
+    Vec4f => 4
+}
+
+/// Kind of shader
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ShaderKind {
+    Vertex,
+    Fragment
+}
+
+impl ShaderKind {
+    #[inline]
+    fn as_gl (self) -> u32 {
+        return match self {
+            Self::Vertex => WebGl2RenderingContext::VERTEX_SHADER,
+            Self::Fragment => WebGl2RenderingContext::FRAGMENT_SHADER
+        }
+    }
+}
+
+/// A WebGL2 rendering context
+#[derive(Debug, Clone)]
+pub struct Gl {
+    inner: WebGl2RenderingContext
+}
+
+impl Gl {
+    /// Acquires the WebGL2 context of the canvas
+    pub fn new (canvas: &Canvas) -> Result<Self> {
+        return match canvas.get_context("webgl2")? {
+            Some(context) => Ok(Self { inner: context.unchecked_into() }),
+            None => Err(Error::new(ErrorKind::NotSupported, "WebGL2 not supported"))
+        }
+    }
+
+    /// Returns the underlying [`WebGl2RenderingContext`]
+    #[inline]
+    pub fn raw (&self) -> &WebGl2RenderingContext {
+        return &self.inner
+    }
+
+    /// Compiles a shader, returning it's info log as an error if it fails
+    pub fn compile_shader (&self, kind: ShaderKind, source: &str) -> Result<WebGlShader> {
+        let shader = match self.inner.create_shader(kind.as_gl()) {
+            Some(shader) => shader,
+            None => return Err(Error::new(ErrorKind::Js, "unable to create shader"))
+        };
+
+        self.inner.shader_source(&shader, source);
+        self.inner.compile_shader(&shader);
+
+        if self.inner.get_shader_parameter(&shader, WebGl2RenderingContext::COMPILE_STATUS).as_bool().unwrap_or_default() {
+            return Ok(shader)
+        }
+
+        let log = self.inner.get_shader_info_log(&shader).unwrap_or_default();
+        self.inner.delete_shader(Some(&shader));
+        return Err(Error::new(ErrorKind::Js, &log))
+    }
+
+    /// Links a program from the vertex and fragment shaders, returning it's info log as an error if it fails
+    pub fn link_program (&self, vertex: &WebGlShader, fragment: &WebGlShader) -> Result<WebGlProgram> {
+        let program = match self.inner.create_program() {
+            Some(program) => program,
+            None => return Err(Error::new(ErrorKind::Js, "unable to create program"))
+        };
+
+        self.inner.attach_shader(&program, vertex);
+        self.inner.attach_shader(&program, fragment);
+        self.inner.link_program(&program);
+
+        if self.inner.get_program_parameter(&program, WebGl2RenderingContext::LINK_STATUS).as_bool().unwrap_or_default() {
+            return Ok(program)
+        }
+
+        let log = self.inner.get_program_info_log(&program).unwrap_or_default();
+        self.inner.delete_program(Some(&program));
+        return Err(Error::new(ErrorKind::Js, &log))
+    }
+
+    /// Compiles both shaders and links them into a program
+    pub fn program (&self, vertex: &str, fragment: &str) -> Result<WebGlProgram> {
+        let vertex = self.compile_shader(ShaderKind::Vertex, vertex)?;
+        let fragment = match self.compile_shader(ShaderKind::Fragment, fragment) {
+            Ok(fragment) => fragment,
+            Err(e) => {
+                self.inner.delete_shader(Some(&vertex));
+                return Err(e)
+            }
+        };
+
+        let program = self.link_program(&vertex, &fragment);
+        self.inner.delete_shader(Some(&vertex));
+        self.inner.delete_shader(Some(&fragment));
+        return program
+    }
+
+    /// Creates a new buffer, bound to `target`, and uploads `data` into it
+    pub fn create_buffer<T: GlVector> (&self, target: u32, data: &[T], usage: u32) -> Result<WebGlBuffer> {
+        let buffer = match self.inner.create_buffer() {
+            Some(buffer) => buffer,
+            None => return Err(Error::new(ErrorKind::Js, "unable to create buffer"))
+        };
+
+        self.inner.bind_buffer(target, Some(&buffer));
+        self.buffer_data(target, data, usage);
+        return Ok(buffer)
+    }
+
+    /// Uploads `data` into the buffer bound to `target`, without copying it into an intermediate JavaScript array
+    pub fn buffer_data<T: GlVector> (&self, target: u32, data: &[T], usage: u32) {
+        let len = core::mem::size_of_val(data) / core::mem::size_of::<f32>();
+        // The view is consumed before any allocation can happen, so it can't be invalidated
+        unsafe {
+            let view = Float32Array::view(core::slice::from_raw_parts(data.as_ptr().cast::<f32>(), len));
+            self.inner.buffer_data_with_array_buffer_view(target, &view, usage);
+        }
+    }
+
+    /// Describes the layout of the buffer bound to `ARRAY_BUFFER` as a tightly packed array of `T`, and enables the attribute
+    #[inline]
+    pub fn vertex_attrib<T: GlVector> (&self, location: u32) {
+        self.inner.vertex_attrib_pointer_with_i32(location, T::COMPONENTS, WebGl2RenderingContext::FLOAT, false, core::mem::size_of::<T>() as i32, 0);
+        self.inner.enable_vertex_attrib_array(location);
+    }
+}
+
+struct LoopInner {
+    handle: Cell<i32>,
+    closure: RefCell<Option<Closure<dyn FnMut(f64)>>>
+}
+
+/// Calls `f` before every repaint (via `requestAnimationFrame`), with the current timestamp in milliseconds,
+/// for as long as it returns `true` and the returned [`RenderLoop`] is alive.
+pub fn render_loop<F: 'static + FnMut(f64) -> bool> (mut f: F) -> Result<RenderLoop> {
+    let inner = Rc::new(LoopInner {
+        handle: Cell::new(0),
+        closure: RefCell::new(None)
+    });
+
+    let weak: Weak<LoopInner> = Rc::downgrade(&inner);
+    let closure = Closure::<dyn FnMut(f64)>::new(move |time: f64| {
+        let inner = match weak.upgrade() {
+            Some(inner) => inner,
+            None => return
+        };
+
+        if !f(time) { return }
+        if let Some(ref closure) = *inner.closure.borrow() {
+            if let Ok(handle) = request_animation_frame(closure.as_ref().unchecked_ref()) {
+                inner.handle.set(handle)
+            }
+        }
+    });
+
+    inner.handle.set(request_animation_frame(closure.as_ref().unchecked_ref())?);
+    *inner.closure.borrow_mut() = Some(closure);
+    return Ok(RenderLoop { inner })
+}
+
+/// Handle of a loop started with [`render_loop`].
+///
+/// When droped, the loop is stopped.
+pub struct RenderLoop {
+    inner: Rc<LoopInner>
+}
+
+impl RenderLoop {
+    /// Leaks the handle, keeping the loop running until it's callback returns `false`
+    #[inline]
+    pub fn leak (self) {
+        core::mem::forget(self)
+    }
+}
+
+impl Drop for RenderLoop {
+    #[inline]
+    fn drop(&mut self) {
+        cancel_animation_frame(self.inner.handle.get());
+    }
+}
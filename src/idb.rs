@@ -0,0 +1,386 @@
+use std::{rc::Rc, cell::{Cell, RefCell}, task::{Poll, Waker}, future::Future};
+use futures::{FutureExt, future::LocalBoxFuture};
+use serde::{Serialize, de::DeserializeOwned};
+use wasm_bindgen::{JsCast, JsValue, closure::Closure};
+use web_sys::{Event, IdbFactory, IdbDatabase, IdbRequest, IdbRequestReadyState, IdbTransaction, IdbTransactionMode, IdbObjectStore, IdbVersionChangeEvent};
+use crate::{Result, Error, ErrorKind, storage::KvStore, utils::{to_js, from_js}};
+
+thread_local! {
+    /// Closes the connection whose database is being upgraded by another connection, so that it doesn't block the upgrade
+    static CLOSE_ON_VERSION_CHANGE: Closure<dyn FnMut(Event)> = Closure::new(|evt: Event| {
+        if let Some(db) = evt.target() {
+            db.unchecked_into::<IdbDatabase>().close()
+        }
+    });
+
+    /// Closes the connection opened by a request that was abandoned because it was blocked
+    static CLOSE_ON_SUCCESS: Closure<dyn FnMut(Event)> = Closure::new(|evt: Event| {
+        if let Some(req) = evt.target() && let Ok(db) = req.unchecked_into::<IdbRequest>().result() {
+            db.unchecked_into::<IdbDatabase>().close()
+        }
+    });
+
+    /// Serializes the upgrades of [`IdbStore::open`], so that they don't race for the same version
+    static OPEN_LOCK: Rc<futures::lock::Mutex<()>> = Rc::new(futures::lock::Mutex::new(()));
+}
+
+/// Makes the connection close itself when another connection upgrades the database
+fn close_on_version_change (db: &IdbDatabase) {
+    CLOSE_ON_VERSION_CHANGE.with(|f| db.set_onversionchange(Some(f.as_ref().unchecked_ref())))
+}
+
+/// Returns the IndexedDB factory of the current context
+fn factory () -> Result<IdbFactory> {
+    let factory = js_sys::Reflect::get(&js_sys::global(), &JsValue::from_str("indexedDB"))?;
+    if factory.is_undefined() || factory.is_null() {
+        return Err(Error::new(ErrorKind::NotSupported, "IndexedDB is not supported"))
+    }
+    return Ok(factory.unchecked_into())
+}
+
+/// An IndexedDB database
+#[derive(Debug, Clone)]
+pub struct Database {
+    inner: IdbDatabase
+}
+
+impl Database {
+    /// Opens the database with the specified name, creating it if it doesn't exist.
+    ///
+    /// If the database's version is lower than `version` (or it's being created), `upgrade` is called to bring it up to date.
+    /// If `upgrade` fails, the upgrade is rolled back and the database isn't opened.
+    ///
+    /// Connections opened by this crate close themselves when another connection upgrades the database. If the upgrade is
+    /// blocked by a connection of another context (another tab, for example) that doesn't, an error is returned.
+    pub async fn open<F: 'static + FnOnce(&Upgrade) -> Result<()>> (name: &str, version: u32, upgrade: F) -> Result<Self> {
+        let req = factory()?.open_with_u32(name, version)?;
+        let failure = Rc::new(RefCell::new(None));
+
+        let my_failure = failure.clone();
+        let my_req = req.clone();
+        let on_upgrade = Closure::<dyn FnMut(IdbVersionChangeEvent)>::once(move |evt: IdbVersionChangeEvent| {
+            let (db, transaction) = match (my_req.result(), my_req.transaction()) {
+                (Ok(db), Some(transaction)) => (db.unchecked_into::<IdbDatabase>(), transaction),
+                _ => return
+            };
+
+            let info = Upgrade {
+                db,
+                transaction,
+                old_version: evt.old_version() as u32,
+                new_version: evt.new_version().unwrap_or_default() as u32
+            };
+
+            if let Err(e) = upgrade(&info) {
+                let _ = info.transaction.abort();
+                *my_failure.borrow_mut() = Some(e);
+            }
+        });
+
+        let mut request = RequestFuture::new(req.clone().into());
+        let blocked = Rc::new(Cell::new(false));
+
+        let my_blocked = blocked.clone();
+        let my_waker = request.waker.clone();
+        let on_blocked = Closure::<dyn FnMut()>::new(move || {
+            my_blocked.set(true);
+            if let Some(waker) = my_waker.take() {
+                waker.wake()
+            }
+        });
+
+        req.set_onupgradeneeded(Some(on_upgrade.as_ref().unchecked_ref()));
+        req.set_onblocked(Some(on_blocked.as_ref().unchecked_ref()));
+        let result = futures::future::poll_fn(|cx| match blocked.get() {
+            true => Poll::Ready(None),
+            false => request.poll_unpin(cx).map(Some)
+        }).await;
+        drop(request);
+        req.set_onupgradeneeded(None);
+        req.set_onblocked(None);
+
+        let Some(result) = result else {
+            // the request can't be cancelled, so the connection is closed as soon as it opens
+            CLOSE_ON_SUCCESS.with(|f| req.set_onsuccess(Some(f.as_ref().unchecked_ref())));
+            return Err(Error::new(ErrorKind::Abort, "the upgrade is blocked by connections of other contexts"))
+        };
+
+        if let Some(e) = failure.take() {
+            return Err(e)
+        }
+
+        let inner = result?.unchecked_into::<IdbDatabase>();
+        close_on_version_change(&inner);
+        return Ok(Self { inner })
+    }
+
+    /// Opens the latest version of the database with the specified name, creating it (with version `1`) if it doesn't exist
+    pub async fn open_latest (name: &str) -> Result<Self> {
+        let req = factory()?.open(name)?;
+        let inner = RequestFuture::new(req.into()).await?.unchecked_into::<IdbDatabase>();
+        close_on_version_change(&inner);
+        return Ok(Self { inner })
+    }
+
+    /// Deletes the database with the specified name
+    pub async fn delete (name: &str) -> Result<()> {
+        let req = factory()?.delete_database(name)?;
+        let _ = RequestFuture::new(req.into()).await?;
+        return Ok(())
+    }
+
+    /// Returns the name of the database
+    #[inline]
+    pub fn name (&self) -> String {
+        return self.inner.name()
+    }
+
+    /// Returns the version of the database
+    #[inline]
+    pub fn version (&self) -> u32 {
+        return self.inner.version() as u32
+    }
+
+    /// Returns `true` if the database has an object store with the specified name
+    #[inline]
+    pub fn has_store (&self, name: &str) -> bool {
+        return self.inner.object_store_names().contains(name)
+    }
+
+    /// Returns a [`KvStore`] over the object store with the specified name
+    #[inline]
+    pub fn store (&self, name: impl Into<String>) -> IdbStore {
+        return IdbStore { db: Rc::new(RefCell::new(self.inner.clone())), name: Rc::from(name.into()) }
+    }
+
+    /// Returns the underlying [`IdbDatabase`]
+    #[inline]
+    pub fn raw (&self) -> &IdbDatabase {
+        return &self.inner
+    }
+
+    /// Closes the connection to the database, once it's pending transactions complete
+    #[inline]
+    pub fn close (&self) {
+        self.inner.close()
+    }
+}
+
+/// An upgrade of a [`Database`], passed to the `upgrade` callback of [`Database::open`]
+#[derive(Debug)]
+pub struct Upgrade {
+    db: IdbDatabase,
+    transaction: IdbTransaction,
+    old_version: u32,
+    new_version: u32
+}
+
+impl Upgrade {
+    /// Returns the version of the database before the upgrade, or `0` if it's being created
+    #[inline]
+    pub fn old_version (&self) -> u32 {
+        return self.old_version
+    }
+
+    /// Returns the version of the database after the upgrade
+    #[inline]
+    pub fn new_version (&self) -> u32 {
+        return self.new_version
+    }
+
+    /// Returns `true` if the database has an object store with the specified name
+    #[inline]
+    pub fn has_store (&self, name: &str) -> bool {
+        return self.db.object_store_names().contains(name)
+    }
+
+    /// Creates a new object store with the specified name, whose keys are provided explicitly
+    #[inline]
+    pub fn create_store (&self, name: &str) -> Result<()> {
+        let _ = self.db.create_object_store(name)?;
+        return Ok(())
+    }
+
+    /// Deletes the object store with the specified name
+    #[inline]
+    pub fn delete_store (&self, name: &str) -> Result<()> {
+        return Ok(self.db.delete_object_store(name)?)
+    }
+
+    /// Returns the underlying [`IdbDatabase`]
+    #[inline]
+    pub fn raw (&self) -> &IdbDatabase {
+        return &self.db
+    }
+
+    /// Returns the `versionchange` transaction of the upgrade
+    #[inline]
+    pub fn transaction (&self) -> &IdbTransaction {
+        return &self.transaction
+    }
+}
+
+/// A [`KvStore`] over an IndexedDB object store.
+///
+/// Every operation runs in it's own transaction. Values are stored with the structured clone algorithm,
+/// so byte buffers are stored as-is.
+///
+/// If the connection is closed because another connection upgraded the database, it's reopened on the next operation.
+#[derive(Debug, Clone)]
+pub struct IdbStore {
+    db: Rc<RefCell<IdbDatabase>>,
+    name: Rc<str>
+}
+
+impl IdbStore {
+    /// Opens the object store with the specified name, in the database with the specified name.
+    ///
+    /// If the database or the object store don't exist, they're created.
+    pub async fn open (db: &str, name: &str) -> Result<Self> {
+        let lock = OPEN_LOCK.with(Rc::clone);
+        let _guard = lock.lock().await;
+
+        let database = Database::open_latest(db).await?;
+        if database.has_store(name) {
+            return Ok(database.store(name))
+        }
+
+        let version = database.version() + 1;
+        database.close();
+
+        let my_name = name.to_string();
+        let database = Database::open(db, version, move |upgrade| {
+            if !upgrade.has_store(&my_name) {
+                upgrade.create_store(&my_name)?;
+            }
+            return Ok(())
+        }).await?;
+
+        return Ok(database.store(name))
+    }
+
+    /// Returns the name of the object store
+    #[inline]
+    pub fn name (&self) -> &str {
+        return &self.name
+    }
+
+    /// Removes all the entries from the object store
+    pub async fn clear (&self) -> Result<()> {
+        let req = self.object_store(IdbTransactionMode::Readwrite).await?.clear()?;
+        let _ = RequestFuture::new(req).await?;
+        return Ok(())
+    }
+
+    async fn object_store (&self, mode: IdbTransactionMode) -> Result<IdbObjectStore> {
+        let db = self.db.borrow().clone();
+        let transaction = match db.transaction_with_str_and_mode(&self.name, mode) {
+            Ok(transaction) => transaction,
+            // the connection was closed by an upgrade
+            Err(e) if e.dyn_ref::<web_sys::DomException>().is_some_and(|e| e.name() == "InvalidStateError") => {
+                let db = Database::open_latest(&db.name()).await?.inner;
+                let transaction = db.transaction_with_str_and_mode(&self.name, mode)?;
+                *self.db.borrow_mut() = db;
+                transaction
+            },
+            Err(e) => return Err(e.into())
+        };
+        return Ok(transaction.object_store(&self.name)?)
+    }
+}
+
+impl KvStore for IdbStore {
+    fn get<'a, T: 'a + DeserializeOwned> (&'a self, key: &'a str) -> LocalBoxFuture<'a, Result<Option<T>>> {
+        return async move {
+            let req = self.object_store(IdbTransactionMode::Readonly).await?.get(&JsValue::from_str(key))?;
+            let value = RequestFuture::new(req).await?;
+            if value.is_undefined() {
+                return Ok(None)
+            }
+            return from_js(value).map(Some)
+        }.boxed_local()
+    }
+
+    fn set<'a, T: ?Sized + Serialize> (&'a self, key: &'a str, value: &T) -> LocalBoxFuture<'a, Result<()>> {
+        let value = to_js(value);
+        return async move {
+            let req = self.object_store(IdbTransactionMode::Readwrite).await?.put_with_key(&value?, &JsValue::from_str(key))?;
+            let _ = RequestFuture::new(req).await?;
+            return Ok(())
+        }.boxed_local()
+    }
+
+    fn remove<'a> (&'a self, key: &'a str) -> LocalBoxFuture<'a, Result<()>> {
+        return async move {
+            let req = self.object_store(IdbTransactionMode::Readwrite).await?.delete(&JsValue::from_str(key))?;
+            let _ = RequestFuture::new(req).await?;
+            return Ok(())
+        }.boxed_local()
+    }
+
+    fn iter<'a, T: 'a + DeserializeOwned> (&'a self) -> LocalBoxFuture<'a, Result<std::vec::IntoIter<(String, T)>>> {
+        return async move {
+            // both requests run in the same transaction, so they see the same entries
+            let store = self.object_store(IdbTransactionMode::Readonly).await?;
+            let keys = RequestFuture::new(store.get_all_keys()?);
+            let values = RequestFuture::new(store.get_all()?);
+            let (keys, values) = futures::try_join!(keys, values)?;
+
+            let keys = keys.unchecked_into::<js_sys::Array>();
+            let values = values.unchecked_into::<js_sys::Array>();
+            let entries = keys.iter().zip(values.iter())
+                .filter_map(|(key, value)| Some((key.as_string()?, value)))
+                .map(|(key, value)| Ok((key, from_js(value)?)))
+                .collect::<Result<Vec<_>>>()?;
+
+            return Ok(entries.into_iter())
+        }.boxed_local()
+    }
+}
+
+/// Future that resolves with the result of an [`IdbRequest`]
+pub(crate) struct RequestFuture {
+    req: IdbRequest,
+    waker: Rc<Cell<Option<Waker>>>,
+    _listener: Closure<dyn FnMut()>
+}
+
+impl RequestFuture {
+    pub(crate) fn new (req: IdbRequest) -> Self {
+        let waker = Rc::new(Cell::new(None::<Waker>));
+        let my_waker = waker.clone();
+        let listener = Closure::<dyn FnMut()>::new(move || {
+            if let Some(waker) = my_waker.take() {
+                waker.wake()
+            }
+        });
+
+        req.set_onsuccess(Some(listener.as_ref().unchecked_ref()));
+        req.set_onerror(Some(listener.as_ref().unchecked_ref()));
+        return Self { req, waker, _listener: listener }
+    }
+}
+
+impl Future for RequestFuture {
+    type Output = Result<JsValue>;
+
+    fn poll(self: std::pin::Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Self::Output> {
+        if self.req.ready_state() == IdbRequestReadyState::Pending {
+            self.waker.set(Some(cx.waker().clone()));
+            return Poll::Pending
+        }
+
+        if let Ok(Some(e)) = self.req.error() {
+            return Poll::Ready(Err(JsValue::from(e).into()))
+        }
+        return Poll::Ready(Ok(self.req.result()?))
+    }
+}
+
+impl Drop for RequestFuture {
+    #[inline]
+    fn drop(&mut self) {
+        self.req.set_onsuccess(None);
+        self.req.set_onerror(None);
+    }
+}
@@ -0,0 +1,163 @@
+use std::{fmt::Display, cell::Cell};
+use wasm_bindgen::JsValue;
+use crate::{Result, random::{random, uuid_v4_bytes, format_uuid}};
+
+/// Crockford's base32 alphabet, used by ULIDs
+const CROCKFORD: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+/// Largest timestamp that fits in 48 bits, in milliseconds
+const MAX_TIMESTAMP: u64 = (1 << 48) - 1;
+
+thread_local! {
+    /// Last timestamp and random bits generated, to keep the ids generated within the same millisecond ordered
+    static LAST: Cell<(u64, u128)> = Cell::new((0, 0));
+}
+
+/// A 128-bit universally unique identifier
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct Uuid([u8; 16]);
+
+impl Uuid {
+    /// Creates a UUID from it's bytes
+    #[inline]
+    pub const fn from_bytes (bytes: [u8; 16]) -> Self {
+        return Self(bytes)
+    }
+
+    /// Returns the bytes of the UUID
+    #[inline]
+    pub const fn as_bytes (&self) -> &[u8; 16] {
+        return &self.0
+    }
+
+    /// Returns the bytes of the UUID
+    #[inline]
+    pub const fn into_bytes (self) -> [u8; 16] {
+        return self.0
+    }
+
+    /// Returns the version of the UUID
+    #[inline]
+    pub const fn version (&self) -> u8 {
+        return self.0[6] >> 4
+    }
+}
+
+impl Display for Uuid {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&format_uuid(&self.0))
+    }
+}
+
+impl From<Uuid> for [u8; 16] {
+    #[inline]
+    fn from(value: Uuid) -> Self {
+        value.0
+    }
+}
+
+/// A universally unique lexicographically sortable identifier ([ULID](https://github.com/ulid/spec))
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct Ulid([u8; 16]);
+
+impl Ulid {
+    /// Creates a ULID from it's bytes
+    #[inline]
+    pub const fn from_bytes (bytes: [u8; 16]) -> Self {
+        return Self(bytes)
+    }
+
+    /// Returns the bytes of the ULID
+    #[inline]
+    pub const fn as_bytes (&self) -> &[u8; 16] {
+        return &self.0
+    }
+
+    /// Returns the bytes of the ULID
+    #[inline]
+    pub const fn into_bytes (self) -> [u8; 16] {
+        return self.0
+    }
+
+    /// Returns the time at which the ULID was generated, in milliseconds since the Unix epoch
+    #[inline]
+    pub fn timestamp (&self) -> u64 {
+        return (u128::from_be_bytes(self.0) >> 80) as u64
+    }
+}
+
+impl Display for Ulid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = u128::from_be_bytes(self.0);
+        let mut result = [0u8; 26];
+        for (i, char) in result.iter_mut().enumerate() {
+            let shift = 5 * (25 - i);
+            *char = CROCKFORD[((value >> shift) & 0x1f) as usize];
+        }
+        // SAFETY: The alphabet is ASCII
+        f.write_str(unsafe { core::str::from_utf8_unchecked(&result) })
+    }
+}
+
+impl From<Ulid> for [u8; 16] {
+    #[inline]
+    fn from(value: Ulid) -> Self {
+        value.0
+    }
+}
+
+/// Returns a random (version 4) UUID
+#[inline]
+pub fn uuid_v4 () -> Result<Uuid> {
+    return uuid_v4_bytes().map(Uuid)
+}
+
+/// Returns a time-ordered (version 7) UUID, whose first 48 bits are the current time in milliseconds.
+///
+/// UUIDs generated by the same thread are strictly increasing, even within the same millisecond.
+pub fn uuid_v7 () -> Result<Uuid> {
+    let mut bytes = next_ordered()?.to_be_bytes();
+    bytes[6] = (bytes[6] & 0x0f) | 0x70;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    return Ok(Uuid(bytes))
+}
+
+/// Returns a new ULID, whose first 48 bits are the current time in milliseconds.
+///
+/// ULIDs generated by the same thread are strictly increasing, even within the same millisecond.
+#[inline]
+pub fn ulid () -> Result<Ulid> {
+    return next_ordered().map(|x| Ulid(x.to_be_bytes()))
+}
+
+/// Returns a 48-bit timestamp followed by 80 random bits, incrementing the random bits of the last value
+/// if the clock hasn't advanced since.
+fn next_ordered () -> Result<u128> {
+    const RANDOM_MASK: u128 = (1 << 80) - 1;
+
+    let now = now_ms().min(MAX_TIMESTAMP);
+    let (last_ms, last_random) = LAST.with(Cell::get);
+
+    let (ms, random) = match now <= last_ms && last_random < RANDOM_MASK {
+        true => (last_ms, last_random + 1),
+        false => (now.max(last_ms + 1).min(MAX_TIMESTAMP), u128::from_be_bytes(random::<[u8; 16]>()?) & RANDOM_MASK)
+    };
+
+    LAST.with(|last| last.set((ms, random)));
+    return Ok(((ms as u128) << 80) | random)
+}
+
+/// Returns the current time in milliseconds since the Unix epoch, from the high resolution clock if available
+fn now_ms () -> u64 {
+    let origin = js_sys::Reflect::get(&js_sys::global(), &JsValue::from_str("performance")).ok()
+        .filter(|x| x.is_object())
+        .and_then(|perf| js_sys::Reflect::get(&perf, &JsValue::from_str("timeOrigin")).ok())
+        .and_then(|x| x.as_f64());
+
+    return match origin {
+        Some(origin) => (origin + crate::time::now().as_secs_f64() * 1000.) as u64,
+        None => js_sys::Date::now() as u64
+    }
+}
@@ -0,0 +1,92 @@
+use js_sys::Uint8ClampedArray;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{ImageBitmap, OffscreenCanvas, OffscreenCanvasRenderingContext2d, Blob, ImageData};
+use crate::{Result, Error, ErrorKind, io::{Request, Response}, scope::create_image_bitmap};
+
+/// A decoded image, backed by an [`ImageBitmap`].
+///
+/// The bitmap is released when the image is droped.
+#[derive(Debug)]
+pub struct Image {
+    bitmap: ImageBitmap
+}
+
+impl Image {
+    /// Fetches and decodes the image at the specified url
+    #[inline]
+    pub async fn from_url (url: &str) -> Result<Self> {
+        let resp = Request::get(url).await?.error_for_status().await?;
+        return Self::from_response(resp).await
+    }
+
+    /// Decodes the image contained in the response's body
+    #[inline]
+    pub async fn from_response (resp: Response) -> Result<Self> {
+        let blob = resp.blob().await?;
+        return Self::from_blob(&blob).await
+    }
+
+    /// Decodes the image contained in the blob
+    #[inline]
+    pub async fn from_blob (blob: &Blob) -> Result<Self> {
+        return Self::from_source(blob).await
+    }
+
+    /// Decodes the image from any source accepted by [`createImageBitmap`](https://developer.mozilla.org/en-US/docs/Web/API/createImageBitmap)
+    pub async fn from_source (source: &JsValue) -> Result<Self> {
+        let bitmap = JsFuture::from(create_image_bitmap(source)?).await?;
+        return Ok(Self { bitmap: bitmap.dyn_into().map_err(Error::from)? })
+    }
+
+    /// Returns the width of the image, in pixels
+    #[inline]
+    pub fn width (&self) -> u32 {
+        return self.bitmap.width()
+    }
+
+    /// Returns the height of the image, in pixels
+    #[inline]
+    pub fn height (&self) -> u32 {
+        return self.bitmap.height()
+    }
+
+    /// Returns the underlying [`ImageBitmap`]
+    #[inline]
+    pub fn bitmap (&self) -> &ImageBitmap {
+        return &self.bitmap
+    }
+
+    /// Returns the RGBA pixel data of the image, row by row, as a [`Uint8ClampedArray`]
+    pub fn pixels_js (&self) -> Result<Uint8ClampedArray> {
+        let data = self.image_data()?;
+        let pixels = js_sys::Reflect::get(&data, &JsValue::from_str("data"))?;
+        return pixels.dyn_into().map_err(Into::into)
+    }
+
+    /// Returns the RGBA pixel data of the image, row by row
+    #[inline]
+    pub fn pixels (&self) -> Result<Vec<u8>> {
+        return Ok(self.image_data()?.data().0)
+    }
+
+    fn image_data (&self) -> Result<ImageData> {
+        let (width, height) = (self.width(), self.height());
+        let canvas = OffscreenCanvas::new(width, height)?;
+
+        let context = match canvas.get_context("2d")? {
+            Some(context) => context.unchecked_into::<OffscreenCanvasRenderingContext2d>(),
+            None => return Err(Error::new(ErrorKind::NotSupported, "2D context not supported"))
+        };
+
+        context.draw_image_with_image_bitmap(&self.bitmap, 0., 0.)?;
+        return Ok(context.get_image_data(0., 0., width as f64, height as f64)?)
+    }
+}
+
+impl Drop for Image {
+    #[inline]
+    fn drop(&mut self) {
+        self.bitmap.close()
+    }
+}
@@ -0,0 +1,282 @@
+use std::task::Poll;
+use futures::{Stream, StreamExt};
+use wasm_bindgen::{JsCast, prelude::Closure};
+use web_sys::{EventTarget, AddEventListenerOptions};
+use crate::{Result, utils::{LocalReceiver, local_channel}, math::{Vec2d, Vec3d}, diagnostics::Tracked};
+
+flat_mod! { clipboard }
+
+/// Options of an input listener
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ListenOptions {
+    /// If `true`, events are received during the capture phase, before they reach the target's descendants
+    pub capture: bool,
+    /// If `true`, the listener promises to never cancel the events, allowing the browser to optimize scrolling
+    pub passive: bool
+}
+
+/// State of the modifier keys when an event happened
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Modifiers {
+    pub ctrl: bool,
+    pub shift: bool,
+    pub alt: bool,
+    pub meta: bool
+}
+
+/// A keyboard event
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyEvent {
+    /// The value of the key (`"a"`, `"Enter"`, `"ArrowUp"`, ...), taking into account the keyboard layout
+    pub key: String,
+    /// The physical key (`"KeyA"`, `"Enter"`, ...), independent of the keyboard layout
+    pub code: String,
+    /// `true` if the key is being held down, and the event is being automatically repeated
+    pub repeat: bool,
+    pub modifiers: Modifiers
+}
+
+impl From<web_sys::KeyboardEvent> for KeyEvent {
+    #[inline]
+    fn from(evt: web_sys::KeyboardEvent) -> Self {
+        return Self {
+            key: evt.key(),
+            code: evt.code(),
+            repeat: evt.repeat(),
+            modifiers: Modifiers {
+                ctrl: evt.ctrl_key(),
+                shift: evt.shift_key(),
+                alt: evt.alt_key(),
+                meta: evt.meta_key()
+            }
+        }
+    }
+}
+
+/// A pointer (mouse, pen or touch) event
+#[derive(Debug, Clone, PartialEq)]
+pub struct PointerEvent {
+    /// Unique identifier of the pointer
+    pub id: i32,
+    /// Type of the pointer (`"mouse"`, `"pen"` or `"touch"`)
+    pub pointer_type: String,
+    /// Position of the pointer, relative to the viewport
+    pub position: Vec2d,
+    /// Position of the pointer, relative to the target
+    pub offset: Vec2d,
+    /// Bitmask of the buttons being pressed
+    pub buttons: u16,
+    /// Pressure of the pointer, between `0` and `1`
+    pub pressure: f32,
+    pub modifiers: Modifiers
+}
+
+impl From<web_sys::PointerEvent> for PointerEvent {
+    #[inline]
+    fn from(evt: web_sys::PointerEvent) -> Self {
+        return Self {
+            id: evt.pointer_id(),
+            pointer_type: evt.pointer_type(),
+            position: Vec2d::new(evt.client_x() as f64, evt.client_y() as f64),
+            offset: Vec2d::new(evt.offset_x() as f64, evt.offset_y() as f64),
+            buttons: evt.buttons(),
+            pressure: evt.pressure(),
+            modifiers: Modifiers {
+                ctrl: evt.ctrl_key(),
+                shift: evt.shift_key(),
+                alt: evt.alt_key(),
+                meta: evt.meta_key()
+            }
+        }
+    }
+}
+
+/// Unit of the deltas of a [`WheelEvent`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DeltaMode {
+    Pixel,
+    Line,
+    Page
+}
+
+/// A wheel event
+#[derive(Debug, Clone, PartialEq)]
+pub struct WheelEvent {
+    /// Scroll amount in each axis
+    pub delta: Vec3d,
+    pub mode: DeltaMode,
+    /// Position of the pointer, relative to the viewport
+    pub position: Vec2d,
+    pub modifiers: Modifiers
+}
+
+impl From<web_sys::WheelEvent> for WheelEvent {
+    #[inline]
+    fn from(evt: web_sys::WheelEvent) -> Self {
+        return Self {
+            delta: Vec3d::new(evt.delta_x(), evt.delta_y(), evt.delta_z()),
+            mode: match evt.delta_mode() {
+                web_sys::WheelEvent::DOM_DELTA_LINE => DeltaMode::Line,
+                web_sys::WheelEvent::DOM_DELTA_PAGE => DeltaMode::Page,
+                _ => DeltaMode::Pixel
+            },
+            position: Vec2d::new(evt.client_x() as f64, evt.client_y() as f64),
+            modifiers: Modifiers {
+                ctrl: evt.ctrl_key(),
+                shift: evt.shift_key(),
+                alt: evt.alt_key(),
+                meta: evt.meta_key()
+            }
+        }
+    }
+}
+
+/// A single touch point
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Touch {
+    /// Unique identifier of the touch point, for as long as it's in contact with the surface
+    pub id: i32,
+    /// Position of the touch point, relative to the viewport
+    pub position: Vec2d
+}
+
+/// A touch event
+#[derive(Debug, Clone, PartialEq)]
+pub struct TouchEvent {
+    /// Every touch point currently in contact with the surface
+    pub touches: Vec<Touch>,
+    /// The touch points that changed in this event
+    pub changed: Vec<Touch>,
+    pub modifiers: Modifiers
+}
+
+impl From<web_sys::TouchEvent> for TouchEvent {
+    #[inline]
+    fn from(evt: web_sys::TouchEvent) -> Self {
+        fn touches (list: web_sys::TouchList) -> Vec<Touch> {
+            return (0..list.length())
+                .filter_map(|i| list.get(i))
+                .map(|x| Touch { id: x.identifier(), position: Vec2d::new(x.client_x() as f64, x.client_y() as f64) })
+                .collect()
+        }
+
+        return Self {
+            touches: touches(evt.touches()),
+            changed: touches(evt.changed_touches()),
+            modifiers: Modifiers {
+                ctrl: evt.ctrl_key(),
+                shift: evt.shift_key(),
+                alt: evt.alt_key(),
+                meta: evt.meta_key()
+            }
+        }
+    }
+}
+
+macro_rules! impl_listen {
+    ($($(#[$meta:meta])* $name:ident => $ty:ident: $raw:ident [$event:literal]),+) => {
+        $(
+            $(#[$meta])*
+            #[inline]
+            pub fn $name (target: &EventTarget, options: ListenOptions) -> Result<EventStream<$ty>> {
+                return EventStream::new::<web_sys::$raw>(target, $event, options)
+            }
+        )+
+    };
+}
+
+impl_listen! {
+    /// Returns a stream of the keys pressed while `target` is focused
+    key_down => KeyEvent: KeyboardEvent ["keydown"],
+    /// Returns a stream of the keys released while `target` is focused
+    key_up => KeyEvent: KeyboardEvent ["keyup"],
+    /// Returns a stream of the pointer movements over `target`
+    pointer_move => PointerEvent: PointerEvent ["pointermove"],
+    /// Returns a stream of the pointers pressed over `target`
+    pointer_down => PointerEvent: PointerEvent ["pointerdown"],
+    /// Returns a stream of the pointers released over `target`
+    pointer_up => PointerEvent: PointerEvent ["pointerup"],
+    /// Returns a stream of the wheel events over `target`
+    wheel => WheelEvent: WheelEvent ["wheel"],
+    /// Returns a stream of the touch points that start touching `target`
+    touch_start => TouchEvent: TouchEvent ["touchstart"],
+    /// Returns a stream of the touch points that move along `target`
+    touch_move => TouchEvent: TouchEvent ["touchmove"],
+    /// Returns a stream of the touch points that stop touching `target`
+    touch_end => TouchEvent: TouchEvent ["touchend"]
+}
+
+/// A [`Stream`] of typed events of a target.
+///
+/// When droped, the listener is removed, releasing all the memory of it's closure.
+pub struct EventStream<T> {
+    target: EventTarget,
+    ty: &'static str,
+    capture: bool,
+    closure: Closure<dyn FnMut(web_sys::Event)>,
+    recv: LocalReceiver<T>,
+    _tracked: Tracked
+}
+
+impl<T: 'static> EventStream<T> {
+    /// Listens for events of type `ty` on `target`, converting them into `T`
+    #[inline]
+    pub fn new<E: 'static + JsCast + Into<T>> (target: &EventTarget, ty: &'static str, options: ListenOptions) -> Result<Self> {
+        return Self::with_map(target, ty, options, E::into)
+    }
+
+    /// Listens for events of type `ty` on `target`, mapping them into `T` with `f`
+    pub fn with_map<E: 'static + JsCast, F: 'static + FnMut(E) -> T> (target: &EventTarget, ty: &'static str, options: ListenOptions, mut f: F) -> Result<Self> {
+        let (send, recv) = local_channel();
+        let closure = Closure::<dyn FnMut(web_sys::Event)>::new(move |evt: web_sys::Event| {
+            let _ = send.try_send(f(evt.unchecked_into::<E>()));
+        });
+
+        let listener: &js_sys::Function;
+        cfg_if::cfg_if! {
+            if #[cfg(debug_assertions)] {
+                listener = closure.as_ref().dyn_ref().unwrap();
+            } else {
+                listener = closure.as_ref().unchecked_ref();
+            }
+        }
+
+        let mut ops = AddEventListenerOptions::new();
+        ops.capture(options.capture);
+        ops.passive(options.passive);
+        target.add_event_listener_with_callback_and_add_event_listener_options(ty, listener, &ops)?;
+
+        return Ok(Self {
+            target: target.clone(),
+            ty,
+            capture: options.capture,
+            closure,
+            recv,
+            _tracked: Tracked::new("input::EventStream")
+        })
+    }
+}
+
+impl<T> Stream for EventStream<T> {
+    type Item = T;
+
+    #[inline]
+    fn poll_next(mut self: std::pin::Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Option<Self::Item>> {
+        self.recv.poll_next_unpin(cx)
+    }
+}
+
+impl<T> Drop for EventStream<T> {
+    fn drop(&mut self) {
+        let listener: &js_sys::Function;
+        cfg_if::cfg_if! {
+            if #[cfg(debug_assertions)] {
+                listener = self.closure.as_ref().dyn_ref().unwrap();
+            } else {
+                listener = self.closure.as_ref().unchecked_ref();
+            }
+        }
+
+        let _ = self.target.remove_event_listener_with_callback_and_bool(self.ty, listener, self.capture);
+    }
+}
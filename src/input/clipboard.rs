@@ -0,0 +1,144 @@
+use web_sys::{EventTarget, ClipboardEvent, DataTransfer};
+use crate::{Result, fs::File, scope::window};
+use super::{EventStream, ListenOptions};
+
+/// Options of a clipboard listener
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ClipboardOptions {
+    pub listen: ListenOptions,
+    /// If `true`, the browser's default action (inserting the pasted contents, or copying the selection) is prevented
+    pub prevent_default: bool
+}
+
+/// Contents of the clipboard, as seen by a clipboard event
+#[derive(Debug, Default)]
+pub struct ClipboardData {
+    /// Plain text contents (`text/plain`), if any
+    pub text: Option<String>,
+    /// HTML contents (`text/html`), if any
+    pub html: Option<String>,
+    /// Files that aren't images
+    pub files: Vec<File>,
+    /// Image files (`image/*`), like screenshots
+    pub images: Vec<File>
+}
+
+impl ClipboardData {
+    /// Reads the contents of a [`DataTransfer`]
+    pub fn from_data_transfer (data: &DataTransfer) -> Self {
+        fn get (data: &DataTransfer, format: &str) -> Option<String> {
+            return data.get_data(format).ok().filter(|x| !x.is_empty())
+        }
+
+        let mut files = Vec::new();
+        let mut images = Vec::new();
+        if let Some(list) = data.files() {
+            for file in (0..list.length()).filter_map(|i| list.get(i)) {
+                match file.type_().starts_with("image/") {
+                    true => images.push(File::from(file)),
+                    false => files.push(File::from(file))
+                }
+            }
+        }
+
+        return Self {
+            text: get(data, "text/plain"),
+            html: get(data, "text/html"),
+            files,
+            images
+        }
+    }
+}
+
+/// Contents written into the clipboard by [`watch_copy_with`], replacing the copied selection
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ClipboardWrite {
+    entries: Vec<(String, String)>
+}
+
+impl ClipboardWrite {
+    /// Creates an empty write
+    #[inline]
+    pub fn new () -> Self {
+        return Self::default()
+    }
+
+    /// Sets the plain text contents (`text/plain`)
+    #[inline]
+    pub fn text (self, text: impl Into<String>) -> Self {
+        return self.data("text/plain", text)
+    }
+
+    /// Sets the HTML contents (`text/html`)
+    #[inline]
+    pub fn html (self, html: impl Into<String>) -> Self {
+        return self.data("text/html", html)
+    }
+
+    /// Sets the contents of the specified format
+    #[inline]
+    pub fn data (mut self, format: impl Into<String>, data: impl Into<String>) -> Self {
+        self.entries.push((format.into(), data.into()));
+        self
+    }
+
+    fn write_into (&self, data: &DataTransfer) -> Result<()> {
+        data.clear_data()?;
+        for (format, value) in self.entries.iter() {
+            data.set_data(format, value)?;
+        }
+        return Ok(())
+    }
+}
+
+/// Returns a stream of the contents pasted into `target`
+#[inline]
+pub fn watch_paste (target: &EventTarget, options: ClipboardOptions) -> Result<EventStream<ClipboardData>> {
+    return EventStream::with_map(target, "paste", options.listen, move |evt: ClipboardEvent| {
+        if options.prevent_default {
+            evt.prevent_default()
+        }
+
+        return evt.clipboard_data()
+            .map(|data| ClipboardData::from_data_transfer(&data))
+            .unwrap_or_default()
+    })
+}
+
+/// Returns a stream of the contents copied from `target`, whose text is the selected text
+#[inline]
+pub fn watch_copy (target: &EventTarget, options: ClipboardOptions) -> Result<EventStream<ClipboardData>> {
+    return watch_copy_with(target, options, |_| None)
+}
+
+/// Returns a stream of the contents copied from `target`, whose text is the selected text.
+///
+/// `f` is called while the event is being dispatched, and if it returns some contents, they're written into the clipboard
+/// instead of the selection.
+/// ```ignore
+/// let copies = watch_copy_with(&editor, ClipboardOptions::default(), |copied| {
+///     let text = copied.text.as_deref()?;
+///     Some(ClipboardWrite::new().text(text).html(render_markdown(text)))
+/// })?;
+/// ```
+pub fn watch_copy_with<F: 'static + FnMut(&ClipboardData) -> Option<ClipboardWrite>> (target: &EventTarget, options: ClipboardOptions, mut f: F) -> Result<EventStream<ClipboardData>> {
+    return EventStream::with_map(target, "copy", options.listen, move |evt: ClipboardEvent| {
+        let copied = ClipboardData {
+            text: window().ok()
+                .and_then(|window| window.get_selection().ok().flatten())
+                .map(|selection| String::from(selection.to_string()))
+                .filter(|x| !x.is_empty()),
+            ..Default::default()
+        };
+
+        let replaced = match (f(&copied), evt.clipboard_data()) {
+            (Some(write), Some(data)) => write.write_into(&data).is_ok(),
+            _ => false
+        };
+
+        if replaced || options.prevent_default {
+            evt.prevent_default()
+        }
+        return copied
+    })
+}
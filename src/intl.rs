@@ -0,0 +1,294 @@
+use chrono::{DateTime, TimeZone};
+use js_sys::{Array, Intl};
+use serde::Serialize;
+use wasm_bindgen::{JsCast, JsValue};
+use crate::{Result, scope::navigator};
+
+/// Returns the user's preferred languages, as BCP 47 language tags ordered by preference
+pub fn languages () -> Vec<String> {
+    let nav = navigator();
+    if let Ok(languages) = js_sys::Reflect::get(&nav, &JsValue::from_str("languages")) && Array::is_array(&languages) {
+        let languages = languages.unchecked_into::<Array>().iter().filter_map(|x| x.as_string()).collect::<Vec<_>>();
+        if !languages.is_empty() {
+            return languages
+        }
+    }
+
+    return js_sys::Reflect::get(&nav, &JsValue::from_str("language")).ok()
+        .and_then(|x| x.as_string())
+        .into_iter()
+        .collect()
+}
+
+/// Returns the locale of `available` that best matches the user's [preferred languages](languages), if any.
+///
+/// Exact matches are preferred, falling back to locales that share the same language (`en-GB` matches `en` and `en-US`).
+pub fn negotiate<'a> (available: &[&'a str]) -> Option<&'a str> {
+    fn language (tag: &str) -> &str {
+        return tag.split(['-', '_']).next().unwrap_or(tag)
+    }
+
+    for preferred in languages() {
+        if let Some(exact) = available.iter().find(|x| x.eq_ignore_ascii_case(&preferred)) {
+            return Some(*exact)
+        }
+
+        let preferred = language(&preferred);
+        if let Some(partial) = available.iter().find(|x| language(x).eq_ignore_ascii_case(preferred)) {
+            return Some(*partial)
+        }
+    }
+
+    return None
+}
+
+#[inline]
+fn locales (locales: &[&str]) -> Array {
+    return locales.iter().map(|x| JsValue::from_str(x)).collect()
+}
+
+#[inline]
+fn options<T: Serialize> (options: &T) -> Result<js_sys::Object> {
+    return Ok(crate::utils::to_js(options)?.unchecked_into())
+}
+
+/// Formatting style of a number
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NumberStyle {
+    Decimal,
+    Percent,
+    /// Requires [`currency`](NumberFormatOptions::currency) to be set
+    Currency,
+    /// Requires [`unit`](NumberFormatOptions::unit) to be set
+    Unit
+}
+
+/// Notation of a formatted number
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Notation {
+    Standard,
+    Scientific,
+    Engineering,
+    Compact
+}
+
+/// Options of a [`NumberFormat`]. Unset options use the locale's defaults.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct NumberFormatOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub style: Option<NumberStyle>,
+    /// ISO 4217 currency code (`"EUR"`, `"USD"`, ...)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
+    /// Sanctioned unit identifier (`"kilometer-per-hour"`, `"byte"`, ...)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unit: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notation: Option<Notation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minimum_fraction_digits: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maximum_fraction_digits: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub use_grouping: Option<bool>
+}
+
+/// Language-sensitive number formatting
+#[derive(Debug, Clone)]
+pub struct NumberFormat {
+    format: js_sys::Function
+}
+
+impl NumberFormat {
+    /// Creates a new number format for the specified locales, or the user's locale if empty
+    #[inline]
+    pub fn new (locales: &[&str]) -> Result<Self> {
+        return Self::with_options(locales, &Default::default())
+    }
+
+    /// Creates a new number format for the specified locales (or the user's locale if empty) and options
+    pub fn with_options (locales: &[&str], options: &NumberFormatOptions) -> Result<Self> {
+        let inner = Intl::NumberFormat::new(&self::locales(locales), &self::options(options)?);
+        return Ok(Self { format: inner.format() })
+    }
+
+    /// Formats the number
+    #[inline]
+    pub fn format (&self, value: f64) -> String {
+        return self.format.call1(&JsValue::UNDEFINED, &JsValue::from_f64(value)).ok()
+            .and_then(|x| x.as_string())
+            .unwrap_or_else(|| value.to_string())
+    }
+}
+
+/// Length of a formatted date or time
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DateTimeStyle {
+    Full,
+    Long,
+    Medium,
+    Short
+}
+
+/// Options of a [`DateTimeFormat`]. Unset options use the locale's defaults.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct DateTimeFormatOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date_style: Option<DateTimeStyle>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time_style: Option<DateTimeStyle>,
+    /// IANA time zone (`"Europe/Madrid"`, `"UTC"`, ...). By default, the user's time zone is used.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time_zone: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hour12: Option<bool>
+}
+
+/// Language-sensitive date and time formatting
+#[derive(Debug, Clone)]
+pub struct DateTimeFormat {
+    format: js_sys::Function
+}
+
+impl DateTimeFormat {
+    /// Creates a new date format for the specified locales, or the user's locale if empty
+    #[inline]
+    pub fn new (locales: &[&str]) -> Result<Self> {
+        return Self::with_options(locales, &Default::default())
+    }
+
+    /// Creates a new date format for the specified locales (or the user's locale if empty) and options
+    pub fn with_options (locales: &[&str], options: &DateTimeFormatOptions) -> Result<Self> {
+        let inner = Intl::DateTimeFormat::new(&self::locales(locales), &self::options(options)?);
+        return Ok(Self { format: inner.format() })
+    }
+
+    /// Formats the date.
+    ///
+    /// The date is formatted in the format's time zone, regardless of the time zone of `date`.
+    #[inline]
+    pub fn format<Tz: TimeZone> (&self, date: &DateTime<Tz>) -> String {
+        return self.format_js(&js_sys::Date::new(&JsValue::from_f64(date.timestamp_millis() as f64)))
+    }
+
+    /// Formats the JavaScript date
+    #[inline]
+    pub fn format_js (&self, date: &js_sys::Date) -> String {
+        return self.format.call1(&JsValue::UNDEFINED, date).ok()
+            .and_then(|x| x.as_string())
+            .unwrap_or_default()
+    }
+}
+
+/// Unit of a relative time
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RelativeTimeUnit {
+    Second,
+    Minute,
+    Hour,
+    Day,
+    Week,
+    Month,
+    Quarter,
+    Year
+}
+
+impl RelativeTimeUnit {
+    /// Returns the unit's name, as expected by JavaScript
+    #[inline]
+    pub fn as_str (self) -> &'static str {
+        return match self {
+            Self::Second => "second",
+            Self::Minute => "minute",
+            Self::Hour => "hour",
+            Self::Day => "day",
+            Self::Week => "week",
+            Self::Month => "month",
+            Self::Quarter => "quarter",
+            Self::Year => "year"
+        }
+    }
+}
+
+/// Options of a [`RelativeTimeFormat`]. Unset options use the locale's defaults.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct RelativeTimeFormatOptions {
+    /// If `true`, idiomatic phrases like "yesterday" are used instead of "1 day ago"
+    #[serde(rename = "numeric", skip_serializing_if = "Option::is_none", serialize_with = "serialize_numeric")]
+    pub idiomatic: Option<bool>,
+    /// Length of the formatted time
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub style: Option<RelativeTimeStyle>
+}
+
+/// Length of a formatted relative time
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RelativeTimeStyle {
+    Long,
+    Short,
+    Narrow
+}
+
+fn serialize_numeric<S: serde::Serializer> (idiomatic: &Option<bool>, serializer: S) -> ::core::result::Result<S::Ok, S::Error> {
+    return serializer.serialize_str(match idiomatic {
+        Some(true) => "auto",
+        _ => "always"
+    })
+}
+
+/// Language-sensitive relative time formatting ("in 3 days", "5 minutes ago", ...)
+#[derive(Debug, Clone)]
+pub struct RelativeTimeFormat {
+    inner: Intl::RelativeTimeFormat
+}
+
+impl RelativeTimeFormat {
+    /// Creates a new relative time format for the specified locales, or the user's locale if empty
+    #[inline]
+    pub fn new (locales: &[&str]) -> Result<Self> {
+        return Self::with_options(locales, &Default::default())
+    }
+
+    /// Creates a new relative time format for the specified locales (or the user's locale if empty) and options
+    pub fn with_options (locales: &[&str], options: &RelativeTimeFormatOptions) -> Result<Self> {
+        let inner = Intl::RelativeTimeFormat::new(&self::locales(locales), &self::options(options)?);
+        return Ok(Self { inner })
+    }
+
+    /// Formats the value in the specified unit. Negative values are formatted as past times.
+    #[inline]
+    pub fn format (&self, value: f64, unit: RelativeTimeUnit) -> String {
+        return self.inner.format(value, unit.as_str()).into()
+    }
+
+    /// Formats the duration in it's largest whole unit. Negative durations are formatted as past times.
+    pub fn format_duration (&self, duration: chrono::Duration) -> String {
+        const UNITS: &[(RelativeTimeUnit, i64)] = &[
+            (RelativeTimeUnit::Year, 365 * 24 * 3600),
+            (RelativeTimeUnit::Month, 30 * 24 * 3600),
+            (RelativeTimeUnit::Week, 7 * 24 * 3600),
+            (RelativeTimeUnit::Day, 24 * 3600),
+            (RelativeTimeUnit::Hour, 3600),
+            (RelativeTimeUnit::Minute, 60)
+        ];
+
+        let secs = duration.num_seconds();
+        for &(unit, unit_secs) in UNITS {
+            if secs.abs() >= unit_secs {
+                return self.format((secs / unit_secs) as f64, unit)
+            }
+        }
+
+        return self.format(secs as f64, RelativeTimeUnit::Second)
+    }
+}
@@ -0,0 +1,116 @@
+use std::{mem::ManuallyDrop, cell::{Cell, RefCell}};
+use js_sys::Uint8Array;
+use wasm_bindgen::{JsCast, JsValue, prelude::Closure};
+use crate::Result;
+use super::{JsReadStream, TransformStream, TransformStreamDefaultController, cast_chunk};
+
+impl<T: JsCast> JsReadStream<'static, T> {
+    /// Returns a stream whose chunks are the result of applying `f` to the chunks of this one.
+    ///
+    /// If `f` fails, the returned stream errors with it's error, and this one is cancelled.
+    pub fn map_chunk<U: JsCast, F: 'static + FnMut(T) -> Result<U>> (self, mut f: F) -> Result<JsReadStream<'static, U>> {
+        return self.transform(
+            move |chunk, con| {
+                let chunk = f(cast_chunk::<T>(chunk)?)?;
+                con.enqueue(chunk.as_ref());
+                return Ok(())
+            },
+            None
+        )
+    }
+
+    /// Pipes the stream through a `TransformStream` with the specified callbacks, which live as long as the returned stream
+    fn transform<U: JsCast, F: 'static + FnMut(JsValue, &TransformStreamDefaultController) -> Result<()>> (
+        self,
+        mut transform: F,
+        flush: Option<Box<dyn FnOnce(&TransformStreamDefaultController)>>
+    ) -> Result<JsReadStream<'static, U>> {
+        let mut this = ManuallyDrop::new(self);
+        if let Some(reader) = this.reader.take() {
+            reader.release_lock()
+        }
+
+        // custom sources must live as long as they're piped
+        #[cfg(web_sys_unstable_apis)]
+        let builder = this._builder.take();
+        let stream = unsafe { core::ptr::read(&this._stream) };
+
+        let transform = Closure::<dyn FnMut(JsValue, TransformStreamDefaultController) -> ::core::result::Result<(), JsValue>>::new(move |chunk, con| {
+            #[cfg(web_sys_unstable_apis)]
+            let _ = &builder;
+            return transform(chunk, &con).map_err(JsValue::from)
+        });
+
+        let transformer = js_sys::Object::new();
+        js_sys::Reflect::set(&transformer, &JsValue::from_str("transform"), &transform.into_js_value())?;
+        if let Some(flush) = flush {
+            let flush = Closure::once_into_js(move |con: TransformStreamDefaultController| flush(&con));
+            js_sys::Reflect::set(&transformer, &JsValue::from_str("flush"), &flush)?;
+        }
+
+        let through = TransformStream::new(&transformer)?;
+        // the pipe is rejected when the derived stream is cancelled or terminated, which is expected
+        let ignore = Closure::once_into_js(|_: JsValue| {});
+        let _ = stream.pipe_to(&through.writable()).catch(ignore.unchecked_ref());
+        return JsReadStream::new(through.readable())
+    }
+}
+
+impl JsReadStream<'static, Uint8Array> {
+    /// Returns a stream that ends after `n` bytes, cancelling the rest of this one
+    pub fn take_bytes (self, n: u64) -> Result<Self> {
+        let remaining = Cell::new(n);
+        return self.transform(
+            move |chunk, con| {
+                let chunk = cast_chunk::<Uint8Array>(chunk)?;
+                let len = chunk.byte_length() as u64;
+
+                if len < remaining.get() {
+                    remaining.set(remaining.get() - len);
+                    con.enqueue(&chunk);
+                    return Ok(())
+                }
+
+                if remaining.get() > 0 {
+                    con.enqueue(&chunk.subarray(0, remaining.get() as u32));
+                    remaining.set(0);
+                }
+                con.terminate();
+                return Ok(())
+            },
+            None
+        )
+    }
+
+    /// Returns a stream that re-chunks this one into chunks of exactly `n` bytes.
+    ///
+    /// If the length of the stream isn't a multiple of `n`, the last chunk will contain the remaining bytes.
+    pub fn chunk_exact (self, n: u32) -> Result<Self> {
+        let n = n.max(1) as usize;
+        let pending = std::rc::Rc::new(RefCell::new(Vec::<u8>::new()));
+        let my_pending = pending.clone();
+
+        return self.transform(
+            move |chunk, con| {
+                let chunk = cast_chunk::<Uint8Array>(chunk)?;
+                let mut pending = my_pending.borrow_mut();
+                pending.extend(chunk.to_vec());
+
+                let mut chunks = pending.chunks_exact(n);
+                for chunk in chunks.by_ref() {
+                    con.enqueue(&Uint8Array::from(chunk));
+                }
+
+                let consumed = pending.len() - chunks.remainder().len();
+                pending.drain(..consumed);
+                return Ok(())
+            },
+            Some(Box::new(move |con| {
+                let pending = core::mem::take(&mut *pending.borrow_mut());
+                if !pending.is_empty() {
+                    con.enqueue(&Uint8Array::from(&pending as &[u8]));
+                }
+            }))
+        )
+    }
+}
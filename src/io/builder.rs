@@ -1,7 +1,7 @@
 use futures::{Future};
 use wasm_bindgen::{prelude::Closure, JsValue};
 use web_sys::{ReadableStreamDefaultController, WritableStreamDefaultController};
-use crate::{Result, utils::{AbortHandle, Abortable}};
+use crate::{Result, utils::{AbortHandle, Abortable, SignalLink}};
 use super::*;
 use core::marker::PhantomData;
 use std::pin::Pin;
@@ -11,7 +11,7 @@ use wasm_bindgen::JsCast;
 
 #[derive(Debug)]
 enum MaybePromise<'a, T> {
-    Blocking (Closure<dyn FnMut<T, Output = Result<()>>>, PhantomData<&'a mut &'a dyn FnMut<T, Output = Result<()>>>),
+    Blocking (Closure<dyn FnMut<T, Output = Result<(), JsValue>>>, PhantomData<&'a mut &'a dyn FnMut<T, Output = Result<(), JsValue>>>),
     Promise (Closure<dyn FnMut<T, Output = js_sys::Promise>>, PhantomData<&'a mut (&'a (dyn 'a + Future<Output = Result<()>>), &'a dyn FnMut<T, Output = js_sys::Promise>)>)
 }
 
@@ -30,6 +30,10 @@ pub struct ReadBuilder<'a, T> {
     start: Option<MaybePromise<'a, (ReadableStreamDefaultController,)>>,
     pull: Option<MaybePromise<'a, (ReadableStreamDefaultController,)>>,
     cancel: Option<MaybePromise<'a, (JsValue,)>>,
+    high_water_mark: Option<f64>,
+    size: Option<SizeFn<'a>>,
+    signal: Option<web_sys::AbortSignal>,
+    link: Option<SignalLink>,
     pub(super) handle: AbortHandle,
     _phtm: PhantomData<T>
 }
@@ -43,11 +47,11 @@ impl<'a, T: JsCast> ReadBuilder<'a, T> {
     /// This is a method, called immediately when the object is constructed. The contents of this method are defined by the developer, and should aim to get access to the stream source, and do anything else required to set up the stream functionality.
     #[inline]
     pub fn start<F: 'a + FnOnce(ReadStreamController<T>) -> Result<()>> (mut self, f: F) -> Self {
-        let f = move |inner| f(ReadStreamController { inner, _phtm: PhantomData });
+        let f = move |inner| f(ReadStreamController { inner, _phtm: PhantomData }).map_err(JsValue::from);
         let f = unsafe {
             core::mem::transmute::<
-                Box<dyn 'a + FnOnce(ReadableStreamDefaultController) -> Result<()>>,
-                Box<dyn 'static + FnOnce(ReadableStreamDefaultController) -> Result<()>>,
+                Box<dyn 'a + FnOnce(ReadableStreamDefaultController) -> Result<(), JsValue>>,
+                Box<dyn 'static + FnOnce(ReadableStreamDefaultController) -> Result<(), JsValue>>,
             >(Box::new(f))
         };
 
@@ -77,11 +81,11 @@ impl<'a, T: JsCast> ReadBuilder<'a, T> {
     /// This method, also defined by the developer, will be called repeatedly when the stream's internal queue of chunks is not full, up until it reaches its high water mark.
     #[inline]
     pub fn pull<F: 'a + FnMut(ReadStreamController<T>) -> Result<()>> (mut self, mut f: F) -> Self {
-        let f = move |inner| f(ReadStreamController { inner, _phtm: PhantomData });
+        let f = move |inner| f(ReadStreamController { inner, _phtm: PhantomData }).map_err(JsValue::from);
         let f = unsafe {
             core::mem::transmute::<
-                Box<dyn 'a + FnMut(ReadableStreamDefaultController) -> Result<()>>,
-                Box<dyn 'static + FnMut(ReadableStreamDefaultController) -> Result<()>>,
+                Box<dyn 'a + FnMut(ReadableStreamDefaultController) -> Result<(), JsValue>>,
+                Box<dyn 'static + FnMut(ReadableStreamDefaultController) -> Result<(), JsValue>>,
             >(Box::new(f))
         };
 
@@ -111,10 +115,11 @@ impl<'a, T: JsCast> ReadBuilder<'a, T> {
     /// This method, also defined by the developer, will be called if the app signals that the stream is to be cancelled
     #[inline]
     pub fn cancel<F: 'a + FnOnce(JsValue) -> Result<()>> (mut self, f: F) -> Self {
+        let f = move |c| f(c).map_err(JsValue::from);
         let f = unsafe {
             core::mem::transmute::<
-                Box<dyn 'a + FnOnce(JsValue) -> Result<()>>,
-                Box<dyn 'static + FnOnce(JsValue) -> Result<()>>,
+                Box<dyn 'a + FnOnce(JsValue) -> Result<(), JsValue>>,
+                Box<dyn 'static + FnOnce(JsValue) -> Result<(), JsValue>>,
             >(Box::new(f))
         };
 
@@ -141,7 +146,35 @@ impl<'a, T: JsCast> ReadBuilder<'a, T> {
         self
     }
 
-    pub fn build (self) -> Result<JsReadStream<'a, T>> {
+    /// Sets the total number of chunks (or of their [`size`](ReadBuilder::size)) that can be contained in the internal queue before backpressure is applied.
+    #[inline]
+    pub fn high_water_mark (mut self, high_water_mark: f64) -> Self {
+        self.high_water_mark = Some(high_water_mark);
+        self
+    }
+
+    /// Sets the function used to compute the size of each chunk. By default, every chunk has a size of `1`.
+    #[inline]
+    pub fn size<F: 'a + FnMut(T) -> f64> (mut self, f: F) -> Self {
+        self.size = Some(SizeFn::new(f));
+        self
+    }
+
+    /// Measures the size of each chunk by it's `byteLength`, making the high water mark a number of bytes.
+    #[inline]
+    pub fn size_by_bytes (mut self) -> Self {
+        self.size = Some(SizeFn::bytes());
+        self
+    }
+
+    /// Sets a signal that, when aborted, aborts every pending asynchronous callback of the stream.
+    #[inline]
+    pub fn signal<S> (mut self, signal: &AbortSignal<S>) -> Self {
+        self.signal = Some(signal.as_ref().clone());
+        self
+    }
+
+    pub fn build (mut self) -> Result<JsReadStream<'a, T>> {
         macro_rules! set {
             ($($name:ident [$key:literal] = $value:expr;)+) => {
                 $(
@@ -164,9 +197,13 @@ impl<'a, T: JsCast> ReadBuilder<'a, T> {
             set! { underlying_source["cancel"] = cancel.as_ref(); }
         }
 
-        let stream = web_sys::ReadableStream::new_with_underlying_source(&underlying_source)?;
+        let stream = match queuing_strategy(self.high_water_mark, self.size.as_ref()) {
+            Some(strategy) => web_sys::ReadableStream::new_with_underlying_source_and_strategy(&underlying_source, &strategy)?,
+            None => web_sys::ReadableStream::new_with_underlying_source(&underlying_source)?
+        };
+        self.link = link_signal(self.signal.as_ref(), &self.handle)?;
         let mut result = JsReadStream::new(stream)?;
-        result._builder = Some(self);
+        result._builder = Some(std::rc::Rc::new(self));
         return Ok(result)
     }
 }
@@ -179,6 +216,10 @@ impl<T> Default for ReadBuilder<'_, T> {
             start: Default::default(),
             pull: Default::default(),
             cancel: Default::default(),
+            high_water_mark: Default::default(),
+            size: Default::default(),
+            signal: Default::default(),
+            link: Default::default(),
             handle: Default::default(),
             _phtm: Default::default(),
         }
@@ -191,6 +232,10 @@ pub struct WriteBuilder<'a, T> {
     write: Option<MaybePromise<'a, (JsValue, WritableStreamDefaultController)>>,
     close: Option<MaybePromise<'a, (WritableStreamDefaultController,)>>,
     abort: Option<MaybePromise<'a, (JsValue,)>>,
+    high_water_mark: Option<f64>,
+    size: Option<SizeFn<'a>>,
+    signal: Option<web_sys::AbortSignal>,
+    link: Option<SignalLink>,
     pub(super) handle: AbortHandle,
     _phtm: PhantomData<T>
 }
@@ -204,11 +249,11 @@ impl<'a, T: JsCast> WriteBuilder<'a, T> {
     /// This is a method, called immediately when the object is constructed. The contents of this method are defined by the developer, and should aim to get access to the underlying sink.
     #[inline]
     pub fn start<F: 'a + FnOnce(WriteStreamController) -> Result<()>> (mut self, f: F) -> Self {
-        let f = move |inner| f(WriteStreamController { inner });
+        let f = move |inner| f(WriteStreamController { inner }).map_err(JsValue::from);
         let f = unsafe {
             core::mem::transmute::<
-                Box<dyn 'a + FnOnce(WritableStreamDefaultController) -> Result<()>>,
-                Box<dyn 'static + FnOnce(WritableStreamDefaultController) -> Result<()>>,
+                Box<dyn 'a + FnOnce(WritableStreamDefaultController) -> Result<(), JsValue>>,
+                Box<dyn 'static + FnOnce(WritableStreamDefaultController) -> Result<(), JsValue>>,
             >(Box::new(f))
         };
 
@@ -240,13 +285,13 @@ impl<'a, T: JsCast> WriteBuilder<'a, T> {
     pub fn write<F: 'a + FnMut(T, WriteStreamController) -> Result<()>> (mut self, mut f: F) -> Self {
         let f = move |chunk, inner| {
             let chunk = JsCast::dyn_into::<T>(chunk)?;
-            f(chunk, WriteStreamController { inner })
+            f(chunk, WriteStreamController { inner }).map_err(JsValue::from)
         };
 
         let f = unsafe {
             core::mem::transmute::<
-                Box<dyn 'a + FnMut(JsValue, WritableStreamDefaultController) -> Result<()>>,
-                Box<dyn 'static + FnMut(JsValue, WritableStreamDefaultController) -> Result<()>>,
+                Box<dyn 'a + FnMut(JsValue, WritableStreamDefaultController) -> Result<(), JsValue>>,
+                Box<dyn 'static + FnMut(JsValue, WritableStreamDefaultController) -> Result<(), JsValue>>,
             >(Box::new(f))
         };
 
@@ -281,11 +326,11 @@ impl<'a, T: JsCast> WriteBuilder<'a, T> {
     /// This method, also defined by the developer, will be called if the app signals that it has finished writing chunks to the stream. The contents should do whatever is necessary to finalize writes to the underlying sink, and release access to it.
     #[inline]
     pub fn close<F: 'a + FnOnce(WriteStreamController) -> Result<()>> (mut self, f: F) -> Self {
-        let f = move |inner| f(WriteStreamController { inner });
+        let f = move |inner| f(WriteStreamController { inner }).map_err(JsValue::from);
         let f = unsafe {
             core::mem::transmute::<
-                Box<dyn 'a + FnOnce(WritableStreamDefaultController) -> Result<()>>,
-                Box<dyn 'static + FnOnce(WritableStreamDefaultController) -> Result<()>>,
+                Box<dyn 'a + FnOnce(WritableStreamDefaultController) -> Result<(), JsValue>>,
+                Box<dyn 'static + FnOnce(WritableStreamDefaultController) -> Result<(), JsValue>>,
             >(Box::new(f))
         };
 
@@ -315,10 +360,11 @@ impl<'a, T: JsCast> WriteBuilder<'a, T> {
     /// This method, also defined by the developer, will be called if the app signals that it wishes to abruptly close the stream and put it in an errored state. It can clean up any held resources, much like close(), but abort() will be called even if writes are queued up — those chunks will be thrown away.
     #[inline]
     pub fn abort<F: 'a + FnOnce(JsValue) -> Result<()>> (mut self, f: F) -> Self {
+        let f = move |c| f(c).map_err(JsValue::from);
         let f = unsafe {
             core::mem::transmute::<
-                Box<dyn 'a + FnOnce(JsValue) -> Result<()>>,
-                Box<dyn 'static + FnOnce(JsValue) -> Result<()>>,
+                Box<dyn 'a + FnOnce(JsValue) -> Result<(), JsValue>>,
+                Box<dyn 'static + FnOnce(JsValue) -> Result<(), JsValue>>,
             >(Box::new(f))
         };
 
@@ -345,7 +391,35 @@ impl<'a, T: JsCast> WriteBuilder<'a, T> {
         self
     }
 
-    pub fn build (self) -> Result<JsWriteStream<'a, T>> {
+    /// Sets the total number of chunks (or of their [`size`](WriteBuilder::size)) that can be contained in the internal queue before backpressure is applied.
+    #[inline]
+    pub fn high_water_mark (mut self, high_water_mark: f64) -> Self {
+        self.high_water_mark = Some(high_water_mark);
+        self
+    }
+
+    /// Sets the function used to compute the size of each chunk. By default, every chunk has a size of `1`.
+    #[inline]
+    pub fn size<F: 'a + FnMut(T) -> f64> (mut self, f: F) -> Self {
+        self.size = Some(SizeFn::new(f));
+        self
+    }
+
+    /// Measures the size of each chunk by it's `byteLength`, making the high water mark a number of bytes.
+    #[inline]
+    pub fn size_by_bytes (mut self) -> Self {
+        self.size = Some(SizeFn::bytes());
+        self
+    }
+
+    /// Sets a signal that, when aborted, aborts every pending asynchronous callback of the stream.
+    #[inline]
+    pub fn signal<S> (mut self, signal: &AbortSignal<S>) -> Self {
+        self.signal = Some(signal.as_ref().clone());
+        self
+    }
+
+    pub fn build (mut self) -> Result<JsWriteStream<'a, T>> {
         macro_rules! set {
             ($($name:ident [$key:literal] = $value:expr;)+) => {
                 $(
@@ -372,7 +446,11 @@ impl<'a, T: JsCast> WriteBuilder<'a, T> {
             set! { underlying_source["abort"] = abort.as_ref(); }
         }
 
-        let stream = web_sys::WritableStream::new_with_underlying_sink(&underlying_source)?;
+        let stream = match queuing_strategy(self.high_water_mark, self.size.as_ref()) {
+            Some(strategy) => web_sys::WritableStream::new_with_underlying_sink_and_strategy(&underlying_source, &strategy)?,
+            None => web_sys::WritableStream::new_with_underlying_sink(&underlying_source)?
+        };
+        self.link = link_signal(self.signal.as_ref(), &self.handle)?;
         let mut result = JsWriteStream::new(stream)?;
         result._builder = Some(self);
         return Ok(result)
@@ -387,6 +465,10 @@ impl<T> Default for WriteBuilder<'_, T> {
             write: Default::default(),
             close: Default::default(),
             abort: Default::default(),
+            high_water_mark: Default::default(),
+            size: Default::default(),
+            signal: Default::default(),
+            link: Default::default(),
             handle: Default::default(),
             _phtm: Default::default()
         }
@@ -402,7 +484,7 @@ pub struct ReadStreamController<T: ?Sized> {
 impl<T: ?Sized> ReadStreamController<T> {
     #[inline]
     pub fn close (&self) -> Result<()> {
-        self.inner.close()
+        return Ok(self.inner.close()?)
     }
 
     #[inline]
@@ -412,7 +494,7 @@ impl<T: ?Sized> ReadStreamController<T> {
 
     #[inline]
     pub fn enqueue (&self, chunk: &T) -> Result<()> where T: AsRef<JsValue> {
-        return self.inner.enqueue_with_chunk(chunk.as_ref())
+        return Ok(self.inner.enqueue_with_chunk(chunk.as_ref())?)
     }
 
     #[inline]
@@ -449,6 +531,65 @@ impl WriteStreamController {
     }
 }
 
+#[derive(Debug)]
+struct SizeFn<'a> {
+    inner: Closure<dyn FnMut(JsValue) -> f64>,
+    _phtm: PhantomData<&'a mut &'a dyn FnMut(JsValue) -> f64>
+}
+
+impl<'a> SizeFn<'a> {
+    #[inline]
+    fn new<T: JsCast, F: 'a + FnMut(T) -> f64> (mut f: F) -> Self {
+        let f = move |chunk: JsValue| f(chunk.unchecked_into());
+        let f = unsafe {
+            core::mem::transmute::<
+                Box<dyn 'a + FnMut(JsValue) -> f64>,
+                Box<dyn 'static + FnMut(JsValue) -> f64>,
+            >(Box::new(f))
+        };
+
+        return Self { inner: Closure::wrap(f), _phtm: PhantomData }
+    }
+
+    #[inline]
+    fn bytes () -> Self {
+        return Self::new(|chunk: JsValue| {
+            js_sys::Reflect::get(&chunk, &JsValue::from_str("byteLength")).ok()
+                .and_then(|x| x.as_f64())
+                .unwrap_or(1f64)
+        })
+    }
+}
+
+fn queuing_strategy (high_water_mark: Option<f64>, size: Option<&SizeFn<'_>>) -> Option<web_sys::QueuingStrategy> {
+    if high_water_mark.is_none() && size.is_none() {
+        return None
+    }
+
+    let mut strategy = web_sys::QueuingStrategy::new();
+    if let Some(high_water_mark) = high_water_mark {
+        strategy.high_water_mark(high_water_mark);
+    }
+    if let Some(size) = size {
+        strategy.size(size.inner.as_ref().unchecked_ref());
+    }
+    return Some(strategy)
+}
+
+fn link_signal (signal: Option<&web_sys::AbortSignal>, handle: &AbortHandle) -> Result<Option<SignalLink>> {
+    match signal {
+        Some(signal) if signal.aborted() => {
+            handle.abort();
+            return Ok(None)
+        },
+        Some(signal) => {
+            let handle = handle.clone();
+            return SignalLink::new(signal.clone(), move || handle.abort()).map(Some)
+        },
+        None => return Ok(None)
+    }
+}
+
 fn future_to_promise<'a> (fut: Pin<Box<dyn 'a + Future<Output = Result<()>>>>, handle: AbortHandle) -> js_sys::Promise {
     let fut: Pin<Box<dyn 'static + Future<Output = Result<()>>>> = unsafe { core::mem::transmute(fut) };
     let fut = Abortable::new(fut, handle);
@@ -457,7 +598,7 @@ fn future_to_promise<'a> (fut: Pin<Box<dyn 'a + Future<Output = Result<()>>>>, h
     return wasm_bindgen_futures::future_to_promise(async move {
         match fut.await {
             Ok(Ok(_)) | Err(_) => return Ok(JsValue::UNDEFINED),
-            Ok(Err(e)) => return Err(e) 
+            Ok(Err(e)) => return Err(e.into())
         }
     });
 }
\ No newline at end of file
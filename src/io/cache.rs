@@ -0,0 +1,190 @@
+use std::time::Duration;
+use serde::{Serialize, Deserialize};
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{ResponseInit, Headers};
+use crate::{Result, storage::Storage, Error, ErrorKind, encoding::{encode_base64, decode_base64}};
+use super::{FetchClient, Response, FetchError};
+
+/// Order in which a [`CachedClient`] looks for responses
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum CacheStrategy {
+    /// Fresh cached responses are served without hitting the network
+    #[default]
+    CacheFirst,
+    /// The network is always tried first, falling back to the cache if the request fails or the response isn't successful
+    NetworkFirst
+}
+
+/// Policy that decides how long a [`CachedClient`]'s responses are served for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CachePolicy {
+    /// Amount of time a cached response is considered fresh
+    pub max_age: Duration,
+    /// Amount of time after `max_age` a stale response will still be served, while it's revalidated in the background
+    pub stale_while_revalidate: Duration,
+    pub strategy: CacheStrategy
+}
+
+impl Default for CachePolicy {
+    #[inline]
+    fn default() -> Self {
+        return Self {
+            max_age: Duration::from_secs(60),
+            stale_while_revalidate: Duration::ZERO,
+            strategy: CacheStrategy::default()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedEntry {
+    status: u16,
+    headers: Vec<(String, String)>,
+    /// Base64-encoded body, which is much smaller than a JSON array of it's bytes
+    body: String,
+    timestamp: f64
+}
+
+impl CachedEntry {
+    #[inline]
+    fn age (&self) -> Duration {
+        return Duration::from_secs_f64((js_sys::Date::now() - self.timestamp).max(0f64) / 1000f64)
+    }
+
+    fn to_response (&self) -> Result<Response> {
+        let headers = Headers::new()?;
+        for (key, value) in self.headers.iter() {
+            headers.append(key, value)?;
+        }
+
+        let mut init = ResponseInit::new();
+        init.status(self.status).headers(&headers);
+
+        let mut body = decode_base64(&self.body)?;
+        let body = match body.is_empty() {
+            true => None,
+            false => Some(&mut body as &mut [u8])
+        };
+
+        let inner = web_sys::Response::new_with_opt_u8_array_and_init(body, &init)?;
+        return Ok(Response { inner })
+    }
+}
+
+/// A [`FetchClient`] that caches the successful responses of it's GET requests in a [`Storage`],
+/// keyed by their url, and serves them according to a [`CachePolicy`].
+#[derive(Clone)]
+pub struct CachedClient {
+    client: FetchClient,
+    storage: Storage,
+    prefix: String,
+    policy: CachePolicy
+}
+
+impl CachedClient {
+    /// Creates a new cached client that stores it's responses in the specified storage
+    #[inline]
+    pub fn new (client: FetchClient, storage: Storage) -> Self {
+        return Self {
+            client,
+            storage,
+            prefix: String::from("rustww-cache:"),
+            policy: CachePolicy::default()
+        }
+    }
+
+    /// Creates a new cached client that stores it's responses in local storage
+    #[inline]
+    pub fn local (client: FetchClient) -> Result<Self> {
+        return match Storage::local()? {
+            Some(storage) => Ok(Self::new(client, storage)),
+            None => Err(Error::new(ErrorKind::NotSupported, "local storage isn't available"))
+        }
+    }
+
+    /// Sets the cache policy of the client
+    #[inline]
+    pub fn policy (mut self, policy: CachePolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Sets the prefix of the keys the responses are stored with
+    #[inline]
+    pub fn prefix (mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// Executes an HTTP GET request, serving it from the cache when the policy allows it.
+    pub async fn get (&self, url: &str) -> ::core::result::Result<Response, FetchError> {
+        let key = format!("{}{url}", self.prefix);
+        let entry = self.storage.get::<CachedEntry>(&key).ok().flatten();
+        let stale_limit = self.policy.max_age + self.policy.stale_while_revalidate;
+
+        if self.policy.strategy == CacheStrategy::CacheFirst && let Some(ref entry) = entry {
+            let age = entry.age();
+            if age <= self.policy.max_age {
+                return Ok(entry.to_response()?)
+            }
+
+            if age <= stale_limit {
+                let this = self.clone();
+                let url = url.to_string();
+                wasm_bindgen_futures::spawn_local(async move {
+                    let _ = this.revalidate(&url, &key).await;
+                });
+                return Ok(entry.to_response()?)
+            }
+        }
+
+        // unsuccessful responses (like 5xx errors) aren't cached, so the cached entry is served in their place
+        return match self.revalidate(url, &key).await {
+            Ok(resp) if resp.ok() => Ok(resp),
+            Ok(resp) => match entry {
+                Some(entry) => Ok(entry.to_response()?),
+                None => Ok(resp)
+            },
+            Err(e) => match entry {
+                Some(entry) => Ok(entry.to_response()?),
+                None => Err(e)
+            }
+        }
+    }
+
+    /// Removes the cached response of the specified url, if any
+    #[inline]
+    pub fn invalidate (&self, url: &str) -> Result<()> {
+        return self.storage.remove(&format!("{}{url}", self.prefix))
+    }
+
+    async fn revalidate (&self, url: &str, key: &str) -> ::core::result::Result<Response, FetchError> {
+        let mut resp = self.client.get(url).await?;
+        if !resp.ok() {
+            return Ok(resp)
+        }
+
+        let mut headers = Vec::new();
+        if let Ok(Some(iter)) = js_sys::try_iter(&resp.inner.headers()) {
+            for entry in iter.filter_map(|x| x.ok()) {
+                let entry = entry.unchecked_into::<js_sys::Array>();
+                if let (Some(key), Some(value)) = (entry.get(0).as_string(), entry.get(1).as_string()) {
+                    headers.push((key, value));
+                }
+            }
+        }
+
+        let entry = CachedEntry {
+            status: resp.status(),
+            headers,
+            body: encode_base64(&resp.tee()?.bytes().await?)?,
+            timestamp: js_sys::Date::now()
+        };
+
+        // a failed write (like when the storage quota is exceeded) doesn't invalidate the response
+        if let Err(e) = self.storage.set(key, &entry) {
+            web_sys::console::warn_1(&JsValue::from_str(&format!("failed to cache the response of {url}: {e}")));
+        }
+        return Ok(resp)
+    }
+}
@@ -0,0 +1,103 @@
+use std::{rc::Rc, future::Future};
+use futures::{future::LocalBoxFuture, FutureExt};
+use super::{Request, Response, FetchError};
+
+/// A middleware of a [`FetchClient`].
+///
+/// Middlewares receive every request made through the client, alongside the rest of the chain, and can
+/// modify the request, short-circuit it, or inspect it's response.
+///
+/// This trait is implemented for every async function with the signature `Fn(Request, Next) -> Future<Output = Result<Response, FetchError>>`.
+pub trait Middleware: 'static {
+    fn handle (&self, req: Request, next: Next) -> LocalBoxFuture<'static, Result<Response, FetchError>>;
+}
+
+impl<F: 'static + Fn(Request, Next) -> Fut, Fut: 'static + Future<Output = Result<Response, FetchError>>> Middleware for F {
+    #[inline]
+    fn handle (&self, req: Request, next: Next) -> LocalBoxFuture<'static, Result<Response, FetchError>> {
+        return self(req, next).boxed_local()
+    }
+}
+
+/// The remaining middlewares of a [`FetchClient`]'s chain
+#[derive(Clone)]
+pub struct Next {
+    middlewares: Rc<Vec<Rc<dyn Middleware>>>,
+    idx: usize
+}
+
+impl Next {
+    /// Passes the request to the next middleware of the chain, or executes it if there are no middlewares left.
+    pub fn run (self, req: Request) -> LocalBoxFuture<'static, Result<Response, FetchError>> {
+        return match self.middlewares.get(self.idx).cloned() {
+            Some(middleware) => {
+                let next = Self { middlewares: self.middlewares, idx: self.idx + 1 };
+                middleware.handle(req, next)
+            },
+            None => req.send().boxed_local()
+        }
+    }
+}
+
+/// An HTTP client with a base url, default headers and a chain of [`Middleware`]s that are applied to every request made through it.
+///
+/// Middlewares are run in the order they were added.
+#[derive(Clone, Default)]
+pub struct FetchClient {
+    base_url: Option<String>,
+    headers: Vec<(String, String)>,
+    middlewares: Rc<Vec<Rc<dyn Middleware>>>
+}
+
+impl FetchClient {
+    /// Creates a new client without base url, default headers or middlewares
+    #[inline]
+    pub fn new () -> Self {
+        return Default::default()
+    }
+
+    /// Sets the url relative urls will be resolved against
+    #[inline]
+    pub fn base_url (mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = Some(base_url.into());
+        self
+    }
+
+    /// Adds a header that will be sent with every request, unless the request sets it itself.
+    #[inline]
+    pub fn default_header (mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((key.into(), value.into()));
+        self
+    }
+
+    /// Appends a middleware to the end of the chain
+    #[inline]
+    pub fn with (mut self, middleware: impl Middleware) -> Self {
+        Rc::make_mut(&mut self.middlewares).push(Rc::new(middleware));
+        self
+    }
+
+    /// Executes an HTTP GET request with the default parameters, targeting the specified url
+    #[inline]
+    pub async fn get (&self, url: &str) -> Result<Response, FetchError> {
+        return self.fetch(url, Request::new()).await
+    }
+
+    /// Executes the request through the client's middlewares, targeting the specified url
+    pub async fn fetch (&self, url: &str, mut req: Request) -> Result<Response, FetchError> {
+        let url = match self.base_url {
+            Some(ref base) => web_sys::Url::new_with_base(url, base)?.href(),
+            None => url.to_string()
+        };
+        req.target(url);
+
+        for (key, value) in self.headers.iter() {
+            if req.get_header(key).is_none() {
+                req.header(key, value)?;
+            }
+        }
+
+        let next = Next { middlewares: self.middlewares.clone(), idx: 0 };
+        return next.run(req).await
+    }
+}
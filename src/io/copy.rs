@@ -0,0 +1,105 @@
+use js_sys::Uint8Array;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{ReadableStreamByobReader, ReadableStreamGetReaderOptions, ReadableStreamReaderMode};
+use crate::{Result, scope::{TaskPriority, post_task}};
+use super::{JsReadStream, JsWriteStream};
+
+/// Copies the remaining contents of `src` into `dst`, returning the number of bytes copied.
+/// 
+/// The chunks are handed from one stream to the other without being copied into WASM memory. If `src` is a byte stream,
+/// it's read with a BYOB reader into a single buffer, that is reused for every chunk once it has been written.
+#[inline]
+pub async fn copy (src: &mut JsReadStream<'_, Uint8Array>, dst: &mut JsWriteStream<'_, Uint8Array>) -> Result<u64> {
+    return copy_inner(src, dst, None, |_| {}).await
+}
+
+/// Copies the remaining contents of `src` into `dst`, returning the number of bytes copied.
+/// 
+/// After every chunk is written, `progress` is called with the total number of bytes copied so far.
+#[inline]
+pub async fn copy_with_progress<F: FnMut(u64)> (src: &mut JsReadStream<'_, Uint8Array>, dst: &mut JsWriteStream<'_, Uint8Array>, progress: F) -> Result<u64> {
+    return copy_inner(src, dst, None, progress).await
+}
+
+/// Copies the remaining contents of `src` into `dst`, returning the number of bytes copied.
+/// 
+/// Every chunk is copied within a task of the specified priority, so that [`Background`](TaskPriority::Background)
+/// copies yield to more important work.
+#[inline]
+pub async fn copy_with_priority (src: &mut JsReadStream<'_, Uint8Array>, dst: &mut JsWriteStream<'_, Uint8Array>, priority: TaskPriority) -> Result<u64> {
+    return copy_inner(src, dst, Some(priority), |_| {}).await
+}
+
+/// Size of the buffer byte streams are read into
+const CHUNK_SIZE: u32 = 64 * 1024;
+
+async fn copy_inner<F: FnMut(u64)> (src: &mut JsReadStream<'_, Uint8Array>, dst: &mut JsWriteStream<'_, Uint8Array>, priority: Option<TaskPriority>, progress: F) -> Result<u64> {
+    match byob_reader(src)? {
+        Some(reader) => {
+            let result = copy_byob(&reader, dst, priority, progress).await;
+            reader.release_lock();
+            return result
+        },
+        None => return copy_chunks(src, dst, priority, progress).await
+    }
+}
+
+async fn copy_byob<F: FnMut(u64)> (reader: &ReadableStreamByobReader, dst: &mut JsWriteStream<'_, Uint8Array>, priority: Option<TaskPriority>, mut progress: F) -> Result<u64> {
+    let mut count = 0;
+    let mut buf = Uint8Array::new_with_length(CHUNK_SIZE);
+
+    loop {
+        let result = JsFuture::from(reader.read_with_array_buffer_view(&buf)).await?;
+        if js_sys::Reflect::get(&result, &JsValue::from_str("done"))?.is_truthy() {
+            break
+        }
+
+        let chunk = js_sys::Reflect::get(&result, &JsValue::from_str("value"))?.unchecked_into::<Uint8Array>();
+        let len = chunk.byte_length() as u64;
+        dst.write(&chunk).await?;
+
+        count += len;
+        progress(count);
+
+        // the read transferred the buffer into `chunk`, which can be reused now that it has been written
+        buf = Uint8Array::new_with_byte_offset_and_length(&chunk.buffer(), 0, CHUNK_SIZE);
+
+        if let Some(priority) = priority {
+            JsFuture::from(post_task(priority, || {})?).await?;
+        }
+    }
+
+    return Ok(count)
+}
+
+async fn copy_chunks<F: FnMut(u64)> (src: &mut JsReadStream<'_, Uint8Array>, dst: &mut JsWriteStream<'_, Uint8Array>, priority: Option<TaskPriority>, mut progress: F) -> Result<u64> {
+    let mut count = 0;
+    while let Some(chunk) = src.read_chunk().await? {
+        let len = chunk.byte_length() as u64;
+        dst.write(&chunk).await?;
+
+        count += len;
+        progress(count);
+
+        if let Some(priority) = priority {
+            JsFuture::from(post_task(priority, || {})?).await?;
+        }
+    }
+
+    return Ok(count)
+}
+
+/// Returns a BYOB reader of the stream, or `None` if it isn't a byte stream
+fn byob_reader (src: &mut JsReadStream<'_, Uint8Array>) -> Result<Option<ReadableStreamByobReader>> {
+    if let Some(reader) = src.reader.take() {
+        reader.release_lock()
+    }
+
+    let mut options = ReadableStreamGetReaderOptions::new();
+    options.mode(ReadableStreamReaderMode::Byob);
+
+    // streams that aren't byte streams throw a `TypeError`
+    let get_reader = js_sys::Reflect::get(&src._stream, &JsValue::from_str("getReader"))?.unchecked_into::<js_sys::Function>();
+    return Ok(get_reader.call1(&src._stream, &options).ok().map(JsCast::unchecked_into))
+}
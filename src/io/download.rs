@@ -0,0 +1,114 @@
+use crate::{Result, fs::FileWrite, utils::{AbortController, AbortSignal, any_signal}};
+use super::{Request, FetchError, PipeOptions};
+
+/// Progress of a [`Download`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DownloadProgress {
+    /// Number of bytes written into the file so far
+    pub downloaded: u64,
+    /// Total number of bytes to download, if the server reported it via the `Content-Length` header
+    pub total: Option<u64>
+}
+
+impl DownloadProgress {
+    /// Returns the downloaded fraction, between 0 and 1, if the total size is known
+    #[inline]
+    pub fn fraction (&self) -> Option<f64> {
+        return self.total.map(|total| match total {
+            0 => 1.,
+            total => self.downloaded as f64 / total as f64
+        })
+    }
+}
+
+/// Downloads the contents of the specified url directly into the file, without buffering them in memory.
+/// Returns the number of bytes written.
+///
+/// The writer isn't closed after the download, so the contents aren't commited into the file until [`close`](FileWrite::close) is called.
+#[inline]
+pub async fn download_to_file (url: &str, dst: &mut FileWrite) -> ::core::result::Result<u64, FetchError> {
+    return Download::new().send(url, dst).await
+}
+
+/// A builder that streams the body of an HTTP request into a file
+pub struct Download {
+    request: Request,
+    signals: Vec<web_sys::AbortSignal>,
+    on_progress: Option<Box<dyn FnMut(&DownloadProgress)>>
+}
+
+impl Download {
+    /// Creates a new download with a default GET request
+    #[inline]
+    pub fn new () -> Self {
+        return Self::with_request(Request::new())
+    }
+
+    /// Creates a new download that will send the specified request
+    #[inline]
+    pub fn with_request (request: Request) -> Self {
+        return Self { request, signals: Vec::new(), on_progress: None }
+    }
+
+    /// Sets a callback that's called every time a chunk is written into the file
+    #[inline]
+    pub fn on_progress<F: 'static + FnMut(&DownloadProgress)> (&mut self, f: F) -> &mut Self {
+        self.on_progress = Some(Box::new(f));
+        self
+    }
+
+    /// Makes the download abortable, returning it's [`AbortController`]
+    #[inline]
+    pub fn abortable<T> (&mut self) -> Result<(AbortController<T>, &mut Self)> {
+        let con = AbortController::new()?;
+        self.signals.push(con.raw_signal());
+        return Ok((con, self))
+    }
+
+    /// Adds `signal` as a signal to abort the download.
+    /// The download is aborted as soon as any of it's signals aborts, either while the request is sent or while the body is written.
+    #[inline]
+    pub fn abortable_with_signal<T> (&mut self, signal: &AbortSignal<T>) -> &mut Self {
+        self.signals.push(signal.as_ref().clone());
+        self
+    }
+
+    /// Sends the request to the specified url, and pipes it's body into `dst`. Returns the number of bytes written.
+    ///
+    /// The writer isn't closed after the download, so the contents aren't commited into the file until [`close`](FileWrite::close) is called.
+    pub async fn send (self, url: &str, dst: &mut FileWrite) -> ::core::result::Result<u64, FetchError> {
+        let Self { mut request, signals, on_progress } = self;
+        for signal in signals.iter() {
+            request.abortable_with_raw(signal);
+        }
+
+        let resp = request.fetch(url).await?.error_for_status().await?;
+        let total = resp.header("Content-Length").and_then(|x| x.trim().parse::<u64>().ok());
+        let body = match resp.body()? {
+            Some(body) => body,
+            None => return Ok(0)
+        };
+
+        let mut options = PipeOptions::default();
+        options.prevent_close = true;
+        options.signal = match signals.len() {
+            0 => None,
+            1 => signals.into_iter().next(),
+            _ => Some(any_signal(&signals)?)
+        };
+
+        let progress = on_progress.map(|mut f| Box::new(move |downloaded| f(&DownloadProgress { downloaded, total })) as Box<dyn FnMut(u64)>);
+        let (pipe, _) = body.pipe_to_with_progress(dst.write_stream(), options, progress)?;
+        let len = pipe.await?;
+
+        dst.advance(len);
+        return Ok(len)
+    }
+}
+
+impl Default for Download {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,10 +1,10 @@
-use std::{rc::Rc, sync::Arc, fmt::Debug};
+use std::{rc::Rc, sync::Arc, fmt::{Debug, Display}, time::Duration, ops::{RangeBounds, Bound}};
 use js_sys::Uint8Array;
-use serde::{de::DeserializeOwned};
-use wasm_bindgen::{JsValue, prelude::wasm_bindgen, JsCast};
+use serde::{Serialize, de::DeserializeOwned};
+use wasm_bindgen::{JsValue, JsCast};
 use wasm_bindgen_futures::JsFuture;
-use web_sys::{RequestInit, RequestCache, RequestCredentials, Headers, RequestMode, RequestRedirect, ReferrerPolicy};
-use crate::{Result, utils::{AbortController, AbortSignal}, scope::fetch};
+use web_sys::{RequestInit, ResponseInit, RequestCache, RequestCredentials, Headers, RequestMode, RequestRedirect, ReferrerPolicy};
+use crate::{Result, Error, ErrorKind, utils::{AbortController, AbortSignal, timeout_signal, any_signal}, scope::{fetch, window_only, TaskPriority, TaskController}};
 use super::{JsReadStream};
 
 macro_rules! impl_ident {
@@ -58,6 +58,42 @@ impl<T: IntoFetchBody> IntoFetchBody for Option<T> {
     }
 }
 
+impl IntoFetchBody for () {
+    #[inline]
+    fn into_body (self) -> Option<JsValue> {
+        return None
+    }
+}
+
+impl IntoFetchBody for &[u8] {
+    #[inline]
+    fn into_body (self) -> Option<JsValue> {
+        return Some(Uint8Array::from(self).into())
+    }
+}
+
+impl IntoFetchBody for Vec<u8> {
+    #[inline]
+    fn into_body (self) -> Option<JsValue> {
+        return self.as_slice().into_body()
+    }
+}
+
+impl IntoFetchBody for Box<[u8]> {
+    #[inline]
+    fn into_body (self) -> Option<JsValue> {
+        return (&self as &[u8]).into_body()
+    }
+}
+
+#[cfg(feature = "http")]
+impl IntoFetchBody for bytes::Bytes {
+    #[inline]
+    fn into_body (self) -> Option<JsValue> {
+        return (&self as &[u8]).into_body()
+    }
+}
+
 impl IntoFetchBody for &str {
     #[inline]
     fn into_body (self) -> Option<JsValue> {
@@ -94,21 +130,69 @@ impl IntoFetchBody for Arc<str> {
 }
 
 /// A fetch request's method
-#[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
-#[wasm_bindgen]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
 pub enum Method {
     #[default]
-    Get = "GET",
-    Post = "POST",
-    Head = "HEAD",
-    Put = "PUT"
+    Get,
+    Post,
+    Head,
+    Put,
+    Delete,
+    Patch,
+    Options,
+    /// Any other method
+    Other (Box<str>)
+}
+
+impl Method {
+    /// Returns the method's name, as sent in the request
+    #[inline]
+    pub fn as_str (&self) -> &str {
+        return match self {
+            Self::Get => "GET",
+            Self::Post => "POST",
+            Self::Head => "HEAD",
+            Self::Put => "PUT",
+            Self::Delete => "DELETE",
+            Self::Patch => "PATCH",
+            Self::Options => "OPTIONS",
+            Self::Other(x) => x
+        }
+    }
+}
+
+impl From<&str> for Method {
+    fn from(value: &str) -> Self {
+        return match value.to_ascii_uppercase().as_str() {
+            "GET" => Self::Get,
+            "POST" => Self::Post,
+            "HEAD" => Self::Head,
+            "PUT" => Self::Put,
+            "DELETE" => Self::Delete,
+            "PATCH" => Self::Patch,
+            "OPTIONS" => Self::Options,
+            _ => Self::Other(value.into())
+        }
+    }
+}
+
+impl Display for Method {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
 }
 
 /// A builder that allows to customize the parameters for an HTTP request
 #[derive(Default)]
 pub struct Request {
     inner: RequestInit,
-    headers: Option<Headers>
+    url: Option<String>,
+    method: Method,
+    headers: Option<Headers>,
+    signals: Vec<web_sys::AbortSignal>,
+    timeout: Option<Duration>
 }
 
 impl Request {
@@ -120,10 +204,74 @@ impl Request {
 
     /// Executes an HTTP GET request with the default parameters, targeting the specified url
     #[inline]
-    pub async fn get (url: &str) -> Result<Response> {
+    pub async fn get (url: &str) -> ::core::result::Result<Response, FetchError> {
         return Self::new().fetch(url).await
     }
 
+    /// Executes an HTTP POST request with the specified body, targeting the specified url
+    #[inline]
+    pub async fn post (url: &str, body: impl IntoFetchBody) -> ::core::result::Result<Response, FetchError> {
+        return Self::with_body(Method::Post, body).fetch(url).await
+    }
+
+    /// Executes an HTTP PUT request with the specified body, targeting the specified url
+    #[inline]
+    pub async fn put (url: &str, body: impl IntoFetchBody) -> ::core::result::Result<Response, FetchError> {
+        return Self::with_body(Method::Put, body).fetch(url).await
+    }
+
+    /// Executes an HTTP DELETE request with the specified body, targeting the specified url
+    #[inline]
+    pub async fn delete (url: &str, body: impl IntoFetchBody) -> ::core::result::Result<Response, FetchError> {
+        return Self::with_body(Method::Delete, body).fetch(url).await
+    }
+
+    /// Executes an HTTP PATCH request with the specified body, targeting the specified url
+    #[inline]
+    pub async fn patch (url: &str, body: impl IntoFetchBody) -> ::core::result::Result<Response, FetchError> {
+        return Self::with_body(Method::Patch, body).fetch(url).await
+    }
+
+    /// Executes an HTTP POST request with the value serialized as it's JSON body, targeting the specified url
+    #[inline]
+    pub async fn post_json<T: ?Sized + Serialize> (url: &str, value: &T) -> ::core::result::Result<Response, FetchError> {
+        let mut this = Self::new();
+        this.method(Method::Post).json(value)?;
+        return this.fetch(url).await
+    }
+
+    #[inline]
+    fn with_body (method: Method, body: impl IntoFetchBody) -> Self {
+        let mut this = Self::new();
+        this.method(method).body(body);
+        return this
+    }
+
+    /// Returns the url the request targets, if it has been set
+    #[inline]
+    pub fn url (&self) -> Option<&str> {
+        return self.url.as_deref()
+    }
+
+    /// Returns the HTTP method of the request
+    #[inline]
+    pub fn get_method (&self) -> &Method {
+        return &self.method
+    }
+
+    /// Returns the value of the specified header of the request, if it has been set
+    #[inline]
+    pub fn get_header (&self, key: &str) -> Option<String> {
+        return self.headers.as_ref().and_then(|headers| headers.get(key).ok().flatten())
+    }
+
+    /// Sets the url the request will target when [`send`](Request::send) is called
+    #[inline]
+    pub fn target (&mut self, url: impl Into<String>) -> &mut Self {
+        self.url = Some(url.into());
+        self
+    }
+
     /// Assigns a body to the request
     #[inline]
     pub fn body (&mut self, body: impl IntoFetchBody) -> &mut Self {
@@ -131,6 +279,14 @@ impl Request {
         self
     }
 
+    /// Assigns the value, serialized as JSON, as the body of the request, and sets it's `Content-Type` header accordingly
+    #[inline]
+    pub fn json<T: ?Sized + Serialize> (&mut self, value: &T) -> Result<&mut Self> {
+        let body = serde_json::to_string(value)?;
+        self.header("Content-Type", "application/json")?;
+        return Ok(self.body(body))
+    }
+
     /// Specifies the cache mode of the request
     #[inline]
     pub fn cache (&mut self, cache: RequestCache) -> &mut Self {
@@ -157,6 +313,28 @@ impl Request {
         return Ok(self)
     }
 
+    /// Requests only the specified byte range of the resource, by setting the `Range` header.
+    /// 
+    /// Servers that support range requests respond with the status code `206 Partial Content`.
+    pub fn range (&mut self, range: impl RangeBounds<u64>) -> Result<&mut Self> {
+        let start = match range.start_bound() {
+            Bound::Included(&x) => x,
+            Bound::Excluded(&x) => x.saturating_add(1),
+            Bound::Unbounded => 0
+        };
+
+        let value = match range.end_bound() {
+            Bound::Included(&end) => format!("bytes={start}-{end}"),
+            Bound::Excluded(&end) => match end.checked_sub(1) {
+                Some(end) => format!("bytes={start}-{end}"),
+                None => return Err(Error::new(ErrorKind::Io, "empty byte range"))
+            },
+            Bound::Unbounded => format!("bytes={start}-")
+        };
+
+        return self.header("Range", &value)
+    }
+
     /// Adds the specified headers to the request
     #[inline]
     pub fn headers<K: AsRef<str>, V: AsRef<str>> (&mut self, headers: impl IntoIterator<Item = (K, V)>) -> Result<&mut Self> {
@@ -176,7 +354,8 @@ impl Request {
     /// Specifies the HTTP method the request will be sent as.
     #[inline]
     pub fn method (&mut self, method: Method) -> &mut Self {
-        self.inner.method(method.to_str());
+        self.inner.method(method.as_str());
+        self.method = method;
         self
     }
 
@@ -208,6 +387,38 @@ impl Request {
         self
     }
 
+    /// Indicates whether the request should outlive the page that initiated it.
+    /// 
+    /// Requests with keepalive enabled can be used to reliably send data while the page is being unloaded,
+    /// but their body can't exceed 64 KiB.
+    #[inline]
+    pub fn keepalive (&mut self, keepalive: bool) -> &mut Self {
+        let _ = js_sys::Reflect::set(&self.inner, &JsValue::from_str("keepalive"), &JsValue::from_bool(keepalive));
+        self
+    }
+
+    /// Sets the priority of the request relative to the page's other requests, via the `priority` fetch option.
+    ///
+    /// [`UserBlocking`](TaskPriority::UserBlocking) requests are fetched with a high priority, and
+    /// [`Background`](TaskPriority::Background) ones with a low priority, so that bulk transfers don't compete with interactive ones.
+    #[inline]
+    pub fn priority (&mut self, priority: TaskPriority) -> &mut Self {
+        let priority = match priority {
+            TaskPriority::UserBlocking => "high",
+            TaskPriority::UserVisible => "auto",
+            TaskPriority::Background => "low"
+        };
+
+        let _ = js_sys::Reflect::set(&self.inner, &JsValue::from_str("priority"), &JsValue::from_str(priority));
+        self
+    }
+
+    /// Sets the priority of the request to the controller's current one, and aborts the request when the controller is aborted
+    #[inline]
+    pub fn task_controller (&mut self, con: &TaskController) -> &mut Self {
+        self.priority(con.priority()).abortable_with_raw(&con.signal())
+    }
+
     /// Makes the request abortable, returning it's [`AbortController`]
     #[inline]
     pub fn abortable<T> (&mut self) -> Result<(AbortController<T>, &mut Self)> {
@@ -216,48 +427,152 @@ impl Request {
         return Ok((con, self))
     }
 
-    /// Assigns `con` as the abort controller of the request
+    /// Adds `con` as an abort controller of the request.
+    /// The request is aborted as soon as any of it's controllers or signals aborts.
     #[inline]
     pub fn abortable_with<T> (&mut self, con: &AbortController<T>) -> &mut Self {
         self.abortable_with_raw(&con.raw_signal())
     }
 
-    /// Assigns `signal` as the signal to abort the request
+    /// Adds `signal` as a signal to abort the request.
+    /// The request is aborted as soon as any of it's controllers or signals aborts.
     #[inline]
     pub fn abortable_with_signal<T> (&mut self, signal: &AbortSignal<T>) -> &mut Self {
         self.abortable_with_raw(signal.as_ref())
     }
 
-    /// Assigns `signal` as the signal to abort the request
+    /// Adds `signal` as a signal to abort the request.
+    /// The request is aborted as soon as any of it's controllers or signals aborts.
     #[inline]
     pub fn abortable_with_raw (&mut self, signal: &web_sys::AbortSignal) -> &mut Self {
-        self.inner.signal(Some(signal));
+        self.signals.push(signal.clone());
+        self
+    }
+
+    /// Sets the maximum amount of time the request can take before it's aborted with [`FetchError::Timeout`].
+    /// By default, requests don't have a timeout.
+    #[inline]
+    pub fn timeout (&mut self, timeout: Duration) -> &mut Self {
+        self.timeout = Some(timeout);
         self
     }
 
     /// Executes the request, returning it's [`Response`]
     #[inline]
-    pub async fn fetch (mut self, url: &str) -> Result<Response> {
-        if let Some(headers) = self.headers {
-            self.inner.headers(&headers);
-        }
+    pub async fn fetch (mut self, url: &str) -> ::core::result::Result<Response, FetchError> {
+        self.url = Some(url.to_string());
+        return self.send().await
+    }
 
-        let req = web_sys::Request::new_with_str_and_init(url, &self.inner)?;
-        let fetch = JsFuture::from(fetch(&req)).await?;
-        debug_assert!(fetch.is_instance_of::<web_sys::Response>());
+    /// Executes the request against it's [`target`](Request::target) url, returning it's [`Response`]
+    pub async fn send (mut self) -> ::core::result::Result<Response, FetchError> {
+        let url = match self.url.take() {
+            Some(url) => url,
+            None => return Err(FetchError::Other(js_sys::Error::new("request has no target url").into()))
+        };
+
+        let req = self.into_raw_request(&url)?;
+        let fetch = match JsFuture::from(fetch(&req)).await {
+            Ok(fetch) => fetch,
+            Err(e) => return Err(FetchError::from(e))
+        };
 
+        debug_assert!(fetch.is_instance_of::<web_sys::Response>());
         return Ok(Response {
             inner: fetch.unchecked_into()
         })
     }
+
+    /// Converts the request into a [`web_sys::Request`] targeting the specified url, without sending it.
+    ///
+    /// If the request has a [`timeout`](Request::timeout), it starts counting once the request is converted.
+    pub fn into_raw_request (mut self, url: &str) -> Result<web_sys::Request> {
+        if let Some(headers) = self.headers.take() {
+            self.inner.headers(&headers);
+        }
+
+        let mut signals = core::mem::take(&mut self.signals);
+        if let Some(timeout) = self.timeout {
+            signals.push(timeout_signal(timeout)?);
+        }
+
+        match signals.len() {
+            0 => {},
+            1 => { self.inner.signal(Some(&signals[0])); },
+            _ => { self.inner.signal(Some(&any_signal(&signals)?)); }
+        }
+
+        return Ok(web_sys::Request::new_with_str_and_init(url, &self.inner)?)
+    }
+}
+
+impl From<web_sys::Request> for Request {
+    /// Creates a request with the same target, method, headers, body and options as the JavaScript request.
+    /// Requests in `navigate` mode are converted into `same-origin` ones, since the former can't be constructed.
+    fn from(req: web_sys::Request) -> Self {
+        let mut this = Self::new();
+        this.target(req.url()).method(Method::from(req.method().as_str()));
+
+        this.headers = Headers::new_with_headers(&req.headers()).ok();
+        if let Some(body) = req.body() {
+            this.body(body);
+            // streaming bodies must be sent in half-duplex mode
+            let _ = js_sys::Reflect::set(&this.inner, &JsValue::from_str("duplex"), &JsValue::from_str("half"));
+        }
+
+        this.mode(match req.mode() {
+            RequestMode::Navigate => RequestMode::SameOrigin,
+            other => other
+        });
+
+        this.cache(req.cache()).credentials(req.credentials()).redirect(req.redirect())
+            .referrer(&req.referrer()).referrer_policy(req.referrer_policy()).integrity(&req.integrity())
+            .abortable_with_raw(&req.signal());
+
+        return this
+    }
+}
+
+/// Asynchronously sends a small amount of data to the specified url via an HTTP POST request.
+/// 
+/// Unlike [`Request`], beacons are guaranteed to be sent even if the page is being unloaded, which makes them suitable
+/// for analytics and telemetry. Returns `true` if the user agent queued the data for transfer, `false` otherwise.
+#[inline]
+pub fn send_beacon (url: &str, data: impl IntoFetchBody) -> Result<bool> {
+    let navigator = window_only("Beacon API")?.navigator();
+    let Some(data) = data.into_body() else {
+        return Ok(navigator.send_beacon(url)?)
+    };
+
+    let queued = if let Some(data) = data.as_string() {
+        navigator.send_beacon_with_opt_str(url, Some(&data))
+    } else if let Some(data) = data.dyn_ref::<web_sys::Blob>() {
+        navigator.send_beacon_with_opt_blob(url, Some(data))
+    } else if let Some(data) = data.dyn_ref::<web_sys::FormData>() {
+        navigator.send_beacon_with_opt_form_data(url, Some(data))
+    } else if let Some(data) = data.dyn_ref::<web_sys::UrlSearchParams>() {
+        navigator.send_beacon_with_opt_url_search_params(url, Some(data))
+    } else {
+        // `ArrayBuffer` and it's views
+        navigator.send_beacon_with_opt_buffer_source(url, Some(data.unchecked_ref()))
+    };
+    return Ok(queued?)
 }
 
 /// Reponse to a HTTP [`Request`]
 pub struct Response {
-    inner: web_sys::Response
+    pub(super) inner: web_sys::Response
 }
 
 impl Response {
+    /// Creates a new response with the specified status code and body
+    #[inline]
+    pub fn new (status: u16, body: impl IntoFetchBody) -> Result<Self> {
+        let mut init = ResponseInit::new();
+        init.status(status);
+        return Ok(Self { inner: raw_response(body.into_body().as_ref(), &init)? })
+    }
+
     /// Returns the body of the reponse as a [`JsReadStream`], if available.
     /// Otherwise, `None` is returned.
     #[inline]
@@ -275,6 +590,31 @@ impl Response {
         }
     }
 
+    /// Splits the response in two, so that it's body can be consumed twice (for example, by a cache and by the application).
+    ///
+    /// The body stream is teed with [`JsReadStream::from_mut`], with one branch kept by `self` and the other one
+    /// given to the returned response. Both responses share the same status, headers and url, and the body is
+    /// consumed at the pace of the fastest one. Opaque responses (with status `0`) can't be teed.
+    pub fn tee (&mut self) -> Result<Self> {
+        let mut body = match self.inner.body() {
+            Some(body) => body,
+            None => return Ok(Self { inner: rebuild_response(&self.inner, None)? })
+        };
+
+        let other = JsReadStream::<Uint8Array>::from_mut(&mut body)?.into_raw();
+        let this = rebuild_response(&self.inner, Some(&body))?;
+        let other = rebuild_response(&self.inner, Some(&other))?;
+
+        self.inner = this;
+        return Ok(Self { inner: other })
+    }
+
+    /// Returns the underlying [`web_sys::Response`]
+    #[inline]
+    pub fn into_raw (self) -> web_sys::Response {
+        return self.inner
+    }
+
     /// Returns the URL of the response
     #[inline]
     pub fn url (&self) -> String {
@@ -295,16 +635,36 @@ impl Response {
         return self.inner.ok()
     }
 
+    /// Returns the value of the specified header of the response, if present
+    #[inline]
+    pub fn header (&self, key: &str) -> Option<String> {
+        return self.inner.headers().get(key).ok().flatten()
+    }
+
     /// Returns `true` if the response is the result of a redirected request.
     #[inline]
     pub fn redirected (&self) -> bool {
         self.inner.redirected()
     }
 
+    /// Returns an error if the response isn't successful (it's status code isn't in the 200-299 range),
+    /// and the response otherwise.
+    /// 
+    /// The body of unsuccessful responses is read and included in the error, if possible.
+    pub async fn error_for_status (self) -> ::core::result::Result<Self, FetchError> {
+        if self.ok() {
+            return Ok(self)
+        }
+
+        let code = self.status();
+        let body = self.text().await.ok();
+        return Err(FetchError::Status { code, body })
+    }
+
     /// Returns the response's body as a byte sequence
-    pub async fn bytes (self) -> Result<Vec<u8>> {
+    pub async fn bytes (self) -> ::core::result::Result<Vec<u8>, FetchError> {
         return match self.try_body()? {
-            Ok(mut body) => body.read_remaining_bytes().await,
+            Ok(mut body) => Ok(body.read_remaining_bytes().await?),
             Err(this) => {
                 let value = JsFuture::from(this.inner.array_buffer()?).await?;
                 let buffer = value.unchecked_into::<js_sys::ArrayBuffer>();
@@ -314,14 +674,22 @@ impl Response {
         }
     }
 
+    /// Returns the response's body as a [`Blob`](web_sys::Blob)
+    #[inline]
+    pub async fn blob (self) -> ::core::result::Result<web_sys::Blob, FetchError> {
+        let blob = JsFuture::from(self.inner.blob()?).await?;
+        debug_assert!(blob.is_instance_of::<web_sys::Blob>());
+        return Ok(blob.unchecked_into())
+    }
+
     /// Returns the response's body as a UTF-8 parsed string
-    pub async fn text (self) -> Result<String> {
+    pub async fn text (self) -> ::core::result::Result<String, FetchError> {
         return match self.try_body()? {
             Ok(mut body) => {
                 let bytes = body.read_remaining_bytes().await?;
                 match String::from_utf8(bytes) {
                     Ok(string) => Ok(string),
-                    Err(e) => Err(JsValue::from_str(&e.to_string()))
+                    Err(e) => Err(FetchError::Decode(e.to_string()))
                 }
             },
 
@@ -335,25 +703,34 @@ impl Response {
     }
 
     /// Deserializes the response's body from JSON into the specified type.
-    pub async fn json<T: DeserializeOwned> (self) -> Result<T> {
+    pub async fn json<T: DeserializeOwned> (self) -> ::core::result::Result<T, FetchError> {
         return match self.try_body()? {
             Ok(mut body) => {
                 let bytes = body.read_remaining_bytes().await?;
                 match serde_json::from_slice::<T>(&bytes) {
                     Ok(json) => Ok(json),
-                    Err(e) => Err(JsValue::from_str(&e.to_string()))
+                    Err(e) => Err(FetchError::Decode(e.to_string()))
                 }
             },
 
             Err(this) => {
                 let json = JsFuture::from(this.inner.json()?).await?;
-                let v = serde_wasm_bindgen::from_value::<T>(json)?;
-                Ok(v)
+                match crate::utils::from_js::<T>(json) {
+                    Ok(json) => Ok(json),
+                    Err(e) => Err(FetchError::Decode(e.to_string()))
+                }
             }
         }
     }
 }
 
+impl From<web_sys::Response> for Response {
+    #[inline]
+    fn from(inner: web_sys::Response) -> Self {
+        return Self { inner }
+    }
+}
+
 impl Debug for Response {
     #[inline]
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -361,9 +738,118 @@ impl Debug for Response {
     }
 }
 
-impl Clone for Response {
+/// Creates a new [`web_sys::Response`] with any valid body
+#[inline]
+pub(super) fn raw_response (body: Option<&JsValue>, init: &ResponseInit) -> Result<web_sys::Response> {
+    // the body is passed to the constructor as is, which accepts any of the `IntoFetchBody` values
+    return Ok(web_sys::Response::new_with_opt_buffer_source_and_init(body.map(JsCast::unchecked_ref), init)?)
+}
+
+/// Creates a copy of the response with the specified body, keeping it's status, headers, url and redirection flag
+fn rebuild_response (resp: &web_sys::Response, body: Option<&web_sys::ReadableStream>) -> Result<web_sys::Response> {
+    let mut init = ResponseInit::new();
+    init.status(resp.status()).status_text(&resp.status_text()).headers(&resp.headers());
+
+    let result = web_sys::Response::new_with_opt_readable_stream_and_init(body, &init)?;
+
+    // `url` and `redirected` can't be set through the constructor, so they're shadowed on the instance
+    for (key, value) in [("url", JsValue::from_str(&resp.url())), ("redirected", JsValue::from_bool(resp.redirected()))] {
+        let descriptor = js_sys::Object::new();
+        js_sys::Reflect::set(&descriptor, &JsValue::from_str("value"), &value)?;
+        js_sys::Object::define_property(&result, &JsValue::from_str(key), &descriptor);
+    }
+
+    return Ok(result)
+}
+
+/// Error of a fetch [`Request`] or of the decoding of it's [`Response`]
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum FetchError {
+    /// The request couldn't be completed, due to a network error, a CORS violation, an invalid URL, etc.
+    Network (JsValue),
+    /// The request was aborted, with the specified reason
+    Aborted (JsValue),
+    /// The request took longer than it's [`timeout`](Request::timeout)
+    Timeout,
+    /// The response wasn't successful
+    Status {
+        /// Status code of the response
+        code: u16,
+        /// Body of the response, if it could be read
+        body: Option<String>
+    },
+    /// The body of the response couldn't be decoded
+    Decode (String),
+    /// Any other JavaScript error
+    Other (JsValue)
+}
+
+impl FetchError {
+    /// Returns `true` if the error is a [`Timeout`](FetchError::Timeout), `false` otherwise
+    #[inline]
+    pub fn is_timeout (&self) -> bool {
+        return matches!(self, Self::Timeout)
+    }
+
+    /// Returns the status code of the response, if the error is a [`Status`](FetchError::Status) error
+    #[inline]
+    pub fn status (&self) -> Option<u16> {
+        return match self {
+            Self::Status { code, .. } => Some(*code),
+            _ => None
+        }
+    }
+}
+
+impl From<JsValue> for FetchError {
+    fn from(value: JsValue) -> Self {
+        if value.is_instance_of::<js_sys::TypeError>() {
+            return Self::Network(value)
+        }
+
+        let name = js_sys::Reflect::get(&value, &JsValue::from_str("name"))
+            .ok()
+            .and_then(|x| x.as_string());
+
+        return match name.as_deref() {
+            Some("AbortError") => Self::Aborted(value),
+            Some("TimeoutError") => Self::Timeout,
+            Some("NetworkError") => Self::Network(value),
+            _ => Self::Other(value)
+        }
+    }
+}
+
+impl From<crate::Error> for FetchError {
     #[inline]
-    fn clone(&self) -> Self {
-        Self { inner: self.inner.clone().unwrap() }
+    fn from(value: crate::Error) -> Self {
+        return Self::from(value.into_js())
     }
-}
\ No newline at end of file
+}
+
+impl From<FetchError> for JsValue {
+    #[inline]
+    fn from(value: FetchError) -> Self {
+        return match value {
+            FetchError::Network(e) | FetchError::Aborted(e) | FetchError::Other(e) => e,
+            other => js_sys::Error::new(&other.to_string()).into()
+        }
+    }
+}
+
+impl Display for FetchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Network(e) => write!(f, "network error: {e:?}"),
+            Self::Aborted(e) => write!(f, "request aborted: {e:?}"),
+            Self::Timeout => f.write_str("request timed out"),
+            Self::Status { code, body: Some(body) } => write!(f, "unsuccessful status code {code}: {body}"),
+            Self::Status { code, body: None } => write!(f, "unsuccessful status code {code}"),
+            Self::Decode(e) => write!(f, "error decoding response body: {e}"),
+            Self::Other(e) => write!(f, "{e:?}")
+        }
+    }
+}
+
+impl std::error::Error for FetchError {}
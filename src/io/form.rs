@@ -0,0 +1,200 @@
+use js_sys::Array;
+use wasm_bindgen::JsValue;
+use web_sys::{Blob, BlobPropertyBag};
+use crate::{Result, fs::File};
+use super::IntoFetchBody;
+
+#[derive(Debug, Clone)]
+enum PartValue {
+    Text (String),
+    Blob (Blob)
+}
+
+/// A single part of a [`FormData`] body
+#[derive(Debug, Clone)]
+pub struct Part {
+    name: String,
+    value: PartValue,
+    filename: Option<String>,
+    content_type: Option<String>,
+    headers: Vec<(String, String)>
+}
+
+impl Part {
+    /// Creates a new text part
+    #[inline]
+    pub fn text (name: impl Into<String>, value: impl Into<String>) -> Self {
+        return Self::new(name.into(), PartValue::Text(value.into()))
+    }
+
+    /// Creates a new binary part from a [`Blob`]. If the blob has a type, it will be used as the part's content type.
+    #[inline]
+    pub fn blob (name: impl Into<String>, blob: Blob) -> Self {
+        let content_type = Some(blob.type_()).filter(|x| !x.is_empty());
+        let mut this = Self::new(name.into(), PartValue::Blob(blob));
+        this.content_type = content_type;
+        return this
+    }
+
+    /// Creates a new binary part from a byte slice
+    #[inline]
+    pub fn bytes (name: impl Into<String>, bytes: &[u8]) -> Result<Self> {
+        let parts = Array::of1(&js_sys::Uint8Array::from(bytes));
+        let blob = Blob::new_with_u8_array_sequence(&parts)?;
+        return Ok(Self::blob(name, blob))
+    }
+
+    /// Creates a new binary part with the contents of a [`File`], using it's name as the part's filename
+    pub async fn file (name: impl Into<String>, file: &mut File) -> Result<Self> {
+        let file = file.get_read().await?;
+        let filename = file.name();
+        return Ok(Self::blob(name, file.into()).filename(filename))
+    }
+
+    #[inline]
+    fn new (name: String, value: PartValue) -> Self {
+        return Self {
+            name,
+            value,
+            filename: None,
+            content_type: None,
+            headers: Vec::new()
+        }
+    }
+
+    /// Sets the filename of the part
+    #[inline]
+    pub fn filename (mut self, filename: impl Into<String>) -> Self {
+        self.filename = Some(filename.into());
+        self
+    }
+
+    /// Sets the content type of the part
+    #[inline]
+    pub fn content_type (mut self, content_type: impl Into<String>) -> Self {
+        self.content_type = Some(content_type.into());
+        self
+    }
+
+    /// Adds a custom header to the part
+    #[inline]
+    pub fn header (mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((key.into(), value.into()));
+        self
+    }
+
+    fn write_head (&self, head: &mut String) {
+        head.push_str("Content-Disposition: form-data; name=\"");
+        head.push_str(&escape(&self.name));
+        head.push('"');
+
+        if let Some(ref filename) = self.filename {
+            head.push_str("; filename=\"");
+            head.push_str(&escape(filename));
+            head.push('"');
+        }
+        head.push_str("\r\n");
+
+        if let Some(ref content_type) = self.content_type {
+            head.push_str("Content-Type: ");
+            head.push_str(content_type);
+            head.push_str("\r\n");
+        }
+
+        for (key, value) in self.headers.iter() {
+            head.push_str(key);
+            head.push_str(": ");
+            head.push_str(value);
+            head.push_str("\r\n");
+        }
+        head.push_str("\r\n");
+    }
+}
+
+/// A `multipart/form-data` body builder
+#[derive(Debug, Clone, Default)]
+pub struct FormData {
+    parts: Vec<Part>
+}
+
+impl FormData {
+    /// Creates a new empty body
+    #[inline]
+    pub fn new () -> Self {
+        return Default::default()
+    }
+
+    /// Adds a text field to the body
+    #[inline]
+    pub fn text (self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        return self.part(Part::text(name, value))
+    }
+
+    /// Adds a [`Blob`] to the body
+    #[inline]
+    pub fn blob (self, name: impl Into<String>, blob: Blob) -> Self {
+        return self.part(Part::blob(name, blob))
+    }
+
+    /// Adds a [`Blob`] with the specified filename to the body
+    #[inline]
+    pub fn blob_with_filename (self, name: impl Into<String>, blob: Blob, filename: impl Into<String>) -> Self {
+        return self.part(Part::blob(name, blob).filename(filename))
+    }
+
+    /// Adds the contents of a [`File`] to the body
+    #[inline]
+    pub async fn file (self, name: impl Into<String>, file: &mut File) -> Result<Self> {
+        let part = Part::file(name, file).await?;
+        return Ok(self.part(part))
+    }
+
+    /// Adds a custom part to the body
+    #[inline]
+    pub fn part (mut self, part: Part) -> Self {
+        self.parts.push(part);
+        self
+    }
+
+    /// Encodes the body into a [`Blob`], whose type is the `multipart/form-data` content type (including it's boundary).
+    pub fn into_blob (self) -> Result<Blob> {
+        let boundary = format!("----rustww{:016x}{:016x}", rand::random::<u64>(), rand::random::<u64>());
+        let parts = Array::new();
+
+        for part in self.parts.iter() {
+            let mut head = format!("--{boundary}\r\n");
+            part.write_head(&mut head);
+            parts.push(&JsValue::from_str(&head));
+
+            match part.value {
+                PartValue::Text(ref text) => parts.push(&JsValue::from_str(text)),
+                PartValue::Blob(ref blob) => parts.push(blob)
+            };
+            parts.push(&JsValue::from_str("\r\n"));
+        }
+        parts.push(&JsValue::from_str(&format!("--{boundary}--\r\n")));
+
+        let mut ops = BlobPropertyBag::new();
+        ops.type_(&format!("multipart/form-data; boundary={boundary}"));
+        return Ok(Blob::new_with_blob_sequence_and_options(&parts, &ops)?)
+    }
+}
+
+/// The body is encoded with [`into_blob`](FormData::into_blob), throwing a JavaScript exception if it fails.
+/// To handle the error instead, encode it beforehand and use the resulting [`Blob`] as the body.
+impl IntoFetchBody for FormData {
+    #[inline]
+    fn into_body (self) -> Option<JsValue> {
+        return match self.into_blob() {
+            Ok(blob) => Some(blob.into()),
+            Err(e) => wasm_bindgen::throw_val(e.into())
+        }
+    }
+}
+
+#[inline]
+fn escape (s: &str) -> String {
+    return s.replace('"', "%22")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
@@ -0,0 +1,180 @@
+use std::fmt::Display;
+use serde::{Serialize, Deserialize, de::DeserializeOwned};
+use serde_json::Value;
+use crate::{Result, crypto::sha256, encoding::encode_hex};
+use super::{Request, Method, FetchClient, FetchError};
+
+/// Error returned by a GraphQL server, inside the `errors` field of it's response
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ServerError {
+    /// Description of the error
+    pub message: String,
+    /// Locations of the document the error refers to
+    #[serde(default)]
+    pub locations: Vec<Location>,
+    /// Path of the response field where the error occurred
+    #[serde(default)]
+    pub path: Vec<Value>,
+    /// Additional server-specific information
+    #[serde(default)]
+    pub extensions: Option<Value>
+}
+
+/// Location of a GraphQL document
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+pub struct Location {
+    pub line: u32,
+    pub column: u32
+}
+
+/// Response envelope of a GraphQL server
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(bound = "D: DeserializeOwned")]
+pub struct GraphQLResponse<D> {
+    /// Data of the response, if any. Responses may contain partial data alongside errors.
+    #[serde(default)]
+    pub data: Option<D>,
+    /// Errors of the response
+    #[serde(default)]
+    pub errors: Vec<ServerError>
+}
+
+/// Error of a GraphQL operation
+#[derive(Debug)]
+pub enum GraphQLError {
+    /// The request couldn't be executed, or it's response couldn't be decoded
+    Fetch (FetchError),
+    /// The server returned errors
+    Server (Vec<ServerError>),
+    /// The server returned neither data nor errors
+    NoData
+}
+
+impl From<FetchError> for GraphQLError {
+    #[inline]
+    fn from(value: FetchError) -> Self {
+        return Self::Fetch(value)
+    }
+}
+
+impl From<crate::Error> for GraphQLError {
+    #[inline]
+    fn from(value: crate::Error) -> Self {
+        return Self::Fetch(value.into())
+    }
+}
+
+impl Display for GraphQLError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Fetch(e) => Display::fmt(e, f),
+            Self::Server(errors) => {
+                f.write_str("GraphQL errors: ")?;
+                for (i, e) in errors.iter().enumerate() {
+                    if i > 0 { f.write_str("; ")? }
+                    f.write_str(&e.message)?;
+                }
+                Ok(())
+            },
+            Self::NoData => f.write_str("GraphQL response has no data")
+        }
+    }
+}
+
+impl std::error::Error for GraphQLError {}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Operation<'a, V> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    query: Option<&'a str>,
+    variables: &'a V,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    extensions: Option<Value>
+}
+
+/// A GraphQL client, which sends operations as HTTP POST requests to a single endpoint
+#[derive(Clone)]
+pub struct Client {
+    url: String,
+    client: FetchClient,
+    persisted: bool
+}
+
+impl Client {
+    /// Creates a new client targeting the specified endpoint
+    #[inline]
+    pub fn new (url: impl Into<String>) -> Self {
+        return Self::with_client(url, FetchClient::new())
+    }
+
+    /// Creates a new client targeting the specified endpoint, that sends it's requests through `client`
+    #[inline]
+    pub fn with_client (url: impl Into<String>, client: FetchClient) -> Self {
+        return Self { url: url.into(), client, persisted: false }
+    }
+
+    /// Enables or disables [automatic persisted queries](https://www.apollographql.com/docs/apollo-server/performance/apq/).
+    ///
+    /// When enabled, only the SHA-256 hash of the document is sent at first, and the full document is sent only if the server doesn't know it yet.
+    #[inline]
+    pub fn persisted_queries (mut self, persisted: bool) -> Self {
+        self.persisted = persisted;
+        self
+    }
+
+    /// Executes a query, returning it's data
+    #[inline]
+    pub async fn query<V: Serialize, D: DeserializeOwned> (&self, document: &str, variables: &V) -> ::core::result::Result<D, GraphQLError> {
+        return self.execute(document, variables).await
+    }
+
+    /// Executes a mutation, returning it's data
+    #[inline]
+    pub async fn mutation<V: Serialize, D: DeserializeOwned> (&self, document: &str, variables: &V) -> ::core::result::Result<D, GraphQLError> {
+        return self.execute(document, variables).await
+    }
+
+    /// Executes an operation, returning it's data. If the server returns any error, it's returned instead.
+    pub async fn execute<V: Serialize, D: DeserializeOwned> (&self, document: &str, variables: &V) -> ::core::result::Result<D, GraphQLError> {
+        let response = self.execute_raw::<V, D>(document, variables).await?;
+        if !response.errors.is_empty() {
+            return Err(GraphQLError::Server(response.errors))
+        }
+        return response.data.ok_or(GraphQLError::NoData)
+    }
+
+    /// Executes an operation, returning the full response envelope, which may contain both partial data and errors
+    pub async fn execute_raw<V: Serialize, D: DeserializeOwned> (&self, document: &str, variables: &V) -> ::core::result::Result<GraphQLResponse<D>, FetchError> {
+        if !self.persisted {
+            return self.send(&Operation { query: Some(document), variables, extensions: None }).await
+        }
+
+        let extensions = serde_json::json!({
+            "persistedQuery": { "version": 1, "sha256Hash": sha256_hex(document).await? }
+        });
+
+        let response = self.send::<V, D>(&Operation { query: None, variables, extensions: Some(extensions.clone()) }).await?;
+        let not_found = response.errors.iter().any(|e| {
+            e.message == "PersistedQueryNotFound"
+                || e.extensions.as_ref().and_then(|x| x.get("code")).and_then(Value::as_str) == Some("PERSISTED_QUERY_NOT_FOUND")
+        });
+
+        if not_found {
+            return self.send(&Operation { query: Some(document), variables, extensions: Some(extensions) }).await
+        }
+        return Ok(response)
+    }
+
+    async fn send<V: Serialize, D: DeserializeOwned> (&self, operation: &Operation<'_, V>) -> ::core::result::Result<GraphQLResponse<D>, FetchError> {
+        let mut req = Request::new();
+        req.method(Method::Post).json(operation)?.header("Accept", "application/json")?;
+        return self.client.fetch(&self.url, req).await?.json().await
+    }
+}
+
+/// Returns the hexadecimal SHA-256 hash of the document
+#[inline]
+async fn sha256_hex (document: &str) -> Result<String> {
+    return Ok(encode_hex(&sha256(document.as_bytes()).await?))
+}
@@ -0,0 +1,88 @@
+use js_sys::Uint8Array;
+use wasm_bindgen::JsCast;
+use web_sys::{Headers, ResponseInit};
+use crate::{Result, Error, ErrorKind};
+use super::{raw_response, Request, Response, Method, IntoFetchBody, JsReadStream, FetchError};
+
+impl<B: IntoFetchBody> TryFrom<::http::Request<B>> for Request {
+    type Error = Error;
+
+    /// Creates a request with the same target, method, headers and body as the [`http::Request`](::http::Request)
+    fn try_from(req: ::http::Request<B>) -> Result<Self> {
+        let (parts, body) = req.into_parts();
+
+        let mut this = Request::new();
+        this.target(parts.uri.to_string()).method(Method::from(parts.method.as_str())).body(body);
+        for (key, value) in parts.headers.iter() {
+            this.header(key.as_str(), header_str(value)?)?;
+        }
+
+        return Ok(this)
+    }
+}
+
+impl<B: IntoFetchBody> TryFrom<::http::Response<B>> for Response {
+    type Error = Error;
+
+    /// Creates a response with the same status, headers and body as the [`http::Response`](::http::Response)
+    fn try_from(resp: ::http::Response<B>) -> Result<Self> {
+        let (parts, body) = resp.into_parts();
+
+        let headers = Headers::new()?;
+        for (key, value) in parts.headers.iter() {
+            headers.append(key.as_str(), header_str(value)?)?;
+        }
+
+        let mut init = ResponseInit::new();
+        init.status(parts.status.as_u16()).headers(&headers);
+        if let Some(reason) = parts.status.canonical_reason() {
+            init.status_text(reason);
+        }
+
+        let inner = raw_response(body.into_body().as_ref(), &init)?;
+        return Ok(Self { inner })
+    }
+}
+
+impl TryFrom<Response> for ::http::Response<Option<JsReadStream<'static, Uint8Array>>> {
+    type Error = Error;
+
+    /// Converts the response into an [`http::Response`](::http::Response) whose body is the response's stream, if any
+    fn try_from(resp: Response) -> Result<Self> {
+        let builder = http_builder(&resp)?;
+        return builder.body(resp.body()?).map_err(http_error)
+    }
+}
+
+impl Response {
+    /// Reads the response's body, returning an [`http::Response`](::http::Response) with it's status, headers and body
+    pub async fn into_http (self) -> ::core::result::Result<::http::Response<Vec<u8>>, FetchError> {
+        let builder = http_builder(&self)?;
+        let body = self.bytes().await?;
+        return Ok(builder.body(body).map_err(http_error)?)
+    }
+}
+
+/// Returns an [`http::response::Builder`](::http::response::Builder) with the response's status and headers
+fn http_builder (resp: &Response) -> Result<::http::response::Builder> {
+    let mut builder = ::http::Response::builder().status(resp.status());
+    if let Some(iter) = js_sys::try_iter(&resp.inner.headers())? {
+        for entry in iter {
+            let entry = entry?.unchecked_into::<js_sys::Array>();
+            if let (Some(key), Some(value)) = (entry.get(0).as_string(), entry.get(1).as_string()) {
+                builder = builder.header(key, value);
+            }
+        }
+    }
+    return Ok(builder)
+}
+
+#[inline]
+fn header_str (value: &::http::HeaderValue) -> Result<&str> {
+    return value.to_str().map_err(|e| Error::new(ErrorKind::Serde, &e.to_string()))
+}
+
+#[inline]
+fn http_error (e: ::http::Error) -> Error {
+    return Error::new(ErrorKind::Serde, &e.to_string())
+}
@@ -0,0 +1,202 @@
+use std::{task::Poll, marker::PhantomData};
+use futures::{Stream, StreamExt};
+use serde::de::DeserializeOwned;
+use crate::{Result, Error, ErrorKind};
+use super::{Response, TextStream};
+
+impl Response {
+    /// Returns a [`Stream`] that incrementally deserializes the response's newline-delimited JSON body as it arrives,
+    /// yielding one value per line.
+    #[inline]
+    pub fn json_stream_lines<T: DeserializeOwned> (self) -> Result<JsonStream<T>> {
+        return self.json_stream(JsonMode::Lines)
+    }
+
+    /// Returns a [`Stream`] that incrementally deserializes the response's body, a top-level JSON array, as it arrives,
+    /// yielding it's elements one by one.
+    #[inline]
+    pub fn json_stream_array<T: DeserializeOwned> (self) -> Result<JsonStream<T>> {
+        return self.json_stream(JsonMode::ArrayStart)
+    }
+
+    fn json_stream<T: DeserializeOwned> (self, mode: JsonMode) -> Result<JsonStream<T>> {
+        let inner = match self.body()? {
+            Some(body) => Some(body.text("utf-8")?),
+            None => None
+        };
+
+        return Ok(JsonStream {
+            inner,
+            buffer: String::new(),
+            pos: 0,
+            depth: 0,
+            in_string: false,
+            escape: false,
+            mode,
+            done: false,
+            _phtm: PhantomData
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum JsonMode {
+    Lines,
+    ArrayStart,
+    Array,
+    ArrayEnd,
+    Finished
+}
+
+/// A [`Stream`] of values deserialized from a newline-delimited JSON or JSON array body.
+///
+/// See [`json_stream_lines`](Response::json_stream_lines) and [`json_stream_array`](Response::json_stream_array)
+pub struct JsonStream<T> {
+    inner: Option<TextStream<'static>>,
+    buffer: String,
+    pos: usize,
+    depth: u32,
+    in_string: bool,
+    escape: bool,
+    mode: JsonMode,
+    done: bool,
+    _phtm: PhantomData<T>
+}
+
+impl<T: DeserializeOwned> JsonStream<T> {
+    /// Attempts to extract the next value from the buffer
+    fn next_value (&mut self) -> Option<Result<T>> {
+        loop {
+            match self.mode {
+                JsonMode::ArrayStart => {
+                    let trimmed = self.buffer.trim_start();
+                    match trimmed.chars().next() {
+                        Some('[') => {
+                            let offset = self.buffer.len() - trimmed.len() + 1;
+                            self.buffer.drain(..offset);
+                            self.mode = JsonMode::Array;
+                        },
+                        Some(_) => {
+                            self.mode = JsonMode::Finished;
+                            return Some(Err(Error::new(ErrorKind::Serde, "expected a JSON array")))
+                        },
+                        None if self.done => {
+                            self.mode = JsonMode::Finished;
+                            return Some(Err(Error::new(ErrorKind::Serde, "expected a JSON array")))
+                        },
+                        None => return None
+                    }
+                },
+
+                JsonMode::Lines => {
+                    let line = match self.buffer.find('\n') {
+                        Some(idx) => self.buffer.drain(..=idx).collect::<String>(),
+                        None if self.done => core::mem::take(&mut self.buffer),
+                        None => return None
+                    };
+
+                    if line.trim().is_empty() {
+                        if self.buffer.is_empty() && self.done { return None }
+                        continue
+                    }
+                    return Some(serde_json::from_str(&line).map_err(Into::into))
+                },
+
+                JsonMode::Array => {
+                    let mut empty = false;
+                    let bytes = self.buffer.as_bytes();
+                    while self.pos < bytes.len() {
+                        let i = self.pos;
+                        self.pos += 1;
+
+                        if self.in_string {
+                            match bytes[i] {
+                                _ if self.escape => self.escape = false,
+                                b'\\' => self.escape = true,
+                                b'"' => self.in_string = false,
+                                _ => {}
+                            }
+                            continue
+                        }
+
+                        match bytes[i] {
+                            b'"' => self.in_string = true,
+                            b'{' | b'[' => self.depth += 1,
+                            b'}' | b']' if self.depth > 0 => self.depth -= 1,
+                            b']' | b',' if self.depth == 0 => {
+                                if bytes[i] == b']' { self.mode = JsonMode::ArrayEnd }
+                                let element = self.buffer.drain(..=i).collect::<String>();
+                                self.pos = 0;
+
+                                let element = element[..element.len() - 1].trim();
+                                if element.is_empty() {
+                                    empty = true;
+                                    break
+                                }
+                                return Some(serde_json::from_str(element).map_err(Into::into))
+                            },
+                            _ => {}
+                        }
+                    }
+
+                    if empty { continue }
+                    if self.mode == JsonMode::Array {
+                        if self.done {
+                            self.mode = JsonMode::Finished;
+                            return Some(Err(Error::new(ErrorKind::Serde, "unterminated JSON array")))
+                        }
+                        return None
+                    }
+                },
+
+                // only whitespace may follow the array
+                JsonMode::ArrayEnd => {
+                    if !self.buffer.trim().is_empty() {
+                        self.mode = JsonMode::Finished;
+                        return Some(Err(Error::new(ErrorKind::Serde, "trailing content after the JSON array")))
+                    }
+
+                    self.buffer.clear();
+                    if self.done { self.mode = JsonMode::Finished }
+                    return None
+                },
+
+                JsonMode::Finished => return None
+            }
+        }
+    }
+}
+
+impl<T: DeserializeOwned> Stream for JsonStream<T> {
+    type Item = Result<T>;
+
+    fn poll_next(mut self: std::pin::Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        loop {
+            if let Some(value) = this.next_value() {
+                return Poll::Ready(Some(value))
+            }
+
+            if this.done {
+                return Poll::Ready(None)
+            }
+
+            let inner = match this.inner {
+                Some(ref mut inner) => inner,
+                None => {
+                    this.done = true;
+                    continue
+                }
+            };
+
+            match inner.poll_next_unpin(cx) {
+                Poll::Ready(Some(Ok(text))) => this.buffer.push_str(&text),
+                Poll::Ready(Some(Err(e))) => return Poll::Ready(Some(Err(e))),
+                Poll::Ready(None) => this.done = true,
+                Poll::Pending => return Poll::Pending
+            }
+        }
+    }
+}
+
+impl<T> Unpin for JsonStream<T> {}
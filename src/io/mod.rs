@@ -1,4 +1,15 @@
-flat_mod! { read, write, pipe, fetch }
+flat_mod! { read, write, pipe, adapt, fetch, form, client, cache, text, json, source, copy, upload, download, range }
+
+#[cfg(feature = "http")]
+mod http;
+
+/// GraphQL client
+pub mod graphql;
+
+/// AWS Signature Version 4 request signing
+#[cfg(feature = "sign")]
+#[cfg_attr(docsrs, doc(cfg(feature = "sign")))]
+pub mod sign;
 
 #[cfg(web_sys_unstable_apis)]
 #[cfg_attr(docsrs, doc(cfg(web_sys_unstable_apis)))]
@@ -1,61 +1,217 @@
-use std::marker::PhantomData;
-
-use wasm_bindgen::{JsCast};
+use std::{marker::PhantomData, rc::Rc, cell::Cell, task::Poll};
+use futures::{Future, FutureExt};
+use wasm_bindgen::{JsCast, JsValue, prelude::{wasm_bindgen, Closure}};
 use wasm_bindgen_futures::JsFuture;
 use web_sys::StreamPipeOptions;
-use crate::{Result, utils::AbortController};
+use crate::{Result, utils::{AbortController, AbortSignal, any_signal}, scope::{TaskPriority, post_task}};
 use super::{JsReadStream, JsWriteStream};
 
-pub struct PipeController<'a, 'b, T> {
-    src: JsReadStream<'a, T>,
-    abort: AbortController<()>,
+#[wasm_bindgen]
+extern "C" {
+    #[derive(Debug, Clone)]
+    pub(super) type TransformStream;
+    #[derive(Debug, Clone)]
+    pub(super) type TransformStreamDefaultController;
+
+    #[wasm_bindgen(constructor, catch)]
+    pub(super) fn new (transformer: &js_sys::Object) -> ::core::result::Result<TransformStream, JsValue>;
+    #[wasm_bindgen(method, getter)]
+    pub(super) fn readable (this: &TransformStream) -> web_sys::ReadableStream;
+    #[wasm_bindgen(method, getter)]
+    pub(super) fn writable (this: &TransformStream) -> web_sys::WritableStream;
+
+    #[wasm_bindgen(method)]
+    pub(super) fn enqueue (this: &TransformStreamDefaultController, chunk: &JsValue);
+    #[wasm_bindgen(method)]
+    pub(super) fn terminate (this: &TransformStreamDefaultController);
+}
+
+/// Options of a pipe between a [`JsReadStream`] and a [`JsWriteStream`]
+#[derive(Debug, Clone, Default)]
+pub struct PipeOptions {
+    /// If `true`, the destination won't be closed when the source is closed
+    pub prevent_close: bool,
+    /// If `true`, the destination won't be aborted when the source errors
+    pub prevent_abort: bool,
+    /// If `true`, the source won't be canceled when the destination errors
+    pub prevent_cancel: bool,
+    /// If set, the piping will be aborted when the signal is
+    pub signal: Option<web_sys::AbortSignal>,
+    priority: Option<TaskPriority>
+}
+
+impl PipeOptions {
+    /// Sets the signal that aborts the piping
+    #[inline]
+    pub fn with_signal<T> (mut self, signal: &AbortSignal<T>) -> Self {
+        self.signal = Some(signal.as_ref().clone());
+        self
+    }
+
+    /// Sets the priority of the tasks the chunks are piped within, so that
+    /// [`Background`](TaskPriority::Background) transfers yield to more important work
+    #[inline]
+    pub fn with_priority (mut self, priority: TaskPriority) -> Self {
+        self.priority = Some(priority);
+        self
+    }
+}
+
+/// Handle that allows to abort a running [`Pipe`]
+#[derive(Debug, Clone)]
+pub struct PipeHandle {
+    abort: AbortController<()>
+}
+
+impl PipeHandle {
+    /// Aborts the piping. The [`Pipe`] will resolve to an error.
+    #[inline]
+    pub fn abort (&self) {
+        self.abort.abort()
+    }
+}
+
+/// A [`Future`] that resolves to the number of bytes piped when the piping completes.
+///
+/// For chunks without a `byteLength` (like strings), their `length` is counted instead.
+///
+/// If droped before it completes, the piping will be aborted.
+pub struct Pipe<'a, 'b, T> {
+    future: JsFuture,
+    count: Rc<Cell<u64>>,
+    transform: Option<Closure<dyn FnMut(JsValue, TransformStreamDefaultController) -> JsValue>>,
+    handle: PipeHandle,
+    _src: JsReadStream<'a, T>,
     _dst: PhantomData<&'b mut JsWriteStream<'a, T>>,
 }
 
-impl<'a, T> PipeController<'a, '_, T> {
-    /// Aborts the pipeing, returning it's underlying [`JsReadStream`]
+impl<'a, 'b, T> Pipe<'a, 'b, T> {
+    /// Returns the number of bytes piped so far
+    #[inline]
+    pub fn piped (&self) -> u64 {
+        return self.count.get()
+    }
+
+    /// Returns a handle to the pipe
+    #[inline]
+    pub fn handle (&self) -> PipeHandle {
+        return self.handle.clone()
+    }
+}
+
+impl<T> Future for Pipe<'_, '_, T> {
+    type Output = Result<u64>;
+
+    #[inline]
+    fn poll(mut self: std::pin::Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Self::Output> {
+        if let Poll::Ready(result) = self.future.poll_unpin(cx) {
+            self.transform = None;
+            result?;
+            return Poll::Ready(Ok(self.count.get()))
+        }
+        return Poll::Pending
+    }
+}
+
+impl<T> Drop for Pipe<'_, '_, T> {
     #[inline]
-    pub fn abort (self) -> JsReadStream<'a, T> {
-        self.abort.abort();
-        return self.src
+    fn drop(&mut self) {
+        // the transform closure is released with the pipe, after the abort stops both legs from transforming more chunks
+        if self.transform.is_some() {
+            self.handle.abort();
+        }
     }
 }
 
 impl<'a, T: JsCast> JsReadStream<'a, T> {
-    /// Pipes the contents from the [`JsReadStream`] into the [`JsWriteStream`]
-    pub async fn pipe_to<'d> (self, dst: &'d mut JsWriteStream<'a, T>) -> Result<PipeController<'a, 'd, T>> {
+    /// Pipes the contents from the [`JsReadStream`] into the [`JsWriteStream`], returning immediately
+    /// the [`Pipe`] future and a [`PipeHandle`] that can abort it.
+    #[inline]
+    pub fn pipe_to<'d> (self, dst: &'d mut JsWriteStream<'a, T>, options: PipeOptions) -> Result<(Pipe<'a, 'd, T>, PipeHandle)> {
+        return self.pipe_to_with_progress(dst, options, None)
+    }
+
+    /// Pipes the contents like [`pipe_to`](JsReadStream::pipe_to), calling `progress` with the number of bytes piped so far after every chunk.
+    pub(crate) fn pipe_to_with_progress<'d> (mut self, dst: &'d mut JsWriteStream<'a, T>, options: PipeOptions, mut progress: Option<Box<dyn FnMut(u64)>>) -> Result<(Pipe<'a, 'd, T>, PipeHandle)> {
         // Release locks
-        if let Some(ref reader) = self.reader {
+        if let Some(reader) = self.reader.take() {
             reader.release_lock();
         }
-        if let Some(ref writer) = dst.writer {
+        if let Some(writer) = dst.writer.take() {
             writer.release_lock();
         }
-    
+
+        // Chunk counter
+        let count = Rc::new(Cell::new(0u64));
+        let my_count = count.clone();
+        let priority = options.priority;
+        let transform = Closure::<dyn FnMut(JsValue, TransformStreamDefaultController) -> JsValue>::new(move |chunk: JsValue, con: TransformStreamDefaultController| {
+            let len = js_sys::Reflect::get(&chunk, &JsValue::from_str("byteLength")).ok()
+                .and_then(|x| x.as_f64())
+                .or_else(|| js_sys::Reflect::get(&chunk, &JsValue::from_str("length")).ok().and_then(|x| x.as_f64()))
+                .unwrap_or_default();
+
+            let count = my_count.get() + len as u64;
+            my_count.set(count);
+            con.enqueue(&chunk);
+
+            if let Some(ref mut progress) = progress {
+                progress(count)
+            }
+
+            // the next chunk isn't transformed until the returned promise resolves
+            return match priority {
+                Some(priority) => post_task(priority, || {}).map(JsValue::from).unwrap_or(JsValue::UNDEFINED),
+                None => JsValue::UNDEFINED
+            }
+        });
+
+        let transformer = js_sys::Object::new();
+        js_sys::Reflect::set(&transformer, &JsValue::from_str("transform"), transform.as_ref())?;
+        let counter = TransformStream::new(&transformer)?;
+
         // Pipeing options
-        let mut ops = StreamPipeOptions::new();
         let abort = AbortController::new()?;
+        let mut ops = StreamPipeOptions::new();
+        ops.prevent_close(options.prevent_close);
+        ops.prevent_abort(options.prevent_abort);
+        ops.prevent_cancel(options.prevent_cancel);
+        match options.signal {
+            Some(ref signal) => ops.signal(&any_signal(&[abort.raw_signal(), signal.clone()])?),
+            None => ops.signal(&abort.raw_signal())
+        };
+
+        // the internal leg must always propagate to the counter, so that the outer leg settles
+        let mut through_ops = ops.clone();
+        through_ops.prevent_close(false);
+        through_ops.prevent_abort(false);
+        through_ops.prevent_cancel(false);
 
-        ops.prevent_close(true);
-        ops.prevent_abort(true);
-        ops.prevent_cancel(true);
-        ops.signal(&abort.raw_signal());
-        // todo signal
-    
         // Perform pipeing
-        let _ = JsFuture::from(self._stream.pipe_to_with_options(&dst._stream, &ops)).await?;
-        return Ok(PipeController {
-            src: self,
-            _dst: PhantomData,
-            abort
-        })
+        let through = self._stream.pipe_to_with_options(&counter.writable(), &through_ops);
+        let outer = counter.readable().pipe_to_with_options(&dst._stream, &ops);
+        let future = JsFuture::from(js_sys::Promise::all(&js_sys::Array::of2(&through, &outer)));
+        let handle = PipeHandle { abort };
+
+        return Ok((
+            Pipe {
+                future,
+                count,
+                transform: Some(transform),
+                handle: handle.clone(),
+                _src: self,
+                _dst: PhantomData
+            },
+            handle
+        ))
     }
 }
 
 impl<'a, T: JsCast> JsWriteStream<'a, T> {
-    /// Pipes the contents from the [`JsReadStream`] into the [`JsWriteStream`]
+    /// Pipes the contents from the [`JsReadStream`] into the [`JsWriteStream`], returning immediately
+    /// the [`Pipe`] future and a [`PipeHandle`] that can abort it.
     #[inline]
-    pub async fn pipe_from<'d> (&'d mut self, src: JsReadStream<'a, T>) -> Result<PipeController<'a, 'd, T>> {
-        return src.pipe_to(self).await
+    pub fn pipe_from<'d> (&'d mut self, src: JsReadStream<'a, T>, options: PipeOptions) -> Result<(Pipe<'a, 'd, T>, PipeHandle)> {
+        return src.pipe_to(self, options)
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,206 @@
+use std::{rc::Rc, collections::{HashMap, VecDeque}, io::SeekFrom, pin::Pin, task::{Context, Poll}};
+use futures::{AsyncRead, AsyncSeek, future::LocalBoxFuture, FutureExt};
+use super::{Request, Method, FetchError};
+
+type BlockFuture = LocalBoxFuture<'static, ::core::result::Result<(u64, Vec<u8>, Option<u64>), FetchError>>;
+
+/// A reader over a remote resource that fetches it's contents on demand via HTTP Range requests.
+///
+/// The resource is read in blocks, the most recently used of which are kept in memory, which allows to efficiently
+/// seek within big remote files (media, database images, archives, ...) without downloading them whole.
+pub struct RangeReader {
+    url: Rc<str>,
+    pos: u64,
+    len: Option<u64>,
+    block_size: u64,
+    cache_blocks: usize,
+    cache: HashMap<u64, Rc<[u8]>>,
+    order: VecDeque<u64>,
+    pending: Option<BlockFuture>
+}
+
+impl RangeReader {
+    /// Default size of the fetched blocks (64 KiB)
+    pub const DEFAULT_BLOCK_SIZE: u64 = 64 * 1024;
+    /// Default number of blocks kept in memory
+    pub const DEFAULT_CACHE_BLOCKS: usize = 16;
+
+    /// Creates a new reader over the resource at the specified url, without checking it first.
+    ///
+    /// The length of the resource will be known once the first block is fetched.
+    #[inline]
+    pub fn new (url: impl Into<String>) -> Self {
+        return Self {
+            url: Rc::from(url.into()),
+            pos: 0,
+            len: None,
+            block_size: Self::DEFAULT_BLOCK_SIZE,
+            cache_blocks: Self::DEFAULT_CACHE_BLOCKS,
+            cache: HashMap::new(),
+            order: VecDeque::new(),
+            pending: None
+        }
+    }
+
+    /// Creates a new reader over the resource at the specified url, sending an HTTP HEAD request to find out it's length.
+    ///
+    /// Returns an error if the server doesn't advertise support for range requests.
+    pub async fn open (url: impl Into<String>) -> ::core::result::Result<Self, FetchError> {
+        let mut this = Self::new(url);
+
+        let mut req = Request::new();
+        req.method(Method::Head);
+        let resp = req.fetch(&this.url).await?.error_for_status().await?;
+
+        if resp.header("Accept-Ranges").is_some_and(|x| x.trim().eq_ignore_ascii_case("none")) {
+            return Err(FetchError::Decode("the server doesn't support range requests".to_string()))
+        }
+
+        this.len = resp.header("Content-Length").and_then(|x| x.trim().parse().ok());
+        return Ok(this)
+    }
+
+    /// Sets the size, in bytes, of the fetched blocks
+    ///
+    /// # Panics
+    /// This method panics if `size` is zero.
+    #[inline]
+    pub fn block_size (mut self, size: u64) -> Self {
+        assert!(size > 0, "block size must be greater than zero");
+        self.block_size = size;
+        self.cache.clear();
+        self.order.clear();
+        self
+    }
+
+    /// Sets the maximum number of blocks kept in memory
+    #[inline]
+    pub fn cache_blocks (mut self, blocks: usize) -> Self {
+        self.cache_blocks = usize::max(blocks, 1);
+        self
+    }
+
+    /// Returns the url of the resource
+    #[inline]
+    pub fn url (&self) -> &str {
+        return &self.url
+    }
+
+    /// Returns the length of the resource, if known
+    #[inline]
+    pub fn len (&self) -> Option<u64> {
+        return self.len
+    }
+
+    /// Returns the current position of the reader
+    #[inline]
+    pub fn position (&self) -> u64 {
+        return self.pos
+    }
+
+    fn cached (&mut self, block: u64) -> Option<Rc<[u8]>> {
+        let data = self.cache.get(&block)?.clone();
+        if let Some(idx) = self.order.iter().position(|&x| x == block) {
+            self.order.remove(idx);
+        }
+        self.order.push_back(block);
+        return Some(data)
+    }
+
+    fn insert (&mut self, block: u64, data: Rc<[u8]>) {
+        if self.cache.insert(block, data).is_none() {
+            self.order.push_back(block);
+        }
+
+        while self.order.len() > self.cache_blocks {
+            if let Some(old) = self.order.pop_front() {
+                self.cache.remove(&old);
+            }
+        }
+    }
+}
+
+impl AsyncRead for RangeReader {
+    fn poll_read(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<std::io::Result<usize>> {
+        let this = &mut *self;
+        loop {
+            if buf.is_empty() || this.len.is_some_and(|len| this.pos >= len) {
+                return Poll::Ready(Ok(0))
+            }
+
+            let block = this.pos / this.block_size;
+            if let Some(data) = this.cached(block) {
+                let offset = (this.pos - block * this.block_size) as usize;
+                if offset >= data.len() {
+                    // short block, we reached the end of the resource
+                    this.len = Some(block * this.block_size + data.len() as u64);
+                    return Poll::Ready(Ok(0))
+                }
+
+                let n = usize::min(buf.len(), data.len() - offset);
+                buf[..n].copy_from_slice(&data[offset..offset + n]);
+                this.pos += n as u64;
+                return Poll::Ready(Ok(n))
+            }
+
+            let pending = this.pending.get_or_insert_with(|| {
+                fetch_block(this.url.clone(), block, this.block_size).boxed_local()
+            });
+
+            match pending.poll_unpin(cx) {
+                Poll::Ready(Ok((block, data, len))) => {
+                    this.pending = None;
+                    if len.is_some() {
+                        this.len = len;
+                    }
+                    this.insert(block, Rc::from(data));
+                },
+                Poll::Ready(Err(e)) => {
+                    this.pending = None;
+                    return Poll::Ready(Err(std::io::Error::other(e.to_string())))
+                },
+                Poll::Pending => return Poll::Pending
+            }
+        }
+    }
+}
+
+impl AsyncSeek for RangeReader {
+    fn poll_seek(mut self: Pin<&mut Self>, _cx: &mut Context<'_>, pos: SeekFrom) -> Poll<std::io::Result<u64>> {
+        let pos = match pos {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::Current(offset) => self.pos.checked_add_signed(offset),
+            SeekFrom::End(offset) => match self.len {
+                Some(len) => len.checked_add_signed(offset),
+                None => return Poll::Ready(Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "the length of the remote resource is unknown")))
+            }
+        };
+
+        return match pos {
+            Some(pos) => {
+                self.pos = pos;
+                Poll::Ready(Ok(pos))
+            },
+            None => Poll::Ready(Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "invalid seek to a negative or overflowing position")))
+        }
+    }
+}
+
+async fn fetch_block (url: Rc<str>, block: u64, block_size: u64) -> ::core::result::Result<(u64, Vec<u8>, Option<u64>), FetchError> {
+    let start = block * block_size;
+    let mut req = Request::new();
+    req.range(start..start + block_size)?;
+
+    let resp = req.fetch(&url).await?;
+    return match resp.status() {
+        206 => {
+            // Content-Range: bytes <start>-<end>/<len | *>
+            let len = resp.header("Content-Range")
+                .and_then(|x| x.rsplit_once('/').and_then(|(_, len)| len.trim().parse().ok()));
+            Ok((block, resp.bytes().await?, len))
+        },
+        416 => Ok((block, Vec::new(), None)),
+        200 => Err(FetchError::Decode("the server doesn't support range requests".to_string())),
+        _ => Err(resp.error_for_status().await.err().unwrap_or(FetchError::Decode("unexpected response status".to_string())))
+    }
+}
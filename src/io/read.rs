@@ -1,10 +1,10 @@
-use std::{task::{Poll}, marker::PhantomData};
+use std::{task::{Poll}, marker::PhantomData, mem::ManuallyDrop};
 use docfg::docfg;
 use futures::{Future, TryFutureExt, Stream, FutureExt};
 use js_sys::{Uint8Array};
 use wasm_bindgen::{JsCast, JsValue, prelude::{wasm_bindgen}};
 use wasm_bindgen_futures::JsFuture;
-use crate::{Result, utils::{TypedArrayExt, TypedArray}};
+use crate::{Result, Error, ErrorKind, utils::{TypedArrayExt, TypedArray}};
 use super::{IntoFetchBody};
 
 #[wasm_bindgen]
@@ -22,7 +22,7 @@ pub struct JsReadStream<'a, T> {
     pub(super) _stream: web_sys::ReadableStream,
     pub(super) reader: Option<web_sys::ReadableStreamDefaultReader>,
     #[cfg(web_sys_unstable_apis)]
-    pub(super) _builder: Option<super::builder::ReadBuilder<'a, T>>,
+    pub(super) _builder: Option<std::rc::Rc<super::builder::ReadBuilder<'a, T>>>,
     _phtm: PhantomData<&'a T>
 }
 
@@ -96,10 +96,62 @@ impl<'a, T: JsCast> JsReadStream<'a, T> {
         return Ok(Self { _stream: this, reader: None, #[cfg(web_sys_unstable_apis)] _builder: None, _phtm: PhantomData })
     }
 
-    /// Reads the next chunk of the stream
+    /// Splits the stream into two independent streams, both of which will yield the same chunks.
+    /// 
+    /// Chunks that haven't been read yet by one of the branches are buffered, so the stream is consumed
+    /// at the pace of the fastest branch.
+    pub fn tee (self) -> (Self, Self) {
+        let mut this = ManuallyDrop::new(self);
+        if let Some(reader) = this.reader.take() {
+            reader.release_lock()
+        }
+
+        let tee = this._stream.tee();
+        let (left, right) = (tee.get(0), tee.get(1));
+        debug_assert!(left.is_instance_of::<web_sys::ReadableStream>());
+        debug_assert!(right.is_instance_of::<web_sys::ReadableStream>());
+
+        #[cfg(web_sys_unstable_apis)]
+        let builder = this._builder.take();
+        drop(unsafe { core::ptr::read(&this._stream) });
+
+        return (
+            Self { _stream: left.unchecked_into(), reader: None, #[cfg(web_sys_unstable_apis)] _builder: builder.clone(), _phtm: PhantomData },
+            Self { _stream: right.unchecked_into(), reader: None, #[cfg(web_sys_unstable_apis)] _builder: builder, _phtm: PhantomData }
+        )
+    }
+
+    /// Returns the underlying [`ReadableStream`](web_sys::ReadableStream), without cancelling it
+    pub(crate) fn into_raw (self) -> web_sys::ReadableStream {
+        let mut this = ManuallyDrop::new(self);
+        if let Some(reader) = this.reader.take() {
+            reader.release_lock()
+        }
+
+        #[cfg(web_sys_unstable_apis)]
+        drop(this._builder.take());
+        return unsafe { core::ptr::read(&this._stream) }
+    }
+
+    /// Cancels the stream, signaling the source that no more chunks will be read
+    #[inline]
+    pub async fn cancel (self) -> Result<()> {
+        return self.cancel_with_reason(JsValue::UNDEFINED).await
+    }
+
+    /// Cancels the stream with the specified reason, which is passed to the `cancel` callback of the source
+    pub async fn cancel_with_reason (self, reason: JsValue) -> Result<()> {
+        let promise = self.into_raw().cancel_with_reason(&reason);
+        let _ = JsFuture::from(promise).await?;
+        return Ok(())
+    }
+
+    /// Reads the next chunk of the stream.
+    ///
+    /// If the stream errored, the error it errored with is returned.
     pub async fn read_chunk (&mut self) -> Result<Option<T>> {
         if let ChunkResult { value: Some(value), .. } = self.next_chunk().await? {
-            return JsCast::dyn_into::<T>(value).map(Some);
+            return cast_chunk(value).map(Some);
         }
         return Ok(None)
     }
@@ -143,7 +195,7 @@ impl<'a, T: JsCast> JsReadStream<'a, T> {
 impl IntoFetchBody for JsReadStream<'static, Uint8Array> {
     #[inline]
     fn into_body (self) -> Option<JsValue> {
-        return Some(self._stream.clone().into())
+        return Some(self.into_raw().into())
     }
 }
 
@@ -155,14 +207,42 @@ impl<T> Drop for JsReadStream<'_, T> {
         }
 
         #[cfg(web_sys_unstable_apis)]
-        if let Some(ref builder) = self._builder {
+        if let Some(ref builder) = self._builder && std::rc::Rc::strong_count(builder) == 1 {
             builder.handle.abort();
         }
 
-        let _ = self._stream.cancel();
+        // the stream is still in use (like by a pipe), so it isn't ours to cancel
+        if self._stream.locked() {
+            return
+        }
+
+        let promise = self._stream.cancel();
+        #[cfg(debug_assertions)]
+        {
+            let f = wasm_bindgen::closure::Closure::once_into_js(|err: JsValue| crate::diagnostics::failure("io::JsReadStream::cancel", &err));
+            let _ = promise.catch(f.unchecked_ref());
+        }
+        #[cfg(not(debug_assertions))]
+        let _ = promise;
     }
 }
 
+/// Casts a chunk into the stream's type
+#[inline]
+pub(super) fn cast_chunk<T: JsCast> (value: JsValue) -> Result<T> {
+    return JsCast::dyn_into::<T>(value).map_err(|_| Error::new(ErrorKind::Io, "the chunk isn't of the expected type"))
+}
+
+/// Classifies the rejection of a `read` call, which is the error the stream errored with.
+/// Errors that aren't otherwise classified are considered I/O errors.
+fn read_error (value: JsValue) -> Error {
+    let error = Error::from(value);
+    if error.kind() == ErrorKind::Js {
+        return Error::from_js(ErrorKind::Io, error.into_js())
+    }
+    return error
+}
+
 /// The [`Stream`](futures::Stream) version of [`JsReadStream`]
 pub struct ReadStream<'a, T> {
     inner: JsReadStream<'a, T>,
@@ -175,14 +255,19 @@ impl<'a, T: Unpin + JsCast> Stream for ReadStream<'a, T> {
     #[inline]
     fn poll_next(mut self: std::pin::Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Option<Self::Item>> {
         if let Some(ref mut current) = self.current {
-            return match current.poll_unpin(cx)? {
-                Poll::Ready(ChunkResult { value: None, .. } | ChunkResult { done: true, .. }) => {
+            return match current.poll_unpin(cx) {
+                Poll::Ready(Ok(ChunkResult { value: None, .. } | ChunkResult { done: true, .. })) => {
                     self.current = None;
                     Poll::Ready(None)
                 },
-                Poll::Ready(ChunkResult { value: Some(value), .. }) => {
+                Poll::Ready(Ok(ChunkResult { value: Some(value), .. })) => {
                     self.current = Some(self.inner.next_chunk());
-                    Poll::Ready(Some(JsCast::dyn_into::<T>(value)))
+                    Poll::Ready(Some(cast_chunk(value)))
+                },
+                // an errored stream won't yield any more chunks
+                Poll::Ready(Err(e)) => {
+                    self.current = None;
+                    Poll::Ready(Some(Err(e)))
                 },
                 Poll::Pending => Poll::Pending
             }
@@ -201,21 +286,21 @@ impl Future for NextChunk {
 
     #[inline]
     fn poll(mut self: std::pin::Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Self::Output> {
-        if let Poll::Ready(result) = self.future.try_poll_unpin(cx)? {
-            return Poll::Ready(ChunkResult::try_from(&result))
+        return match self.future.try_poll_unpin(cx) {
+            Poll::Ready(Ok(result)) => Poll::Ready(ChunkResult::try_from(&result)),
+            Poll::Ready(Err(e)) => Poll::Ready(Err(read_error(e))),
+            Poll::Pending => Poll::Pending
         }
-
-        return Poll::Pending
     }
 }
 
 impl TryFrom<&JsValue> for ChunkResult {
-    type Error = JsValue;
+    type Error = Error;
 
     fn try_from(result: &JsValue) -> Result<Self> {
         let done = match js_sys::Reflect::get(result, &JsValue::from_str("done"))?.as_bool() {
             Some(x) => x,
-            None => return Err(JsValue::from_str("`done` field not found"))
+            None => return Err(Error::new(ErrorKind::Io, "`done` field not found"))
         };
 
         let value = js_sys::Reflect::get(result, &JsValue::from_str("value"))?;
@@ -225,7 +310,7 @@ impl TryFrom<&JsValue> for ChunkResult {
 }
 
 impl TryFrom<JsValue> for ChunkResult {
-    type Error = JsValue;
+    type Error = Error;
 
     #[inline]
     fn try_from(result: JsValue) -> Result<Self> {
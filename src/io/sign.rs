@@ -0,0 +1,238 @@
+use std::{fmt::Write, time::Duration};
+use chrono::{DateTime, Utc};
+use wasm_bindgen::JsCast;
+use crate::{Result, Error, ErrorKind, crypto::{sha256, hmac_sha256}, encoding::encode_hex};
+use super::{Request, Method};
+
+const ALGORITHM: &str = "AWS4-HMAC-SHA256";
+const UNSIGNED_PAYLOAD: &str = "UNSIGNED-PAYLOAD";
+
+/// AWS credentials used to sign requests
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Credentials {
+    pub access_key_id: String,
+    pub secret_access_key: String,
+    /// Session token of temporary credentials
+    pub session_token: Option<String>
+}
+
+impl Credentials {
+    /// Creates new long-term credentials
+    #[inline]
+    pub fn new (access_key_id: impl Into<String>, secret_access_key: impl Into<String>) -> Self {
+        return Self { access_key_id: access_key_id.into(), secret_access_key: secret_access_key.into(), session_token: None }
+    }
+
+    /// Creates new temporary credentials, with the specified session token
+    #[inline]
+    pub fn with_session_token (access_key_id: impl Into<String>, secret_access_key: impl Into<String>, session_token: impl Into<String>) -> Self {
+        return Self { access_key_id: access_key_id.into(), secret_access_key: secret_access_key.into(), session_token: Some(session_token.into()) }
+    }
+}
+
+/// Payload of a request, as hashed into it's signature
+#[derive(Debug, Clone, Copy, Default)]
+pub enum Payload<'a> {
+    /// The payload isn't included in the signature (only supported by S3)
+    #[default]
+    Unsigned,
+    /// The payload's SHA-256 hash is included in the signature
+    Bytes (&'a [u8])
+}
+
+/// Signer of requests with [AWS Signature Version 4](https://docs.aws.amazon.com/IAM/latest/UserGuide/reference_aws-signing.html).
+///
+/// Paths are encoded only once, as expected by S3.
+/// ```ignore
+/// let signer = SigV4::new(credentials, "eu-west-1", "s3");
+/// let mut req = Request::new();
+/// req.method(Method::Put).target("https://bucket.s3.eu-west-1.amazonaws.com/photo.png").body(data.as_slice());
+/// signer.sign(&mut req, Payload::Unsigned).await?;
+/// req.send().await?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct SigV4 {
+    credentials: Credentials,
+    region: String,
+    service: String
+}
+
+impl SigV4 {
+    /// Creates a new signer for the specified region and service
+    #[inline]
+    pub fn new (credentials: Credentials, region: impl Into<String>, service: impl Into<String>) -> Self {
+        return Self { credentials, region: region.into(), service: service.into() }
+    }
+
+    /// Returns the credentials of the signer
+    #[inline]
+    pub fn credentials (&self) -> &Credentials {
+        return &self.credentials
+    }
+
+    /// Signs the request, adding the `Authorization`, `X-Amz-Date`, `X-Amz-Content-Sha256` and (if required) `X-Amz-Security-Token` headers.
+    ///
+    /// The request's url must have been set with [`target`](Request::target), and no signed header may be modified after signing.
+    pub async fn sign (&self, req: &mut Request, payload: Payload<'_>) -> Result<()> {
+        return self.sign_at(req, payload, Utc::now()).await
+    }
+
+    /// Signs the request as if it was sent at the specified time
+    pub async fn sign_at (&self, req: &mut Request, payload: Payload<'_>, time: DateTime<Utc>) -> Result<()> {
+        let url = match req.url() {
+            Some(url) => web_sys::Url::new(url)?,
+            None => return Err(Error::new(ErrorKind::Io, "the request's url must be set before signing it"))
+        };
+
+        let amz_date = time.format("%Y%m%dT%H%M%SZ").to_string();
+        let payload_hash = match payload {
+            Payload::Unsigned => UNSIGNED_PAYLOAD.to_string(),
+            Payload::Bytes(bytes) => encode_hex(&sha256(bytes).await?)
+        };
+
+        req.header("X-Amz-Date", &amz_date)?.header("X-Amz-Content-Sha256", &payload_hash)?;
+        if let Some(ref token) = self.credentials.session_token {
+            req.header("X-Amz-Security-Token", token)?;
+        }
+
+        let mut headers = vec![
+            ("host".to_string(), url.host()),
+            ("x-amz-content-sha256".to_string(), payload_hash.clone()),
+            ("x-amz-date".to_string(), amz_date.clone())
+        ];
+        if let Some(ref token) = self.credentials.session_token {
+            headers.push(("x-amz-security-token".to_string(), token.clone()));
+        }
+        for name in ["content-type", "content-md5", "range"] {
+            if let Some(value) = req.get_header(name) {
+                headers.push((name.to_string(), value.trim().to_string()));
+            }
+        }
+        headers.sort();
+
+        let signed_headers = headers.iter().map(|(k, _)| k.as_str()).collect::<Vec<_>>().join(";");
+        let canonical = canonical_request(req.get_method(), &url, &canonical_query(&url, &[])?, &headers, &signed_headers, &payload_hash);
+        let (scope, signature) = self.signature(&time, &canonical).await?;
+
+        let authorization = format!(
+            "{ALGORITHM} Credential={}/{scope}, SignedHeaders={signed_headers}, Signature={signature}",
+            self.credentials.access_key_id
+        );
+        req.header("Authorization", &authorization)?;
+        return Ok(())
+    }
+
+    /// Returns a presigned url, which grants access to the resource with the specified method until it expires.
+    ///
+    /// Presigned urls don't require any headers, so they can be used directly by the browser (for example, as the target of a form or an `<img>`)
+    pub async fn presign (&self, method: &Method, url: &str, expires: Duration) -> Result<String> {
+        return self.presign_at(method, url, expires, Utc::now()).await
+    }
+
+    /// Returns a presigned url, as if it was signed at the specified time
+    pub async fn presign_at (&self, method: &Method, url: &str, expires: Duration, time: DateTime<Utc>) -> Result<String> {
+        let url = web_sys::Url::new(url)?;
+        let amz_date = time.format("%Y%m%dT%H%M%SZ").to_string();
+        let scope = self.scope(&time);
+
+        let mut params = vec![
+            ("X-Amz-Algorithm".to_string(), ALGORITHM.to_string()),
+            ("X-Amz-Credential".to_string(), format!("{}/{scope}", self.credentials.access_key_id)),
+            ("X-Amz-Date".to_string(), amz_date),
+            ("X-Amz-Expires".to_string(), expires.as_secs().to_string()),
+            ("X-Amz-SignedHeaders".to_string(), "host".to_string())
+        ];
+        if let Some(ref token) = self.credentials.session_token {
+            params.push(("X-Amz-Security-Token".to_string(), token.clone()));
+        }
+
+        let query = canonical_query(&url, &params)?;
+        let headers = [("host".to_string(), url.host())];
+        let canonical = canonical_request(method, &url, &query, &headers, "host", UNSIGNED_PAYLOAD);
+        let (_, signature) = self.signature(&time, &canonical).await?;
+
+        let mut result = format!("{}{}?{query}&X-Amz-Signature={signature}", url.origin(), url.pathname());
+        let hash = url.hash();
+        if !hash.is_empty() {
+            result.push_str(&hash);
+        }
+        return Ok(result)
+    }
+
+    #[inline]
+    fn scope (&self, time: &DateTime<Utc>) -> String {
+        return format!("{}/{}/{}/aws4_request", time.format("%Y%m%d"), self.region, self.service)
+    }
+
+    /// Returns the credential scope and the hexadecimal signature of the canonical request
+    async fn signature (&self, time: &DateTime<Utc>, canonical: &str) -> Result<(String, String)> {
+        let scope = self.scope(time);
+        let string_to_sign = format!(
+            "{ALGORITHM}\n{}\n{scope}\n{}",
+            time.format("%Y%m%dT%H%M%SZ"),
+            encode_hex(&sha256(canonical.as_bytes()).await?)
+        );
+
+        let key = format!("AWS4{}", self.credentials.secret_access_key);
+        let key = hmac_sha256(key.as_bytes(), time.format("%Y%m%d").to_string().as_bytes()).await?;
+        let key = hmac_sha256(&key, self.region.as_bytes()).await?;
+        let key = hmac_sha256(&key, self.service.as_bytes()).await?;
+        let key = hmac_sha256(&key, b"aws4_request").await?;
+
+        let signature = hmac_sha256(&key, string_to_sign.as_bytes()).await?;
+        return Ok((scope, encode_hex(&signature)))
+    }
+}
+
+fn canonical_request (method: &Method, url: &web_sys::Url, query: &str, headers: &[(String, String)], signed_headers: &str, payload_hash: &str) -> String {
+    let mut canonical = format!("{}\n{}\n{query}\n", method.as_str(), canonical_path(&url.pathname()));
+    for (key, value) in headers {
+        let _ = writeln!(canonical, "{key}:{value}");
+    }
+    let _ = write!(canonical, "\n{signed_headers}\n{payload_hash}");
+    return canonical
+}
+
+/// Returns the path with each segment decoded and encoded again, following AWS' rules
+fn canonical_path (path: &str) -> String {
+    if path.is_empty() {
+        return "/".to_string()
+    }
+
+    return path.split('/')
+        .map(|segment| {
+            let decoded = js_sys::decode_uri_component(segment).ok().and_then(|x| x.as_string()).unwrap_or_else(|| segment.to_string());
+            uri_encode(&decoded)
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Returns the url's query parameters, alongside the extra ones, encoded and sorted
+fn canonical_query (url: &web_sys::Url, extra: &[(String, String)]) -> Result<String> {
+    let mut params = extra.iter().map(|(k, v)| (uri_encode(k), uri_encode(v))).collect::<Vec<_>>();
+
+    if let Some(entries) = js_sys::try_iter(url.search_params().as_ref())? {
+        for entry in entries {
+            let entry = entry?.unchecked_into::<js_sys::Array>();
+            let key = entry.get(0).as_string().unwrap_or_default();
+            let value = entry.get(1).as_string().unwrap_or_default();
+            params.push((uri_encode(&key), uri_encode(&value)));
+        }
+    }
+
+    params.sort();
+    return Ok(params.into_iter().map(|(k, v)| format!("{k}={v}")).collect::<Vec<_>>().join("&"))
+}
+
+/// Percent-encodes every byte except the unreserved characters of RFC 3986
+fn uri_encode (value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => result.push(byte as char),
+            _ => { let _ = write!(result, "%{byte:02X}"); }
+        }
+    }
+    return result
+}
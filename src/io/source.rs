@@ -0,0 +1,128 @@
+use std::{rc::Rc, cell::{Cell, RefCell}};
+use futures::{AsyncRead, AsyncReadExt};
+use js_sys::{Uint8Array, Array};
+use wasm_bindgen::{JsCast, JsValue, prelude::{wasm_bindgen, Closure}};
+use crate::{Result, diagnostics::Tracked};
+use super::JsReadStream;
+
+const DEFAULT_CHUNK_SIZE: usize = 8 * 1024;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_name = ReadableStream)]
+    type RawReadableStream;
+    type SourceController;
+
+    #[wasm_bindgen(constructor, catch)]
+    fn new (source: &js_sys::Object) -> ::core::result::Result<RawReadableStream, JsValue>;
+
+    #[wasm_bindgen(method)]
+    fn enqueue (this: &SourceController, chunk: &JsValue);
+    #[wasm_bindgen(method)]
+    fn close (this: &SourceController);
+    #[wasm_bindgen(method)]
+    fn error (this: &SourceController, e: &JsValue);
+}
+
+impl JsReadStream<'static, Uint8Array> {
+    /// Creates a new [`JsReadStream`] that yields the specified bytes
+    #[inline]
+    pub fn from_bytes (bytes: Vec<u8>) -> Result<Self> {
+        let parts = Array::of1(&Uint8Array::from(bytes.as_slice()));
+        let blob = web_sys::Blob::new_with_u8_array_sequence(&parts)?;
+        return Self::new(blob.stream())
+    }
+
+    /// Creates a new [`JsReadStream`] that yields the contents of the [`AsyncRead`], in chunks of up to 8 KiB.
+    #[inline]
+    pub fn from_async_read<R: 'static + Unpin + AsyncRead> (read: R) -> Result<Self> {
+        return Self::from_async_read_with_capacity(DEFAULT_CHUNK_SIZE, read)
+    }
+
+    /// Creates a new [`JsReadStream`] that yields the contents of the [`AsyncRead`], in chunks of up to `capacity` bytes.
+    pub fn from_async_read_with_capacity<R: 'static + Unpin + AsyncRead> (capacity: usize, read: R) -> Result<Self> {
+        let read = Rc::new(RefCell::new(Some(read)));
+        // the registration is droped when the stream finishes or is canceled
+        let tracked = Rc::new(Cell::new(Some(Tracked::new("io::from_async_read"))));
+
+        let my_read = read.clone();
+        let my_tracked = tracked.clone();
+        let pull = Closure::<dyn FnMut(SourceController) -> js_sys::Promise>::new(move |con: SourceController| {
+            let read = my_read.clone();
+            let tracked = my_tracked.clone();
+            return wasm_bindgen_futures::future_to_promise(async move {
+                // pulls are never called concurrently, so the reader will always be available
+                let mut reader = match read.borrow_mut().take() {
+                    Some(reader) => reader,
+                    None => return Ok(JsValue::UNDEFINED)
+                };
+
+                let mut buf = vec![0; capacity];
+                match reader.read(&mut buf).await {
+                    Ok(0) => {
+                        tracked.take();
+                        con.close()
+                    },
+                    Ok(len) => {
+                        con.enqueue(&Uint8Array::from(&buf[..len]));
+                        // if the stream was canceled while reading, the reader is droped instead
+                        if let Some(registration) = tracked.take() {
+                            *read.borrow_mut() = Some(reader);
+                            tracked.set(Some(registration));
+                        }
+                    },
+                    Err(e) => {
+                        tracked.take();
+                        con.error(&crate::Error::from(e).into())
+                    }
+                }
+
+                return Ok(JsValue::UNDEFINED)
+            })
+        });
+
+        let cancel = Closure::<dyn FnMut(JsValue)>::new(move |_: JsValue| {
+            read.borrow_mut().take();
+            tracked.take();
+        });
+
+        return Self::from_source(pull.into_js_value(), cancel.into_js_value())
+    }
+}
+
+impl<T: 'static + JsCast> JsReadStream<'static, T> {
+    /// Creates a new [`JsReadStream`] that yields the items of the iterator, pulling them lazily.
+    pub fn from_iter<I: IntoIterator<Item = T>> (iter: I) -> Result<Self> where I::IntoIter: 'static {
+        // the iterator and it's registration are droped when the stream finishes or is canceled
+        let state = Rc::new(RefCell::new(Some((iter.into_iter(), Tracked::new("io::from_iter")))));
+
+        let my_state = state.clone();
+        let pull = Closure::<dyn FnMut(SourceController)>::new(move |con: SourceController| {
+            let mut state = my_state.borrow_mut();
+            let Some((ref mut iter, _)) = *state else { return };
+
+            match iter.next() {
+                Some(chunk) => con.enqueue(chunk.as_ref()),
+                None => {
+                    *state = None;
+                    con.close()
+                }
+            }
+        });
+
+        let cancel = Closure::<dyn FnMut(JsValue)>::new(move |_: JsValue| {
+            state.borrow_mut().take();
+        });
+
+        return Self::from_source(pull.into_js_value(), cancel.into_js_value())
+    }
+
+    fn from_source (pull: JsValue, cancel: JsValue) -> Result<Self> {
+        let source = js_sys::Object::new();
+        js_sys::Reflect::set(&source, &JsValue::from_str("pull"), &pull)?;
+        js_sys::Reflect::set(&source, &JsValue::from_str("cancel"), &cancel)?;
+
+        let stream = RawReadableStream::new(&source)?;
+        return Self::new(stream.unchecked_into::<web_sys::ReadableStream>())
+    }
+}
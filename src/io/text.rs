@@ -0,0 +1,160 @@
+use std::task::Poll;
+use futures::{Stream, StreamExt};
+use js_sys::{Uint8Array, JsString};
+use wasm_bindgen::{JsValue, prelude::wasm_bindgen};
+use web_sys::{TextDecoder, TextDecodeOptions};
+use crate::Result;
+use super::{JsReadStream, ReadStream};
+
+#[wasm_bindgen]
+extern "C" {
+    #[derive(Debug, Clone)]
+    type TextDecoderStream;
+
+    #[wasm_bindgen(constructor, catch)]
+    fn new (label: &str) -> ::core::result::Result<TextDecoderStream, JsValue>;
+    #[wasm_bindgen(method, getter)]
+    fn readable (this: &TextDecoderStream) -> web_sys::ReadableStream;
+    #[wasm_bindgen(method, getter)]
+    fn writable (this: &TextDecoderStream) -> web_sys::WritableStream;
+}
+
+impl<'a> JsReadStream<'a, Uint8Array> {
+    /// Turns the byte stream into a [`Stream`] of decoded strings, with the specified encoding (`"utf-8"`, `"utf-16le"`, ...).
+    ///
+    /// Multi-byte characters split between chunks are decoded correctly.
+    ///
+    /// # Compatibility
+    /// If [`TextDecoderStream`](https://developer.mozilla.org/en-US/docs/Web/API/TextDecoderStream) isn't available,
+    /// the chunks are decoded manually with a [`TextDecoder`].
+    pub fn text (mut self, encoding: &str) -> Result<TextStream<'a>> {
+        let supported = js_sys::Reflect::has(&js_sys::global(), &JsValue::from_str("TextDecoderStream")).unwrap_or_default();
+
+        if supported {
+            if let Some(reader) = self.reader.take() {
+                reader.release_lock();
+            }
+
+            let decoder = TextDecoderStream::new(encoding)?;
+            let _ = self._stream.pipe_to(&decoder.writable());
+            let stream = JsReadStream::<JsString>::new(decoder.readable())?.into_stream();
+            return Ok(TextStream { inner: TextInner::Native { stream, _src: self } })
+        }
+
+        let decoder = TextDecoder::new_with_label(encoding)?;
+        return Ok(TextStream { inner: TextInner::Fallback { stream: self.into_stream(), decoder, done: false } })
+    }
+}
+
+enum TextInner<'a> {
+    Native {
+        stream: ReadStream<'a, JsString>,
+        _src: JsReadStream<'a, Uint8Array>
+    },
+    Fallback {
+        stream: ReadStream<'a, Uint8Array>,
+        decoder: TextDecoder,
+        done: bool
+    }
+}
+
+/// A [`Stream`] of strings decoded from a byte stream.
+///
+/// See [`text`](JsReadStream::text)
+pub struct TextStream<'a> {
+    inner: TextInner<'a>
+}
+
+impl<'a> TextStream<'a> {
+    /// Returns a [`Stream`] over the lines of the text
+    #[inline]
+    pub fn lines (self) -> Lines<Self> {
+        return Lines::new(self)
+    }
+}
+
+impl Stream for TextStream<'_> {
+    type Item = Result<String>;
+
+    fn poll_next(mut self: std::pin::Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Option<Self::Item>> {
+        match self.inner {
+            TextInner::Native { ref mut stream, .. } => {
+                return stream.poll_next_unpin(cx).map(|x| x.map(|x| x.map(String::from)))
+            },
+
+            TextInner::Fallback { ref mut stream, ref decoder, ref mut done } => loop {
+                if *done { return Poll::Ready(None) }
+                match stream.poll_next_unpin(cx) {
+                    Poll::Ready(Some(Ok(chunk))) => {
+                        let mut ops = TextDecodeOptions::new();
+                        ops.stream(true);
+
+                        let text = decoder.decode_with_buffer_source_and_options(&chunk, &ops);
+                        match text {
+                            Ok(text) if text.is_empty() => continue,
+                            Ok(text) => return Poll::Ready(Some(Ok(text))),
+                            Err(e) => return Poll::Ready(Some(Err(e.into())))
+                        }
+                    },
+
+                    Poll::Ready(Some(Err(e))) => return Poll::Ready(Some(Err(e))),
+
+                    Poll::Ready(None) => {
+                        *done = true;
+                        return match decoder.decode() {
+                            Ok(text) if text.is_empty() => Poll::Ready(None),
+                            Ok(text) => Poll::Ready(Some(Ok(text))),
+                            Err(e) => Poll::Ready(Some(Err(e.into())))
+                        }
+                    },
+
+                    Poll::Pending => return Poll::Pending
+                }
+            }
+        }
+    }
+}
+
+/// A [`Stream`] over the lines of a stream of strings.
+///
+/// Lines are split on `\n`, with trailing `\r` removed. The last line is yielded even if it doesn't end with a newline.
+pub struct Lines<S> {
+    inner: S,
+    buffer: String,
+    done: bool
+}
+
+impl<S> Lines<S> {
+    /// Creates a new line adapter over the stream
+    #[inline]
+    pub fn new (inner: S) -> Self {
+        return Self { inner, buffer: String::new(), done: false }
+    }
+}
+
+impl<S: Unpin + Stream<Item = Result<String>>> Stream for Lines<S> {
+    type Item = Result<String>;
+
+    fn poll_next(mut self: std::pin::Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            if let Some(idx) = self.buffer.find('\n') {
+                let mut line = self.buffer.drain(..=idx).collect::<String>();
+                line.pop();
+                if line.ends_with('\r') { line.pop(); }
+                return Poll::Ready(Some(Ok(line)))
+            }
+
+            if self.done {
+                if self.buffer.is_empty() { return Poll::Ready(None) }
+                return Poll::Ready(Some(Ok(core::mem::take(&mut self.buffer))))
+            }
+
+            match self.inner.poll_next_unpin(cx) {
+                Poll::Ready(Some(Ok(text))) => self.buffer.push_str(&text),
+                Poll::Ready(Some(Err(e))) => return Poll::Ready(Some(Err(e))),
+                Poll::Ready(None) => self.done = true,
+                Poll::Pending => return Poll::Pending
+            }
+        }
+    }
+}
@@ -0,0 +1,321 @@
+use std::{time::Duration, future::Future};
+use futures::{StreamExt, TryStreamExt};
+use crate::{fs::File, time::{Backoff, sleep, retry_if}, encoding::encode_base64, sync::RateLimiter};
+use super::{Request, Response, Method, FetchError};
+
+const TUS_VERSION: &str = "1.0.0";
+
+/// Progress of an [`Upload`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UploadProgress {
+    /// Number of bytes uploaded so far
+    pub uploaded: u64,
+    /// Total number of bytes to upload
+    pub total: u64,
+    /// Url of the upload on the server, if the tus protocol is being used.
+    ///
+    /// This url can be used to [`resume`](Upload::resume) the upload if it fails.
+    pub location: Option<String>
+}
+
+impl UploadProgress {
+    /// Returns the uploaded fraction, between 0 and 1
+    #[inline]
+    pub fn fraction (&self) -> f64 {
+        if self.total == 0 { return 1. }
+        return self.uploaded as f64 / self.total as f64
+    }
+}
+
+/// A builder that uploads a [`File`] in chunks.
+///
+/// By default, every chunk is sent as an HTTP PUT request with it's `Content-Range` header set.
+/// If the [tus](https://tus.io/protocols/resumable-upload) protocol is enabled, the upload is instead created with a POST request
+/// and it's chunks are sent sequentially as PATCH requests, which allows for interrupted uploads to be resumed.
+pub struct Upload<'a> {
+    url: String,
+    method: Method,
+    headers: Vec<(String, String)>,
+    chunk_size: u64,
+    concurrency: usize,
+    backoff: Backoff,
+    tus: bool,
+    resume: Option<String>,
+    metadata: Vec<(String, String)>,
+    rate_limiter: Option<RateLimiter>,
+    on_progress: Option<Box<dyn 'a + FnMut(&UploadProgress)>>
+}
+
+impl<'a> Upload<'a> {
+    /// Default size of the uploaded chunks (5 MiB)
+    pub const DEFAULT_CHUNK_SIZE: u64 = 5 * 1024 * 1024;
+
+    /// Creates a new upload targeting the specified url
+    #[inline]
+    pub fn new (url: impl Into<String>) -> Self {
+        return Self {
+            url: url.into(),
+            method: Method::Put,
+            headers: Vec::new(),
+            chunk_size: Self::DEFAULT_CHUNK_SIZE,
+            concurrency: 1,
+            backoff: Backoff::new(Duration::from_millis(500)).max_retries(3),
+            tus: false,
+            resume: None,
+            metadata: Vec::new(),
+            rate_limiter: None,
+            on_progress: None
+        }
+    }
+
+    /// Specifies the HTTP method the chunks will be sent as. By default, chunks are sent as PUT requests.
+    ///
+    /// This option is ignored when the tus protocol is enabled.
+    #[inline]
+    pub fn method (&mut self, method: Method) -> &mut Self {
+        self.method = method;
+        self
+    }
+
+    /// Adds the specified header to every request of the upload
+    #[inline]
+    pub fn header (&mut self, key: impl Into<String>, value: impl Into<String>) -> &mut Self {
+        self.headers.push((key.into(), value.into()));
+        self
+    }
+
+    /// Sets the size, in bytes, of the uploaded chunks.
+    ///
+    /// # Panics
+    /// This method panics if `size` is zero.
+    #[inline]
+    pub fn chunk_size (&mut self, size: u64) -> &mut Self {
+        assert!(size > 0, "chunk size must be greater than zero");
+        self.chunk_size = size;
+        self
+    }
+
+    /// Sets the maximum number of chunks uploaded at the same time. By default, chunks are uploaded sequentially.
+    ///
+    /// Uploads that use the tus protocol are always sequential.
+    #[inline]
+    pub fn concurrency (&mut self, concurrency: usize) -> &mut Self {
+        self.concurrency = usize::max(concurrency, 1);
+        self
+    }
+
+    /// Sets the policy used to retry chunks after a network error, a timeout or a server error (5xx or 429).
+    /// The upload fails once the policy runs out of retries.
+    ///
+    /// By default, chunks are retried 3 times with an exponential backoff starting at 500 milliseconds.
+    #[inline]
+    pub fn retries (&mut self, policy: Backoff) -> &mut Self {
+        self.backoff = policy;
+        self
+    }
+
+    /// Enables or disables the tus resumable-upload protocol
+    #[inline]
+    pub fn tus (&mut self, tus: bool) -> &mut Self {
+        self.tus = tus;
+        self
+    }
+
+    /// Resumes a previously created tus upload located at `location`, instead of creating a new one.
+    /// Enables the tus protocol.
+    #[inline]
+    pub fn resume (&mut self, location: impl Into<String>) -> &mut Self {
+        self.tus = true;
+        self.resume = Some(location.into());
+        self
+    }
+
+    /// Adds a key-value pair to the `Upload-Metadata` header sent when creating a tus upload
+    #[inline]
+    pub fn metadata (&mut self, key: impl Into<String>, value: impl Into<String>) -> &mut Self {
+        self.metadata.push((key.into(), value.into()));
+        self
+    }
+
+    /// Makes every request of the upload acquire a token from the rate limiter before it's sent
+    #[inline]
+    pub fn rate_limit (&mut self, limiter: RateLimiter) -> &mut Self {
+        self.rate_limiter = Some(limiter);
+        self
+    }
+
+    /// Sets a callback that's called every time a chunk finishes uploading
+    #[inline]
+    pub fn on_progress<F: 'a + FnMut(&UploadProgress)> (&mut self, f: F) -> &mut Self {
+        self.on_progress = Some(Box::new(f));
+        self
+    }
+
+    /// Uploads the file.
+    ///
+    /// When the tus protocol is enabled, the url of the upload on the server is returned. Otherwise, `None` is returned.
+    pub async fn send (&mut self, file: &mut File) -> ::core::result::Result<Option<String>, FetchError> {
+        let total = file.metadata().await?.len();
+        if self.tus {
+            return self.send_tus(file, total).await.map(Some)
+        }
+
+        let mut chunks = Vec::with_capacity(((total + self.chunk_size - 1) / self.chunk_size) as usize);
+        let mut start = 0;
+        while start < total {
+            let end = u64::min(start + self.chunk_size, total);
+            chunks.push((start, end, file.slice(start..end).await?));
+            start = end;
+        }
+
+        let mut on_progress = self.on_progress.take();
+        let this = &*self;
+        let mut uploads = futures::stream::iter(chunks)
+            .map(|(start, end, blob)| async move {
+                let blob = &blob;
+                this.with_retries(move || async move {
+                    let mut req = this.request(this.method.clone())?;
+                    req.header("Content-Range", &format!("bytes {start}-{}/{total}", end - 1))?;
+                    req.body(blob);
+                    this.fetch(req, &this.url).await
+                }).await?;
+                Ok::<_, FetchError>(end - start)
+            })
+            .buffer_unordered(self.concurrency);
+
+        let mut progress = UploadProgress { uploaded: 0, total, location: None };
+        let result = loop {
+            match uploads.try_next().await {
+                Ok(Some(len)) => {
+                    progress.uploaded += len;
+                    if let Some(ref mut f) = on_progress { f(&progress) }
+                },
+                Ok(None) => break Ok(None),
+                Err(e) => break Err(e)
+            }
+        };
+
+        drop(uploads);
+        self.on_progress = on_progress;
+        return result
+    }
+
+    async fn send_tus (&mut self, file: &mut File, total: u64) -> ::core::result::Result<String, FetchError> {
+        let (location, mut offset) = match self.resume {
+            Some(ref location) => (location.clone(), self.tus_offset(location).await?),
+            None => (self.tus_create(total).await?, 0)
+        };
+
+        let mut progress = UploadProgress { uploaded: offset, total, location: Some(location.clone()) };
+        let mut backoff = self.backoff.clone();
+
+        while offset < total {
+            let end = u64::min(offset + self.chunk_size, total);
+            let blob = file.slice(offset..end).await?;
+
+            let mut req = self.request(Method::Patch)?;
+            req.header("Upload-Offset", &offset.to_string())?
+                .header("Content-Type", "application/offset+octet-stream")?
+                .body(&blob);
+
+            let result = match self.fetch(req, &location).await {
+                Ok(resp) => resp.error_for_status().await,
+                Err(e) => Err(e)
+            };
+
+            match result {
+                Ok(resp) => {
+                    offset = parse_offset(&resp).unwrap_or(end);
+                    backoff.reset();
+                    progress.uploaded = offset;
+                    if let Some(ref mut f) = self.on_progress { f(&progress) }
+                    continue
+                },
+                Err(e) => {
+                    if !is_retryable(&e) {
+                        return Err(e)
+                    }
+
+                    match backoff.next().map(sleep) {
+                        Some(Ok(sleep)) => sleep.await,
+                        _ => return Err(e)
+                    }
+                }
+            }
+
+            // after a failed chunk, the server may have stored part of it
+            offset = self.tus_offset(&location).await?;
+        }
+
+        return Ok(location)
+    }
+
+    async fn tus_create (&self, total: u64) -> ::core::result::Result<String, FetchError> {
+        let mut metadata = Vec::with_capacity(self.metadata.len());
+        for (key, value) in self.metadata.iter() {
+            metadata.push(format!("{key} {}", encode_base64(value.as_bytes())?));
+        }
+
+        let metadata = &metadata.join(",");
+        let resp = self.with_retries(move || async move {
+            let mut req = self.request(Method::Post)?;
+            req.header("Upload-Length", &total.to_string())?;
+            if !metadata.is_empty() {
+                req.header("Upload-Metadata", metadata)?;
+            }
+            self.fetch(req, &self.url).await
+        }).await?;
+        let location = match resp.header("Location") {
+            Some(location) => location,
+            None => return Err(FetchError::Decode("tus creation response has no `Location` header".to_string()))
+        };
+
+        return Ok(web_sys::Url::new_with_base(&location, &self.url)?.href())
+    }
+
+    async fn tus_offset (&self, location: &str) -> ::core::result::Result<u64, FetchError> {
+        let resp = self.with_retries(move || async move {
+            self.fetch(self.request(Method::Head)?, location).await
+        }).await?;
+
+        return parse_offset(&resp).ok_or_else(|| FetchError::Decode("invalid `Upload-Offset` header".to_string()))
+    }
+
+    fn request (&self, method: Method) -> ::core::result::Result<Request, FetchError> {
+        let mut req = Request::new();
+        req.method(method).headers(self.headers.iter().map(|(k, v)| (k, v)))?;
+        if self.tus {
+            req.header("Tus-Resumable", TUS_VERSION)?;
+        }
+        return Ok(req)
+    }
+
+    async fn fetch (&self, req: Request, url: &str) -> ::core::result::Result<Response, FetchError> {
+        if let Some(ref limiter) = self.rate_limiter {
+            limiter.acquire().await?;
+        }
+        return req.fetch(url).await
+    }
+
+    async fn with_retries<F: FnMut() -> Fut, Fut: Future<Output = ::core::result::Result<Response, FetchError>>> (&self, mut f: F) -> ::core::result::Result<Response, FetchError> {
+        return retry_if(self.backoff.clone(), is_retryable, move || {
+            let fut = f();
+            async move { fut.await?.error_for_status().await }
+        }).await
+    }
+}
+
+/// Returns `true` for errors that may not happen again (network errors, timeouts and server errors)
+#[inline]
+fn is_retryable (e: &FetchError) -> bool {
+    return match e {
+        FetchError::Network(_) | FetchError::Timeout => true,
+        FetchError::Status { code, .. } => *code >= 500 || *code == 429,
+        _ => false
+    }
+}
+
+#[inline]
+fn parse_offset (resp: &Response) -> Option<u64> {
+    return resp.header("Upload-Offset")?.trim().parse().ok()
+}
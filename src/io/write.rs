@@ -1,6 +1,5 @@
-use std::{marker::PhantomData, task::Poll};
+use std::{marker::PhantomData, task::Poll, collections::VecDeque};
 use docfg::docfg;
-use elor::Either;
 use futures::{Future, FutureExt, Sink};
 use js_sys::{Uint8Array, JsString};
 use wasm_bindgen::{JsValue};
@@ -83,10 +82,27 @@ impl<'a, T: AsRef<JsValue>> JsWriteStream<'a, T> {
         return Ok(())
     }
 
+    /// Returns the amount of data that can be written before the stream's internal queue is full,
+    /// or `None` if the stream can't be written to.
+    /// 
+    /// A negative value indicates that backpressure is being applied.
+    #[inline]
+    pub fn desired_size (&mut self) -> Result<Option<f64>> {
+        return Ok(self.get_writer()?.desired_size()?)
+    }
+
+    /// Waits until the desired size of the stream's internal queue becomes positive, signaling that backpressure has ceased.
+    #[inline]
+    pub async fn ready (&mut self) -> Result<()> {
+        let _ = JsFuture::from(self.get_writer()?.ready()).await?;
+        return Ok(())
+    }
+
     pub fn into_sink (self) -> WriteSink<'a, T> where T: Unpin {
         return WriteSink {
             inner: self,
-            flush: Either::Left(js_sys::Array::new()),
+            pending: VecDeque::new(),
+            limit: usize::MAX,
             close: None
         }
     }
@@ -116,7 +132,7 @@ impl<'a> JsWriteStream<'a, Uint8Array> {
                     for i in 0..chunk.length() {
                         let byte = chunk.get_index(i);
                         if let Err(e) = w.write_all(core::slice::from_ref(&byte)).await {
-                            return Err(JsValue::from_str(&e.to_string()));
+                            return Err(e.into());
                         }
                     }
                     return Ok(())
@@ -133,6 +149,16 @@ impl<'a, T: TypedArrayExt> JsWriteStream<'a, T> {
         let chunk = unsafe { T::view(buf) };
         return self.write(&chunk).await
     }
+
+    /// Writes a view of every slice into the stream, in order.
+    ///
+    /// Every write is awaited before the next one is queued, so that the sink never holds more than one view into WASM memory.
+    pub async fn write_iter<'b, I: IntoIterator<Item = &'b [T::Element]>> (&mut self, iter: I) -> Result<()> where T::Element: 'b {
+        for buf in iter {
+            self.write_slice(buf).await?;
+        }
+        return Ok(())
+    }
 }
 
 impl<'a> JsWriteStream<'a, JsString> {
@@ -160,40 +186,65 @@ impl<T> Drop for JsWriteStream<'_, T> {
     }
 }
 
-/// The [`Sink`](futures::Sink) version of [`JsWriteStream`]
+/// The [`Sink`](futures::Sink) version of [`JsWriteStream`].
+///
+/// Chunks are written in the order they are sent, and their results are reported in that same order,
+/// regardless of the order in which the JavaScript write promises resolve.
 pub struct WriteSink<'a, T> {
     inner: JsWriteStream<'a, T>,
-    flush: Either<js_sys::Array, JsFuture>,
+    /// Writes in flight, in the order they were started
+    pending: VecDeque<JsFuture>,
+    limit: usize,
     close: Option<JsFuture>
 }
 
 impl<'a, T: Unpin> WriteSink<'a, T> {
-    fn poll_ready_inner (mut self: std::pin::Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Result<()>> {
-        if let Either::Right(ref mut rhs) = self.flush {
-            if rhs.poll_unpin(cx)?.is_pending() {
-                return Poll::Pending
+    /// Caps the number of writes in flight to `n`, so that the sink isn't ready until the oldest write completes.
+    /// By default, the number of writes in flight is unbounded.
+    #[inline]
+    pub fn buffered (mut self, n: usize) -> Self {
+        self.limit = n.max(1);
+        return self
+    }
+
+    /// Returns the number of writes in flight
+    #[inline]
+    pub fn in_flight (&self) -> usize {
+        return self.pending.len()
+    }
+
+    /// Completes writes in order, until at most `len` remain in flight
+    fn poll_pending (&mut self, cx: &mut std::task::Context<'_>, len: usize) -> Poll<Result<()>> {
+        while self.pending.len() > len {
+            let front = unsafe { self.pending.front_mut().unwrap_unchecked() };
+            match front.poll_unpin(cx) {
+                Poll::Ready(result) => {
+                    self.pending.pop_front();
+                    result?;
+                },
+                Poll::Pending => return Poll::Pending
             }
-            self.flush = Either::Left(js_sys::Array::new())
         }
         return Poll::Ready(Ok(()))
     }
 
-    fn poll_flush_inner (mut self: std::pin::Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Result<()>> {
-        if let Either::Left(ref array) = self.flush {
-            let promise = js_sys::Promise::all(array);
-            self.flush = Either::Right(JsFuture::from(promise))
-        }
+    #[inline]
+    fn poll_ready_inner (mut self: std::pin::Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Result<()>> {
+        let len = self.limit - 1;
+        self.poll_pending(cx, len)
+    }
 
-        let flush = unsafe { self.flush.as_mut().unwrap_right_unchecked() };
-        if flush.poll_unpin(cx)?.is_ready() {
-            self.flush = Either::Left(js_sys::Array::new());
-            return Poll::Ready(Ok(()))
-        }
-        return Poll::Pending
+    #[inline]
+    fn poll_flush_inner (mut self: std::pin::Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Result<()>> {
+        self.poll_pending(cx, 0)
     }
 
     fn poll_close_inner (mut self: std::pin::Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Result<()>> {
         if self.close.is_none() {
+            if self.poll_pending(cx, 0)?.is_pending() {
+                return Poll::Pending
+            }
+
             if let Some(ref writer) = self.inner.writer.take() {
                 writer.release_lock()
             }
@@ -207,21 +258,27 @@ impl<'a, T: Unpin> WriteSink<'a, T> {
         }
         return Poll::Pending
     }
+
+    #[inline]
+    fn start_send_inner (&mut self, chunk: &JsValue) -> Result<()> {
+        debug_assert!(self.pending.len() < self.limit);
+        let promise = self.inner.get_writer()?.write_with_chunk(chunk);
+        self.pending.push_back(JsFuture::from(promise));
+        return Ok(())
+    }
 }
 
 impl<'a, T: Unpin + AsRef<JsValue>> Sink<T> for WriteSink<'a, T> {
-    type Error = JsValue;
+    type Error = crate::Error;
 
     #[inline]
     fn poll_ready(self: std::pin::Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Result<()>> {
         self.poll_ready_inner(cx)
     }
 
+    #[inline]
     fn start_send(mut self: std::pin::Pin<&mut Self>, item: T) -> Result<()> {
-        debug_assert!(self.flush.is_left());
-        let promise = self.inner.get_writer()?.write_with_chunk(item.as_ref());
-        unsafe { self.flush.as_mut().unwrap_left_unchecked().push(&promise) };
-        return Ok(())
+        self.start_send_inner(item.as_ref())
     }
 
     #[inline]
@@ -236,19 +293,17 @@ impl<'a, T: Unpin + AsRef<JsValue>> Sink<T> for WriteSink<'a, T> {
 }
 
 impl<'a, T: Unpin + TypedArrayExt> Sink<&'a [T::Element]> for WriteSink<'a, T> {
-    type Error = JsValue;
+    type Error = crate::Error;
 
     #[inline]
     fn poll_ready(self: std::pin::Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Result<()>> {
         self.poll_ready_inner(cx)
     }
 
+    #[inline]
     fn start_send(mut self: std::pin::Pin<&mut Self>, item: &'a [T::Element]) -> Result<()> {
-        debug_assert!(self.flush.is_left());
         let view = unsafe { T::view(item) };
-        let promise = self.inner.get_writer()?.write_with_chunk(view.as_ref());
-        unsafe { self.flush.as_mut().unwrap_left_unchecked().push(&promise) };
-        return Ok(())
+        self.start_send_inner(view.as_ref())
     }
 
     #[inline]
@@ -35,13 +35,27 @@ macro_rules! println {
     }};
 }
 
-/// Logs the inserted values by serializing them into [`JsValue`](wasm_bindgen::JsValue)
+/// Prints the formated arguments into the JavaScript console, through the current thread's [`Buffered`](crate::console::Buffered) logger.
+///
+/// Lines are printed together at the end of the current macrotask, which makes it suitable for hot loops.
+#[macro_export]
+macro_rules! buffered_println {
+    ($($t:tt)*) => {{
+        $crate::console::global().log_fmt(::std::format_args!($($t)*));
+    }};
+}
+
+/// Logs the inserted values by serializing them into [`JsValue`](wasm_bindgen::JsValue).
+/// Values that fail to serialize are logged as their serialization error.
 #[macro_export]
 macro_rules! log {
     ($($v:expr),+) => {{
         let values = $crate::js_sys::Array::new();
         $(
-            let _ = values.push(&$crate::serde_wasm_bindgen::to_value(&$v).unwrap());
+            let _ = values.push(&match $crate::utils::to_js(&$v) {
+                Ok(value) => value,
+                Err(e) => $crate::Error::into_js(e)
+            });
         )+
         $crate::web_sys::console::log(&values)
     }};
@@ -59,17 +73,47 @@ macro_rules! log_js {
     }};
 }
 
+/// Merges multiple streams into a single [`MergeStreams`](crate::utils::MergeStreams), wrapping each item with the specified
+/// function (usually an enum variant), so that items of different streams can be told apart.
+/// ```ignore
+/// enum Event {
+///     Motion (Motion),
+///     Orientation (Orientation),
+///     Position (Geolocation)
+/// }
+///
+/// let mut events = merge_streams! {
+///     Event::Motion => Motion::watch()?,
+///     Event::Orientation => Orientation::watch()?,
+///     Event::Position => Geolocation::watch()?
+/// };
+///
+/// while let Some(event) = events.next().await {
+///     match event { ... }
+/// }
+/// ```
+#[macro_export]
+macro_rules! merge_streams {
+    ($($tag:path => $stream:expr),+ $(,)?) => {{
+        let mut merged = $crate::utils::MergeStreams::new();
+        $(
+            merged.push($crate::futures::StreamExt::map($stream, $tag));
+        )+
+        merged
+    }};
+}
+
 #[allow(unused)]
 macro_rules! throw {
     ($($tt:tt)*) => {
         Err(
-            {
+            $crate::Error::from({
                 let args = ::std::format_args!($($tt)*);
                 match args.as_str() {
                     Some(x) => ::wasm_bindgen::JsValue::from_str(x),
                     None => ::wasm_bindgen::JsValue::from_str(&::std::string::ToString::to_string(&args))
                 }
-            }
+            })
         )
     };
 }
@@ -85,7 +129,7 @@ macro_rules! flat_mod {
 }
 
 #[allow(unused)]
-pub type Result<T> = ::core::result::Result<T, ::wasm_bindgen::JsValue>;
+pub type Result<T, E = Error> = ::core::result::Result<T, E>;
 
 #[doc(hidden)]
 pub extern crate wasm_thread;
@@ -95,13 +139,18 @@ pub extern crate web_sys;
 pub extern crate js_sys;
 #[doc(hidden)]
 pub extern crate serde_wasm_bindgen;
+#[doc(hidden)]
+pub extern crate futures;
 
 use std::borrow::Cow;
 
 use wasm_bindgen::prelude::wasm_bindgen;
-/// Web Worker threads (from the [`wasm_thread`](https://github.com/chemicstry/wasm_thread) crate).
+
+flat_mod! { error }
+
+/// Web Worker threads (from the [`wasm_thread`](https://github.com/chemicstry/wasm_thread) crate), and a pool to run work across them.
 #[docfg::docfg(target_feature = "atomics")]
-pub use wasm_thread as thread;
+pub mod thread;
 
 /// Context scope functionality
 pub mod scope;
@@ -130,15 +179,99 @@ pub mod orient;
 /// Battery API
 pub mod battery;
 
+/// Device information
+pub mod device;
+
+/// Web MIDI API
+pub mod midi;
+
+/// Credential Management & Web Authentication API
+pub mod credentials;
+
+/// Web Crypto API
+pub mod crypto;
+
+/// Cryptographically secure random numbers
+pub mod random;
+
+/// UUID and ULID generation
+pub mod ids;
+
+/// Authentication helpers
+pub mod auth;
+
 /// File API
 pub mod fs;
 
 /// Input-Output
 pub mod io;
 
+/// Asset preloading
+pub mod assets;
+
+/// Service Worker API
+pub mod sw;
+
+/// WebSocket API
+pub mod ws;
+
+/// Encoding API, base64 and hexadecimal
+pub mod encoding;
+
+/// Image decoding
+pub mod image;
+
+/// Canvas 2D API
+pub mod canvas;
+
+/// WebGL2 helpers
+pub mod gl;
+
+/// Keyboard, pointer, wheel and touch input
+pub mod input;
+
+/// Media-related functionality
+pub mod media;
+
+/// Shape Detection API
+pub mod detect;
+
+/// Window management
+pub mod windowing;
+
+/// Fullscreen, Screen Orientation & Screen Wake Lock API
+pub mod screen;
+
+/// Minimal DOM API
+pub mod dom;
+
+/// Page Lifecycle API
+pub mod lifecycle;
+
+/// Runtime feature detection
+pub mod caps;
+
+/// Memory usage introspection
+pub mod perf;
+
+/// Buffered console logging
+pub mod console;
+
+/// Leak diagnostics of the closures registered by rustww
+pub mod diagnostics;
+
+/// Internationalization API
+pub mod intl;
+
 /// Local Storage
 pub mod storage;
 
+/// IndexedDB API
+pub mod idb;
+
+/// Reactive signal primitives
+pub mod reactive;
+
 /// Various utils
 pub mod utils;
 
@@ -149,7 +282,7 @@ pub mod runtime;
 
 /// Prelude
 pub mod prelude {
-    pub use crate::{Result, log, println};
+    pub use crate::{Result, Error, ErrorKind, log, println};
     pub use crate::io::{JsReadStream, JsWriteStream};
     pub use crate::math::*;
     pub use crate::battery::{Battery, Snapshot, BatteryTime};
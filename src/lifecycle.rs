@@ -0,0 +1,122 @@
+use std::{task::Poll, rc::Rc, cell::Cell};
+use futures::{Stream, StreamExt};
+use wasm_bindgen::{JsCast, prelude::Closure};
+use web_sys::{Window, AddEventListenerOptions, EventListenerOptions};
+use crate::{Result, scope::window, dom::document, utils::{LocalReceiver, local_channel}};
+
+/// Events from which the page's lifecycle state is assembled
+const EVENTS: &[&str] = &["focus", "blur", "visibilitychange", "freeze", "resume", "pageshow", "pagehide"];
+
+/// A state of the [Page Lifecycle](https://developer.chrome.com/blog/page-lifecycle-api/)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PageState {
+    /// The page is visible and has input focus
+    Active,
+    /// The page is visible, but doesn't have input focus
+    Passive,
+    /// The page isn't visible
+    Hidden,
+    /// The page has been frozen by the browser, and it's tasks won't run until it's resumed
+    Frozen,
+    /// The page is being unloaded
+    Terminated
+}
+
+impl PageState {
+    /// Returns the current state of the page, as reported by the document
+    pub fn current () -> Result<Self> {
+        let document = document()?;
+        if document.hidden() {
+            return Ok(Self::Hidden)
+        }
+
+        return Ok(match document.has_focus()? {
+            true => Self::Active,
+            false => Self::Passive
+        })
+    }
+}
+
+/// Returns a stream of the page's lifecycle states, yielding a new item every time the state changes
+#[inline]
+pub fn watch () -> Result<LifecycleWatcher> {
+    return LifecycleWatcher::new()
+}
+
+/// Watcher of the page's lifecycle states
+pub struct LifecycleWatcher {
+    window: Window,
+    closure: Closure<dyn FnMut(web_sys::Event)>,
+    recv: LocalReceiver<PageState>
+}
+
+impl LifecycleWatcher {
+    pub fn new () -> Result<Self> {
+        let window = window()?;
+        let last = Rc::new(Cell::new(PageState::current()?));
+        let (send, recv) = local_channel();
+
+        let closure = Closure::<dyn FnMut(web_sys::Event)>::new(move |evt: web_sys::Event| {
+            let state = match evt.type_().as_str() {
+                "freeze" => PageState::Frozen,
+                "pagehide" => match evt.unchecked_ref::<web_sys::PageTransitionEvent>().persisted() {
+                    true => PageState::Frozen,
+                    false => PageState::Terminated
+                },
+                _ => match PageState::current() {
+                    Ok(state) => state,
+                    Err(_) => return
+                }
+            };
+
+            if last.replace(state) != state {
+                let _ = send.try_send(state);
+            }
+        });
+
+        let listener: &js_sys::Function;
+        cfg_if::cfg_if! {
+            if #[cfg(debug_assertions)] {
+                listener = closure.as_ref().dyn_ref().unwrap();
+            } else {
+                listener = closure.as_ref().unchecked_ref();
+            }
+        }
+
+        let mut ops = AddEventListenerOptions::new();
+        ops.capture(true);
+        for ty in EVENTS {
+            window.add_event_listener_with_callback_and_add_event_listener_options(ty, listener, &ops)?;
+        }
+
+        return Ok(Self { window, closure, recv })
+    }
+}
+
+impl Stream for LifecycleWatcher {
+    type Item = PageState;
+
+    #[inline]
+    fn poll_next(mut self: std::pin::Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Option<Self::Item>> {
+        self.recv.poll_next_unpin(cx)
+    }
+}
+
+impl Drop for LifecycleWatcher {
+    fn drop(&mut self) {
+        let listener: &js_sys::Function;
+        cfg_if::cfg_if! {
+            if #[cfg(debug_assertions)] {
+                listener = self.closure.as_ref().dyn_ref().unwrap();
+            } else {
+                listener = self.closure.as_ref().unchecked_ref();
+            }
+        }
+
+        let mut ops = EventListenerOptions::new();
+        ops.capture(true);
+        for ty in EVENTS {
+            let _ = self.window.remove_event_listener_with_callback_and_event_listener_options(ty, listener, &ops);
+        }
+    }
+}
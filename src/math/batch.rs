@@ -0,0 +1,206 @@
+#[cfg(all(feature = "simd", target_arch = "wasm32"))]
+use core::arch::wasm32::*;
+#[cfg(all(feature = "simd", target_arch = "wasm64"))]
+use core::arch::wasm64::*;
+#[cfg(feature = "parallel")]
+use docfg::docfg;
+use super::{Mat4f, Vec4f};
+
+/// Calculates the dot product of two slices
+///
+/// # Panics
+/// This method panics if the slices have different lengths.
+pub fn dot (a: &[f32], b: &[f32]) -> f32 {
+    assert_eq!(a.len(), b.len(), "slices must have the same length");
+
+    cfg_if::cfg_if! {
+        if #[cfg(feature = "simd")] {
+            let mut acc = f32x4_splat(0.);
+            let (a_chunks, b_chunks) = (a.chunks_exact(4), b.chunks_exact(4));
+            let tail = a_chunks.remainder().iter().zip(b_chunks.remainder()).map(|(a, b)| a * b).sum::<f32>();
+
+            for (a, b) in a_chunks.zip(b_chunks) {
+                // SAFETY: Chunks have exactly 4 elements, and unaligned loads are allowed
+                let (a, b) = unsafe { (v128_load(a.as_ptr().cast()), v128_load(b.as_ptr().cast())) };
+                acc = f32x4_add(acc, f32x4_mul(a, b));
+            }
+
+            return horizontal_sum(acc) + tail
+        } else {
+            return a.iter().zip(b).map(|(a, b)| a * b).sum()
+        }
+    }
+}
+
+/// Calculates the sum of the slice's values
+pub fn sum (a: &[f32]) -> f32 {
+    cfg_if::cfg_if! {
+        if #[cfg(feature = "simd")] {
+            let mut acc = f32x4_splat(0.);
+            let chunks = a.chunks_exact(4);
+            let tail = chunks.remainder().iter().sum::<f32>();
+
+            for a in chunks {
+                // SAFETY: Chunks have exactly 4 elements, and unaligned loads are allowed
+                acc = f32x4_add(acc, unsafe { v128_load(a.as_ptr().cast()) });
+            }
+
+            return horizontal_sum(acc) + tail
+        } else {
+            return a.iter().sum()
+        }
+    }
+}
+
+/// Multiplies every value of the slice by `k`
+pub fn scale (a: &mut [f32], k: f32) {
+    cfg_if::cfg_if! {
+        if #[cfg(feature = "simd")] {
+            let k4 = f32x4_splat(k);
+            let mut chunks = a.chunks_exact_mut(4);
+            for a in chunks.by_ref() {
+                // SAFETY: Chunks have exactly 4 elements, and unaligned loads and stores are allowed
+                unsafe { v128_store(a.as_mut_ptr().cast(), f32x4_mul(v128_load(a.as_ptr().cast()), k4)) }
+            }
+            chunks.into_remainder().iter_mut().for_each(|a| *a *= k);
+        } else {
+            a.iter_mut().for_each(|a| *a *= k)
+        }
+    }
+}
+
+/// Adds `alpha * x` to `y`, element-wise
+///
+/// # Panics
+/// This method panics if the slices have different lengths.
+pub fn axpy (alpha: f32, x: &[f32], y: &mut [f32]) {
+    assert_eq!(x.len(), y.len(), "slices must have the same length");
+
+    cfg_if::cfg_if! {
+        if #[cfg(feature = "simd")] {
+            let alpha4 = f32x4_splat(alpha);
+            let x_chunks = x.chunks_exact(4);
+            let x_tail = x_chunks.remainder();
+            let mut y_chunks = y.chunks_exact_mut(4);
+
+            for (x, y) in x_chunks.zip(y_chunks.by_ref()) {
+                // SAFETY: Chunks have exactly 4 elements, and unaligned loads and stores are allowed
+                unsafe {
+                    let result = f32x4_add(v128_load(y.as_ptr().cast()), f32x4_mul(v128_load(x.as_ptr().cast()), alpha4));
+                    v128_store(y.as_mut_ptr().cast(), result)
+                }
+            }
+
+            y_chunks.into_remainder().iter_mut().zip(x_tail).for_each(|(y, x)| *y += alpha * x);
+        } else {
+            y.iter_mut().zip(x).for_each(|(y, x)| *y += alpha * x)
+        }
+    }
+}
+
+/// Transforms every homogeneous vector of the slice by `m`, in place.
+///
+/// With the `simd` feature, the matrix is kept on SIMD registers for the whole slice.
+#[inline]
+pub fn transform_slice (m: &Mat4f, points: &mut [Vec4f]) {
+    let m = *m;
+    for p in points.iter_mut() {
+        *p = m.transform(*p)
+    }
+}
+
+#[cfg(feature = "simd")]
+#[inline]
+fn horizontal_sum (v: v128) -> f32 {
+    return f32x4_extract_lane::<0>(v) + f32x4_extract_lane::<1>(v) + f32x4_extract_lane::<2>(v) + f32x4_extract_lane::<3>(v)
+}
+
+/// Number of elements each worker processes at a time. Smaller inputs are processed on the current thread.
+#[cfg(all(feature = "parallel", target_feature = "atomics"))]
+const PAR_CHUNK: usize = 16 * 1024;
+
+cfg_if::cfg_if! {
+    if #[cfg(all(feature = "parallel", target_feature = "atomics"))] {
+        use crate::thread::pool::ThreadPool;
+
+        static POOL: once_cell::sync::OnceCell<ThreadPool> = once_cell::sync::OnceCell::new();
+
+        /// Sets the pool the `par_*` kernels run on. Until a pool is set, they run on the current thread.
+        ///
+        /// The pool can only be set once, so if it was already set, `pool` is returned.
+        #[docfg(all(feature = "parallel", target_feature = "atomics"))]
+        #[inline]
+        pub fn set_pool (pool: ThreadPool) -> ::core::result::Result<(), ThreadPool> {
+            return POOL.set(pool)
+        }
+
+        #[inline]
+        fn pool (len: usize) -> Option<&'static ThreadPool> {
+            return POOL.get().filter(|_| len > PAR_CHUNK)
+        }
+    }
+}
+
+/// Calculates the dot product of two slices, across the [pool](set_pool)'s workers if available
+///
+/// # Panics
+/// This method panics if the slices have different lengths.
+#[docfg(feature = "parallel")]
+pub fn par_dot (a: &[f32], b: &[f32]) -> f32 {
+    assert_eq!(a.len(), b.len(), "slices must have the same length");
+
+    #[cfg(target_feature = "atomics")]
+    if let Some(pool) = pool(a.len()) {
+        let mut partials = vec![0f32; a.len().div_ceil(PAR_CHUNK)];
+        pool.scope(|s| {
+            for ((a, b), partial) in a.chunks(PAR_CHUNK).zip(b.chunks(PAR_CHUNK)).zip(partials.iter_mut()) {
+                s.spawn(move || *partial = dot(a, b))
+            }
+        });
+        return partials.into_iter().sum()
+    }
+
+    return dot(a, b)
+}
+
+/// Calculates the sum of the slice's values, across the [pool](set_pool)'s workers if available
+#[docfg(feature = "parallel")]
+pub fn par_sum (a: &[f32]) -> f32 {
+    #[cfg(target_feature = "atomics")]
+    if let Some(pool) = pool(a.len()) {
+        return pool.map_chunks(a, PAR_CHUNK, sum).into_iter().sum()
+    }
+
+    return sum(a)
+}
+
+/// Multiplies every value of the slice by `k`, across the [pool](set_pool)'s workers if available
+#[docfg(feature = "parallel")]
+pub fn par_scale (a: &mut [f32], k: f32) {
+    #[cfg(target_feature = "atomics")]
+    if let Some(pool) = pool(a.len()) {
+        return pool.for_each_slice(a, PAR_CHUNK, |_, a| scale(a, k))
+    }
+
+    scale(a, k)
+}
+
+/// Adds `alpha * x` to `y`, element-wise, across the [pool](set_pool)'s workers if available
+///
+/// # Panics
+/// This method panics if the slices have different lengths.
+#[docfg(feature = "parallel")]
+pub fn par_axpy (alpha: f32, x: &[f32], y: &mut [f32]) {
+    assert_eq!(x.len(), y.len(), "slices must have the same length");
+
+    #[cfg(target_feature = "atomics")]
+    if let Some(pool) = pool(x.len()) {
+        return pool.scope(|s| {
+            for (x, y) in x.chunks(PAR_CHUNK).zip(y.chunks_mut(PAR_CHUNK)) {
+                s.spawn(move || axpy(alpha, x, y))
+            }
+        })
+    }
+
+    axpy(alpha, x, y)
+}
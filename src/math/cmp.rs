@@ -0,0 +1,143 @@
+use super::*;
+#[cfg(feature = "approx")]
+use docfg::docfg;
+
+trait ApproxScalar: Copy {
+    fn abs_diff_eq (self, other: Self, epsilon: Self) -> bool;
+    fn approx_eq (self, other: Self, epsilon: Self) -> bool;
+    fn ulps_eq (self, other: Self, max_ulps: u32) -> bool;
+}
+
+macro_rules! impl_scalar {
+    ($($ty:ty => $bits:ty),+) => {
+        $(
+            impl ApproxScalar for $ty {
+                #[inline]
+                fn abs_diff_eq (self, other: Self, epsilon: Self) -> bool {
+                    return <$ty>::abs(self - other) <= epsilon
+                }
+
+                #[inline]
+                fn approx_eq (self, other: Self, epsilon: Self) -> bool {
+                    // handles infinities
+                    if self == other {
+                        return true
+                    } else if !self.is_finite() || !other.is_finite() {
+                        return false
+                    }
+
+                    let diff = <$ty>::abs(self - other);
+                    return diff <= epsilon || diff <= epsilon * <$ty>::max(<$ty>::abs(self), <$ty>::abs(other))
+                }
+
+                #[inline]
+                fn ulps_eq (self, other: Self, max_ulps: u32) -> bool {
+                    // handles `0.0 == -0.0`
+                    if self == other {
+                        return true
+                    } else if self.is_nan() || other.is_nan() || self.is_sign_negative() != other.is_sign_negative() {
+                        return false
+                    }
+
+                    let ulps = (self.to_bits() as $bits).abs_diff(other.to_bits() as $bits);
+                    return ulps <= max_ulps as _
+                }
+            }
+        )+
+    };
+}
+
+macro_rules! impl_approx {
+    ($($name:ident as $ty:ty => |$this:ident| $components:expr),+) => {
+        $(
+            impl $name {
+                /// Returns `true` if no component differs from the other's by more than `epsilon`
+                #[inline]
+                pub fn abs_diff_eq (self, other: Self, epsilon: $ty) -> bool {
+                    return self.components().zip(other.components()).all(|(a, b)| a.abs_diff_eq(b, epsilon))
+                }
+
+                /// Returns `true` if no component differs from the other's by more than `epsilon`, either in absolute terms,
+                /// or relative to the largest of them.
+                ///
+                /// Unlike [`abs_diff_eq`](Self::abs_diff_eq), the tolerance grows with the magnitude of the components.
+                #[inline]
+                pub fn approx_eq (self, other: Self, epsilon: $ty) -> bool {
+                    return self.components().zip(other.components()).all(|(a, b)| a.approx_eq(b, epsilon))
+                }
+
+                /// Returns `true` if no component is more than `max_ulps` representable values away from the other's
+                #[inline]
+                pub fn ulps_eq (self, other: Self, max_ulps: u32) -> bool {
+                    return self.components().zip(other.components()).all(|(a, b)| a.ulps_eq(b, max_ulps))
+                }
+
+                #[inline]
+                fn components (self) -> impl Iterator<Item = $ty> {
+                    let $this = self;
+                    return $components
+                }
+            }
+
+            #[docfg(feature = "approx")]
+            impl ::approx::AbsDiffEq for $name {
+                type Epsilon = $ty;
+
+                #[inline]
+                fn default_epsilon() -> Self::Epsilon {
+                    return <$ty>::EPSILON
+                }
+
+                #[inline]
+                fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool {
+                    return $name::abs_diff_eq(*self, *other, epsilon)
+                }
+            }
+
+            #[docfg(feature = "approx")]
+            impl ::approx::RelativeEq for $name {
+                #[inline]
+                fn default_max_relative() -> Self::Epsilon {
+                    return <$ty>::EPSILON
+                }
+
+                #[inline]
+                fn relative_eq(&self, other: &Self, epsilon: Self::Epsilon, max_relative: Self::Epsilon) -> bool {
+                    return self.components().zip(other.components()).all(|(a, b)| ::approx::RelativeEq::relative_eq(&a, &b, epsilon, max_relative))
+                }
+            }
+
+            #[docfg(feature = "approx")]
+            impl ::approx::UlpsEq for $name {
+                #[inline]
+                fn default_max_ulps() -> u32 {
+                    return 4
+                }
+
+                #[inline]
+                fn ulps_eq(&self, other: &Self, epsilon: Self::Epsilon, max_ulps: u32) -> bool {
+                    return self.components().zip(other.components()).all(|(a, b)| ::approx::UlpsEq::ulps_eq(&a, &b, epsilon, max_ulps))
+                }
+            }
+        )+
+    };
+}
+
+impl_scalar! {
+    f32 => i32,
+    f64 => i64
+}
+
+impl_approx! {
+    Vec2f as f32 => |v| [v.x(), v.y()].into_iter(),
+    Vec3f as f32 => |v| [v.x(), v.y(), v.z()].into_iter(),
+    Vec4f as f32 => |v| [v.x(), v.y(), v.z(), v.w()].into_iter(),
+
+    Vec2d as f64 => |v| [v.x(), v.y()].into_iter(),
+    Vec3d as f64 => |v| [v.x(), v.y(), v.z()].into_iter(),
+    Vec4d as f64 => |v| [v.x(), v.y(), v.z(), v.w()].into_iter(),
+
+    Mat3f as f32 => |m| m.to_cols_array().into_iter().flatten(),
+    Mat4f as f32 => |m| m.to_cols_array().into_iter().flatten(),
+    Quatd as f64 => |q| [q.x, q.y, q.z, q.w].into_iter()
+}
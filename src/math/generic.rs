@@ -6,6 +6,36 @@ use serde::ser::SerializeSeq;
 macro_rules! impl_generic {
     ($($name:ident as [$ty:ty; $len:literal] => ($($var:ident),+)),+) => {
         $(
+            impl $name {
+                /// Calculates the unit vector, or returns `None` if the vector's magnitude is zero or isn't finite
+                #[inline]
+                pub fn try_unit (self) -> Option<Self> {
+                    let magn = self.magn();
+                    if magn > 0. && magn.is_finite() {
+                        return Some(self / magn)
+                    }
+                    return None
+                }
+
+                /// Calculates the unit vector, or returns the zero vector if the vector's magnitude is zero or isn't finite
+                #[inline]
+                pub fn normalize_or_zero (self) -> Self {
+                    return self.try_unit().unwrap_or_default()
+                }
+
+                /// Returns `true` if every component of the vector is finite
+                #[inline]
+                pub fn is_finite (self) -> bool {
+                    return $(self.$var().is_finite())&&+
+                }
+
+                /// Returns `true` if any component of the vector is `NaN`
+                #[inline]
+                pub fn is_nan (self) -> bool {
+                    return $(self.$var().is_nan())||+
+                }
+            }
+
             impl From<[$ty; $len]> for $name {
                 #[inline]
                 fn from ([$($var),+]: [$ty; $len]) -> Self {
@@ -52,6 +82,21 @@ macro_rules! impl_generic {
                     return deserializer.deserialize_seq(Vis)
                 }
             }
+
+            impl soa::Lanes for $name {
+                type Scalar = $ty;
+                const NAMES: &'static [&'static str] = &[$(stringify!($var)),+];
+
+                #[inline]
+                fn lane (self, i: usize) -> $ty {
+                    return [$(self.$var()),+][i]
+                }
+
+                #[inline]
+                fn from_lanes (lanes: &[$ty]) -> Self {
+                    return <[$ty; $len]>::try_from(lanes).map(Self::from).expect(concat!("expected ", stringify!($len), " lanes"))
+                }
+            }
         )+
     };
 }
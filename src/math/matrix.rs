@@ -0,0 +1,264 @@
+use core::ops::Mul;
+use super::{Vec2f, Vec3f, Vec4f};
+
+#[cfg(all(feature = "simd", target_arch = "wasm32"))]
+use core::arch::wasm32::*;
+#[cfg(all(feature = "simd", target_arch = "wasm64"))]
+use core::arch::wasm64::*;
+
+/// 3x3 matrix of `f32` values, stored in column-major order.
+///
+/// Mostly used to represent 2D affine transformations.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Mat3f {
+    cols: [[f32; 3]; 3]
+}
+
+impl Mat3f {
+    /// The identity matrix
+    pub const IDENTITY: Self = Self::new([
+        [1., 0., 0.],
+        [0., 1., 0.],
+        [0., 0., 1.]
+    ]);
+
+    /// Creates a new [`Mat3f`] from it's columns
+    #[inline]
+    pub const fn new (cols: [[f32; 3]; 3]) -> Self {
+        return Self { cols }
+    }
+
+    /// Creates a 2D translation matrix
+    #[inline]
+    pub fn from_translation (v: Vec2f) -> Self {
+        return Self::new([
+            [1., 0., 0.],
+            [0., 1., 0.],
+            [v.x(), v.y(), 1.]
+        ])
+    }
+
+    /// Creates a 2D scaling matrix
+    #[inline]
+    pub fn from_scale (v: Vec2f) -> Self {
+        return Self::new([
+            [v.x(), 0., 0.],
+            [0., v.y(), 0.],
+            [0., 0., 1.]
+        ])
+    }
+
+    /// Creates a 2D rotation matrix, with the angle in radians
+    #[inline]
+    pub fn from_rotation (angle: f32) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        return Self::new([
+            [cos, sin, 0.],
+            [-sin, cos, 0.],
+            [0., 0., 1.]
+        ])
+    }
+
+    /// Returns the columns of the matrix
+    #[inline]
+    pub const fn to_cols_array (self) -> [[f32; 3]; 3] {
+        return self.cols
+    }
+
+    /// Returns the transposed matrix
+    #[inline]
+    pub fn transpose (self) -> Self {
+        let c = self.cols;
+        return Self::new([
+            [c[0][0], c[1][0], c[2][0]],
+            [c[0][1], c[1][1], c[2][1]],
+            [c[0][2], c[1][2], c[2][2]]
+        ])
+    }
+
+    /// Transforms the point, applying the translation
+    #[inline]
+    pub fn transform_point (self, p: Vec2f) -> Vec2f {
+        let c = self.cols;
+        return Vec2f::new(
+            c[0][0] * p.x() + c[1][0] * p.y() + c[2][0],
+            c[0][1] * p.x() + c[1][1] * p.y() + c[2][1]
+        )
+    }
+
+    /// Transforms the vector, ignoring the translation
+    #[inline]
+    pub fn transform_vector (self, v: Vec2f) -> Vec2f {
+        let c = self.cols;
+        return Vec2f::new(
+            c[0][0] * v.x() + c[1][0] * v.y(),
+            c[0][1] * v.x() + c[1][1] * v.y()
+        )
+    }
+}
+
+impl Default for Mat3f {
+    #[inline]
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Mul for Mat3f {
+    type Output = Self;
+
+    fn mul (self, rhs: Self) -> Self::Output {
+        let (a, b) = (self.cols, rhs.cols);
+        let mut cols = [[0f32; 3]; 3];
+
+        for col in 0..3 {
+            for row in 0..3 {
+                cols[col][row] = a[0][row] * b[col][0] + a[1][row] * b[col][1] + a[2][row] * b[col][2];
+            }
+        }
+
+        return Self::new(cols)
+    }
+}
+
+/// 4x4 matrix of `f32` values, stored in column-major order.
+///
+/// Mostly used to represent 3D affine and projective transformations. Each column is a [`Vec4f`], so with the `simd`
+/// feature, transformations stay on SIMD registers.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Mat4f {
+    cols: [Vec4f; 4]
+}
+
+impl Mat4f {
+    /// The identity matrix
+    pub const IDENTITY: Self = Self::new([
+        [1., 0., 0., 0.],
+        [0., 1., 0., 0.],
+        [0., 0., 1., 0.],
+        [0., 0., 0., 1.]
+    ]);
+
+    /// Creates a new [`Mat4f`] from it's columns
+    #[inline]
+    pub const fn new (cols: [[f32; 4]; 4]) -> Self {
+        return Self::from_cols([
+            Vec4f::new(cols[0][0], cols[0][1], cols[0][2], cols[0][3]),
+            Vec4f::new(cols[1][0], cols[1][1], cols[1][2], cols[1][3]),
+            Vec4f::new(cols[2][0], cols[2][1], cols[2][2], cols[2][3]),
+            Vec4f::new(cols[3][0], cols[3][1], cols[3][2], cols[3][3])
+        ])
+    }
+
+    /// Creates a new [`Mat4f`] from it's column vectors
+    #[inline]
+    pub const fn from_cols (cols: [Vec4f; 4]) -> Self {
+        return Self { cols }
+    }
+
+    /// Creates a 3D translation matrix
+    #[inline]
+    pub fn from_translation (v: Vec3f) -> Self {
+        return Self::new([
+            [1., 0., 0., 0.],
+            [0., 1., 0., 0.],
+            [0., 0., 1., 0.],
+            [v.x(), v.y(), v.z(), 1.]
+        ])
+    }
+
+    /// Creates a 3D scaling matrix
+    #[inline]
+    pub fn from_scale (v: Vec3f) -> Self {
+        return Self::new([
+            [v.x(), 0., 0., 0.],
+            [0., v.y(), 0., 0.],
+            [0., 0., v.z(), 0.],
+            [0., 0., 0., 1.]
+        ])
+    }
+
+    /// Returns the column vectors of the matrix
+    #[inline]
+    pub const fn cols (self) -> [Vec4f; 4] {
+        return self.cols
+    }
+
+    /// Returns the columns of the matrix
+    #[inline]
+    pub fn to_cols_array (self) -> [[f32; 4]; 4] {
+        return self.cols.map(|c| [c.x(), c.y(), c.z(), c.w()])
+    }
+
+    /// Returns the transposed matrix
+    #[inline]
+    pub fn transpose (self) -> Self {
+        let c = self.to_cols_array();
+        return Self::new([
+            [c[0][0], c[1][0], c[2][0], c[3][0]],
+            [c[0][1], c[1][1], c[2][1], c[3][1]],
+            [c[0][2], c[1][2], c[2][2], c[3][2]],
+            [c[0][3], c[1][3], c[2][3], c[3][3]]
+        ])
+    }
+
+    /// Transforms the homogeneous vector
+    #[inline]
+    pub fn transform (self, v: Vec4f) -> Vec4f {
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "simd")] {
+                let [c0, c1, c2, c3] = self.cols.map(|c| c.inner);
+                let x = i32x4_shuffle::<0, 0, 0, 0>(v.inner, v.inner);
+                let y = i32x4_shuffle::<1, 1, 1, 1>(v.inner, v.inner);
+                let z = i32x4_shuffle::<2, 2, 2, 2>(v.inner, v.inner);
+                let w = i32x4_shuffle::<3, 3, 3, 3>(v.inner, v.inner);
+
+                let xy = f32x4_add(f32x4_mul(c0, x), f32x4_mul(c1, y));
+                let zw = f32x4_add(f32x4_mul(c2, z), f32x4_mul(c3, w));
+                return Vec4f { inner: f32x4_add(xy, zw) }
+            } else {
+                let [c0, c1, c2, c3] = self.cols;
+                return (c0 * v.x() + c1 * v.y()) + (c2 * v.z() + c3 * v.w())
+            }
+        }
+    }
+
+    /// Transforms the point, applying the translation
+    #[inline]
+    pub fn transform_point (self, p: Vec3f) -> Vec3f {
+        let result = self.transform(Vec4f::new(p.x(), p.y(), p.z(), 1.));
+        return Vec3f::new(result.x(), result.y(), result.z())
+    }
+
+    /// Transforms the vector, ignoring the translation
+    #[inline]
+    pub fn transform_vector (self, v: Vec3f) -> Vec3f {
+        let result = self.transform(Vec4f::new(v.x(), v.y(), v.z(), 0.));
+        return Vec3f::new(result.x(), result.y(), result.z())
+    }
+}
+
+impl Default for Mat4f {
+    #[inline]
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Mul for Mat4f {
+    type Output = Self;
+
+    #[inline]
+    fn mul (self, rhs: Self) -> Self::Output {
+        return Self::from_cols(rhs.cols.map(|col| self.transform(col)))
+    }
+}
+
+impl Mul<Vec4f> for Mat4f {
+    type Output = Vec4f;
+
+    #[inline]
+    fn mul (self, rhs: Vec4f) -> Self::Output {
+        return self.transform(rhs)
+    }
+}
@@ -11,6 +11,7 @@ macro_rules! impl_scalar_vec {
         $(
             #[doc = concat!("Euclidian vector of ", stringify!($len), " `", stringify!($ty), "` values")]
             #[derive(Debug, Clone, Copy, PartialEq, Default)]
+            #[repr(C)]
             $v struct $name {
                 $(
                     $vname: $ty
@@ -176,7 +177,12 @@ macro_rules! impl_scalar_vec {
     };
 }
 
-flat_mod! { generic }
+flat_mod! { generic, matrix, quat, cmp }
+
+/// SIMD-accelerated kernels over slices of values
+pub mod batch;
+/// Struct-of-arrays serialization of vectors
+pub mod soa;
 
 #[cfg(feature = "simd")]
 flat_mod! { full, padded, extended }
@@ -0,0 +1,145 @@
+use core::ops::{Mul, Neg};
+use super::Vec3d;
+
+/// Quaternion of `f64` values, mostly used to represent 3D rotations
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quatd {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    pub w: f64
+}
+
+impl Quatd {
+    /// The identity quaternion, representing no rotation
+    pub const IDENTITY: Self = Self::new(0., 0., 0., 1.);
+
+    /// Creates a new [`Quatd`]
+    #[inline]
+    pub const fn new (x: f64, y: f64, z: f64, w: f64) -> Self {
+        return Self { x, y, z, w }
+    }
+
+    /// Creates a rotation of `angle` radians arround `axis`, which must be a unit vector
+    #[inline]
+    pub fn from_axis_angle (axis: Vec3d, angle: f64) -> Self {
+        let (sin, cos) = f64::sin_cos(0.5 * angle);
+        return Self::new(axis.x() * sin, axis.y() * sin, axis.z() * sin, cos)
+    }
+
+    /// Creates a rotation from a vector whose direction is the axis of rotation and whose magnitude is the angle, in radians
+    #[inline]
+    pub fn from_scaled_axis (v: Vec3d) -> Self {
+        let angle = v.magn();
+        if angle == 0. {
+            return Self::IDENTITY
+        }
+        return Self::from_axis_angle(v / angle, angle)
+    }
+
+    /// Creates a rotation from the device orientation angles, in radians, applied intrinsically in Z-X'-Y'' order
+    /// (`alpha` arround the z axis, then `beta` arround the x axis and `gamma` arround the y axis)
+    #[inline]
+    pub fn from_device_angles (alpha: f64, beta: f64, gamma: f64) -> Self {
+        let z = Self::from_axis_angle(Vec3d::new(0., 0., 1.), alpha);
+        let x = Self::from_axis_angle(Vec3d::new(1., 0., 0.), beta);
+        let y = Self::from_axis_angle(Vec3d::new(0., 1., 0.), gamma);
+        return z * x * y
+    }
+
+    /// Calculates the dot product between the quaternions
+    #[inline]
+    pub fn dot (self, rhs: Self) -> f64 {
+        return self.x * rhs.x + self.y * rhs.y + self.z * rhs.z + self.w * rhs.w
+    }
+
+    /// Calculates the magnitude of the quaternion
+    #[inline]
+    pub fn magn (self) -> f64 {
+        return f64::sqrt(self.dot(self))
+    }
+
+    /// Calculates the unit quaternion
+    #[inline]
+    pub fn unit (self) -> Self {
+        let magn = self.magn();
+        return Self::new(self.x / magn, self.y / magn, self.z / magn, self.w / magn)
+    }
+
+    /// Returns the conjugate of the quaternion, which is it's inverse if it's a unit quaternion
+    #[inline]
+    pub fn conjugate (self) -> Self {
+        return Self::new(-self.x, -self.y, -self.z, self.w)
+    }
+
+    /// Rotates the vector by the quaternion, which must be a unit quaternion
+    #[inline]
+    pub fn rotate (self, v: Vec3d) -> Vec3d {
+        let q = self * Self::new(v.x(), v.y(), v.z(), 0.) * self.conjugate();
+        return Vec3d::new(q.x, q.y, q.z)
+    }
+
+    /// Spherically interpolates between the unit quaternions, with `t` between 0 and 1
+    pub fn slerp (self, rhs: Self, t: f64) -> Self {
+        let mut rhs = rhs;
+        let mut cos = self.dot(rhs);
+
+        // Take the shortest path
+        if cos < 0. {
+            rhs = -rhs;
+            cos = -cos;
+        }
+
+        // Quaternions are too close, interpolate linearly to avoid dividing by ~0
+        if cos > 0.9995 {
+            return Self::new(
+                self.x + t * (rhs.x - self.x),
+                self.y + t * (rhs.y - self.y),
+                self.z + t * (rhs.z - self.z),
+                self.w + t * (rhs.w - self.w)
+            ).unit()
+        }
+
+        let theta = f64::acos(cos);
+        let sin = f64::sin(theta);
+        let a = f64::sin((1. - t) * theta) / sin;
+        let b = f64::sin(t * theta) / sin;
+
+        return Self::new(
+            a * self.x + b * rhs.x,
+            a * self.y + b * rhs.y,
+            a * self.z + b * rhs.z,
+            a * self.w + b * rhs.w
+        )
+    }
+}
+
+impl Mul for Quatd {
+    type Output = Self;
+
+    #[inline]
+    fn mul (self, rhs: Self) -> Self::Output {
+        return Self::new(
+            self.w * rhs.x + self.x * rhs.w + self.y * rhs.z - self.z * rhs.y,
+            self.w * rhs.y - self.x * rhs.z + self.y * rhs.w + self.z * rhs.x,
+            self.w * rhs.z + self.x * rhs.y - self.y * rhs.x + self.z * rhs.w,
+            self.w * rhs.w - self.x * rhs.x - self.y * rhs.y - self.z * rhs.z
+        )
+    }
+}
+
+impl Neg for Quatd {
+    type Output = Self;
+
+    #[inline]
+    fn neg (self) -> Self::Output {
+        return Self::new(-self.x, -self.y, -self.z, -self.w)
+    }
+}
+
+impl Default for Quatd {
+    #[inline]
+    fn default() -> Self {
+        return Self::IDENTITY
+    }
+}
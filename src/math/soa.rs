@@ -0,0 +1,108 @@
+//! Helpers to serialize slices of vectors as a struct of arrays, with one array per component,
+//! to be used with `#[serde(with = "rustww::math::soa")]`.
+//! ```ignore
+//! #[derive(Serialize, Deserialize)]
+//! struct Mesh {
+//!     // serialized as `{ "x": [...], "y": [...], "z": [...] }`
+//!     #[serde(with = "rustww::math::soa")]
+//!     positions: Vec<Vec3f>
+//! }
+//! ```
+
+use core::marker::PhantomData;
+use serde::{Serialize, Serializer, Deserializer, de::{self, DeserializeOwned, Visitor, SeqAccess, MapAccess}, ser::SerializeStruct};
+
+/// A vector whose components can be serialized as separate arrays
+pub trait Lanes: Copy {
+    /// Type of the vector's components
+    type Scalar: Copy + Serialize + DeserializeOwned;
+    /// Names of the vector's components, in order
+    const NAMES: &'static [&'static str];
+
+    /// Returns the `i`-th component of the vector
+    fn lane (self, i: usize) -> Self::Scalar;
+    /// Creates a new vector from it's components, in order
+    fn from_lanes (lanes: &[Self::Scalar]) -> Self;
+}
+
+/// Serializes the vectors as a struct with an array for each of their components
+pub fn serialize<V: Lanes, S: Serializer> (values: &[V], serializer: S) -> Result<S::Ok, S::Error> {
+    struct Column<'a, V> (&'a [V], usize);
+
+    impl<V: Lanes> Serialize for Column<'_, V> {
+        #[inline]
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+            return serializer.collect_seq(self.0.iter().map(|v| v.lane(self.1)))
+        }
+    }
+
+    let mut serializer = serializer.serialize_struct("Lanes", V::NAMES.len())?;
+    for (i, name) in V::NAMES.iter().enumerate() {
+        serializer.serialize_field(name, &Column(values, i))?;
+    }
+    return serializer.end()
+}
+
+/// Deserializes the vectors from a struct (or a sequence) with an array for each of their components.
+///
+/// Every array must have the same length.
+pub fn deserialize<'de, V: Lanes, D: Deserializer<'de>> (deserializer: D) -> Result<Vec<V>, D::Error> {
+    struct Vis<V> (PhantomData<V>);
+
+    impl<'de, V: Lanes> Visitor<'de> for Vis<V> {
+        type Value = Vec<V>;
+
+        #[inline]
+        fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            write!(f, "a struct with the arrays of {:?}", V::NAMES)
+        }
+
+        fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error> where A: SeqAccess<'de> {
+            let mut columns = Vec::with_capacity(V::NAMES.len());
+            for name in V::NAMES {
+                match seq.next_element::<Vec<V::Scalar>>()? {
+                    Some(column) => columns.push(column),
+                    None => return Err(<A::Error as de::Error>::missing_field(name))
+                }
+            }
+            return from_columns(columns)
+        }
+
+        fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error> where A: MapAccess<'de> {
+            let mut columns = vec![None; V::NAMES.len()];
+            while let Some(key) = map.next_key::<String>()? {
+                let Some(i) = V::NAMES.iter().position(|name| *name == key) else {
+                    return Err(<A::Error as de::Error>::unknown_field(&key, V::NAMES))
+                };
+
+                if columns[i].is_some() {
+                    return Err(<A::Error as de::Error>::duplicate_field(V::NAMES[i]))
+                }
+                columns[i] = Some(map.next_value::<Vec<V::Scalar>>()?);
+            }
+
+            let columns = columns.into_iter()
+                .zip(V::NAMES)
+                .map(|(column, name)| column.ok_or_else(|| <A::Error as de::Error>::missing_field(name)))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            return from_columns(columns)
+        }
+    }
+
+    return deserializer.deserialize_struct("Lanes", V::NAMES, Vis(PhantomData))
+}
+
+fn from_columns<V: Lanes, E: de::Error> (columns: Vec<Vec<V::Scalar>>) -> Result<Vec<V>, E> {
+    let len = columns.first().map_or(0, Vec::len);
+    if columns.iter().any(|column| column.len() != len) {
+        return Err(E::custom("every component must have the same number of values"))
+    }
+
+    let mut lanes = Vec::with_capacity(columns.len());
+    return Ok((0..len).map(|i| {
+        lanes.clear();
+        lanes.extend(columns.iter().map(|column| column[i]));
+        V::from_lanes(&lanes)
+    }).collect())
+}
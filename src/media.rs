@@ -0,0 +1,119 @@
+use futures::stream::{Select, select};
+use js_sys::Promise;
+use wasm_bindgen::{JsCast, JsValue, prelude::wasm_bindgen};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{HtmlVideoElement, EventTarget};
+use crate::{Result, Error, ErrorKind, input::{EventStream, ListenOptions}};
+
+#[wasm_bindgen]
+extern "C" {
+    #[derive(Debug, Clone)]
+    #[wasm_bindgen(extends = HtmlVideoElement)]
+    type VideoElementExt;
+
+    #[wasm_bindgen(method, catch, js_name = requestPictureInPicture)]
+    fn request_picture_in_picture (this: &VideoElementExt) -> ::core::result::Result<Promise, JsValue>;
+
+    #[derive(Debug, Clone)]
+    #[wasm_bindgen(extends = EventTarget, js_name = PictureInPictureWindow)]
+    type PipWindow;
+
+    #[wasm_bindgen(method, getter)]
+    fn width (this: &PipWindow) -> u32;
+    #[wasm_bindgen(method, getter)]
+    fn height (this: &PipWindow) -> u32;
+
+    #[wasm_bindgen(js_namespace = document, js_name = exitPictureInPicture, catch)]
+    fn exit_picture_in_picture () -> ::core::result::Result<Promise, JsValue>;
+}
+
+/// Size of a Picture-in-Picture window, in pixels
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PipSize {
+    pub width: u32,
+    pub height: u32
+}
+
+/// A Picture-in-Picture transition of a video
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PipEvent {
+    /// The video entered Picture-in-Picture mode
+    Enter,
+    /// The video left Picture-in-Picture mode
+    Leave
+}
+
+/// A [`Stream`](futures::Stream) of the Picture-in-Picture transitions of a video
+pub type PipEvents = Select<EventStream<PipEvent>, EventStream<PipEvent>>;
+
+/// A floating Picture-in-Picture window, playing a video
+#[derive(Debug, Clone)]
+pub struct PictureInPicture {
+    window: PipWindow,
+    video: HtmlVideoElement
+}
+
+impl PictureInPicture {
+    /// Returns `true` if Picture-in-Picture is supported and allowed on the current document, `false` otherwise
+    pub fn is_supported () -> bool {
+        let document = match js_sys::Reflect::get(&js_sys::global(), &JsValue::from_str("document")) {
+            Ok(document) if !document.is_undefined() => document,
+            _ => return false
+        };
+
+        return js_sys::Reflect::get(&document, &JsValue::from_str("pictureInPictureEnabled")).ok()
+            .and_then(|x| x.as_bool())
+            .unwrap_or_default()
+    }
+
+    /// Requests the video to be played in a Picture-in-Picture window.
+    ///
+    /// This method must be called in response to a user gesture.
+    pub async fn request (video: &HtmlVideoElement) -> Result<Self> {
+        if !Self::is_supported() {
+            return Err(Error::new(ErrorKind::NotSupported, "Picture-in-Picture is not supported"))
+        }
+
+        let promise = video.unchecked_ref::<VideoElementExt>().request_picture_in_picture()?;
+        let window = JsFuture::from(promise).await?;
+
+        return Ok(Self {
+            window: window.unchecked_into(),
+            video: video.clone()
+        })
+    }
+
+    /// Returns the video being played in the window
+    #[inline]
+    pub fn video (&self) -> &HtmlVideoElement {
+        return &self.video
+    }
+
+    /// Returns the current size of the window
+    #[inline]
+    pub fn size (&self) -> PipSize {
+        return PipSize { width: self.window.width(), height: self.window.height() }
+    }
+
+    /// Returns a stream of the window's new sizes, every time it's resized
+    pub fn watch_resize (&self) -> Result<EventStream<PipSize>> {
+        let window = self.window.clone();
+        return EventStream::with_map(&self.window, "resize", ListenOptions::default(), move |_: web_sys::Event| {
+            PipSize { width: window.width(), height: window.height() }
+        })
+    }
+
+    /// Closes the window, returning the video to the page
+    #[inline]
+    pub async fn exit (self) -> Result<()> {
+        let _ = JsFuture::from(exit_picture_in_picture()?).await?;
+        return Ok(())
+    }
+
+    /// Returns a stream of the Picture-in-Picture transitions of the video
+    pub fn events (video: &HtmlVideoElement) -> Result<PipEvents> {
+        let enter = EventStream::with_map(video, "enterpictureinpicture", ListenOptions::default(), |_: web_sys::Event| PipEvent::Enter)?;
+        let leave = EventStream::with_map(video, "leavepictureinpicture", ListenOptions::default(), |_: web_sys::Event| PipEvent::Leave)?;
+        return Ok(select(enter, leave))
+    }
+}
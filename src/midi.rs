@@ -0,0 +1,305 @@
+use std::task::Poll;
+use futures::{Stream, StreamExt};
+use js_sys::Uint8Array;
+use wasm_bindgen::{prelude::Closure, JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{MidiAccess, MidiMessageEvent, MidiOptions};
+use crate::{Result, utils::{LocalReceiver, local_channel}, scope::window_only};
+
+/// Access to the device's MIDI ports.
+#[derive(Debug, Clone)]
+pub struct Midi {
+    inner: MidiAccess
+}
+
+impl Midi {
+    /// Requests access to the device's MIDI ports, optionally with the ability to send and receive system exclusive messages.
+    ///
+    /// # Compatibility
+    /// Check the [compatibility table](https://developer.mozilla.org/en-US/docs/Web/API/Navigator/requestMIDIAccess#browser_compatibility)
+    pub async fn request (sysex: bool) -> Result<Self> {
+        let mut ops = MidiOptions::new();
+        ops.sysex(sysex);
+
+        let access = JsFuture::from(window_only("Web MIDI API")?.navigator().request_midi_access_with_options(&ops)?).await?;
+        let inner: MidiAccess;
+        cfg_if::cfg_if! {
+            if #[cfg(debug_assertions)] {
+                inner = access.dyn_into().unwrap();
+            } else {
+                inner = access.unchecked_into();
+            }
+        }
+
+        return Ok(Self { inner })
+    }
+
+    /// Returns `true` if system exclusive messages are enabled, `false` otherwise
+    #[inline]
+    pub fn sysex_enabled (&self) -> bool {
+        return self.inner.sysex_enabled()
+    }
+
+    /// Returns an iterator over the currently available input ports
+    #[inline]
+    pub fn inputs (&self) -> impl Iterator<Item = MidiInput> {
+        return map_values(self.inner.inputs().unchecked_ref())
+            .map(|inner| MidiInput { inner: inner.unchecked_into() })
+    }
+
+    /// Returns an iterator over the currently available output ports
+    #[inline]
+    pub fn outputs (&self) -> impl Iterator<Item = MidiOutput> {
+        return map_values(self.inner.outputs().unchecked_ref())
+            .map(|inner| MidiOutput { inner: inner.unchecked_into() })
+    }
+}
+
+/// Information about a MIDI port
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct PortInfo {
+    /// Unique ID of the port
+    pub id: String,
+    /// Manufacturer of the port, if available
+    pub manufacturer: Option<String>,
+    /// System name of the port, if available
+    pub name: Option<String>,
+    /// Version of the port, if available
+    pub version: Option<String>
+}
+
+impl From<&web_sys::MidiPort> for PortInfo {
+    #[inline]
+    fn from(value: &web_sys::MidiPort) -> Self {
+        return Self {
+            id: value.id(),
+            manufacturer: value.manufacturer(),
+            name: value.name(),
+            version: value.version()
+        }
+    }
+}
+
+/// A MIDI input port
+#[derive(Debug, Clone)]
+pub struct MidiInput {
+    inner: web_sys::MidiInput
+}
+
+impl MidiInput {
+    /// Returns the information of the port
+    #[inline]
+    pub fn info (&self) -> PortInfo {
+        return PortInfo::from(&*self.inner as &web_sys::MidiPort)
+    }
+
+    /// Returns a watcher over the messages received by the port
+    #[inline]
+    pub fn watch (&self) -> Result<MessageWatcher> {
+        return MessageWatcher::new(self)
+    }
+}
+
+/// A MIDI output port
+#[derive(Debug, Clone)]
+pub struct MidiOutput {
+    inner: web_sys::MidiOutput
+}
+
+impl MidiOutput {
+    /// Returns the information of the port
+    #[inline]
+    pub fn info (&self) -> PortInfo {
+        return PortInfo::from(&*self.inner as &web_sys::MidiPort)
+    }
+
+    /// Sends the raw bytes through the port immediately
+    #[inline]
+    pub fn send_raw (&self, data: &[u8]) -> Result<()> {
+        return Ok(self.inner.send(&Uint8Array::from(data))?)
+    }
+
+    /// Sends the raw bytes through the port at the specified timestamp, in milliseconds relative to the
+    /// [time origin](https://developer.mozilla.org/en-US/docs/Web/API/Performance/timeOrigin).
+    #[inline]
+    pub fn send_raw_at (&self, data: &[u8], timestamp: f64) -> Result<()> {
+        return Ok(self.inner.send_with_timestamp(&Uint8Array::from(data), timestamp)?)
+    }
+
+    /// Sends the message through the port immediately
+    #[inline]
+    pub fn send (&self, msg: &Message) -> Result<()> {
+        return self.send_raw(&msg.to_bytes())
+    }
+
+    /// Sends the message through the port at the specified timestamp, in milliseconds relative to the
+    /// [time origin](https://developer.mozilla.org/en-US/docs/Web/API/Performance/timeOrigin).
+    #[inline]
+    pub fn send_at (&self, msg: &Message, timestamp: f64) -> Result<()> {
+        return self.send_raw_at(&msg.to_bytes(), timestamp)
+    }
+
+    /// Clears any pending send data from the port's queue
+    #[inline]
+    pub fn clear (&self) {
+        self.inner.clear()
+    }
+}
+
+/// A note message
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Note {
+    /// Channel of the message, between `0` and `15`
+    pub channel: u8,
+    /// Key number of the note, between `0` and `127`
+    pub key: u8,
+    /// Velocity of the note, between `0` and `127`
+    pub velocity: u8
+}
+
+/// A control change message
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ControlChange {
+    /// Channel of the message, between `0` and `15`
+    pub channel: u8,
+    /// Controller number, between `0` and `127`
+    pub controller: u8,
+    /// New value of the controller, between `0` and `127`
+    pub value: u8
+}
+
+/// A parsed MIDI message
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Message {
+    /// A key has been released. Note on messages with a velocity of zero are also parsed as note off.
+    NoteOff (Note),
+    /// A key has been pressed
+    NoteOn (Note),
+    /// The pressure of a key has changed
+    Aftertouch (Note),
+    /// A controller has changed it's value
+    ControlChange (ControlChange),
+    /// The program (patch) of a channel has changed
+    ProgramChange { channel: u8, program: u8 },
+    /// The pressure of a whole channel has changed
+    ChannelPressure { channel: u8, pressure: u8 },
+    /// The pitch wheel has changed, with `value` being between `0` and `16383` (centered at `8192`)
+    PitchBend { channel: u8, value: u16 },
+    /// Any other message (system exclusive, clock, ...)
+    Other (Vec<u8>)
+}
+
+impl Message {
+    /// Parses a message from it's raw bytes
+    pub fn parse (data: &[u8]) -> Self {
+        let (status, channel) = match data.first() {
+            Some(x) => (x & 0xf0, x & 0x0f),
+            None => return Self::Other(Vec::new())
+        };
+
+        return match (status, &data[1..]) {
+            (0x80, &[key, velocity, ..]) => Self::NoteOff(Note { channel, key, velocity }),
+            (0x90, &[key, 0, ..]) => Self::NoteOff(Note { channel, key, velocity: 0 }),
+            (0x90, &[key, velocity, ..]) => Self::NoteOn(Note { channel, key, velocity }),
+            (0xa0, &[key, velocity, ..]) => Self::Aftertouch(Note { channel, key, velocity }),
+            (0xb0, &[controller, value, ..]) => Self::ControlChange(ControlChange { channel, controller, value }),
+            (0xc0, &[program, ..]) => Self::ProgramChange { channel, program },
+            (0xd0, &[pressure, ..]) => Self::ChannelPressure { channel, pressure },
+            (0xe0, &[lsb, msb, ..]) => Self::PitchBend { channel, value: ((msb as u16) << 7) | (lsb as u16) },
+            _ => Self::Other(data.to_vec())
+        }
+    }
+
+    /// Returns the raw bytes of the message
+    pub fn to_bytes (&self) -> Vec<u8> {
+        return match self {
+            Self::NoteOff(Note { channel, key, velocity }) => vec![0x80 | channel, *key, *velocity],
+            Self::NoteOn(Note { channel, key, velocity }) => vec![0x90 | channel, *key, *velocity],
+            Self::Aftertouch(Note { channel, key, velocity }) => vec![0xa0 | channel, *key, *velocity],
+            Self::ControlChange(ControlChange { channel, controller, value }) => vec![0xb0 | channel, *controller, *value],
+            Self::ProgramChange { channel, program } => vec![0xc0 | channel, *program],
+            Self::ChannelPressure { channel, pressure } => vec![0xd0 | channel, *pressure],
+            Self::PitchBend { channel, value } => vec![0xe0 | channel, (value & 0x7f) as u8, ((value >> 7) & 0x7f) as u8],
+            Self::Other(data) => data.clone()
+        }
+    }
+}
+
+/// A MIDI message received by an input port
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimedMessage {
+    /// Time at which the message was received, in milliseconds relative to the
+    /// [time origin](https://developer.mozilla.org/en-US/docs/Web/API/Performance/timeOrigin).
+    pub timestamp: f64,
+    pub message: Message
+}
+
+/// A watcher for the messages of a [`MidiInput`].
+///
+/// Every time the port receives a message, [`MessageWatcher`] will be notified.
+///
+/// When droped, the watcher will be closed, releasing all the memory of it's closure, avoiding a memory leak.
+pub struct MessageWatcher {
+    inner: web_sys::MidiInput,
+    resolve: Closure<dyn FnMut(MidiMessageEvent)>,
+    recv: LocalReceiver<TimedMessage>
+}
+
+impl MessageWatcher {
+    /// Creates a new [`MessageWatcher`]
+    pub fn new (input: &MidiInput) -> Result<Self> {
+        let (send, recv) = local_channel();
+        let resolve = Closure::<dyn FnMut(MidiMessageEvent)>::new(move |evt: MidiMessageEvent| {
+            if let Ok(data) = evt.data() {
+                let _ = send.try_send(TimedMessage { timestamp: evt.time_stamp(), message: Message::parse(&data) });
+            }
+        });
+
+        let listener: &js_sys::Function;
+        cfg_if::cfg_if! {
+            if #[cfg(debug_assertions)] {
+                listener = resolve.as_ref().dyn_ref().unwrap();
+            } else {
+                listener = resolve.as_ref().unchecked_ref();
+            }
+        }
+
+        input.inner.add_event_listener_with_callback("midimessage", listener)?;
+        return Ok(Self {
+            inner: input.inner.clone(),
+            resolve,
+            recv
+        })
+    }
+}
+
+impl Stream for MessageWatcher {
+    type Item = TimedMessage;
+
+    #[inline]
+    fn poll_next(mut self: std::pin::Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Option<Self::Item>> {
+        self.recv.poll_next_unpin(cx)
+    }
+}
+
+impl Drop for MessageWatcher {
+    fn drop(&mut self) {
+        let listener: &js_sys::Function;
+        cfg_if::cfg_if! {
+            if #[cfg(debug_assertions)] {
+                listener = self.resolve.as_ref().dyn_ref().unwrap();
+            } else {
+                listener = self.resolve.as_ref().unchecked_ref();
+            }
+        }
+
+        let _ = self.inner.remove_event_listener_with_callback("midimessage", listener);
+    }
+}
+
+#[inline]
+fn map_values (map: &js_sys::Map) -> impl Iterator<Item = JsValue> {
+    return map.values().into_iter().filter_map(|x| x.ok())
+}
@@ -1,19 +1,18 @@
-use std::{time::{Duration}};
+use std::{time::{Duration}, rc::Rc, cell::{Cell, RefCell}, collections::VecDeque, future::Future, pin::Pin, task::{Context, Poll}};
+use futures::{FutureExt, future::{select, Either}};
 use into_string::IntoString;
+use wasm_bindgen::{JsCast, JsValue, prelude::Closure};
 use web_sys::{NotificationOptions, NotificationPermission};
-use crate::{Result};
-
-pub(crate) enum Delay {
-    Duration (Duration),
-    Date (chrono::DateTime<chrono::Utc>)
-}
+use crate::{Result, Error, ErrorKind, sync::{one_shot, ShotReceiver}, time::{Deadline, long_timeout}};
 
 /// Notification builder.
 pub struct Notification {
     pub(crate) title: String,
     pub(crate) body: Option<String>,
-    pub(crate) open: Option<Delay>,
-    pub(crate) close: Option<Delay>
+    pub(crate) data: Option<JsValue>,
+    pub(crate) tag: Option<String>,
+    pub(crate) open: Option<Deadline>,
+    pub(crate) close: Option<Deadline>
 }
 
 impl Notification {
@@ -23,6 +22,8 @@ impl Notification {
         Self {
             title: title.into_string(),
             body: None,
+            data: None,
+            tag: None,
             open: None,
             close: None
         }
@@ -35,18 +36,32 @@ impl Notification {
         self   
     }
 
+    /// Attaches a data payload to the notification, returned by it's [`NotificationHandle`]
+    #[inline]
+    pub fn data (mut self, data: JsValue) -> Self {
+        self.data = Some(data);
+        self
+    }
+
+    /// Sets the notification's tag. Notifications with the same tag replace each other instead of stacking.
+    #[inline]
+    pub fn tag (mut self, tag: impl IntoString) -> Self {
+        self.tag = Some(tag.into_string());
+        self
+    }
+
     /// Makes the notification fire with the specified delay after [`spawn`](Notification::spawn) is called.
     /// By default, notifiactions don't have a delay.
     #[inline]
     pub fn fire_after (mut self, delay: Duration) -> Self {
-        self.open = Some(Delay::Duration(delay));
+        self.open = Some(Deadline::After(delay));
         self
     }
 
     /// Sets a new date for the notification to fire at.
     /// By default, notifiactions don't have a delay.
     pub fn fire_date<Tz: chrono::TimeZone> (mut self, date: chrono::DateTime<Tz>) -> Self {
-        self.open = Some(Delay::Date(date.with_timezone(&chrono::Utc)));
+        self.open = Some(Deadline::from(date));
         self
     }
 
@@ -54,49 +69,157 @@ impl Notification {
     /// By default, notifiactions don't close automatically.
     #[inline]
     pub fn close_after (mut self, delay: Duration) -> Self {
-        self.close = Some(Delay::Duration(delay));
+        self.close = Some(Deadline::After(delay));
         self
     }
 
     /// Makes the notification close with the specified delay after it's fired.
     /// By default, notifiactions don't close automatically.
     pub fn close_date<Tz: chrono::TimeZone> (mut self, date: chrono::DateTime<Tz>) -> Self {
-        self.close = Some(Delay::Date(date.with_timezone(&chrono::Utc)));
+        self.close = Some(Deadline::from(date));
         self
     }
 
     /// Spawns a [`Future`](std::future::Future) that will wait for the specified fire delay, show the notification, and wait the specified close delay before closing it.
     /// 
+    /// The returned handle resolves when the user interacts with the notification, or when it's closed.
+    /// 
     /// # Panics
     /// The spawned future will panic if the user doesn't grant permission to show notifications.
-    pub fn spawn (self) {
-        async fn wait_delay (delay: Option<Delay>) -> Result<()> {
+    /// Use [`spawn_catch`](Notification::spawn_catch) to receive the denial as an error instead.
+    #[inline]
+    pub fn spawn (self) -> NotificationHandle {
+        return self.spawn_inner(true)
+    }
+
+    /// Spawns a [`Future`](std::future::Future) that will wait for the specified fire delay, show the notification, and wait the specified close delay before closing it.
+    /// 
+    /// Unlike [`spawn`](Notification::spawn), if the user doesn't grant permission to show notifications, the returned handle will resolve to an error
+    /// of kind [`Permission`](ErrorKind::Permission).
+    #[inline]
+    pub fn spawn_catch (self) -> NotificationHandle {
+        return self.spawn_inner(false)
+    }
+
+    fn spawn_inner (self, panic: bool) -> NotificationHandle {
+        async fn wait_delay (delay: Option<Deadline>) -> Result<()> {
             if let Some(delay) = delay {
-                let delay = match delay {
-                    Delay::Duration(dur) => dur,
-                    Delay::Date(date) => (date - chrono::Utc::now()).to_std().unwrap()
-                };
-                crate::time::sleep(delay)?.await
+                long_timeout(delay)?.await?;
             }
             
             return Ok(())
         }
 
-        wasm_bindgen_futures::spawn_local(async move {
-            if !get_permision().await.is_ok_and(core::convert::identity) {
-                panic!("Notification access denied");
+        async fn run (this: Notification, panic: bool) -> Result<NotificationOutcome> {
+            if !get_permision().await? {
+                if panic { panic!("Notification access denied") }
+                return Err(Error::new(ErrorKind::Permission, "Notification access denied"))
             }
             
-            let mut options = NotificationOptions::new();
-            if let Some(body) = self.body {
-                options.body(&body);
+            wait_delay(this.open).await?;
+            let notification = this.show()?;
+
+            let (send, recv) = one_shot();
+            let send = Rc::new(send);
+
+            let click_send = send.clone();
+            let on_click = Closure::<dyn FnMut(web_sys::Event)>::new(move |evt: web_sys::Event| {
+                let data = evt.target().map(|x| x.unchecked_into::<web_sys::Notification>().data()).unwrap_or(JsValue::UNDEFINED);
+                let _ = click_send.try_send(NotificationOutcome::Clicked(data));
+            });
+
+            let on_close = Closure::<dyn FnMut(web_sys::Event)>::new(move |evt: web_sys::Event| {
+                let data = evt.target().map(|x| x.unchecked_into::<web_sys::Notification>().data()).unwrap_or(JsValue::UNDEFINED);
+                let _ = send.try_send(NotificationOutcome::Dismissed(data));
+            });
+
+            notification.set_onclick(Some(on_click.as_ref().unchecked_ref()));
+            notification.set_onclose(Some(on_close.as_ref().unchecked_ref()));
+
+            let outcome = match this.close {
+                Some(close) => {
+                    let close = Box::pin(wait_delay(Some(close)));
+                    match select(recv, close).await {
+                        Either::Left((outcome, _)) => outcome,
+                        Either::Right((res, _)) => {
+                            res?;
+                            Some(NotificationOutcome::Closed(notification.data()))
+                        }
+                    }
+                },
+                None => recv.await
+            };
+
+            notification.set_onclick(None);
+            notification.set_onclose(None);
+            if let Some(NotificationOutcome::Clicked(_) | NotificationOutcome::Closed(_)) = outcome {
+                notification.close();
             }
 
-            wait_delay(self.open).await;
-            let notification: web_sys::Notification = web_sys::Notification::new_with_options(&self.title, &options).unwrap();
-            wait_delay(self.close).await;
-            notification.close();
+            return outcome.ok_or_else(|| Error::new(ErrorKind::Abort, "notification listeners were dropped"))
+        }
+
+        let (send, inner) = one_shot();
+        wasm_bindgen_futures::spawn_local(async move {
+            let _ = send.try_send(run(self, panic).await);
         });
+
+        return NotificationHandle { inner }
+    }
+
+    /// Shows the notification immediately, ignoring it's delays
+    fn show (&self) -> Result<web_sys::Notification> {
+        let mut options = NotificationOptions::new();
+        if let Some(ref body) = self.body {
+            options.body(body);
+        }
+        if let Some(ref data) = self.data {
+            options.data(data);
+        }
+        if let Some(ref tag) = self.tag {
+            options.tag(tag);
+        }
+
+        return Ok(web_sys::Notification::new_with_options(&self.title, &options)?)
+    }
+}
+
+/// Result of the user's interaction with a notification, with the notification's data payload
+#[derive(Debug, Clone, PartialEq)]
+pub enum NotificationOutcome {
+    /// The user clicked the notification
+    Clicked (JsValue),
+    /// The notification was closed after it's close delay
+    Closed (JsValue),
+    /// The notification was dismissed, by the user or the platform
+    Dismissed (JsValue)
+}
+
+impl NotificationOutcome {
+    /// Returns the data payload of the notification
+    #[inline]
+    pub fn data (&self) -> &JsValue {
+        return match self {
+            Self::Clicked(data) | Self::Closed(data) | Self::Dismissed(data) => data
+        }
+    }
+}
+
+/// Handle of a spawned [`Notification`], resolving to the user's interaction with it
+pub struct NotificationHandle {
+    inner: ShotReceiver<Result<NotificationOutcome>>
+}
+
+impl Future for NotificationHandle {
+    type Output = Result<NotificationOutcome>;
+
+    #[inline]
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        return match self.inner.poll_unpin(cx) {
+            Poll::Ready(Some(result)) => Poll::Ready(result),
+            Poll::Ready(None) => Poll::Ready(Err(Error::new(ErrorKind::Abort, "notification task was dropped"))),
+            Poll::Pending => Poll::Pending
+        }
     }
 }
 
@@ -115,4 +238,141 @@ pub async fn get_permision () -> Result<bool> {
             _ => unreachable!()
         }
     }
-}
\ No newline at end of file
+}
+/// App-level manager of notifications.
+///
+/// The center limits how many of it's notifications are visible at the same time, queueing the rest until a visible one is closed.
+/// Notifications with the same [tag](Notification::tag) are coalesced, with the newest replacing the older one, whether it's visible or queued.
+///
+/// The fire and close delays of notifications shown through a center are ignored.
+/// Queued notifications are discarded when the center is dropped, but visible ones remain open.
+pub struct Center {
+    inner: Rc<CenterInner>
+}
+
+struct CenterInner {
+    max_visible: usize,
+    next_id: Cell<u64>,
+    visible: RefCell<Vec<Visible>>,
+    queue: RefCell<VecDeque<Notification>>
+}
+
+struct Visible {
+    id: u64,
+    tag: Option<String>,
+    notification: web_sys::Notification
+}
+
+impl Center {
+    /// Creates a new notification center that shows at most `max_visible` notifications at the same time
+    #[inline]
+    pub fn new (max_visible: usize) -> Self {
+        return Self {
+            inner: Rc::new(CenterInner {
+                max_visible: usize::max(max_visible, 1),
+                next_id: Cell::new(0),
+                visible: RefCell::new(Vec::new()),
+                queue: RefCell::new(VecDeque::new())
+            })
+        }
+    }
+
+    /// Requests the user's permission to show notifications, and creates a new notification center if it's granted.
+    /// Otherwise, an error of kind [`Permission`](ErrorKind::Permission) is returned.
+    pub async fn with_permission (max_visible: usize) -> Result<Self> {
+        if !get_permision().await? {
+            return Err(Error::new(ErrorKind::Permission, "Notification access denied"))
+        }
+        return Ok(Self::new(max_visible))
+    }
+
+    /// Shows the notification if the maximum number of visible notifications hasn't been reached, queueing it otherwise.
+    ///
+    /// Returns an error of kind [`Permission`](ErrorKind::Permission) if the user hasn't granted permission to show notifications.
+    pub fn show (&self, notification: Notification) -> Result<()> {
+        if web_sys::Notification::permission() != NotificationPermission::Granted {
+            return Err(Error::new(ErrorKind::Permission, "Notification access denied"))
+        }
+
+        if let Some(ref tag) = notification.tag {
+            let mut queue = self.inner.queue.borrow_mut();
+            if let Some(queued) = queue.iter_mut().find(|x| x.tag.as_ref() == Some(tag)) {
+                *queued = notification;
+                return Ok(())
+            }
+            drop(queue);
+
+            let mut visible = self.inner.visible.borrow_mut();
+            if let Some(idx) = visible.iter().position(|x| x.tag.as_ref() == Some(tag)) {
+                let old = visible.remove(idx);
+                drop(visible);
+                old.notification.close();
+                return CenterInner::display(&self.inner, notification)
+            }
+        }
+
+        if self.inner.visible.borrow().len() < self.inner.max_visible {
+            return CenterInner::display(&self.inner, notification)
+        }
+
+        self.inner.queue.borrow_mut().push_back(notification);
+        return Ok(())
+    }
+
+    /// Returns the number of visible notifications
+    #[inline]
+    pub fn visible_count (&self) -> usize {
+        return self.inner.visible.borrow().len()
+    }
+
+    /// Returns the number of queued notifications
+    #[inline]
+    pub fn queued_count (&self) -> usize {
+        return self.inner.queue.borrow().len()
+    }
+
+    /// Discards every queued notification and closes every visible one
+    pub fn close_all (&self) {
+        self.inner.queue.borrow_mut().clear();
+        let visible = core::mem::take(&mut *self.inner.visible.borrow_mut());
+        for entry in visible {
+            entry.notification.close();
+        }
+    }
+}
+
+impl CenterInner {
+    fn display (this: &Rc<Self>, notification: Notification) -> Result<()> {
+        let id = this.next_id.get();
+        this.next_id.set(id + 1);
+
+        let shown = notification.show()?;
+        let weak = Rc::downgrade(this);
+        let on_close = Closure::once_into_js(move || {
+            if let Some(this) = weak.upgrade() {
+                this.on_close(id);
+            }
+        });
+
+        shown.set_onclose(Some(on_close.unchecked_ref()));
+        this.visible.borrow_mut().push(Visible { id, tag: notification.tag, notification: shown });
+        return Ok(())
+    }
+
+    /// Frees the slot of a closed notification, showing the next queued one.
+    /// Notifications closed by the center itself have already been removed, so they don't free a slot.
+    fn on_close (self: Rc<Self>, id: u64) {
+        let mut visible = self.visible.borrow_mut();
+        let idx = match visible.iter().position(|x| x.id == id) {
+            Some(idx) => idx,
+            None => return
+        };
+        visible.remove(idx);
+        drop(visible);
+
+        let next = self.queue.borrow_mut().pop_front();
+        if let Some(next) = next {
+            let _ = Self::display(&self, next);
+        }
+    }
+}
@@ -1,9 +1,12 @@
-use std::{task::{Poll}};
+use std::{task::{Poll}, time::Duration};
 use futures::{Stream, StreamExt};
-use wasm_bindgen::{prelude::Closure, JsValue};
+use wasm_bindgen::{prelude::Closure};
 use web_sys::{DeviceOrientationEvent, DeviceMotionEvent, DeviceAcceleration, DeviceRotationRate};
-use crate::{Result, utils::{LocalReceiver, local_channel}, math::Vec3d, scope::GLOBAL_SCOPE, sync::one_shot};
+use crate::{Result, Error, ErrorKind, utils::{LocalReceiver, local_channel}, math::{Vec3d, Quatd}, scope::{GLOBAL_SCOPE, window_only}, sync::one_shot, time::{FirstEventTimeout, first_event_timeout}, diagnostics::Tracked};
 use wasm_bindgen::JsCast;
+use docfg::docfg;
+#[cfg(target_feature = "atomics")]
+use crate::sync::{DropHandle, drop_local};
 
 /// Three angles that represent rotation in three dimensions
 #[derive(Debug, Clone, Copy, PartialEq, Default)]
@@ -24,21 +27,58 @@ impl EulerAngles {
     }
 }
 
+/// [`EulerAngles`] as reported by the browser, where any of the angles may be unavailable
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct RawEulerAngles {
+    pub alpha: Option<f64>,
+    pub beta: Option<f64>,
+    pub gamma: Option<f64>
+}
+
+impl RawEulerAngles {
+    /// Returns the angles if all of them are available
+    #[inline]
+    pub fn complete (self) -> Option<EulerAngles> {
+        return Some(EulerAngles { alpha: self.alpha?, beta: self.beta?, gamma: self.gamma? })
+    }
+
+    /// Returns the angles, with the unavailable ones set to zero
+    #[inline]
+    pub fn unwrap_or_default (self) -> EulerAngles {
+        return EulerAngles {
+            alpha: self.alpha.unwrap_or_default(),
+            beta: self.beta.unwrap_or_default(),
+            gamma: self.gamma.unwrap_or_default()
+        }
+    }
+
+    /// Returns `true` if none of the angles is available
+    #[inline]
+    pub fn is_empty (self) -> bool {
+        return self.alpha.is_none() && self.beta.is_none() && self.gamma.is_none()
+    }
+}
+
 /// Information about the rotation of the device
 #[derive(Debug, Clone, PartialEq, Default)]
 #[non_exhaustive]
 pub struct Orientation {
     /// Indicates whether or not the device is providing orientation data absolutely (that is, in reference to the Earth's coordinate frame) or using some arbitrary frame determined by the device.
     pub absolute: bool,
-    pub angles: EulerAngles
+    /// Angles of the device, with the unavailable ones set to zero
+    pub angles: EulerAngles,
+    /// Angles of the device as reported by the browser, with the unavailable ones set to `None`
+    pub raw_angles: RawEulerAngles,
+    webkit_compass_heading: Option<f64>
 }
 
 impl Orientation {
     /// Returns the device's current orientation information
     pub async fn current () -> Result<Self> {
+        check_support("DeviceOrientationEvent")?;
         let (send, result) = one_shot();
         let f = Closure::<dyn FnMut(DeviceOrientationEvent)>::new(move |evt: DeviceOrientationEvent| {
-            send.try_send(Orientation::from(evt)).unwrap();
+            let _ = send.try_send(Orientation::try_from(evt).ok());
         });
 
         let listener: &js_sys::Function;
@@ -50,18 +90,58 @@ impl Orientation {
             }
         }
         
-        let win = web_sys::window().unwrap();
+        let win = window_only("Device Orientation API")?;
         win.add_event_listener_with_callback_and_bool("deviceorientation", listener, true)?;
         let result = result.await;
         win.remove_event_listener_with_callback_and_bool("deviceorientation", listener, true)?;
-        return result.ok_or_else(|| JsValue::from_str("Error obtaining current orientation"));
+        return match result {
+            Some(Some(orientation)) => Ok(orientation),
+            Some(None) => Err(Error::new(ErrorKind::NotSupported, "the device doesn't provide orientation data")),
+            None => Err(Error::new(ErrorKind::Js, "Error obtaining current orientation"))
+        }
     }
 
-    /// Returns a watcher over the device's rotation
+    /// Returns a watcher over the device's rotation.
+    /// 
+    /// Fails if the Device Orientation API isn't supported. Devices without an orientation sensor never produce any item,
+    /// which can be detected with [`first_event_timeout`](OrientationWatcher::first_event_timeout).
     #[inline]
     pub fn watch () -> Result<OrientationWatcher> {
         return OrientationWatcher::new()
     }
+
+    /// Returns a watcher over the device's rotation referenced to the Earth's coordinate frame, whose orientations
+    /// provide a [`compass_heading`](Orientation::compass_heading) on devices with a magnetometer.
+    #[inline]
+    pub fn watch_absolute () -> Result<OrientationWatcher> {
+        return OrientationWatcher::absolute()
+    }
+
+    /// Returns a thread-safe watcher over the device's rotation, that can be sent to other threads
+    #[docfg(target_feature = "atomics")]
+    #[inline]
+    pub fn watch_send () -> Result<SendOrientationWatcher> {
+        return SendOrientationWatcher::new()
+    }
+
+    /// Returns the device's compass heading, in degrees clockwise from north, ranging from 0 (inclusive) to 360 (exclusive).
+    /// 
+    /// On WebKit, the heading is obtained from `webkitCompassHeading`. On other platforms, it's computed from
+    /// [`alpha`](EulerAngles::alpha), which is only referenced to north if the orientation is [`absolute`](Orientation::absolute).
+    /// Chrome and Android only report absolute orientations through `deviceorientationabsolute` events, so orientations
+    /// should be obtained with [`watch_absolute`](Orientation::watch_absolute). Returns `None` if the heading can't be determined.
+    #[inline]
+    pub fn compass_heading (&self) -> Option<f64> {
+        if let Some(heading) = self.webkit_compass_heading {
+            return Some(heading.rem_euclid(360.))
+        }
+
+        let alpha = match self.raw_angles.alpha {
+            Some(alpha) if self.absolute && !alpha.is_nan() => alpha,
+            _ => return None
+        };
+        return Some((360. - alpha).rem_euclid(360.))
+    }
 }
 
 /// A watcher for a device's [`Orientation`].
@@ -70,35 +150,45 @@ impl Orientation {
 /// 
 /// When droped, the watcher will be closed, releasing all the memory of it's closure, avoiding a memory leak.
 pub struct OrientationWatcher {
-    _resolve: Closure<dyn FnMut(DeviceOrientationEvent)>,
+    _listener: RawListener,
     recv: LocalReceiver<Orientation>
 }
 
 impl OrientationWatcher {
     #[inline]
     pub fn new () -> Result<Self> {
-        let (send, recv) = local_channel();
-        let resolve = Closure::<dyn FnMut(DeviceOrientationEvent)>::new(move |evt: DeviceOrientationEvent| {
-            let _ = send.try_send(Orientation::from(evt));
-        });
+        return Self::with_event("deviceorientation")
+    }
 
-        let listener: &js_sys::Function;
-        cfg_if::cfg_if! {
-            if #[cfg(debug_assertions)] {
-                listener = resolve.as_ref().dyn_ref().unwrap();
-            } else {
-                listener = resolve.as_ref().unchecked_ref();
-            }
-        }
+    /// Creates a watcher over the device's rotation referenced to the Earth's coordinate frame.
+    /// 
+    /// Listens to `deviceorientationabsolute` events where available (like Chrome and Android), falling back
+    /// to `deviceorientation` events otherwise (like Safari, which provides the compass heading through them).
+    pub fn absolute () -> Result<Self> {
+        let absolute = js_sys::Reflect::has(&js_sys::global(), &wasm_bindgen::JsValue::from_str("ondeviceorientationabsolute"))?;
+        return Self::with_event(if absolute { "deviceorientationabsolute" } else { "deviceorientation" })
+    }
 
-        let win = web_sys::window().unwrap();
-        win.add_event_listener_with_callback_and_bool("deviceorientation", listener, true)?;
+    fn with_event (ty: &'static str) -> Result<Self> {
+        check_support("DeviceOrientationEvent")?;
+        let (send, recv) = local_channel();
+        let listener = RawListener::new(ty, move |evt: DeviceOrientationEvent| {
+            if let Ok(orientation) = Orientation::try_from(evt) {
+                let _ = send.try_send(orientation);
+            }
+        })?;
 
         return Ok(Self {
-            _resolve: resolve,
+            _listener: listener,
             recv,
         })
     }
+
+    /// Returns a stream that yields an error of kind [`NotSupported`](ErrorKind::NotSupported) if no orientation is received within `dur`
+    #[inline]
+    pub fn first_event_timeout (self, dur: Duration) -> Result<FirstEventTimeout<Self>> {
+        return first_event_timeout(self, dur)
+    }
 }
 
 impl Stream for OrientationWatcher {
@@ -110,21 +200,43 @@ impl Stream for OrientationWatcher {
     }
 }
 
-impl Drop for OrientationWatcher {
-    fn drop(&mut self) {
-        let listener: &js_sys::Function;
-        cfg_if::cfg_if! {
-            if #[cfg(debug_assertions)] {
-                listener = self._resolve.as_ref().dyn_ref().unwrap();
-            } else {
-                listener = self._resolve.as_ref().unchecked_ref();
+/// A thread-safe watcher for a device's [`Orientation`].
+/// 
+/// The orientation is watched from the thread that created the watcher, but the watcher itself can be sent to other threads.
+/// 
+/// When droped, the watcher will be closed from it's original thread, releasing all the memory of it's closure.
+#[docfg(target_feature = "atomics")]
+pub struct SendOrientationWatcher {
+    _listener: DropHandle,
+    recv: async_channel::Receiver<Orientation>
+}
+
+#[docfg(target_feature = "atomics")]
+impl SendOrientationWatcher {
+    #[inline]
+    pub fn new () -> Result<Self> {
+        check_support("DeviceOrientationEvent")?;
+        let (send, recv) = async_channel::unbounded();
+        let listener = RawListener::new("deviceorientation", move |evt: DeviceOrientationEvent| {
+            if let Ok(orientation) = Orientation::try_from(evt) {
+                let _ = send.try_send(orientation);
             }
-        }
+        })?;
 
+        return Ok(Self {
+            _listener: drop_local(listener),
+            recv,
+        })
+    }
+}
+
+#[docfg(target_feature = "atomics")]
+impl Stream for SendOrientationWatcher {
+    type Item = Orientation;
 
-        GLOBAL_SCOPE.with(|scope| 
-            scope.remove_event_listener_with_callback_and_bool("deviceorientation", listener, true)
-        ).unwrap();
+    #[inline]
+    fn poll_next(mut self: std::pin::Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Option<Self::Item>> {
+        self.recv.poll_next_unpin(cx)
     }
 }
 
@@ -132,26 +244,36 @@ impl Drop for OrientationWatcher {
 #[derive(Debug, Clone)]
 #[non_exhaustive]
 pub struct Motion {
-    /// Amount of acceleration recorded by the device, in meters per second squared (m/s²).
+    /// Amount of acceleration recorded by the device, in meters per second squared (m/s²), if available.
     /// The acceleration value does not include the effect of the gravity force, in contrast to `acceleration_with_gravity`.
-    pub acceleration: Vec3d,
+    pub acceleration: Option<Vec3d>,
     /// Amount of acceleration recorded by the device, in meters per second squared (m/s²).
     /// Unlike `acceleration` which compensates for the influence of gravity,
     /// its value is the sum of the acceleration of the device as induced by the user and an acceleration equal and opposite to that caused by gravity.
     /// In other words, it measures the g-force. In practice, this value represents the raw data measured by an accelerometer.
-    pub acceleration_with_gravity: Vec3d,
-    /// Interval, in milliseconds, at which data is obtained from the underlying hardware.
-    pub interval: f64,
+    /// 
+    /// `None` if the value (or any of it's axes) isn't available.
+    pub acceleration_with_gravity: Option<Vec3d>,
+    /// Interval, in milliseconds, at which data is obtained from the underlying hardware, if available.
+    pub interval: Option<f64>,
     /// Rate at which the device is rotating around each of its axes in degrees per second.
     pub rotation_rate: Option<EulerAngles>
 }
 
 impl Motion {
+    /// Returns `true` if the motion carries no data at all, as reported by devices without motion sensors
+    #[inline]
+    pub fn is_empty (&self) -> bool {
+        return self.acceleration.is_none() && self.acceleration_with_gravity.is_none() && self.rotation_rate.is_none()
+    }
+
     /// Returns the device's current motion information
     pub async fn current () -> Result<Self> {
+        check_support("DeviceMotionEvent")?;
         let (send, result) = one_shot();
         let f = Closure::<dyn FnMut(DeviceMotionEvent)>::new(move |evt: DeviceMotionEvent| {
-            let _ = send.try_send(Motion::from(evt));
+            let motion = Motion::from(evt);
+            let _ = send.try_send((!motion.is_empty()).then_some(motion));
         });
 
         let listener: &js_sys::Function;
@@ -163,18 +285,32 @@ impl Motion {
             }
         }
         
-        let win = web_sys::window().unwrap();
+        let win = window_only("Device Orientation API")?;
         win.add_event_listener_with_callback_and_bool("devicemotion", listener, true)?;
         let result = result.await;
         win.remove_event_listener_with_callback_and_bool("devicemotion", listener, true)?;
-        return result.ok_or_else(|| JsValue::from_str("Error obtaining current motion"));
+        return match result {
+            Some(Some(motion)) => Ok(motion),
+            Some(None) => Err(Error::new(ErrorKind::NotSupported, "the device doesn't provide motion data")),
+            None => Err(Error::new(ErrorKind::Js, "Error obtaining current motion"))
+        }
     }
 
-    /// Returns a watcher over the device's motion
+    /// Returns a watcher over the device's motion.
+    /// 
+    /// Fails if the Device Orientation API isn't supported. Devices without motion sensors never produce any item,
+    /// which can be detected with [`first_event_timeout`](MotionWatcher::first_event_timeout).
     #[inline]
     pub fn watch () -> Result<MotionWatcher> {
         return MotionWatcher::new()
     }
+
+    /// Returns a thread-safe watcher over the device's motion, that can be sent to other threads
+    #[docfg(target_feature = "atomics")]
+    #[inline]
+    pub fn watch_send () -> Result<SendMotionWatcher> {
+        return SendMotionWatcher::new()
+    }
 }
 
 /// A watcher for a device's [`Motion`].
@@ -183,8 +319,7 @@ impl Motion {
 /// 
 /// When droped, the watcher will be closed, releasing all the memory of it's closure, avoiding a memory leak.
 pub struct MotionWatcher {
-    #[allow(unused)]
-    resolve: Closure<dyn FnMut(DeviceMotionEvent)>,
+    _listener: RawListener,
     recv: LocalReceiver<Motion>
 }
 
@@ -192,28 +327,26 @@ impl MotionWatcher {
     /// Creates a new [`MotionWatcher`]
     #[inline]
     pub fn new () -> Result<Self> {
+        check_support("DeviceMotionEvent")?;
         let (send, recv) = local_channel();
-        let resolve = Closure::<dyn FnMut(DeviceMotionEvent)>::new(move |evt: DeviceMotionEvent| {
-            let _ = send.try_send(Motion::from(evt));
-        });
-
-        let listener: &js_sys::Function;
-        cfg_if::cfg_if! {
-            if #[cfg(debug_assertions)] {
-                listener = resolve.as_ref().dyn_ref().unwrap();
-            } else {
-                listener = resolve.as_ref().unchecked_ref();
+        let listener = RawListener::new("devicemotion", move |evt: DeviceMotionEvent| {
+            let motion = Motion::from(evt);
+            if !motion.is_empty() {
+                let _ = send.try_send(motion);
             }
-        }
-
-        let win = web_sys::window().unwrap();
-        win.add_event_listener_with_callback_and_bool("devicemotion", listener, true)?;
+        })?;
 
         return Ok(Self {
-            resolve,
+            _listener: listener,
             recv,
         })
     }
+
+    /// Returns a stream that yields an error of kind [`NotSupported`](ErrorKind::NotSupported) if no motion is received within `dur`
+    #[inline]
+    pub fn first_event_timeout (self, dur: Duration) -> Result<FirstEventTimeout<Self>> {
+        return first_event_timeout(self, dur)
+    }
 }
 
 impl Stream for MotionWatcher {
@@ -225,73 +358,195 @@ impl Stream for MotionWatcher {
     }
 }
 
-impl Drop for MotionWatcher {
+/// A thread-safe watcher for a device's [`Motion`].
+/// 
+/// The motion is watched from the thread that created the watcher, but the watcher itself can be sent to other threads.
+/// 
+/// When droped, the watcher will be closed from it's original thread, releasing all the memory of it's closure.
+#[docfg(target_feature = "atomics")]
+pub struct SendMotionWatcher {
+    _listener: DropHandle,
+    recv: async_channel::Receiver<Motion>
+}
+
+#[docfg(target_feature = "atomics")]
+impl SendMotionWatcher {
+    /// Creates a new [`SendMotionWatcher`]
+    #[inline]
+    pub fn new () -> Result<Self> {
+        check_support("DeviceMotionEvent")?;
+        let (send, recv) = async_channel::unbounded();
+        let listener = RawListener::new("devicemotion", move |evt: DeviceMotionEvent| {
+            let motion = Motion::from(evt);
+            if !motion.is_empty() {
+                let _ = send.try_send(motion);
+            }
+        })?;
+
+        return Ok(Self {
+            _listener: drop_local(listener),
+            recv,
+        })
+    }
+}
+
+#[docfg(target_feature = "atomics")]
+impl Stream for SendMotionWatcher {
+    type Item = Motion;
+
+    #[inline]
+    fn poll_next(mut self: std::pin::Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Option<Self::Item>> {
+        self.recv.poll_next_unpin(cx)
+    }
+}
+
+/// Fails if the specified event interface isn't available on the current global scope
+#[inline]
+fn check_support (interface: &str) -> Result<()> {
+    if js_sys::Reflect::has(&js_sys::global(), &wasm_bindgen::JsValue::from_str(interface))? {
+        return Ok(())
+    }
+    return Err(Error::new(ErrorKind::NotSupported, "Device Orientation API is not supported"))
+}
+
+/// Listener registered on the window, removed when droped
+struct RawListener {
+    ty: &'static str,
+    closure: Closure<dyn FnMut(web_sys::Event)>,
+    _tracked: Tracked
+}
+
+impl RawListener {
+    fn new<E: JsCast, F: 'static + FnMut(E)> (ty: &'static str, mut f: F) -> Result<Self> {
+        let closure = Closure::<dyn FnMut(web_sys::Event)>::new(move |evt: web_sys::Event| {
+            f(evt.unchecked_into::<E>())
+        });
+
+        let listener: &js_sys::Function;
+        cfg_if::cfg_if! {
+            if #[cfg(debug_assertions)] {
+                listener = closure.as_ref().dyn_ref().unwrap();
+            } else {
+                listener = closure.as_ref().unchecked_ref();
+            }
+        }
+
+        let win = window_only("Device Orientation API")?;
+        win.add_event_listener_with_callback_and_bool(ty, listener, true)?;
+        return Ok(Self { ty, closure, _tracked: Tracked::new("orient::watch") })
+    }
+}
+
+impl Drop for RawListener {
     fn drop(&mut self) {
         let listener: &js_sys::Function;
         cfg_if::cfg_if! {
             if #[cfg(debug_assertions)] {
-                listener = self.resolve.as_ref().dyn_ref().unwrap();
+                listener = self.closure.as_ref().dyn_ref().unwrap();
             } else {
-                listener = self.resolve.as_ref().unchecked_ref();
+                listener = self.closure.as_ref().unchecked_ref();
             }
         }
 
-        let win = web_sys::window().unwrap();
-        win.remove_event_listener_with_callback_and_bool("devicemotion", listener, true).unwrap();
+        let _ = GLOBAL_SCOPE.with(|scope| 
+            scope.remove_event_listener_with_callback_and_bool(self.ty, listener, true)
+        );
     }
 }
 
 impl From<&DeviceMotionEvent> for Motion {
+    /// Converts the event into a [`Motion`], with any unavailable value (or value with an unavailable axis) set to `None`
     #[inline]
     fn from(value: &DeviceMotionEvent) -> Self {
         return Self {
-            acceleration: value.acceleration().unwrap().into(),
-            acceleration_with_gravity: value.acceleration_including_gravity().unwrap().into(),
-            interval: value.interval().unwrap(),
-            rotation_rate: value.rotation_rate().map(EulerAngles::from)
+            acceleration: value.acceleration().and_then(|x| Vec3d::try_from(x).ok()),
+            acceleration_with_gravity: value.acceleration_including_gravity().and_then(|x| Vec3d::try_from(x).ok()),
+            interval: value.interval(),
+            rotation_rate: value.rotation_rate().and_then(|x| RawEulerAngles::from(x).complete())
         }
     }
 }
 
-impl From<&DeviceAcceleration> for Vec3d {
+impl TryFrom<&DeviceAcceleration> for Vec3d {
+    type Error = Error;
+
+    /// Fails if any of the acceleration's axes is unavailable
     #[inline]
-    fn from(value: &DeviceAcceleration) -> Self {
-        return Self::new(value.x().unwrap(), value.y().unwrap(), value.z().unwrap())
+    fn try_from(value: &DeviceAcceleration) -> Result<Self> {
+        return match (value.x(), value.y(), value.z()) {
+            (Some(x), Some(y), Some(z)) => Ok(Self::new(x, y, z)),
+            _ => Err(Error::new(ErrorKind::NotSupported, "acceleration axis is unavailable"))
+        }
     }
 }
 
-impl From<&DeviceOrientationEvent> for Orientation {
+impl TryFrom<&DeviceOrientationEvent> for Orientation {
+    type Error = Error;
+
+    /// Converts the event into an [`Orientation`], preserving the unavailable angles as `None`.
+    /// 
+    /// Fails if none of the angles is available, as with the single event fired by browsers on devices without an orientation sensor.
     #[inline]
-    fn from(value: &DeviceOrientationEvent) -> Self {
-        Self {
-            absolute: value.absolute(),
-            angles: value.into()
+    fn try_from(value: &DeviceOrientationEvent) -> Result<Self> {
+        let raw_angles = RawEulerAngles::from(value);
+        if raw_angles.is_empty() {
+            return Err(Error::new(ErrorKind::NotSupported, "the device doesn't provide orientation data"))
         }
+
+        let webkit_compass_heading = js_sys::Reflect::get(value, &wasm_bindgen::JsValue::from_str("webkitCompassHeading")).ok()
+            .and_then(|x| x.as_f64());
+
+        return Ok(Self {
+            absolute: value.absolute(),
+            angles: raw_angles.unwrap_or_default(),
+            raw_angles,
+            webkit_compass_heading
+        })
     }
 }
 
-impl From<&DeviceOrientationEvent> for EulerAngles {
+impl From<&DeviceOrientationEvent> for RawEulerAngles {
     #[inline]
     fn from(value: &DeviceOrientationEvent) -> Self {
         Self {
-            alpha: value.alpha().unwrap(),
-            beta: value.beta().unwrap(),
-            gamma: value.gamma().unwrap()
+            alpha: value.alpha(),
+            beta: value.beta(),
+            gamma: value.gamma()
         }
     }
 }
 
-impl From<&DeviceRotationRate> for EulerAngles {
+impl From<&DeviceRotationRate> for RawEulerAngles {
     #[inline]
     fn from(value: &DeviceRotationRate) -> Self {
         Self {
-            alpha: value.alpha().unwrap(),
-            beta: value.beta().unwrap(),
-            gamma: value.gamma().unwrap()
+            alpha: value.alpha(),
+            beta: value.beta(),
+            gamma: value.gamma()
         }
     }
 }
 
+impl TryFrom<&DeviceOrientationEvent> for EulerAngles {
+    type Error = Error;
+
+    /// Fails if any of the angles is unavailable
+    #[inline]
+    fn try_from(value: &DeviceOrientationEvent) -> Result<Self> {
+        return RawEulerAngles::from(value).complete().ok_or_else(|| Error::new(ErrorKind::NotSupported, "orientation angle is unavailable"))
+    }
+}
+
+impl TryFrom<&DeviceRotationRate> for EulerAngles {
+    type Error = Error;
+
+    /// Fails if any of the angles is unavailable
+    #[inline]
+    fn try_from(value: &DeviceRotationRate) -> Result<Self> {
+        return RawEulerAngles::from(value).complete().ok_or_else(|| Error::new(ErrorKind::NotSupported, "rotation rate angle is unavailable"))
+    }
+}
+
 impl From<DeviceMotionEvent> for Motion {
     #[inline]
     fn from(value: DeviceMotionEvent) -> Self {
@@ -299,30 +554,201 @@ impl From<DeviceMotionEvent> for Motion {
     }
 }
 
-impl From<DeviceAcceleration> for Vec3d {
+impl TryFrom<DeviceAcceleration> for Vec3d {
+    type Error = Error;
+
     #[inline]
-    fn from(value: DeviceAcceleration) -> Self {
-        return Self::from(&value)
+    fn try_from(value: DeviceAcceleration) -> Result<Self> {
+        return Self::try_from(&value)
     }
 }
 
-impl From<DeviceOrientationEvent> for Orientation {
+impl TryFrom<DeviceOrientationEvent> for Orientation {
+    type Error = Error;
+
     #[inline]
-    fn from(value: DeviceOrientationEvent) -> Self {
-        return Orientation::from(&value)
+    fn try_from(value: DeviceOrientationEvent) -> Result<Self> {
+        return Orientation::try_from(&value)
     }
 }
 
-impl From<DeviceOrientationEvent> for EulerAngles {
+impl From<DeviceOrientationEvent> for RawEulerAngles {
     #[inline]
     fn from(value: DeviceOrientationEvent) -> Self {
-        return EulerAngles::from(&value)
+        return Self::from(&value)
     }
 }
 
-impl From<DeviceRotationRate> for EulerAngles {
+impl From<DeviceRotationRate> for RawEulerAngles {
     #[inline]
     fn from(value: DeviceRotationRate) -> Self {
         return Self::from(&value)
     }
-}
\ No newline at end of file
+}
+
+impl TryFrom<DeviceOrientationEvent> for EulerAngles {
+    type Error = Error;
+
+    #[inline]
+    fn try_from(value: DeviceOrientationEvent) -> Result<Self> {
+        return EulerAngles::try_from(&value)
+    }
+}
+
+impl TryFrom<DeviceRotationRate> for EulerAngles {
+    type Error = Error;
+
+    #[inline]
+    fn try_from(value: DeviceRotationRate) -> Result<Self> {
+        return Self::try_from(&value)
+    }
+}
+/// Options of [`filter`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FilterOptions {
+    /// Weight given to the rotation integrated from the gyroscope over the one reported by the orientation sensor, between 0 and 1.
+    /// Higher values result in smoother, but slower to correct, orientations.
+    pub gyro_weight: f64,
+    /// Smoothing factor of the low-pass filter used to estimate gravity, between 0 and 1.
+    /// Higher values result in a more stable, but slower to adapt, gravity estimate.
+    pub gravity_smoothing: f64
+}
+
+impl Default for FilterOptions {
+    #[inline]
+    fn default() -> Self {
+        return Self {
+            gyro_weight: 0.98,
+            gravity_smoothing: 0.8
+        }
+    }
+}
+
+/// Smoothed pose of the device, produced by [`filter`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FusedPose {
+    /// Rotation of the device
+    pub rotation: Quatd,
+    /// Acceleration of the device without the effect of gravity, in meters per second squared (m/s²)
+    pub linear_acceleration: Vec3d
+}
+
+/// Fuses the `orientation` and `motion` streams with a complementary filter, returning a stream of smoothed poses.
+/// 
+/// The rotation rate reported by `motion` is integrated and corrected with the absolute angles reported by `orientation`,
+/// while gravity is estimated by low-pass filtering the acceleration, and substracted from it.
+#[inline]
+pub fn filter<O, M> (orientation: O, motion: M, options: FilterOptions) -> PoseFilter<O, M> where
+    O: Unpin + Stream<Item = Orientation>,
+    M: Unpin + Stream<Item = Motion>
+{
+    return PoseFilter {
+        orientation: Some(orientation),
+        motion: Some(motion),
+        options,
+        rotation: None,
+        gravity: None,
+        linear_acceleration: Vec3d::default()
+    }
+}
+
+/// Stream returned by [`filter`]
+pub struct PoseFilter<O, M> {
+    orientation: Option<O>,
+    motion: Option<M>,
+    options: FilterOptions,
+    rotation: Option<Quatd>,
+    gravity: Option<Vec3d>,
+    linear_acceleration: Vec3d
+}
+
+impl<O, M> PoseFilter<O, M> {
+    fn update_orientation (&mut self, orientation: Orientation) {
+        let angles = match orientation.raw_angles.complete() {
+            Some(angles) => angles,
+            None => return
+        };
+        let measured = Quatd::from_device_angles(angles.alpha.to_radians(), angles.beta.to_radians(), angles.gamma.to_radians());
+
+        self.rotation = Some(match self.rotation {
+            Some(rotation) => rotation.slerp(measured, 1. - self.options.gyro_weight),
+            None => measured
+        });
+    }
+
+    fn update_motion (&mut self, motion: Motion) {
+        if let (Some(rotation), Some(rate), Some(interval)) = (self.rotation, motion.rotation_rate, motion.interval) {
+            let dt = interval / 1000.;
+            let omega = Vec3d::new(rate.beta.to_radians(), rate.gamma.to_radians(), rate.alpha.to_radians());
+            self.rotation = Some((rotation * Quatd::from_scaled_axis(omega * dt)).unit());
+        }
+
+        let acc = match motion.acceleration_with_gravity {
+            Some(acc) => acc,
+            None => return
+        };
+        let gravity = match self.gravity {
+            Some(gravity) => self.options.gravity_smoothing * gravity + (1. - self.options.gravity_smoothing) * acc,
+            None => acc
+        };
+
+        self.gravity = Some(gravity);
+        self.linear_acceleration = acc - gravity;
+    }
+
+    #[inline]
+    fn pose (&self) -> Option<FusedPose> {
+        return self.rotation.map(|rotation| FusedPose {
+            rotation,
+            linear_acceleration: self.linear_acceleration
+        })
+    }
+}
+
+impl<O, M> Stream for PoseFilter<O, M> where
+    O: Unpin + Stream<Item = Orientation>,
+    M: Unpin + Stream<Item = Motion>
+{
+    type Item = FusedPose;
+
+    fn poll_next(mut self: std::pin::Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        let mut updated = false;
+
+        if let Some(ref mut orientation) = this.orientation {
+            match orientation.poll_next_unpin(cx) {
+                Poll::Ready(Some(x)) => {
+                    this.update_orientation(x);
+                    updated = true;
+                },
+                Poll::Ready(None) => this.orientation = None,
+                Poll::Pending => {}
+            }
+        }
+
+        if let Some(ref mut motion) = this.motion {
+            match motion.poll_next_unpin(cx) {
+                Poll::Ready(Some(x)) => {
+                    this.update_motion(x);
+                    updated = true;
+                },
+                Poll::Ready(None) => this.motion = None,
+                Poll::Pending => {}
+            }
+        }
+
+        if updated {
+            if let Some(pose) = this.pose() {
+                return Poll::Ready(Some(pose))
+            }
+            // No orientation yet, keep polling until one arrives
+            cx.waker().wake_by_ref();
+            return Poll::Pending
+        }
+
+        if this.orientation.is_none() && this.motion.is_none() {
+            return Poll::Ready(None)
+        }
+        return Poll::Pending
+    }
+}
@@ -0,0 +1,73 @@
+use std::time::Duration;
+use wasm_bindgen::{prelude::wasm_bindgen, JsCast, JsValue};
+use crate::{Result, time::Interval};
+
+/// Size of a WebAssembly memory page, in bytes
+pub const WASM_PAGE_SIZE: u64 = 64 * 1024;
+
+#[wasm_bindgen]
+extern "C" {
+    #[derive(Debug, Clone)]
+    type MemoryInfo;
+
+    #[wasm_bindgen(method, getter, js_name = usedJSHeapSize)]
+    fn used_js_heap_size (this: &MemoryInfo) -> f64;
+    #[wasm_bindgen(method, getter, js_name = totalJSHeapSize)]
+    fn total_js_heap_size (this: &MemoryInfo) -> f64;
+    #[wasm_bindgen(method, getter, js_name = jsHeapSizeLimit)]
+    fn js_heap_size_limit (this: &MemoryInfo) -> f64;
+}
+
+/// Statistics of the JavaScript heap, from the non-standard [`performance.memory`](https://developer.mozilla.org/en-US/docs/Web/API/Performance/memory)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct JsHeap {
+    /// Bytes currently used by JavaScript objects
+    pub used: u64,
+    /// Bytes currently allocated by the heap, including free space
+    pub total: u64,
+    /// Maximum size the heap can grow to, in bytes
+    pub limit: u64
+}
+
+/// Memory usage of the current context
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MemoryUsage {
+    /// Statistics of the JavaScript heap, only available on Chromium-based browsers
+    pub js_heap: Option<JsHeap>,
+    /// Size of the module's `WebAssembly.Memory`, in bytes
+    pub wasm_bytes: u64,
+    /// Size of the module's `WebAssembly.Memory`, in pages of [`WASM_PAGE_SIZE`] bytes
+    pub wasm_pages: u32
+}
+
+/// Returns the current memory usage of the JavaScript heap (where available) and of the WebAssembly memory.
+///
+/// The WebAssembly memory never shrinks, so it reflects the peak usage of the module rather than the current one.
+pub fn memory () -> MemoryUsage {
+    let js_heap = js_sys::Reflect::get(&js_sys::global(), &JsValue::from_str("performance")).ok()
+        .and_then(|perf| js_sys::Reflect::get(&perf, &JsValue::from_str("memory")).ok())
+        .filter(|info| info.is_object())
+        .map(|info| {
+            let info = info.unchecked_into::<MemoryInfo>();
+            JsHeap {
+                used: info.used_js_heap_size() as u64,
+                total: info.total_js_heap_size() as u64,
+                limit: info.js_heap_size_limit() as u64
+            }
+        });
+
+    let buffer = wasm_bindgen::memory().unchecked_into::<js_sys::WebAssembly::Memory>().buffer();
+    let wasm_bytes = js_sys::Reflect::get(&buffer, &JsValue::from_str("byteLength")).ok()
+        .and_then(|x| x.as_f64())
+        .unwrap_or_default() as u64;
+
+    return MemoryUsage { js_heap, wasm_bytes, wasm_pages: (wasm_bytes / WASM_PAGE_SIZE) as u32 }
+}
+
+/// Returns a stream that yields the current [`memory`] usage at the specified interval.
+///
+/// When droped, the interval will be cleared.
+#[inline]
+pub fn watch_memory (interval: Duration) -> Result<Interval<'static, MemoryUsage>> {
+    return Interval::new(interval, memory)
+}
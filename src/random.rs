@@ -0,0 +1,81 @@
+use rand::{RngCore, CryptoRng, rngs::OsRng};
+use crate::{Result, Error, ErrorKind};
+
+/// Fills `buf` with cryptographically secure random bytes, from [`crypto.getRandomValues`](https://developer.mozilla.org/en-US/docs/Web/API/Crypto/getRandomValues)
+#[inline]
+pub fn fill (buf: &mut [u8]) -> Result<()> {
+    return getrandom::getrandom(buf).map_err(|e| Error::new(ErrorKind::NotSupported, &e.to_string()))
+}
+
+/// Returns a cryptographically secure random value
+#[inline]
+pub fn random<T: Default + AsMut<[u8]>> () -> Result<T> {
+    let mut value = T::default();
+    fill(value.as_mut())?;
+    return Ok(value)
+}
+
+/// Returns a cryptographically secure random `u64`
+#[inline]
+pub fn next_u64 () -> Result<u64> {
+    return random::<[u8; 8]>().map(u64::from_ne_bytes)
+}
+
+/// Returns the bytes of a random (version 4) UUID
+pub fn uuid_v4_bytes () -> Result<[u8; 16]> {
+    let mut bytes = random::<[u8; 16]>()?;
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    return Ok(bytes)
+}
+
+/// Returns a random (version 4) UUID, in it's hyphenated form (`xxxxxxxx-xxxx-4xxx-yxxx-xxxxxxxxxxxx`)
+#[inline]
+pub fn uuid_v4 () -> Result<String> {
+    return uuid_v4_bytes().map(|bytes| format_uuid(&bytes))
+}
+
+/// Formats the bytes of a UUID in it's hyphenated form
+pub(crate) fn format_uuid (bytes: &[u8; 16]) -> String {
+    let mut result = String::with_capacity(36);
+    for (i, byte) in bytes.iter().enumerate() {
+        if matches!(i, 4 | 6 | 8 | 10) {
+            result.push('-')
+        }
+        result.push_str(&format!("{byte:02x}"))
+    }
+    return result
+}
+
+/// A cryptographically secure random number generator backed by [`crypto.getRandomValues`](https://developer.mozilla.org/en-US/docs/Web/API/Crypto/getRandomValues),
+/// usable with the [`rand`] crate.
+/// ```ignore
+/// use rand::Rng;
+/// let roll = WebRng.gen_range(1..=6);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct WebRng;
+
+impl RngCore for WebRng {
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        OsRng.next_u32()
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        OsRng.next_u64()
+    }
+
+    #[inline]
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        OsRng.fill_bytes(dest)
+    }
+
+    #[inline]
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> ::core::result::Result<(), rand::Error> {
+        OsRng.try_fill_bytes(dest)
+    }
+}
+
+impl CryptoRng for WebRng {}
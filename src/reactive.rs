@@ -0,0 +1,220 @@
+use std::{rc::{Rc, Weak}, cell::{Cell, RefCell}, fmt::Debug};
+use futures::{Stream, StreamExt, FutureExt};
+use wasm_bindgen_futures::spawn_local;
+use crate::{scope::queue_microtask, utils::{LocalSender, LocalReceiver, Overflow, bounded_local_channel, Abortable, AbortHandle}};
+
+/// A value that can be read and subscribed to, like a [`Signal`] or a [`Memo`]
+pub trait Reactive<T> {
+    /// Returns a clone of the current value
+    fn get (&self) -> T;
+    /// Returns a stream of the new values. Values set within the same task are coalesced, and only the latest one is yielded.
+    fn subscribe (&self) -> LocalReceiver<T>;
+}
+
+/// A reactive value, whose subscribers are notified when it changes.
+///
+/// Notifications are sent on a microtask, so every change made within the same task results in a single notification
+/// with the latest value. Clones of the signal share the same value.
+/// ```ignore
+/// let heading = Signal::new(0.);
+/// let _binding = heading.follow(orient::watch()?.map(|x| x.alpha));
+/// let _effect = Effect::new(&heading, |alpha| compass.set_rotation(*alpha));
+/// ```
+pub struct Signal<T> {
+    inner: Rc<SignalInner<T>>
+}
+
+struct SignalInner<T> {
+    value: RefCell<T>,
+    subscribers: RefCell<Vec<LocalSender<T>>>,
+    scheduled: Cell<bool>
+}
+
+impl<T: 'static + Clone> Signal<T> {
+    /// Creates a new signal with the specified initial value
+    #[inline]
+    pub fn new (value: T) -> Self {
+        return Self {
+            inner: Rc::new(SignalInner {
+                value: RefCell::new(value),
+                subscribers: Default::default(),
+                scheduled: Cell::new(false)
+            })
+        }
+    }
+
+    /// Calls `f` with a reference to the current value, without cloning it
+    #[inline]
+    pub fn with<R, F: FnOnce(&T) -> R> (&self, f: F) -> R {
+        return f(&self.inner.value.borrow())
+    }
+
+    /// Sets a new value, notifying the subscribers
+    #[inline]
+    pub fn set (&self, value: T) {
+        *self.inner.value.borrow_mut() = value;
+        self.notify()
+    }
+
+    /// Updates the value in place, notifying the subscribers
+    #[inline]
+    pub fn update<F: FnOnce(&mut T)> (&self, f: F) {
+        f(&mut self.inner.value.borrow_mut());
+        self.notify()
+    }
+
+    /// Sets the value to every item of `stream` (a sensor watcher, for example), until the returned [`Effect`] is droped
+    pub fn follow<S: 'static + Stream<Item = T>> (&self, stream: S) -> Effect {
+        let weak = Rc::downgrade(&self.inner);
+        return Effect::spawn(stream.for_each(move |value| {
+            if let Some(inner) = weak.upgrade() {
+                Signal { inner }.set(value)
+            }
+            futures::future::ready(())
+        }))
+    }
+
+    /// Schedules a notification of the subscribers, unless one is already scheduled
+    fn notify (&self) {
+        if self.inner.scheduled.replace(true) { return }
+
+        let weak = Rc::downgrade(&self.inner);
+        queue_microtask(move || {
+            if let Some(inner) = weak.upgrade() {
+                inner.scheduled.set(false);
+                let value = inner.value.borrow().clone();
+
+                // the subscribers aren't borrowed while sending, since waking them may subscribe new ones
+                let mut subscribers = core::mem::take(&mut *inner.subscribers.borrow_mut());
+                subscribers.retain(|send| send.try_send(value.clone()).is_ok());
+                let mut borrow = inner.subscribers.borrow_mut();
+                subscribers.append(&mut borrow);
+                *borrow = subscribers;
+            }
+        })
+    }
+}
+
+impl<T: 'static + Clone> Reactive<T> for Signal<T> {
+    #[inline]
+    fn get (&self) -> T {
+        return self.inner.value.borrow().clone()
+    }
+
+    #[inline]
+    fn subscribe (&self) -> LocalReceiver<T> {
+        let (send, recv) = bounded_local_channel(1, Overflow::DropOldest);
+        self.inner.subscribers.borrow_mut().push(send);
+        return recv
+    }
+}
+
+impl<T> Clone for Signal<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self { inner: self.inner.clone() }
+    }
+}
+
+impl<T: Debug> Debug for Signal<T> {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Signal").field(&self.inner.value.borrow()).finish()
+    }
+}
+
+impl<T: 'static + Clone + Default> Default for Signal<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+/// A value derived from another reactive value, which is only recomputed when it changes.
+///
+/// Subscribers are only notified when the derived value is different from the previous one.
+/// When droped, the value stops being recomputed.
+pub struct Memo<T> {
+    signal: Signal<T>,
+    _effect: Effect
+}
+
+impl<T: 'static + Clone + PartialEq> Memo<T> {
+    /// Creates a new memo that computes it's value by applying `f` to the value of `source`
+    pub fn new<S: 'static, R: Reactive<S>, F: 'static + FnMut(&S) -> T> (source: &R, mut f: F) -> Self {
+        let signal = Signal::new(f(&source.get()));
+        let weak = Rc::downgrade(&signal.inner);
+
+        let effect = Effect::spawn(source.subscribe().for_each(move |value| {
+            let value = f(&value);
+            if let Some(inner) = Weak::upgrade(&weak) && *inner.value.borrow() != value {
+                Signal { inner }.set(value)
+            }
+            futures::future::ready(())
+        }));
+
+        return Self { signal, _effect: effect }
+    }
+
+    /// Calls `f` with a reference to the current value, without cloning it
+    #[inline]
+    pub fn with<R, F: FnOnce(&T) -> R> (&self, f: F) -> R {
+        return self.signal.with(f)
+    }
+}
+
+impl<T: 'static + Clone> Reactive<T> for Memo<T> {
+    #[inline]
+    fn get (&self) -> T {
+        return self.signal.get()
+    }
+
+    #[inline]
+    fn subscribe (&self) -> LocalReceiver<T> {
+        return self.signal.subscribe()
+    }
+}
+
+impl<T: Debug> Debug for Memo<T> {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Memo").field(&self.signal.inner.value.borrow()).finish()
+    }
+}
+
+/// A side effect that runs every time a reactive value changes, until it's droped
+#[derive(Debug)]
+pub struct Effect {
+    handle: AbortHandle
+}
+
+impl Effect {
+    /// Runs `f` with the current value of `source`, and again every time it changes
+    pub fn new<T: 'static, R: Reactive<T>, F: 'static + FnMut(&T)> (source: &R, mut f: F) -> Self {
+        f(&source.get());
+        return Self::spawn(source.subscribe().for_each(move |value| {
+            f(&value);
+            futures::future::ready(())
+        }))
+    }
+
+    /// Spawns the future, aborting it when the effect is droped
+    fn spawn<Fut: 'static + std::future::Future<Output = ()>> (fut: Fut) -> Self {
+        let handle = AbortHandle::new();
+        spawn_local(Abortable::new(fut, handle.clone()).map(|_| ()));
+        return Self { handle }
+    }
+
+    /// Keeps the effect running for the rest of the program
+    #[inline]
+    pub fn leak (self) {
+        core::mem::forget(self)
+    }
+}
+
+impl Drop for Effect {
+    #[inline]
+    fn drop(&mut self) {
+        self.handle.abort()
+    }
+}
@@ -4,18 +4,14 @@ use wasm_bindgen::{prelude::*, JsCast};
 use crate::{sync::{one_shot, drop_local}, runtime::DynFuture};
 use super::{Handle, Task};
 
-#[wasm_bindgen]
-extern "C" {
-    type Scheduler;
-
-    #[wasm_bindgen(method, js_name = postTask)]
-    fn post_task (this: &Scheduler, cb: &js_sys::Function) -> js_sys::Promise;
-}
+pub use crate::scope::{Scheduler, TaskPriority};
 
 impl Scheduler {
+    /// Spawns the future into the JavaScript runtime, with it's first poll scheduled as a task with the specified priority
     #[inline]
-    pub fn spawn_boxed (fut: DynFuture<()>) {        
-        todo!()
+    pub fn spawn_boxed (fut: DynFuture<()>, priority: TaskPriority) -> crate::Result<()> {
+        let _ = crate::scope::post_task(priority, move || wasm_bindgen_futures::spawn_local(fut))?;
+        return Ok(())
     }
 }
 
@@ -1,7 +1,9 @@
+use std::{task::Poll, rc::Rc, cell::Cell};
+use futures::{Stream, StreamExt};
 use js_sys::Function;
-use wasm_bindgen::JsCast;
-use web_sys::{WorkerGlobalScope, Window, EventTarget};
-use crate::Result;
+use wasm_bindgen::{JsCast, closure::Closure};
+use web_sys::{WorkerGlobalScope, WorkerNavigator, Window, EventTarget};
+use crate::{Result, Error, ErrorKind, utils::{LocalReceiver, local_channel}, diagnostics::Tracked};
 use wasm_bindgen::prelude::*;
 
 thread_local! {
@@ -15,7 +17,7 @@ thread_local! {
 
 #[inline]
 pub fn set_interval(fun: &Function, millis: i32) -> Result<i32> {
-    return GLOBAL_SCOPE.with(|scope| scope.set_interval(fun, millis))
+    return Ok(GLOBAL_SCOPE.with(|scope| scope.set_interval(fun, millis))?)
 }
 
 #[inline]
@@ -25,7 +27,7 @@ pub fn clear_interval(handle: i32) {
 
 #[inline]
 pub fn set_timeout(fun: &Function, millis: i32) -> Result<i32> {
-    return GLOBAL_SCOPE.with(|scope| scope.set_timeout(fun, millis))
+    return Ok(GLOBAL_SCOPE.with(|scope| scope.set_timeout(fun, millis))?)
 }
 
 #[inline]
@@ -38,38 +40,381 @@ pub fn fetch (req: &web_sys::Request) -> js_sys::Promise {
     return GLOBAL_SCOPE.with(|scope| scope.fetch(req))
 }
 
+/// Schedules `f` to be called before the next repaint, returning the request's handle
+#[inline]
+pub fn request_animation_frame (f: &Function) -> Result<i32> {
+    return Ok(GLOBAL_SCOPE.with(|scope| scope.request_animation_frame(f))?)
+}
+
+#[inline]
+pub fn cancel_animation_frame (handle: i32) {
+    return GLOBAL_SCOPE.with(|scope| scope.cancel_animation_frame(handle))
+}
+
+/// Creates an [`ImageBitmap`](web_sys::ImageBitmap) from the specified image source
+#[inline]
+pub fn create_image_bitmap (image: &JsValue) -> Result<js_sys::Promise> {
+    return Ok(GLOBAL_SCOPE.with(|scope| scope.create_image_bitmap(image))?)
+}
+
+/// Queues `f` to be executed as a microtask, right after the current task (and before the browser renders or handles input)
+#[inline]
+pub fn queue_microtask<F: 'static + FnOnce()> (f: F) {
+    let f = Closure::once_into_js(f);
+    return GLOBAL_SCOPE.with(|scope| scope.queue_microtask(f.unchecked_ref()))
+}
+
+/// Priority of a task posted with [`post_task`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum TaskPriority {
+    /// Tasks that block the user from interacting with the page, which should be executed as soon as possible
+    UserBlocking,
+    /// Tasks that are visible to the user, but don't block interaction
+    #[default]
+    UserVisible,
+    /// Tasks that aren't time-critical, like logging or prefetching
+    Background
+}
+
+impl TaskPriority {
+    #[inline]
+    pub fn as_str (self) -> &'static str {
+        return match self {
+            Self::UserBlocking => "user-blocking",
+            Self::UserVisible => "user-visible",
+            Self::Background => "background"
+        }
+    }
+}
+
+/// Posts `f` to be executed as a new task with the specified priority, via the [Prioritized Task Scheduling API](https://developer.mozilla.org/en-US/docs/Web/API/Prioritized_Task_Scheduling_API).
+/// 
+/// If the API isn't available, the task is posted with a zero-delay timeout, ignoring it's priority.
+/// The returned promise resolves once the task has been executed.
+pub fn post_task<F: 'static + FnOnce()> (priority: TaskPriority, f: F) -> Result<js_sys::Promise> {
+    if let Some(scheduler) = GLOBAL_SCOPE.with(|scope| scope.scheduler()) {
+        let options = js_sys::Object::new();
+        js_sys::Reflect::set(&options, &JsValue::from_str("priority"), &JsValue::from_str(priority.as_str()))?;
+
+        let f = Closure::once_into_js(f);
+        return Ok(scheduler.post_task(f.unchecked_ref(), &options)?)
+    }
+
+    let mut f = Some(f);
+    let mut result = Ok(());
+    let promise = js_sys::Promise::new(&mut |resolve, _| {
+        if let Some(f) = f.take() {
+            let f = Closure::once_into_js(move || {
+                f();
+                let _ = resolve.call0(&JsValue::UNDEFINED);
+            });
+            result = set_timeout(f.unchecked_ref(), 0).map(|_| ());
+        }
+    });
+
+    result?;
+    return Ok(promise)
+}
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_name = TaskController)]
+    #[derive(Debug, Clone)]
+    type RawTaskController;
+
+    #[wasm_bindgen(constructor, catch)]
+    fn new (options: &js_sys::Object) -> Result<RawTaskController, JsValue>;
+    #[wasm_bindgen(method, getter)]
+    fn signal (this: &RawTaskController) -> web_sys::AbortSignal;
+    #[wasm_bindgen(method, catch, js_name = setPriority)]
+    fn set_priority (this: &RawTaskController, priority: &str) -> Result<(), JsValue>;
+    #[wasm_bindgen(method)]
+    fn abort (this: &RawTaskController);
+}
+
+/// A controller of the priority of tasks, fetches and transfers, from the [Prioritized Task Scheduling API](https://developer.mozilla.org/en-US/docs/Web/API/TaskController).
+///
+/// Changing the controller's priority affects every task that hasn't started yet, and aborting it aborts
+/// every task and request that uses it's signal.
+#[derive(Debug, Clone)]
+pub struct TaskController {
+    inner: RawTaskController,
+    priority: Rc<Cell<TaskPriority>>
+}
+
+impl TaskController {
+    /// Creates a new controller with the specified priority
+    pub fn new (priority: TaskPriority) -> Result<Self> {
+        if !js_sys::Reflect::has(&js_sys::global(), &JsValue::from_str("TaskController"))? {
+            return Err(Error::new(ErrorKind::NotSupported, "Prioritized Task Scheduling API is not supported"))
+        }
+
+        let options = js_sys::Object::new();
+        js_sys::Reflect::set(&options, &JsValue::from_str("priority"), &JsValue::from_str(priority.as_str()))?;
+        return Ok(Self { inner: RawTaskController::new(&options)?, priority: Rc::new(Cell::new(priority)) })
+    }
+
+    /// Returns the current priority of the controller
+    #[inline]
+    pub fn priority (&self) -> TaskPriority {
+        return self.priority.get()
+    }
+
+    /// Changes the priority of the controller's pending tasks
+    #[inline]
+    pub fn set_priority (&self, priority: TaskPriority) -> Result<()> {
+        self.inner.set_priority(priority.as_str())?;
+        self.priority.set(priority);
+        return Ok(())
+    }
+
+    /// Returns the controller's signal (a `TaskSignal`), which carries it's priority
+    #[inline]
+    pub fn signal (&self) -> web_sys::AbortSignal {
+        return self.inner.signal()
+    }
+
+    /// Aborts the controller's pending tasks and requests
+    #[inline]
+    pub fn abort (&self) {
+        self.inner.abort()
+    }
+
+    /// Yields the current task, continuing once the tasks with a higher priority than the controller's have been executed
+    pub async fn yield_now (&self) -> Result<()> {
+        let scheduler = match GLOBAL_SCOPE.with(|scope| scope.scheduler()) {
+            Some(scheduler) => scheduler,
+            None => return Err(Error::new(ErrorKind::NotSupported, "Prioritized Task Scheduling API is not supported"))
+        };
+
+        let options = js_sys::Object::new();
+        js_sys::Reflect::set(&options, &JsValue::from_str("signal"), &self.signal())?;
+
+        let f = Closure::once_into_js(|| {});
+        wasm_bindgen_futures::JsFuture::from(scheduler.post_task(f.unchecked_ref(), &options)?).await?;
+        return Ok(())
+    }
+}
+
+/// Returns the navigator of the current global scope, which will be a [`Navigator`](web_sys::Navigator)
+/// on windows and a `WorkerNavigator` on workers.
+#[inline]
+pub fn navigator () -> js_sys::Object {
+    return GLOBAL_SCOPE.with(|scope| scope.navigator())
+}
+
 #[inline]
 pub fn add_global_listener (ty: &str, f: &Function) -> Result<()> {
-    return GLOBAL_SCOPE.with(|scope| scope.add_event_listener_with_callback(ty, f))
+    return Ok(GLOBAL_SCOPE.with(|scope| scope.add_event_listener_with_callback(ty, f))?)
 }
 
 #[inline]
 pub fn remove_global_listener (ty: &str, f: &Function) -> Result<()> {
-    return GLOBAL_SCOPE.with(|scope| scope.remove_event_listener_with_callback(ty, f))
+    return Ok(GLOBAL_SCOPE.with(|scope| scope.remove_event_listener_with_callback(ty, f))?)
+}
+
+/// Registers `f` to be called when the page is being unloaded (on the `pagehide` event),
+/// returning a handle that unregisters it when dropped.
+/// 
+/// This is the last reliable moment to flush data, typically via [`send_beacon`](crate::io::send_beacon)
+/// or a [`keepalive`](crate::io::Request::keepalive) request.
+pub fn on_unload<F: 'static + FnMut()> (mut f: F) -> Result<UnloadHandle> {
+    return UnloadHandle::new("pagehide", move |_| f())
+}
+
+/// Registers a confirmation prompt that is shown when the user tries to leave the page while `predicate` returns `true`
+/// (typically, while there are unsaved changes), returning a handle that unregisters it when dropped.
+/// 
+/// Browsers may ignore the prompt if the user hasn't interacted with the page.
+pub fn on_before_unload<F: 'static + FnMut() -> bool> (mut predicate: F) -> Result<UnloadHandle> {
+    return UnloadHandle::new("beforeunload", move |evt| {
+        if predicate() {
+            evt.prevent_default();
+            let evt = evt.unchecked_into::<web_sys::BeforeUnloadEvent>();
+            evt.set_return_value("");
+        }
+    })
+}
+
+/// Handle of a closure registered via [`on_unload`] or [`on_before_unload`].
+/// 
+/// When droped, the closure will be unregistered, releasing all of it's memory.
+pub struct UnloadHandle {
+    ty: &'static str,
+    closure: Closure<dyn FnMut(web_sys::Event)>,
+    _tracked: Tracked
+}
+
+impl UnloadHandle {
+    fn new<F: 'static + FnMut(web_sys::Event)> (ty: &'static str, f: F) -> Result<Self> {
+        let closure = Closure::<dyn FnMut(web_sys::Event)>::new(f);
+
+        let listener: &Function;
+        cfg_if::cfg_if! {
+            if #[cfg(debug_assertions)] {
+                listener = closure.as_ref().dyn_ref().unwrap();
+            } else {
+                listener = closure.as_ref().unchecked_ref();
+            }
+        }
+
+        add_global_listener(ty, listener)?;
+        return Ok(Self { ty, closure, _tracked: Tracked::new("scope::UnloadHandle") })
+    }
+
+    /// Leaks the handle, keeping the closure registered for the rest of the page's lifetime.
+    #[inline]
+    pub fn leak (self) {
+        core::mem::forget(self)
+    }
+}
+
+impl Drop for UnloadHandle {
+    #[inline]
+    fn drop(&mut self) {
+        let listener: &Function;
+        cfg_if::cfg_if! {
+            if #[cfg(debug_assertions)] {
+                listener = self.closure.as_ref().dyn_ref().unwrap();
+            } else {
+                listener = self.closure.as_ref().unchecked_ref();
+            }
+        }
+
+        let _ = remove_global_listener(self.ty, listener);
+    }
+}
+
+/// An uncaught error of the global scope
+#[derive(Debug, Clone)]
+pub enum GlobalError {
+    /// An uncaught exception (from an `error` event)
+    Exception {
+        message: String,
+        /// Script in which the error happened
+        filename: String,
+        line: u32,
+        column: u32,
+        /// The thrown value
+        error: JsValue
+    },
+    /// A promise rejection without a handler (from an `unhandledrejection` event)
+    Rejection {
+        reason: JsValue
+    }
+}
+
+impl GlobalError {
+    /// Returns the thrown value or rejection reason
+    #[inline]
+    pub fn value (&self) -> &JsValue {
+        return match self {
+            Self::Exception { error, .. } => error,
+            Self::Rejection { reason } => reason
+        }
+    }
+}
+
+/// Returns a watcher over the uncaught errors and unhandled promise rejections of the global scope
+#[inline]
+pub fn watch_errors () -> Result<ErrorWatcher> {
+    return ErrorWatcher::new()
+}
+
+/// A watcher over the uncaught errors of the global scope.
+/// 
+/// When droped, the watcher will be closed, releasing all the memory of it's closures.
+pub struct ErrorWatcher {
+    error: Closure<dyn FnMut(web_sys::ErrorEvent)>,
+    rejection: Closure<dyn FnMut(web_sys::PromiseRejectionEvent)>,
+    recv: LocalReceiver<GlobalError>,
+    _tracked: Tracked
+}
+
+impl ErrorWatcher {
+    pub fn new () -> Result<Self> {
+        let (send, recv) = local_channel();
+
+        let my_send = send.clone();
+        let error = Closure::<dyn FnMut(web_sys::ErrorEvent)>::new(move |evt: web_sys::ErrorEvent| {
+            let _ = my_send.try_send(GlobalError::Exception {
+                message: evt.message(),
+                filename: evt.filename(),
+                line: evt.lineno(),
+                column: evt.colno(),
+                error: evt.error()
+            });
+        });
+
+        let rejection = Closure::<dyn FnMut(web_sys::PromiseRejectionEvent)>::new(move |evt: web_sys::PromiseRejectionEvent| {
+            let _ = send.try_send(GlobalError::Rejection { reason: evt.reason() });
+        });
+
+        add_global_listener("error", error.as_ref().unchecked_ref())?;
+        if let Err(e) = add_global_listener("unhandledrejection", rejection.as_ref().unchecked_ref()) {
+            let _ = remove_global_listener("error", error.as_ref().unchecked_ref());
+            return Err(e)
+        }
+
+        return Ok(Self { error, rejection, recv, _tracked: Tracked::new("scope::ErrorWatcher") })
+    }
+}
+
+impl Stream for ErrorWatcher {
+    type Item = GlobalError;
+
+    #[inline]
+    fn poll_next(mut self: std::pin::Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Option<Self::Item>> {
+        self.recv.poll_next_unpin(cx)
+    }
+}
+
+impl Drop for ErrorWatcher {
+    #[inline]
+    fn drop(&mut self) {
+        let _ = remove_global_listener("error", self.error.as_ref().unchecked_ref());
+        let _ = remove_global_listener("unhandledrejection", self.rejection.as_ref().unchecked_ref());
+    }
 }
 
 #[wasm_bindgen]
 extern "C" {
+    /// The [Prioritized Task Scheduling API](https://developer.mozilla.org/en-US/docs/Web/API/Prioritized_Task_Scheduling_API) scheduler
+    #[derive(Debug, Clone)]
+    pub type Scheduler;
+
+    #[wasm_bindgen(js_name = postTask, method, catch)]
+    pub fn post_task (this: &Scheduler, f: &Function, options: &js_sys::Object) -> Result<js_sys::Promise, JsValue>;
+
     /// Represents a JavaScript global scope
     #[derive(Debug, Clone)]
     #[wasm_bindgen(extends = EventTarget)]
     pub type Scope;
 
     #[wasm_bindgen(js_name = setInterval, structural, method, catch)]
-    pub fn set_interval(this: &Scope, fun: &Function, millis: i32) -> Result<i32>;
+    pub fn set_interval(this: &Scope, fun: &Function, millis: i32) -> Result<i32, JsValue>;
     #[wasm_bindgen(js_name = clearInterval, structural, method)]
     pub fn clear_interval(this: &Scope, handle: i32);
 
     #[wasm_bindgen(js_name = setTimeout, structural, method, catch)]
-    pub fn set_timeout(this: &Scope, fun: &Function, millis: i32) -> Result<i32>;
+    pub fn set_timeout(this: &Scope, fun: &Function, millis: i32) -> Result<i32, JsValue>;
     #[wasm_bindgen(js_name = clearTimeout, structural, method)]
     pub fn clear_timeout(this: &Scope, handle: i32);
 
-    #[cfg(target_feature = "atomics")]
-    #[wasm_bindgen(structural, method, catch)]
-    pub fn scheduler (this: &Scope) -> Result<crate::runtime::web::Scheduler>;
+    #[wasm_bindgen(structural, method, getter)]
+    pub fn scheduler (this: &Scope) -> Option<Scheduler>;
+    #[wasm_bindgen(js_name = queueMicrotask, structural, method)]
+    pub fn queue_microtask (this: &Scope, f: &Function);
     #[wasm_bindgen(structural, method)]
     pub fn fetch (this: &Scope, req: &web_sys::Request) -> js_sys::Promise;
+    #[wasm_bindgen(structural, method, getter)]
+    pub fn navigator (this: &Scope) -> js_sys::Object;
+    #[wasm_bindgen(js_name = requestAnimationFrame, structural, method, catch)]
+    pub fn request_animation_frame (this: &Scope, f: &Function) -> Result<i32, JsValue>;
+    #[wasm_bindgen(js_name = cancelAnimationFrame, structural, method)]
+    pub fn cancel_animation_frame (this: &Scope, handle: i32);
+    #[wasm_bindgen(js_name = createImageBitmap, structural, method, catch)]
+    pub fn create_image_bitmap (this: &Scope, image: &JsValue) -> Result<js_sys::Promise, JsValue>;
 }
 
 impl Default for Scope {
@@ -81,5 +426,26 @@ impl Default for Scope {
 
 #[inline]
 pub fn window () -> Result<Window> {
-    return js_sys::global().dyn_into().map_err(|_| JsValue::from_str("current global scope isn't a window. you may be in a web worker."));
+    return js_sys::global().dyn_into().map_err(|_| Error::new(ErrorKind::NotSupported, "current global scope isn't a window. you may be in a web worker."));
+}
+
+/// Returns `true` if the current global scope is a web worker, `false` otherwise
+#[inline]
+pub fn is_worker () -> bool {
+    return js_sys::global().is_instance_of::<WorkerGlobalScope>()
+}
+
+/// Returns the navigator of the current worker
+#[inline]
+pub fn worker_navigator () -> Result<WorkerNavigator> {
+    return match js_sys::global().dyn_into::<WorkerGlobalScope>() {
+        Ok(scope) => Ok(scope.navigator()),
+        Err(_) => Err(Error::new(ErrorKind::NotSupported, "current global scope isn't a web worker"))
+    }
+}
+
+/// Returns the current window, or a [`NotSupported`](ErrorKind::NotSupported) error naming `api` if called from a web worker.
+#[inline]
+pub(crate) fn window_only (api: &str) -> Result<Window> {
+    return js_sys::global().dyn_into().map_err(|_| Error::new(ErrorKind::NotSupported, &format!("{api} is not supported inside web workers")));
 }
\ No newline at end of file
@@ -0,0 +1,355 @@
+use std::{rc::Rc, cell::RefCell, pin::Pin, task::{Context, Poll}};
+use futures::{Stream, StreamExt};
+use js_sys::Promise;
+use wasm_bindgen::{JsCast, JsValue, prelude::{wasm_bindgen, Closure}};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{Element, MediaQueryList, MediaQueryListEvent};
+use crate::{Result, Error, ErrorKind, scope::{navigator, window_only}, dom::Listener, utils::{LocalSender, LocalReceiver, local_channel}};
+
+#[wasm_bindgen]
+extern "C" {
+    #[derive(Debug, Clone)]
+    #[wasm_bindgen(extends = Element)]
+    type ElementExt;
+
+    #[wasm_bindgen(method, catch, js_name = requestFullscreen)]
+    fn request_fullscreen (this: &ElementExt) -> ::core::result::Result<Promise, JsValue>;
+    #[wasm_bindgen(js_namespace = document, js_name = exitFullscreen, catch)]
+    fn exit_fullscreen () -> ::core::result::Result<Promise, JsValue>;
+
+    #[wasm_bindgen(js_namespace = ["screen", "orientation"], js_name = lock, catch)]
+    fn lock_orientation (orientation: &str) -> ::core::result::Result<Promise, JsValue>;
+    #[wasm_bindgen(js_namespace = ["screen", "orientation"], js_name = unlock, catch)]
+    fn unlock_orientation () -> ::core::result::Result<(), JsValue>;
+
+    #[derive(Debug, Clone)]
+    type WakeLockSentinel;
+
+    #[wasm_bindgen(method, catch)]
+    fn release (this: &WakeLockSentinel) -> ::core::result::Result<Promise, JsValue>;
+}
+
+/// An orientation the screen can be locked to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum OrientationLock {
+    /// Any orientation
+    #[default]
+    Any,
+    /// The natural orientation of the screen
+    Natural,
+    Landscape,
+    Portrait,
+    PortraitPrimary,
+    PortraitSecondary,
+    LandscapePrimary,
+    LandscapeSecondary
+}
+
+impl OrientationLock {
+    #[inline]
+    pub fn as_str (self) -> &'static str {
+        return match self {
+            Self::Any => "any",
+            Self::Natural => "natural",
+            Self::Landscape => "landscape",
+            Self::Portrait => "portrait",
+            Self::PortraitPrimary => "portrait-primary",
+            Self::PortraitSecondary => "portrait-secondary",
+            Self::LandscapePrimary => "landscape-primary",
+            Self::LandscapeSecondary => "landscape-secondary"
+        }
+    }
+}
+
+/// Guard of an element in fullscreen mode. When droped, fullscreen mode is exited.
+#[derive(Debug)]
+pub struct Fullscreen {
+    _priv: ()
+}
+
+impl Fullscreen {
+    /// Displays the element in fullscreen mode.
+    ///
+    /// This method must be called in response to a user gesture.
+    pub async fn request (element: &Element) -> Result<Self> {
+        let promise = element.unchecked_ref::<ElementExt>().request_fullscreen()?;
+        let _ = JsFuture::from(promise).await?;
+        return Ok(Self { _priv: () })
+    }
+}
+
+impl Drop for Fullscreen {
+    #[inline]
+    fn drop(&mut self) {
+        let _ = exit_fullscreen();
+    }
+}
+
+/// Guard of a locked screen orientation. When droped, the orientation is unlocked.
+#[derive(Debug)]
+pub struct LockedOrientation {
+    _priv: ()
+}
+
+impl LockedOrientation {
+    /// Locks the screen's orientation.
+    ///
+    /// Most browsers only allow locking the orientation while in fullscreen mode. If the platform doesn't support locking it
+    /// (like Safari and most desktop browsers), an error of kind [`NotSupported`](ErrorKind::NotSupported) is returned.
+    pub async fn request (orientation: OrientationLock) -> Result<Self> {
+        if !supports_orientation_lock()? {
+            return Err(Error::new(ErrorKind::NotSupported, "screen orientation locking is not supported"))
+        }
+
+        match JsFuture::from(lock_orientation(orientation.as_str())?).await {
+            Ok(_) => return Ok(Self { _priv: () }),
+            Err(e) if e.dyn_ref::<web_sys::DomException>().is_some_and(|e| e.name() == "NotSupportedError") => {
+                return Err(Error::new(ErrorKind::NotSupported, "screen orientation locking is not supported"))
+            },
+            Err(e) => return Err(e.into())
+        }
+    }
+}
+
+/// Returns `true` if `screen.orientation.lock` is available
+fn supports_orientation_lock () -> Result<bool> {
+    let screen = js_sys::Reflect::get(&window_only("Screen Orientation API")?, &JsValue::from_str("screen"))?;
+    let orientation = js_sys::Reflect::get(&screen, &JsValue::from_str("orientation"))?;
+    if orientation.is_undefined() {
+        return Ok(false)
+    }
+    return Ok(js_sys::Reflect::get(&orientation, &JsValue::from_str("lock"))?.is_function())
+}
+
+impl Drop for LockedOrientation {
+    #[inline]
+    fn drop(&mut self) {
+        let _ = unlock_orientation();
+    }
+}
+
+/// Guard of a screen wake lock, preventing the device from dimming or locking the screen.
+/// When droped, the wake lock is released.
+#[derive(Debug)]
+pub struct WakeLock {
+    sentinel: WakeLockSentinel
+}
+
+impl WakeLock {
+    /// Requests a screen wake lock
+    pub async fn request () -> Result<Self> {
+        let wake_lock = js_sys::Reflect::get(&navigator(), &JsValue::from_str("wakeLock"))?;
+        if wake_lock.is_undefined() {
+            return Err(Error::new(ErrorKind::NotSupported, "Screen Wake Lock API is not supported"))
+        }
+
+        let request = js_sys::Reflect::get(&wake_lock, &JsValue::from_str("request"))?.dyn_into::<js_sys::Function>().map_err(Error::from)?;
+        let promise = request.call1(&wake_lock, &JsValue::from_str("screen"))?.dyn_into::<Promise>().map_err(Error::from)?;
+        let sentinel = JsFuture::from(promise).await?;
+        return Ok(Self { sentinel: sentinel.unchecked_into() })
+    }
+}
+
+impl Drop for WakeLock {
+    #[inline]
+    fn drop(&mut self) {
+        let _ = self.sentinel.release();
+    }
+}
+
+/// Guard returned by [`kiosk`]. When droped, the wake lock is released, the orientation unlocked and fullscreen mode exited.
+#[derive(Debug)]
+pub struct Kiosk {
+    wake_lock: Option<WakeLock>,
+    orientation: Option<LockedOrientation>,
+    _fullscreen: Fullscreen
+}
+
+impl Kiosk {
+    /// Returns `true` if the screen's orientation was locked
+    #[inline]
+    pub fn is_orientation_locked (&self) -> bool {
+        return self.orientation.is_some()
+    }
+
+    /// Returns `true` if a screen wake lock was acquired
+    #[inline]
+    pub fn is_wake_locked (&self) -> bool {
+        return self.wake_lock.is_some()
+    }
+}
+
+/// Displays the element in fullscreen mode, locks the screen's orientation and acquires a screen wake lock.
+///
+/// Locking the orientation and acquiring the wake lock are skipped on platforms that don't support them.
+/// If any other step fails, the previous ones are undone (exiting fullscreen mode).
+///
+/// This method must be called in response to a user gesture.
+pub async fn kiosk (element: &Element, orientation: OrientationLock) -> Result<Kiosk> {
+    let fullscreen = Fullscreen::request(element).await?;
+
+    let orientation = match LockedOrientation::request(orientation).await {
+        Ok(orientation) => Some(orientation),
+        Err(e) if e.kind() == ErrorKind::NotSupported => None,
+        Err(e) => return Err(e)
+    };
+
+    let wake_lock = match WakeLock::request().await {
+        Ok(wake_lock) => Some(wake_lock),
+        Err(e) if e.kind() == ErrorKind::NotSupported => None,
+        Err(e) => return Err(e)
+    };
+
+    return Ok(Kiosk {
+        wake_lock,
+        orientation,
+        _fullscreen: fullscreen
+    })
+}
+
+/// Evaluates the media query against the current window, returning it's current result and a stream of it's changes.
+///
+/// Useful to react to features like `(prefers-color-scheme: dark)` or `(max-width: 600px)`.
+pub fn media_query (query: &str) -> Result<MediaQuery> {
+    let list = match window_only("Media Queries API")?.match_media(query)? {
+        Some(list) => list,
+        None => return Err(Error::new(ErrorKind::NotSupported, "Media Queries API is not supported"))
+    };
+
+    let (send, recv) = local_channel();
+    let listener = Listener::new(&list, "change", move |evt| {
+        let _ = send.try_send(evt.unchecked_into::<MediaQueryListEvent>().matches());
+    })?;
+
+    return Ok(MediaQuery { list, _listener: listener, recv })
+}
+
+/// A media query, yielding it's new result every time it changes
+pub struct MediaQuery {
+    list: MediaQueryList,
+    _listener: Listener,
+    recv: LocalReceiver<bool>
+}
+
+impl MediaQuery {
+    /// Returns `true` if the document currently matches the media query, `false` otherwise
+    #[inline]
+    pub fn matches (&self) -> bool {
+        return self.list.matches()
+    }
+
+    /// Returns the serialized media query
+    #[inline]
+    pub fn query (&self) -> String {
+        return self.list.media()
+    }
+}
+
+impl Stream for MediaQuery {
+    type Item = bool;
+
+    #[inline]
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.recv.poll_next_unpin(cx)
+    }
+}
+
+/// Returns the ratio between physical pixels and CSS pixels of the current display
+#[inline]
+pub fn device_pixel_ratio () -> Result<f64> {
+    return Ok(window_only("devicePixelRatio")?.device_pixel_ratio())
+}
+
+/// Returns a stream of the device pixel ratio, which yields a new value every time it changes
+/// (for example, when the page is zoomed or moved to another display).
+///
+/// The ratio is watched by matching a `(resolution: <ratio>dppx)` media query, which is replaced every time it stops matching.
+pub fn device_pixel_ratio_watch () -> Result<PixelRatioWatcher> {
+    let window = window_only("devicePixelRatio")?;
+    let (send, recv) = local_channel();
+    let list = resolution_query(&window)?;
+
+    let inner = Rc::new(RatioInner {
+        window,
+        list: RefCell::new(list),
+        listener: RefCell::new(None),
+        send
+    });
+
+    let weak = Rc::downgrade(&inner);
+    let closure = Closure::<dyn FnMut(web_sys::Event)>::new(move |_| {
+        let inner = match weak.upgrade() {
+            Some(inner) => inner,
+            None => return
+        };
+
+        let _ = inner.send.try_send(inner.window.device_pixel_ratio());
+        if let Ok(list) = resolution_query(&inner.window) && let Some(ref listener) = *inner.listener.borrow() {
+            let old = inner.list.replace(list);
+            let _ = old.remove_event_listener_with_callback("change", listener);
+            let _ = inner.list.borrow().add_event_listener_with_callback("change", listener);
+        }
+    });
+
+    let listener: &js_sys::Function;
+    cfg_if::cfg_if! {
+        if #[cfg(debug_assertions)] {
+            listener = closure.as_ref().dyn_ref().unwrap();
+        } else {
+            listener = closure.as_ref().unchecked_ref();
+        }
+    }
+
+    inner.list.borrow().add_event_listener_with_callback("change", listener)?;
+    *inner.listener.borrow_mut() = Some(listener.clone());
+    return Ok(PixelRatioWatcher { inner, _closure: closure, recv })
+}
+
+#[inline]
+fn resolution_query (window: &web_sys::Window) -> Result<MediaQueryList> {
+    let query = format!("(resolution: {}dppx)", window.device_pixel_ratio());
+    return match window.match_media(&query)? {
+        Some(list) => Ok(list),
+        None => Err(Error::new(ErrorKind::NotSupported, "Media Queries API is not supported"))
+    }
+}
+
+struct RatioInner {
+    window: web_sys::Window,
+    list: RefCell<MediaQueryList>,
+    listener: RefCell<Option<js_sys::Function>>,
+    send: LocalSender<f64>
+}
+
+/// Watcher of the device pixel ratio, created by [`device_pixel_ratio_watch`]
+pub struct PixelRatioWatcher {
+    inner: Rc<RatioInner>,
+    _closure: Closure<dyn FnMut(web_sys::Event)>,
+    recv: LocalReceiver<f64>
+}
+
+impl PixelRatioWatcher {
+    /// Returns the current device pixel ratio
+    #[inline]
+    pub fn current (&self) -> f64 {
+        return self.inner.window.device_pixel_ratio()
+    }
+}
+
+impl Stream for PixelRatioWatcher {
+    type Item = f64;
+
+    #[inline]
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.recv.poll_next_unpin(cx)
+    }
+}
+
+impl Drop for PixelRatioWatcher {
+    #[inline]
+    fn drop(&mut self) {
+        if let Some(ref listener) = *self.inner.listener.borrow() {
+            let _ = self.inner.list.borrow().remove_event_listener_with_callback("change", listener);
+        }
+    }
+}
@@ -1,6 +1,11 @@
+use std::{rc::Rc, cell::RefCell, task::Poll, collections::BTreeMap};
+use futures::{Stream, StreamExt, FutureExt, future::{LocalBoxFuture, ready}};
 use serde::{Serialize, de::DeserializeOwned};
-use wasm_bindgen::JsValue;
-use crate::{Result, scope::window};
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{BroadcastChannel, StorageEvent, MessageEvent};
+use crate::{Result, Error, ErrorKind, scope::window, dom::Listener, utils::{LocalSender, LocalReceiver, local_channel, to_js, from_js}};
+
+flat_mod! { migrations }
 
 /// Interface that provides access to a particular domain's session or local storage.
 /// 
@@ -33,9 +38,9 @@ impl Storage {
     pub fn set<T: Serialize> (&self, key: &str, value: &T) -> Result<()> {
         let value = match serde_json::to_string(value) {
             Ok(x) => x,
-            Err(e) => return Err(JsValue::from_str(&e.to_string()))
+            Err(e) => return Err(e.into())
         };
-        return self.inner.set_item(key, &value);
+        return Ok(self.inner.set_item(key, &value)?);
     }
 
     /// Returns the deserialized value from the store.
@@ -43,7 +48,7 @@ impl Storage {
         if let Some(str) = self.inner.get_item(key)? {
             return match serde_json::from_str(&str) {
                 Ok(x) => Ok(x),
-                Err(e) => Err(JsValue::from_str(&e.to_string()))
+                Err(e) => Err(e.into())
             }
         }
         return Ok(None)
@@ -52,13 +57,13 @@ impl Storage {
     /// Removes the value associated to the specified key from the store
     #[inline]
     pub fn remove (&self, key: &str) -> Result<()> {
-        self.inner.remove_item(key)
+        return Ok(self.inner.remove_item(key)?)
     }
 
     /// Removes all the entries from the store.
     #[inline]
     pub fn clear (&self) -> Result<()> {
-        self.inner.clear()
+        return Ok(self.inner.clear()?)
     }
 
     /// Returns an iterator over all of the entries of the store
@@ -78,7 +83,7 @@ impl Storage {
         return StorageIter {
             inner: self.inner.clone(),
             front: 0,
-            back: len - 1,
+            back: len.saturating_sub(1),
             len
         }
     }
@@ -124,7 +129,7 @@ impl StorageIter {
 
         return match serde_json::from_str(&value) {
             Ok(x) => Some(Ok((key, x))),
-            Err(e) => Some(Err(JsValue::from_str(&e.to_string())))
+            Err(e) => Some(Err(e.into()))
         };
     }
 
@@ -145,7 +150,7 @@ impl Iterator for StorageIter {
             ($e:expr) => {
                 match $e {
                     Ok(x) => x,
-                    Err(e) => return Some(Err(e))
+                    Err(e) => return Some(Err(e.into()))
                 }
             };
         }
@@ -189,7 +194,7 @@ impl DoubleEndedIterator for StorageIter {
             ($e:expr) => {
                 match $e {
                     Ok(x) => x,
-                    Err(e) => return Some(Err(e))
+                    Err(e) => return Some(Err(e.into()))
                 }
             };
         }
@@ -215,4 +220,247 @@ impl ExactSizeIterator for StorageIter {
     fn len(&self) -> usize {
         self.len as usize
     }
-}
\ No newline at end of file
+}
+
+/// An asynchronous key-value store of serializable values.
+///
+/// It's implemented by [`Storage`], [`MemoryStore`] and IndexedDB's [`IdbStore`](crate::idb::IdbStore),
+/// so that features can be generic over their backing store.
+pub trait KvStore {
+    /// Returns the deserialized value associated to the key, if any
+    fn get<'a, T: 'a + DeserializeOwned> (&'a self, key: &'a str) -> LocalBoxFuture<'a, Result<Option<T>>>;
+    /// Associates the serialized value to the key, replacing the previous one
+    fn set<'a, T: ?Sized + Serialize> (&'a self, key: &'a str, value: &T) -> LocalBoxFuture<'a, Result<()>>;
+    /// Removes the value associated to the key, if any
+    fn remove<'a> (&'a self, key: &'a str) -> LocalBoxFuture<'a, Result<()>>;
+    /// Returns an iterator over the deserialized entries of the store
+    fn iter<'a, T: 'a + DeserializeOwned> (&'a self) -> LocalBoxFuture<'a, Result<std::vec::IntoIter<(String, T)>>>;
+}
+
+impl KvStore for Storage {
+    #[inline]
+    fn get<'a, T: 'a + DeserializeOwned> (&'a self, key: &'a str) -> LocalBoxFuture<'a, Result<Option<T>>> {
+        return ready(Storage::get(self, key)).boxed_local()
+    }
+
+    #[inline]
+    fn set<'a, T: ?Sized + Serialize> (&'a self, key: &'a str, value: &T) -> LocalBoxFuture<'a, Result<()>> {
+        let result = serde_json::to_string(value)
+            .map_err(Error::from)
+            .and_then(|value| Ok(self.inner.set_item(key, &value)?));
+        return ready(result).boxed_local()
+    }
+
+    #[inline]
+    fn remove<'a> (&'a self, key: &'a str) -> LocalBoxFuture<'a, Result<()>> {
+        return ready(Storage::remove(self, key)).boxed_local()
+    }
+
+    /// Entries whose values can't be deserialized into `T` (like the plain strings stored by other scripts of the origin) are skipped
+    fn iter<'a, T: 'a + DeserializeOwned> (&'a self) -> LocalBoxFuture<'a, Result<std::vec::IntoIter<(String, T)>>> {
+        let entries = Storage::iter(self)
+            .filter_map(|entry| match entry {
+                Ok((key, value)) => Some(Ok((key, serde_json::from_str(&value).ok()?))),
+                Err(e) => Some(Err(e))
+            })
+            .collect::<Result<Vec<_>>>();
+        return ready(entries.map(Vec::into_iter)).boxed_local()
+    }
+}
+
+/// An in-memory [`KvStore`], whose values are lost when the page is closed.
+///
+/// Clones of the store share the same entries.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryStore {
+    inner: Rc<RefCell<BTreeMap<String, serde_json::Value>>>
+}
+
+impl MemoryStore {
+    /// Creates a new empty store
+    #[inline]
+    pub fn new () -> Self {
+        return Self::default()
+    }
+
+    /// Returns the number of entries of the store
+    #[inline]
+    pub fn len (&self) -> usize {
+        return self.inner.borrow().len()
+    }
+
+    /// Returns `true` if the store has no entries
+    #[inline]
+    pub fn is_empty (&self) -> bool {
+        return self.inner.borrow().is_empty()
+    }
+
+    /// Removes all the entries from the store
+    #[inline]
+    pub fn clear (&self) {
+        self.inner.borrow_mut().clear()
+    }
+}
+
+impl KvStore for MemoryStore {
+    fn get<'a, T: 'a + DeserializeOwned> (&'a self, key: &'a str) -> LocalBoxFuture<'a, Result<Option<T>>> {
+        let value = self.inner.borrow().get(key).cloned();
+        let result = value.map(serde_json::from_value).transpose().map_err(Error::from);
+        return ready(result).boxed_local()
+    }
+
+    fn set<'a, T: ?Sized + Serialize> (&'a self, key: &'a str, value: &T) -> LocalBoxFuture<'a, Result<()>> {
+        let result = serde_json::to_value(value).map(|value| {
+            self.inner.borrow_mut().insert(key.to_string(), value);
+        });
+        return ready(result.map_err(Error::from)).boxed_local()
+    }
+
+    #[inline]
+    fn remove<'a> (&'a self, key: &'a str) -> LocalBoxFuture<'a, Result<()>> {
+        self.inner.borrow_mut().remove(key);
+        return ready(Ok(())).boxed_local()
+    }
+
+    fn iter<'a, T: 'a + DeserializeOwned> (&'a self) -> LocalBoxFuture<'a, Result<std::vec::IntoIter<(String, T)>>> {
+        let entries = self.inner.borrow().iter()
+            .map(|(key, value)| Ok((key.clone(), serde_json::from_value(value.clone())?)))
+            .collect::<Result<Vec<_>>>();
+        return ready(entries.map(Vec::into_iter)).boxed_local()
+    }
+}
+
+/// A value persisted in local storage and kept in sync across tabs of the same origin.
+/// 
+/// Changes are broadcast with a [`BroadcastChannel`] (falling back to `storage` events where it isn't available),
+/// so that every tab converges to the latest value set.
+pub struct SyncedCell<T> {
+    key: String,
+    storage: Storage,
+    channel: Option<BroadcastChannel>,
+    subscribers: Rc<RefCell<Vec<LocalSender<T>>>>
+}
+
+impl<T: Serialize + DeserializeOwned> SyncedCell<T> {
+    /// Creates a new cell, persisted in local storage under `key`
+    pub fn new (key: impl Into<String>) -> Result<Self> {
+        let storage = match Storage::local()? {
+            Some(storage) => storage,
+            None => return Err(Error::new(ErrorKind::NotSupported, "local storage is not available"))
+        };
+
+        let key = key.into();
+        let channel = match js_sys::Reflect::has(&js_sys::global(), &JsValue::from_str("BroadcastChannel"))? {
+            true => Some(BroadcastChannel::new(&format!("rustww:synced:{key}"))?),
+            false => None
+        };
+
+        return Ok(Self {
+            key,
+            storage,
+            channel,
+            subscribers: Default::default()
+        })
+    }
+
+    /// Returns the key under which the value is persisted
+    #[inline]
+    pub fn key (&self) -> &str {
+        return &self.key
+    }
+
+    /// Returns the current value, if set
+    #[inline]
+    pub fn get (&self) -> Result<Option<T>> {
+        return self.storage.get(&self.key)
+    }
+
+    /// Sets a new value, persisting it and notifying every other tab
+    pub fn set (&self, value: &T) -> Result<()> {
+        let json = serde_json::to_string(value)?;
+        self.storage.inner.set_item(&self.key, &json)?;
+
+        match self.channel {
+            // Broadcast messages are also received by the streams of the current tab
+            Some(ref channel) => channel.post_message(&to_js(value)?)?,
+            // Storage events are only dispatched to other tabs
+            None => self.subscribers.borrow_mut().retain(|send| match serde_json::from_str(&json) {
+                Ok(value) => send.try_send(value).is_ok(),
+                Err(_) => true
+            })
+        }
+        return Ok(())
+    }
+
+    /// Removes the value from storage
+    #[inline]
+    pub fn remove (&self) -> Result<()> {
+        return self.storage.remove(&self.key)
+    }
+
+    /// Returns a stream of the new values of the cell, whether they were set by this or another tab
+    pub fn changes (&self) -> Result<SyncedChanges<T>> where T: 'static {
+        let (send, recv) = local_channel();
+
+        if self.channel.is_some() {
+            let channel = BroadcastChannel::new(&format!("rustww:synced:{}", self.key))?;
+            let listener = Listener::new(&channel, "message", move |evt: web_sys::Event| {
+                let data = evt.unchecked_into::<MessageEvent>().data();
+                if let Ok(value) = from_js(data) {
+                    let _ = send.try_send(value);
+                }
+            })?;
+
+            return Ok(SyncedChanges { channel: Some(channel), _listener: listener, recv })
+        }
+
+        self.subscribers.borrow_mut().push(send.clone());
+        let key = self.key.clone();
+        let area = self.storage.inner.clone();
+        let listener = Listener::new(&window()?, "storage", move |evt: web_sys::Event| {
+            let evt = evt.unchecked_into::<StorageEvent>();
+            if evt.key().as_deref() != Some(key.as_str()) { return }
+            if !evt.storage_area().is_some_and(|x| JsValue::eq(&x, &area)) { return }
+
+            if let Some(Ok(value)) = evt.new_value().map(|x| serde_json::from_str(&x)) {
+                let _ = send.try_send(value);
+            }
+        })?;
+
+        return Ok(SyncedChanges { channel: None, _listener: listener, recv })
+    }
+}
+
+impl<T> Drop for SyncedCell<T> {
+    #[inline]
+    fn drop(&mut self) {
+        if let Some(ref channel) = self.channel {
+            channel.close();
+        }
+    }
+}
+
+/// Stream of the new values of a [`SyncedCell`]
+pub struct SyncedChanges<T> {
+    channel: Option<BroadcastChannel>,
+    _listener: Listener,
+    recv: LocalReceiver<T>
+}
+
+impl<T> Stream for SyncedChanges<T> {
+    type Item = T;
+
+    #[inline]
+    fn poll_next(mut self: std::pin::Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Option<Self::Item>> {
+        self.recv.poll_next_unpin(cx)
+    }
+}
+
+impl<T> Drop for SyncedChanges<T> {
+    #[inline]
+    fn drop(&mut self) {
+        if let Some(ref channel) = self.channel {
+            channel.close();
+        }
+    }
+}
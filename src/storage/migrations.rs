@@ -0,0 +1,202 @@
+use std::{rc::Rc, cell::RefCell, collections::BTreeMap};
+use futures::{Future, FutureExt, future::LocalBoxFuture};
+use serde::{Serialize, de::DeserializeOwned};
+use serde_json::Value;
+use crate::{Result, Error, ErrorKind};
+use super::KvStore;
+
+type Step<S> = Box<dyn FnOnce(MigrationStore<S>) -> LocalBoxFuture<'static, Result<()>>>;
+
+/// Ordered migrations of the data in a [`KvStore`], tracked by a schema version stored under a key of the store itself.
+///
+/// Migrations run in order of version, skipping those at or below the stored version. Their changes are staged,
+/// and only written into the store once every pending migration succeeds, so a failing migration leaves the store untouched.
+/// If writing the changes fails, the entries written so far are restored, and the new version is only written after every change.
+/// ```ignore
+/// let report = Migrations::new("schema")
+///     .add(1, |store| async move { store.set("settings", &Settings::default()).await })
+///     .add(2, |store| async move {
+///         let name = store.get::<String>("name").await?;
+///         store.remove("name").await?;
+///         store.set("profile", &Profile { name }).await
+///     })
+///     .run(&Storage::local()?.unwrap())
+///     .await?;
+/// ```
+pub struct Migrations<S> {
+    key: String,
+    steps: Vec<(u32, Step<S>)>,
+    dry_run: bool
+}
+
+impl<S: 'static + Clone + KvStore> Migrations<S> {
+    /// Creates a new set of migrations, whose schema version is stored under `key`
+    #[inline]
+    pub fn new (key: impl Into<String>) -> Self {
+        return Self { key: key.into(), steps: Vec::new(), dry_run: false }
+    }
+
+    /// Adds a migration that brings the data up to `version`
+    pub fn add<F, Fut> (mut self, version: u32, f: F) -> Self where
+        F: 'static + FnOnce(MigrationStore<S>) -> Fut,
+        Fut: 'static + Future<Output = Result<()>>
+    {
+        self.steps.push((version, Box::new(move |store| f(store).boxed_local())));
+        self
+    }
+
+    /// If `true`, the pending migrations are run without writing any of their changes (or the new version) into the store.
+    /// Useful to check that they would succeed.
+    #[inline]
+    pub fn dry_run (mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Runs the pending migrations against the store, in order of version.
+    ///
+    /// If any migration fails, none of the changes are written, and the error is returned.
+    pub async fn run (mut self, store: &S) -> Result<MigrationReport> {
+        self.steps.sort_by_key(|(version, _)| *version);
+        if self.steps.windows(2).any(|x| x[0].0 == x[1].0) {
+            return Err(Error::new(ErrorKind::Js, "duplicate migration version"))
+        }
+
+        let from = store.get::<u32>(&self.key).await?.unwrap_or_default();
+        let staged = MigrationStore::new(store.clone());
+        let mut applied = Vec::new();
+
+        for (version, step) in self.steps.into_iter().filter(|(version, _)| *version > from) {
+            step(staged.clone()).await?;
+            applied.push(version);
+        }
+
+        let to = applied.last().copied().unwrap_or(from);
+        if !self.dry_run && !applied.is_empty() {
+            staged.commit(&self.key, to).await?;
+        }
+
+        return Ok(MigrationReport { from, to, applied, dry_run: self.dry_run })
+    }
+}
+
+/// Result of [`Migrations::run`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MigrationReport {
+    /// Schema version before running the migrations
+    pub from: u32,
+    /// Schema version after running the migrations
+    pub to: u32,
+    /// Versions of the migrations that were run, in order
+    pub applied: Vec<u32>,
+    /// Whether the changes were discarded instead of written
+    pub dry_run: bool
+}
+
+/// A [`KvStore`] passed to migrations, which stages their changes on top of the migrated store
+pub struct MigrationStore<S> {
+    inner: Rc<StagedInner<S>>
+}
+
+struct StagedInner<S> {
+    base: S,
+    /// Staged values, where `None` marks a removed entry
+    changes: RefCell<BTreeMap<String, Option<Value>>>
+}
+
+impl<S: KvStore> MigrationStore<S> {
+    #[inline]
+    fn new (base: S) -> Self {
+        return Self { inner: Rc::new(StagedInner { base, changes: Default::default() }) }
+    }
+
+    /// Returns the migrated store, which doesn't reflect the staged changes
+    #[inline]
+    pub fn base (&self) -> &S {
+        return &self.inner.base
+    }
+
+    /// Writes the staged changes into the migrated store, followed by the new version under `version_key`.
+    ///
+    /// If any write fails, the touched entries are restored to their previous values before returning the error.
+    async fn commit (&self, version_key: &str, version: u32) -> Result<()> {
+        let mut changes = core::mem::take(&mut *self.inner.changes.borrow_mut());
+        changes.insert(version_key.to_string(), Some(Value::from(version)));
+
+        // the version is written last, so that a partial commit never claims the new version
+        let version = changes.remove_entry(version_key);
+        let changes = changes.into_iter().chain(version).collect::<Vec<_>>();
+
+        let mut snapshot = Vec::with_capacity(changes.len());
+        for (key, _) in changes.iter() {
+            snapshot.push(self.inner.base.get::<Value>(key).await?);
+        }
+
+        for (i, (key, value)) in changes.iter().enumerate() {
+            if let Err(e) = self.write(key, value.as_ref()).await {
+                for ((key, _), previous) in changes.iter().zip(snapshot.iter()).take(i + 1).rev() {
+                    let _ = self.write(key, previous.as_ref()).await;
+                }
+                return Err(e)
+            }
+        }
+
+        return Ok(())
+    }
+
+    #[inline]
+    async fn write (&self, key: &str, value: Option<&Value>) -> Result<()> {
+        return match value {
+            Some(value) => self.inner.base.set(key, value).await,
+            None => self.inner.base.remove(key).await
+        }
+    }
+}
+
+impl<S> Clone for MigrationStore<S> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self { inner: self.inner.clone() }
+    }
+}
+
+impl<S: KvStore> KvStore for MigrationStore<S> {
+    fn get<'a, T: 'a + DeserializeOwned> (&'a self, key: &'a str) -> LocalBoxFuture<'a, Result<Option<T>>> {
+        let staged = self.inner.changes.borrow().get(key).cloned();
+        return match staged {
+            Some(value) => {
+                let result = value.map(serde_json::from_value).transpose().map_err(Error::from);
+                futures::future::ready(result).boxed_local()
+            },
+            None => self.inner.base.get(key)
+        }
+    }
+
+    fn set<'a, T: ?Sized + Serialize> (&'a self, key: &'a str, value: &T) -> LocalBoxFuture<'a, Result<()>> {
+        let result = serde_json::to_value(value).map(|value| {
+            self.inner.changes.borrow_mut().insert(key.to_string(), Some(value));
+        });
+        return futures::future::ready(result.map_err(Error::from)).boxed_local()
+    }
+
+    #[inline]
+    fn remove<'a> (&'a self, key: &'a str) -> LocalBoxFuture<'a, Result<()>> {
+        self.inner.changes.borrow_mut().insert(key.to_string(), None);
+        return futures::future::ready(Ok(())).boxed_local()
+    }
+
+    fn iter<'a, T: 'a + DeserializeOwned> (&'a self) -> LocalBoxFuture<'a, Result<std::vec::IntoIter<(String, T)>>> {
+        return async move {
+            let mut entries = self.inner.base.iter::<Value>().await?
+                .map(|(key, value)| (key, Some(value)))
+                .collect::<BTreeMap<_, _>>();
+            entries.extend(self.inner.changes.borrow().iter().map(|(key, value)| (key.clone(), value.clone())));
+
+            let entries = entries.into_iter()
+                .filter_map(|(key, value)| Some((key, value?)))
+                .map(|(key, value)| Ok((key, serde_json::from_value(value)?)))
+                .collect::<Result<Vec<_>>>()?;
+            return Ok(entries.into_iter())
+        }.boxed_local()
+    }
+}
@@ -0,0 +1,123 @@
+use futures::Future;
+use wasm_bindgen::{JsCast, JsValue, closure::Closure};
+use wasm_bindgen_futures::JsFuture;
+use crate::{Result, Error, ErrorKind, io::{Request, Response, Method, FetchError}, scope::{add_global_listener, remove_global_listener}, diagnostics::Tracked};
+
+/// A request intercepted by a service worker, from a `fetch` event
+#[derive(Debug, Clone)]
+pub struct FetchEvent {
+    inner: web_sys::FetchEvent
+}
+
+impl FetchEvent {
+    /// Returns a copy of the intercepted request, converted into a [`Request`].
+    ///
+    /// The request's body is cloned, so it can be called multiple times, and the intercepted request can still be [`forward`](FetchEvent::forward)ed.
+    /// Requests of page navigations are converted into `same-origin` ones, since they can't be constructed otherwise.
+    #[inline]
+    pub fn request (&self) -> Result<Request> {
+        let raw = self.inner.request().clone()?;
+        return Ok(Request::from(raw))
+    }
+
+    /// Returns the url of the intercepted request
+    #[inline]
+    pub fn url (&self) -> String {
+        return self.inner.request().url()
+    }
+
+    /// Returns the HTTP method of the intercepted request
+    #[inline]
+    pub fn method (&self) -> Method {
+        return Method::from(self.inner.request().method().as_str())
+    }
+
+    /// Returns the value of the specified header of the intercepted request, if present
+    #[inline]
+    pub fn header (&self, key: &str) -> Option<String> {
+        return self.inner.request().headers().get(key).ok().flatten()
+    }
+
+    /// Sends the intercepted request to the network, as if it hadn't been intercepted
+    pub async fn forward (&self) -> ::core::result::Result<Response, FetchError> {
+        let fetch = match JsFuture::from(crate::scope::fetch(&self.inner.request())).await {
+            Ok(fetch) => fetch,
+            Err(e) => return Err(FetchError::from(e))
+        };
+
+        debug_assert!(fetch.is_instance_of::<web_sys::Response>());
+        return Ok(Response::from(fetch.unchecked_into::<web_sys::Response>()))
+    }
+
+    /// Returns the underlying [`web_sys::FetchEvent`]
+    #[inline]
+    pub fn raw (&self) -> &web_sys::FetchEvent {
+        return &self.inner
+    }
+}
+
+/// Registers `f` to respond to the `fetch` events of the current service worker, returning a handle that
+/// unregisters it when dropped.
+///
+/// Every request made by the worker's clients is passed to `f`, and the response it resolves to is sent back to the client.
+/// If `f` fails, the client's request fails with a network error. Requests that shouldn't be handled can be sent to the
+/// network with [`FetchEvent::forward`].
+///
+/// Browsers only dispatch `fetch` events to handlers registered during the worker's initial evaluation,
+/// so the handler should be registered (and usually [leaked](FetchHandler::leak)) when the worker starts.
+/// ```ignore
+/// sw::fetch_handler(|event| async move {
+///     if event.url().ends_with("/offline") {
+///         return Ok(Response::new(200, "You are offline")?)
+///     }
+///     event.forward().await
+/// })?.leak();
+/// ```
+pub fn fetch_handler<F, Fut> (mut f: F) -> Result<FetchHandler> where
+    F: 'static + FnMut(FetchEvent) -> Fut,
+    Fut: 'static + Future<Output = ::core::result::Result<Response, FetchError>>
+{
+    if !js_sys::global().is_instance_of::<web_sys::ServiceWorkerGlobalScope>() {
+        return Err(Error::new(ErrorKind::NotSupported, "fetch handlers can only be registered within a service worker"))
+    }
+
+    let closure = Closure::<dyn FnMut(web_sys::FetchEvent)>::new(move |evt: web_sys::FetchEvent| {
+        let fut = f(FetchEvent { inner: evt.clone() });
+        let promise = wasm_bindgen_futures::future_to_promise(async move {
+            return match fut.await {
+                Ok(resp) => Ok(JsValue::from(resp.into_raw())),
+                Err(e) => Err(JsValue::from(e))
+            }
+        });
+
+        if let Err(e) = evt.respond_with(&promise) {
+            wasm_bindgen::throw_val(e)
+        }
+    });
+
+    add_global_listener("fetch", closure.as_ref().unchecked_ref())?;
+    return Ok(FetchHandler { closure, _tracked: Tracked::new("sw::fetch_handler") })
+}
+
+/// Handle of a closure registered via [`fetch_handler`].
+///
+/// When droped, the closure will be unregistered, releasing all of it's memory.
+pub struct FetchHandler {
+    closure: Closure<dyn FnMut(web_sys::FetchEvent)>,
+    _tracked: Tracked
+}
+
+impl FetchHandler {
+    /// Leaks the handle, keeping the closure registered for the rest of the worker's lifetime.
+    #[inline]
+    pub fn leak (self) {
+        core::mem::forget(self)
+    }
+}
+
+impl Drop for FetchHandler {
+    #[inline]
+    fn drop(&mut self) {
+        let _ = remove_global_listener("fetch", self.closure.as_ref().unchecked_ref());
+    }
+}
@@ -1,8 +1,16 @@
-use std::{cell::{Cell, UnsafeCell}, task::{Poll, Waker}, collections::VecDeque, ops::{Deref, DerefMut}, sync::{Arc, atomic::{AtomicU8}}, rc::{Rc, Weak}};
-use futures::{Future};
+use std::{cell::{Cell, RefCell, UnsafeCell}, task::{Poll, Waker}, collections::VecDeque, ops::{Deref, DerefMut}, sync::{Arc, atomic::{AtomicU8}}, rc::{Rc, Weak}};
+use futures::{Future, FutureExt, future::FusedFuture};
 use utils_atomics::{flag::spsc::{AsyncFlag, async_flag}};
 use wasm_bindgen_futures::spawn_local;
 
+/// Leader election across tabs
+pub mod leader;
+
+flat_mod! { rate }
+
+#[cfg(target_feature = "atomics")]
+flat_mod! { ring }
+
 cfg_if::cfg_if! {
     if #[cfg(target_feature = "atomics")] {
         use std::sync::atomic::{AtomicBool, Ordering};
@@ -77,8 +85,9 @@ cfg_if::cfg_if! {
     } else {
         pub struct Mutex<T> {
             locked: Cell<bool>,
+            /// Only accessed through a [`MutexGuard`], of which there's at most one at a time
             inner: UnsafeCell<T>,
-            wakers: UnsafeCell<VecDeque<Waker>>
+            wakers: RefCell<VecDeque<Waker>>
         }
         
         impl<T> Mutex<T> {
@@ -87,7 +96,7 @@ cfg_if::cfg_if! {
                 return Self {
                     locked: Cell::new(false),
                     inner: UnsafeCell::new(t),
-                    wakers: UnsafeCell::new(VecDeque::new())
+                    wakers: RefCell::new(VecDeque::new())
                 }
             }
         
@@ -121,7 +130,7 @@ cfg_if::cfg_if! {
                 if let Some(guard) = self.parent.try_lock() {
                     return Poll::Ready(guard)
                 }
-                unsafe { &mut *self.parent.wakers.get() }.push_back(cx.waker().clone());
+                self.parent.wakers.borrow_mut().push_back(cx.waker().clone());
                 return Poll::Pending
             }
         }
@@ -130,7 +139,9 @@ cfg_if::cfg_if! {
             #[inline]
             fn drop(&mut self) {
                 self.parent.locked.set(false);
-                if let Some(waker) = unsafe { &mut *self.parent.wakers.get() }.pop_front() {
+                // the borrow is released before waking, since the waker may re-enter the mutex
+                let waker = self.parent.wakers.borrow_mut().pop_front();
+                if let Some(waker) = waker {
                     waker.wake()
                 }
             }
@@ -147,6 +158,7 @@ impl<T> Deref for MutexGuard<'_, T> {
 
     #[inline]
     fn deref(&self) -> &Self::Target {
+        // SAFETY: The mutex is locked while the guard is alive, so no other reference to the value exists
         unsafe { &*self.parent.inner.get() }
     }
 }
@@ -154,6 +166,7 @@ impl<T> Deref for MutexGuard<'_, T> {
 impl<T> DerefMut for MutexGuard<'_, T> {
     #[inline]
     fn deref_mut(&mut self) -> &mut Self::Target {
+        // SAFETY: The mutex is locked while the guard is alive, so no other reference to the value exists
         unsafe { &mut *self.parent.inner.get() }
     }
 }
@@ -163,7 +176,27 @@ cfg_if::cfg_if! {
         /// Receiver to a local one-shot channel
         pub struct ShotReceiver<T> {
             pub(crate) value: Arc<Option<T>>,
-            sub: utils_atomics::flag::spsc::AsyncSubscribe
+            sub: utils_atomics::flag::spsc::AsyncSubscribe,
+            done: bool
+        }
+
+        impl<T> ShotReceiver<T> {
+            /// Returns the value if it has already been sent, without waiting for it.
+            ///
+            /// Returns `None` if the value hasn't been sent yet, or if it has already been received.
+            pub fn try_recv (&mut self) -> Option<T> {
+                if self.done {
+                    return None
+                }
+
+                let mut cx = std::task::Context::from_waker(futures::task::noop_waker_ref());
+                if self.sub.poll_unpin(&mut cx).is_ready() {
+                    self.done = true;
+                    // SAFETY: Since the flag has been marked, we are the only ones with access to the value
+                    return core::mem::take(unsafe { Arc::get_mut_unchecked(&mut self.value) })
+                }
+                return None
+            }
         }
 
         impl<T> Future for ShotReceiver<T> {
@@ -171,7 +204,12 @@ cfg_if::cfg_if! {
 
             #[inline]
             fn poll(mut self: std::pin::Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> std::task::Poll<Self::Output> {
+                if self.done {
+                    return Poll::Ready(None)
+                }
+
                 if self.sub.poll_unpin(cx).is_ready() {
+                    self.done = true;
                     // SAFETY: Since the flag has been marked, we are the only ones with access to the value
                     let value = core::mem::take(unsafe { Arc::get_mut_unchecked(&mut self.value) });
                     return Poll::Ready(value)
@@ -180,6 +218,13 @@ cfg_if::cfg_if! {
             }
         }
 
+        impl<T> FusedFuture for ShotReceiver<T> {
+            #[inline]
+            fn is_terminated (&self) -> bool {
+                return self.done
+            }
+        }
+
         /// Sender of a local one-shot channel
         pub struct ShotSender<T> {
             pub(crate) value: Arc<Option<T>>,
@@ -207,25 +252,42 @@ cfg_if::cfg_if! {
         pub fn one_shot<T> () -> (ShotSender<T>, ShotReceiver<T>) {
             let (flag, sub) = utils_atomics::flag::spsc::async_flag();
             let value = Arc::new(None);
-            return (ShotSender { value: value.clone(), flag: TakeCell::new(flag) }, ShotReceiver { value, sub })
+            return (ShotSender { value: value.clone(), flag: TakeCell::new(flag) }, ShotReceiver { value, sub, done: false })
         }
     } else {
         /// Receiver to a local one-shot channel
         pub struct ShotReceiver<T> {
-            pub(crate) inner: Rc<FutureInner<T>>
+            pub(crate) inner: Rc<FutureInner<T>>,
+            done: bool
+        }
+
+        impl<T> ShotReceiver<T> {
+            /// Returns the value if it has already been sent, without waiting for it.
+            ///
+            /// Returns `None` if the value hasn't been sent yet, or if it has already been received.
+            #[inline]
+            pub fn try_recv (&mut self) -> Option<T> {
+                let value = self.inner.value.take();
+                if value.is_some() || Rc::weak_count(&self.inner) == 0 {
+                    self.done = true;
+                }
+                return value
+            }
         }
 
         impl<T> Future for ShotReceiver<T> {
             type Output = Option<T>;
 
             #[inline]
-            fn poll(self: std::pin::Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> std::task::Poll<Self::Output> {
+            fn poll(mut self: std::pin::Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> std::task::Poll<Self::Output> {
                 if let Some(geo) = self.inner.value.take() {
+                    self.done = true;
                     return Poll::Ready(Some(geo));
                 }
 
                 // No more senders left
                 if Rc::weak_count(&self.inner) == 0 {
+                    self.done = true;
                     return Poll::Ready(None);
                 }
 
@@ -234,6 +296,13 @@ cfg_if::cfg_if! {
             }
         }
 
+        impl<T> FusedFuture for ShotReceiver<T> {
+            #[inline]
+            fn is_terminated (&self) -> bool {
+                return self.done
+            }
+        }
+
         /// Sender of a local one-shot channel
         pub struct ShotSender<T> {
             inner: Cell<Option<Weak<FutureInner<T>>>>
@@ -281,7 +350,7 @@ cfg_if::cfg_if! {
         #[inline]
         pub fn one_shot<T> () -> (ShotSender<T>, ShotReceiver<T>) {
             let inner = Rc::new(FutureInner::default());
-            return (ShotSender { inner: Cell::new(Some(Rc::downgrade(&inner))) }, ShotReceiver { inner })
+            return (ShotSender { inner: Cell::new(Some(Rc::downgrade(&inner))) }, ShotReceiver { inner, done: false })
         }
 
         pub(crate) struct FutureInner<T> {
@@ -301,6 +370,22 @@ cfg_if::cfg_if! {
     }
 }
 
+impl<T> ShotReceiver<T> {
+    /// Returns `true` if the value has already been received, or if the sender was dropped without sending it
+    #[inline]
+    pub fn is_terminated (&self) -> bool {
+        return FusedFuture::is_terminated(self)
+    }
+
+    /// Waits for the value for up to `dur`, returning `None` if it isn't received in time or if the sender was dropped.
+    ///
+    /// If the timeout elapses, the value can still be received later.
+    #[inline]
+    pub async fn recv_timeout (&mut self, dur: std::time::Duration) -> crate::Result<Option<T>> {
+        return Ok(crate::time::timeout(dur, self).await?.flatten())
+    }
+}
+
 /// Handle to drop a value owned by another thread.
 /// 
 /// When dropped, the associated value will be dropped from it's original thread 
@@ -0,0 +1,202 @@
+use std::{time::Duration, rc::Rc, cell::Cell};
+use js_sys::{Promise, Function};
+use serde::{Serialize, Deserialize};
+use wasm_bindgen::{JsCast, JsValue, prelude::{wasm_bindgen, Closure}};
+use wasm_bindgen_futures::JsFuture;
+use crate::{Result, Error, ErrorKind, scope::navigator, storage::Storage, time::{Interval, sleep}, sync::one_shot};
+
+/// Time a leadership lease lasts in the local storage fallback, unless renewed
+const LEASE: Duration = Duration::from_secs(5);
+/// Interval at which leaders renew their lease, and candidates check for expired ones, in the local storage fallback
+const HEARTBEAT: Duration = Duration::from_secs(1);
+
+#[wasm_bindgen]
+extern "C" {
+    type LockManager;
+
+    #[wasm_bindgen(method, catch)]
+    fn request (this: &LockManager, name: &str, options: &js_sys::Object, callback: &Function) -> ::core::result::Result<Promise, JsValue>;
+}
+
+/// Waits until the current tab becomes the leader for `name`, returning a guard that relinquishes leadership when droped.
+/// 
+/// At most one tab (or worker) of the same origin is leader for a given name at any time, which is useful to deduplicate
+/// background work (like syncing) across tabs. Leadership is coordinated with the [Web Locks API](https://developer.mozilla.org/en-US/docs/Web/API/Web_Locks_API),
+/// falling back to leases in local storage if it isn't available.
+/// 
+/// If the returned future is droped before resolving, the tab stops competing for leadership.
+pub async fn elect (name: &str) -> Result<Leader> {
+    let locks = js_sys::Reflect::get(&navigator(), &JsValue::from_str("locks"))?;
+    if locks.is_undefined() {
+        return elect_storage(name).await
+    }
+    return elect_locks(locks.unchecked_into(), name).await
+}
+
+/// Guard of the leadership of a tab, returned by [`elect`].
+/// 
+/// When droped, leadership is relinquished, and another tab can become leader.
+/// 
+/// With the local storage fallback, leadership can be lost while the guard is alive (for example, if the tab's timers are throttled
+/// in the background and it's lease expires), so callers must check [`is_leader`](Leader::is_leader) before doing leader-only work.
+pub struct Leader {
+    inner: LeaderInner
+}
+
+impl Leader {
+    /// Returns `true` if the tab is still the leader.
+    /// 
+    /// With the Web Locks API, leadership is held until the guard is droped, so this always returns `true`. With the local storage fallback,
+    /// it returns `false` once another tab has taken the lease, or once it has expired without being renewed, and leadership isn't regained afterwards.
+    pub fn is_leader (&self) -> bool {
+        match self.inner {
+            LeaderInner::Locks { .. } => return true,
+            LeaderInner::Storage { ref storage, ref key, ref id, ref lost, .. } => {
+                if lost.get() {
+                    return false
+                }
+
+                let held = matches!(storage.get::<Lease>(key), Ok(Some(lease)) if &lease.id == id && lease.expires > js_sys::Date::now());
+                if !held {
+                    lost.set(true)
+                }
+                return held
+            }
+        }
+    }
+}
+
+enum LeaderInner {
+    Locks {
+        release: Function,
+        _callback: Closure<dyn FnMut(JsValue) -> Promise>
+    },
+    Storage {
+        storage: Storage,
+        key: String,
+        id: String,
+        lost: Rc<Cell<bool>>,
+        _renew: Interval<'static, ()>
+    }
+}
+
+impl Drop for Leader {
+    fn drop(&mut self) {
+        match self.inner {
+            LeaderInner::Locks { ref release, .. } => {
+                let _ = release.call0(&JsValue::UNDEFINED);
+            },
+            LeaderInner::Storage { ref storage, ref key, ref id, .. } => {
+                if let Ok(Some(lease)) = storage.get::<Lease>(key) {
+                    if &lease.id == id {
+                        let _ = storage.remove(key);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Aborts the lock request and releases the lock (if granted) unless disarmed
+struct ElectionGuard {
+    controller: web_sys::AbortController,
+    release: Option<Function>
+}
+
+impl Drop for ElectionGuard {
+    fn drop(&mut self) {
+        if let Some(ref release) = self.release {
+            self.controller.abort();
+            let _ = release.call0(&JsValue::UNDEFINED);
+        }
+    }
+}
+
+async fn elect_locks (locks: LockManager, name: &str) -> Result<Leader> {
+    let mut release = None;
+    let held = Promise::new(&mut |resolve, _| release = Some(resolve));
+    let release = release.unwrap();
+
+    let (send, granted) = one_shot();
+    let callback = Closure::<dyn FnMut(JsValue) -> Promise>::new(move |_| {
+        let _ = send.try_send(());
+        held.clone()
+    });
+
+    let controller = web_sys::AbortController::new()?;
+    let mut guard = ElectionGuard { controller, release: Some(release) };
+
+    let options = js_sys::Object::new();
+    js_sys::Reflect::set(&options, &JsValue::from_str("signal"), &guard.controller.signal())?;
+
+    let request = locks.request(name, &options, callback.as_ref().unchecked_ref())?;
+    // The request rejects when aborted, which is expected
+    let _ = request.catch(&Function::new_no_args(""));
+
+    if granted.await.is_none() {
+        return Err(Error::new(ErrorKind::Abort, "lock request was dropped"))
+    }
+
+    return Ok(Leader {
+        inner: LeaderInner::Locks {
+            release: guard.release.take().unwrap(),
+            _callback: callback
+        }
+    })
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Lease {
+    id: String,
+    expires: f64
+}
+
+async fn elect_storage (name: &str) -> Result<Leader> {
+    let storage = match Storage::local()? {
+        Some(storage) => storage,
+        None => return Err(Error::new(ErrorKind::NotSupported, "neither the Web Locks API nor local storage are available"))
+    };
+
+    let key = format!("rustww:leader:{name}");
+    let id = format!("{:x}", crate::random::next_u64()?);
+    let lease_ms = LEASE.as_secs_f64() * 1000.;
+
+    loop {
+        let now = js_sys::Date::now();
+        let available = match storage.get::<Lease>(&key)? {
+            Some(lease) => lease.id == id || lease.expires <= now,
+            None => true
+        };
+
+        if available {
+            storage.set(&key, &Lease { id: id.clone(), expires: now + lease_ms })?;
+            // Give competing tabs a chance to overwrite the lease, and check if we won
+            sleep(Duration::from_millis(50))?.await;
+            if storage.get::<Lease>(&key)?.is_some_and(|lease| lease.id == id) {
+                break
+            }
+        }
+
+        sleep(HEARTBEAT)?.await;
+    }
+
+    let lost = Rc::new(Cell::new(false));
+    let renew = {
+        let storage = storage.clone();
+        let key = key.clone();
+        let id = id.clone();
+        let lost = lost.clone();
+        Interval::new(HEARTBEAT, move || {
+            if lost.get() { return }
+            match storage.get::<Lease>(&key) {
+                // another tab took the lease, or it expired while the timers were throttled
+                Ok(Some(lease)) if lease.id != id || lease.expires <= js_sys::Date::now() => lost.set(true),
+                _ => { let _ = storage.set(&key, &Lease { id: id.clone(), expires: js_sys::Date::now() + lease_ms }); }
+            }
+        })?
+    };
+
+    return Ok(Leader {
+        inner: LeaderInner::Storage { storage, key, id, lost, _renew: renew }
+    })
+}
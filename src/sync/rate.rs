@@ -0,0 +1,110 @@
+use std::{rc::Rc, cell::Cell, time::Duration};
+use futures::{future::LocalBoxFuture, FutureExt};
+use crate::{Result, time::{now, sleep}, io::{Middleware, Next, Request, Response, FetchError}};
+
+/// A token bucket rate limiter.
+///
+/// The bucket holds up to `burst` tokens, and is refilled at `rate` tokens per second. Every [`acquire`](RateLimiter::acquire)
+/// consumes a token, waiting for the bucket to be refilled if it's empty.
+///
+/// Clones of a limiter share the same bucket, and it can be added as a [`Middleware`] to a [`FetchClient`](crate::io::FetchClient)
+/// to rate limit every request made through it.
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    inner: Rc<Bucket>
+}
+
+#[derive(Debug)]
+struct Bucket {
+    rate: f64,
+    burst: f64,
+    tokens: Cell<f64>,
+    last: Cell<Duration>
+}
+
+impl RateLimiter {
+    /// Creates a new, full, rate limiter that allows `rate` acquisitions per second, with bursts of up to `burst` acquisitions.
+    ///
+    /// # Panics
+    /// This method panics if `rate` isn't a positive number, or if `burst` is zero.
+    pub fn new (rate: f64, burst: u32) -> Self {
+        assert!(rate > 0. && rate.is_finite(), "rate must be a positive number");
+        assert!(burst > 0, "burst must be greater than zero");
+
+        return Self {
+            inner: Rc::new(Bucket {
+                rate,
+                burst: burst as f64,
+                tokens: Cell::new(burst as f64),
+                last: Cell::new(now())
+            })
+        }
+    }
+
+    /// Returns the number of tokens currently available
+    #[inline]
+    pub fn available (&self) -> f64 {
+        self.refill();
+        return self.inner.tokens.get()
+    }
+
+    /// Consumes a token if one is available, returning `true`, or returns `false` otherwise
+    #[inline]
+    pub fn try_acquire (&self) -> bool {
+        return self.try_acquire_n(1)
+    }
+
+    /// Consumes `n` tokens if they're available, returning `true`, or returns `false` otherwise
+    pub fn try_acquire_n (&self, n: u32) -> bool {
+        self.refill();
+        let tokens = self.inner.tokens.get();
+        if tokens >= n as f64 {
+            self.inner.tokens.set(tokens - n as f64);
+            return true
+        }
+        return false
+    }
+
+    /// Consumes a token, waiting until one is available
+    #[inline]
+    pub async fn acquire (&self) -> Result<()> {
+        return self.acquire_n(1).await
+    }
+
+    /// Consumes `n` tokens, waiting until they're available.
+    ///
+    /// # Panics
+    /// This method panics if `n` is greater than the limiter's burst size, since the tokens would never become available.
+    pub async fn acquire_n (&self, n: u32) -> Result<()> {
+        let n = n as f64;
+        assert!(n <= self.inner.burst, "can't acquire more tokens than the burst size");
+
+        loop {
+            self.refill();
+            let tokens = self.inner.tokens.get();
+            if tokens >= n {
+                self.inner.tokens.set(tokens - n);
+                return Ok(())
+            }
+
+            sleep(Duration::from_secs_f64((n - tokens) / self.inner.rate))?.await;
+        }
+    }
+
+    fn refill (&self) {
+        let now = now();
+        let elapsed = now.saturating_sub(self.inner.last.replace(now));
+        let tokens = self.inner.tokens.get() + elapsed.as_secs_f64() * self.inner.rate;
+        self.inner.tokens.set(f64::min(tokens, self.inner.burst));
+    }
+}
+
+impl Middleware for RateLimiter {
+    fn handle (&self, req: Request, next: Next) -> LocalBoxFuture<'static, ::core::result::Result<Response, FetchError>> {
+        let this = self.clone();
+        return async move {
+            this.acquire().await?;
+            next.run(req).await
+        }.boxed_local()
+    }
+}
@@ -0,0 +1,229 @@
+use std::{marker::PhantomData, time::Duration};
+use js_sys::{SharedArrayBuffer, Int32Array, Uint8Array, Atomics};
+use crate::{Result, Error, ErrorKind, utils::{TypedArray, TypedArrayExt}};
+
+/// Index of the read position, in the header of the buffer
+const HEAD: u32 = 0;
+/// Index of the write position, in the header of the buffer
+const TAIL: u32 = 1;
+/// Size of the header (head and tail indices), in bytes
+const HEADER: u32 = 8;
+
+/// Creates a lock-free single-producer single-consumer ring buffer of `capacity` elements, backed by a [`SharedArrayBuffer`].
+///
+/// The elements are those of the typed array `T` (`Uint8Array` for bytes, `Float32Array` for audio samples, ...).
+/// To use an end of the ring from another context (a worker, an audio worklet, ...), send it's [`buffer`](RingProducer::buffer)
+/// with `postMessage`, and open it there with [`RingProducer::from_buffer`] or [`RingConsumer::from_buffer`].
+/// ```ignore
+/// let (producer, consumer) = spsc_ring::<Float32Array>(4096)?;
+/// worker.post_message(consumer.buffer())?;
+/// producer.push(&samples);
+/// ```
+#[docfg::docfg(target_feature = "atomics")]
+pub fn spsc_ring<T: TypedArrayExt> (capacity: u32) -> Result<(RingProducer<T>, RingConsumer<T>)> {
+    if capacity == 0 || capacity > i32::MAX as u32 / 2 {
+        return Err(invalid_capacity())
+    }
+
+    let byte_length = capacity.checked_mul(T::bytes_per_element())
+        .and_then(|x| x.checked_add(HEADER))
+        .ok_or_else(invalid_capacity)?;
+
+    let buffer = SharedArrayBuffer::new(byte_length);
+    return Ok((RingProducer::from_buffer(buffer.clone())?, RingConsumer::from_buffer(buffer)?))
+}
+
+#[inline]
+fn invalid_capacity () -> Error {
+    return Error::from_js(ErrorKind::Js, js_sys::RangeError::new("invalid ring capacity").into())
+}
+
+#[derive(Debug, Clone)]
+struct Ring<T> {
+    buffer: SharedArrayBuffer,
+    indices: Int32Array,
+    data: Uint8Array,
+    capacity: u32,
+    _phantom: PhantomData<T>
+}
+
+impl<T: TypedArrayExt> Ring<T> {
+    fn new (buffer: SharedArrayBuffer) -> Result<Self> {
+        let byte_length = buffer.byte_length();
+        let capacity = byte_length.saturating_sub(HEADER) / T::bytes_per_element();
+        if capacity == 0 || capacity > i32::MAX as u32 / 2 {
+            return Err(invalid_capacity())
+        }
+
+        return Ok(Self {
+            indices: Int32Array::new_with_byte_offset_and_length(&buffer, 0, 2),
+            data: Uint8Array::new_with_byte_offset_and_length(&buffer, HEADER, capacity * T::bytes_per_element()),
+            buffer,
+            capacity,
+            _phantom: PhantomData
+        })
+    }
+
+    #[inline]
+    fn load (&self, idx: u32) -> u32 {
+        return Atomics::load(&self.indices, idx).unwrap_or_default() as u32
+    }
+
+    #[inline]
+    fn store (&self, idx: u32, value: u32) {
+        let _ = Atomics::store(&self.indices, idx, value as i32);
+        let _ = Atomics::notify(&self.indices, idx);
+    }
+
+    /// Positions wrap around twice the capacity, to tell a full ring from an empty one
+    #[inline]
+    fn len_between (&self, head: u32, tail: u32) -> u32 {
+        return (tail + 2 * self.capacity - head) % (2 * self.capacity)
+    }
+
+    #[inline]
+    fn len (&self) -> u32 {
+        return self.len_between(self.load(HEAD), self.load(TAIL))
+    }
+
+    /// Copies `src` into the ring, starting at element `offset`, without wrapping around
+    fn copy_in (&self, offset: u32, src: &[T::Element]) {
+        if src.is_empty() { return }
+        // SAFETY: The view is droped before any allocation could move the memory
+        let bytes = unsafe { T::view(src) }.as_bytes();
+        self.data.set(&bytes, offset * T::bytes_per_element());
+    }
+
+    /// Copies the elements starting at element `offset` into `dst`, without wrapping around
+    fn copy_out (&self, offset: u32, dst: &mut [T::Element]) {
+        if dst.is_empty() { return }
+        let start = offset * T::bytes_per_element();
+        let end = start + dst.len() as u32 * T::bytes_per_element();
+        // SAFETY: The view is droped before any allocation could move the memory
+        let bytes = unsafe { T::view_mut_raw(dst.as_mut_ptr(), dst.len()) }.as_bytes();
+        bytes.set(&self.data.subarray(start, end), 0);
+    }
+}
+
+/// Writing end of a ring buffer created with [`spsc_ring`]
+#[docfg::docfg(target_feature = "atomics")]
+#[derive(Debug)]
+pub struct RingProducer<T> {
+    ring: Ring<T>
+}
+
+impl<T: TypedArrayExt> RingProducer<T> {
+    /// Opens the writing end of the ring buffer backed by `buffer`.
+    ///
+    /// At most one producer should be writing to the same ring at a time.
+    #[inline]
+    pub fn from_buffer (buffer: SharedArrayBuffer) -> Result<Self> {
+        return Ring::new(buffer).map(|ring| Self { ring })
+    }
+
+    /// Writes as many elements of `src` as fit in the ring, returning how many were written
+    pub fn push (&self, src: &[T::Element]) -> usize {
+        let head = self.ring.load(HEAD);
+        let tail = self.ring.load(TAIL);
+        let free = self.ring.capacity - self.ring.len_between(head, tail);
+        let len = free.min(src.len() as u32);
+        if len == 0 { return 0 }
+
+        let offset = tail % self.ring.capacity;
+        let first = len.min(self.ring.capacity - offset);
+        self.ring.copy_in(offset, &src[..first as usize]);
+        self.ring.copy_in(0, &src[first as usize..len as usize]);
+
+        self.ring.store(TAIL, (tail + len) % (2 * self.ring.capacity));
+        return len as usize
+    }
+
+    /// Returns the number of elements that can be written without overwriting unread ones
+    #[inline]
+    pub fn free (&self) -> usize {
+        return (self.ring.capacity - self.ring.len()) as usize
+    }
+
+    /// Returns the capacity of the ring, in elements
+    #[inline]
+    pub fn capacity (&self) -> usize {
+        return self.ring.capacity as usize
+    }
+
+    /// Returns the buffer that backs the ring
+    #[inline]
+    pub fn buffer (&self) -> &SharedArrayBuffer {
+        return &self.ring.buffer
+    }
+}
+
+/// Reading end of a ring buffer created with [`spsc_ring`]
+#[docfg::docfg(target_feature = "atomics")]
+#[derive(Debug)]
+pub struct RingConsumer<T> {
+    ring: Ring<T>
+}
+
+impl<T: TypedArrayExt> RingConsumer<T> {
+    /// Opens the reading end of the ring buffer backed by `buffer`.
+    ///
+    /// At most one consumer should be reading from the same ring at a time.
+    #[inline]
+    pub fn from_buffer (buffer: SharedArrayBuffer) -> Result<Self> {
+        return Ring::new(buffer).map(|ring| Self { ring })
+    }
+
+    /// Reads as many elements as available into `dst`, returning how many were read
+    pub fn pop (&self, dst: &mut [T::Element]) -> usize {
+        let head = self.ring.load(HEAD);
+        let tail = self.ring.load(TAIL);
+        let len = self.ring.len_between(head, tail).min(dst.len() as u32);
+        if len == 0 { return 0 }
+
+        let offset = head % self.ring.capacity;
+        let first = len.min(self.ring.capacity - offset);
+        self.ring.copy_out(offset, &mut dst[..first as usize]);
+        self.ring.copy_out(0, &mut dst[first as usize..len as usize]);
+
+        self.ring.store(HEAD, (head + len) % (2 * self.ring.capacity));
+        return len as usize
+    }
+
+    /// Blocks the current thread until there are elements to read, or until `timeout` elapses.
+    /// Returns `true` if there are elements to read.
+    ///
+    /// Browsers don't allow blocking the main thread, so this will fail outside of workers.
+    pub fn wait (&self, timeout: Duration) -> Result<bool> {
+        let tail = self.ring.load(TAIL);
+        if self.ring.len_between(self.ring.load(HEAD), tail) > 0 {
+            return Ok(true)
+        }
+
+        Atomics::wait_with_timeout(&self.ring.indices, TAIL, tail as i32, timeout.as_secs_f64() * 1000.0)?;
+        return Ok(!self.is_empty())
+    }
+
+    /// Returns the number of elements waiting to be read
+    #[inline]
+    pub fn len (&self) -> usize {
+        return self.ring.len() as usize
+    }
+
+    /// Returns `true` if there are no elements waiting to be read
+    #[inline]
+    pub fn is_empty (&self) -> bool {
+        return self.ring.len() == 0
+    }
+
+    /// Returns the capacity of the ring, in elements
+    #[inline]
+    pub fn capacity (&self) -> usize {
+        return self.ring.capacity as usize
+    }
+
+    /// Returns the buffer that backs the ring
+    #[inline]
+    pub fn buffer (&self) -> &SharedArrayBuffer {
+        return &self.ring.buffer
+    }
+}
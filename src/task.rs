@@ -10,11 +10,20 @@ use crate::{utils::{AbortSignal, AbortController}, Result};
 pub fn spawn_catch_local<Fut: 'static + Future<Output = crate::Result<()>>> (fut: Fut) {
     spawn_local(async move {
         if let Err(e) = fut.await {
-            wasm_bindgen::throw_val(e);
+            wasm_bindgen::throw_val(e.into());
         }
     })
 }
 
+/// Spawns the specified `Future` into the JavaScript runtime, with it's first poll scheduled as a task with the specified priority.
+/// 
+/// See [`post_task`](crate::scope::post_task)
+#[inline]
+pub fn spawn_with_priority<Fut: 'static + Future<Output = ()>> (priority: crate::scope::TaskPriority, fut: Fut) -> Result<()> {
+    let _ = crate::scope::post_task(priority, move || spawn_local(fut))?;
+    return Ok(())
+}
+
 /// Returns a future that resolves when `p` settles, or when `con` is aborted
 pub fn abortable_promise_with_controller<T> (p: js_sys::Promise, con: &AbortController<T>) -> js_sys::Promise {
     let array = js_sys::Array::new_with_length(2);
@@ -37,4 +46,167 @@ pub fn abortable_promise<T> (p: js_sys::Promise) -> Result<(js_sys::Promise, Abo
     let con = AbortController::<T>::new()?;
     let p = abortable_promise_with_controller(p, &con);
     return Ok((p, con))
-}
\ No newline at end of file
+}
+/// Returns a future that yields control back to the event loop, resolving on one of it's next iterations.
+/// 
+/// Unlike awaiting a resolved promise (which only yields to the microtask queue), this allows the browser to
+/// render and handle input before resuming. It's implemented with a [`MessageChannel`](web_sys::MessageChannel), falling back to
+/// a zero-delay timeout.
+pub async fn yield_now () {
+    let mut f = |resolve: Function, _: Function| {
+        if let Ok(channel) = web_sys::MessageChannel::new() {
+            channel.port1().set_onmessage(Some(&resolve));
+            if channel.port2().post_message(&wasm_bindgen::JsValue::NULL).is_ok() { return }
+        }
+        let _ = crate::scope::set_timeout(&resolve, 0);
+    };
+
+    let _ = wasm_bindgen_futures::JsFuture::from(Promise::new(&mut f)).await;
+}
+
+/// Wraps `fut` so that it yields to the event loop (via [`yield_now`]) whenever it has been running for longer than `slice`
+/// without interruption, keeping the page responsive during long computations. Time spent suspended (while `fut` is pending)
+/// doesn't count towards the slice.
+/// 
+/// The budget is only checked between polls of `fut`, so it must have await points (like [`yield_now`] itself, or awaiting other futures).
+#[inline]
+pub fn budget<Fut: Future> (fut: Fut, slice: std::time::Duration) -> Budget<Fut> {
+    return Budget {
+        fut: Box::pin(fut),
+        slice: slice.as_secs_f64() * 1000.,
+        slice_start: None,
+        yielding: None
+    }
+}
+
+/// Future returned by [`budget`]
+pub struct Budget<Fut> {
+    fut: std::pin::Pin<Box<Fut>>,
+    slice: f64,
+    slice_start: Option<f64>,
+    yielding: Option<std::pin::Pin<Box<dyn Future<Output = ()>>>>
+}
+
+impl<Fut: Future> Future for Budget<Fut> {
+    type Output = Fut::Output;
+
+    fn poll(mut self: std::pin::Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> std::task::Poll<Self::Output> {
+        if let Some(ref mut yielding) = self.yielding {
+            match yielding.as_mut().poll(cx) {
+                std::task::Poll::Ready(()) => self.yielding = None,
+                std::task::Poll::Pending => return std::task::Poll::Pending
+            }
+        }
+
+        let now = crate::time::now().as_secs_f64() * 1000.;
+        let slice_start = *self.slice_start.get_or_insert(now);
+        if now - slice_start > self.slice {
+            self.slice_start = None;
+            self.yielding = Some(Box::pin(yield_now()));
+            cx.waker().wake_by_ref();
+            return std::task::Poll::Pending
+        }
+
+        let poll = self.fut.as_mut().poll(cx);
+        if poll.is_pending() {
+            // the inner future already gives control back to the event loop, so the next slice starts when it's woken
+            self.slice_start = None;
+        }
+        return poll
+    }
+}
+
+thread_local! {
+    static HOOKS: std::cell::RefCell<Option<std::rc::Rc<Hooks>>> = Default::default();
+    static NEXT_TASK_ID: std::cell::Cell<u64> = std::cell::Cell::new(0);
+}
+
+/// Information about a task spawned with [`spawn_named`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TaskInfo {
+    /// Unique identifier of the task in the current thread
+    pub id: u64,
+    /// Name of the task
+    pub name: std::rc::Rc<str>
+}
+
+struct Hooks {
+    on_spawn: Box<dyn Fn(&TaskInfo)>,
+    on_poll_start: Box<dyn Fn(&TaskInfo)>,
+    on_poll_end: Box<dyn Fn(&TaskInfo, std::time::Duration)>,
+    on_complete: Box<dyn Fn(&TaskInfo)>
+}
+
+/// Sets the instrumentation hooks of the tasks spawned with [`spawn_named`] on the current thread, replacing the previous ones.
+/// 
+/// - `on_spawn` is called when a task is spawned.
+/// - `on_poll_start` is called right before a task is polled.
+/// - `on_poll_end` is called right after a task is polled, with the time the poll took.
+/// - `on_complete` is called when a task completes.
+pub fn set_hooks<S, PS, PE, C> (on_spawn: S, on_poll_start: PS, on_poll_end: PE, on_complete: C) where
+    S: 'static + Fn(&TaskInfo),
+    PS: 'static + Fn(&TaskInfo),
+    PE: 'static + Fn(&TaskInfo, std::time::Duration),
+    C: 'static + Fn(&TaskInfo)
+{
+    let hooks = Hooks {
+        on_spawn: Box::new(on_spawn),
+        on_poll_start: Box::new(on_poll_start),
+        on_poll_end: Box::new(on_poll_end),
+        on_complete: Box::new(on_complete)
+    };
+    HOOKS.with(|x| *x.borrow_mut() = Some(std::rc::Rc::new(hooks)));
+}
+
+/// Removes the instrumentation hooks of the current thread
+#[inline]
+pub fn clear_hooks () {
+    HOOKS.with(|x| *x.borrow_mut() = None);
+}
+
+#[inline]
+fn hooks () -> Option<std::rc::Rc<Hooks>> {
+    return HOOKS.with(|x| x.borrow().clone())
+}
+
+/// Spawns the specified `Future` into the JavaScript runtime with a name, reporting it's lifecycle to the hooks set with [`set_hooks`].
+/// 
+/// See [`spawn_local`](wasm_bindgen_futures::spawn_local)
+pub fn spawn_named<Fut: 'static + Future<Output = ()>> (name: impl Into<std::rc::Rc<str>>, fut: Fut) {
+    let info = TaskInfo {
+        id: NEXT_TASK_ID.with(|x| x.replace(x.get() + 1)),
+        name: name.into()
+    };
+
+    if let Some(hooks) = hooks() {
+        (hooks.on_spawn)(&info);
+    }
+
+    spawn_local(Instrumented { info, fut: Box::pin(fut) })
+}
+
+struct Instrumented<Fut> {
+    info: TaskInfo,
+    fut: std::pin::Pin<Box<Fut>>
+}
+
+impl<Fut: Future<Output = ()>> Future for Instrumented<Fut> {
+    type Output = ();
+
+    fn poll(mut self: std::pin::Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> std::task::Poll<Self::Output> {
+        let hooks = match hooks() {
+            Some(hooks) => hooks,
+            None => return self.fut.as_mut().poll(cx)
+        };
+
+        (hooks.on_poll_start)(&self.info);
+        let start = crate::time::now();
+        let poll = self.fut.as_mut().poll(cx);
+        (hooks.on_poll_end)(&self.info, crate::time::now().saturating_sub(start));
+
+        if poll.is_ready() {
+            (hooks.on_complete)(&self.info);
+        }
+        return poll
+    }
+}
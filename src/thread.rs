@@ -0,0 +1,4 @@
+pub use wasm_thread::*;
+
+/// Work-stealing pool of worker threads
+pub mod pool;
@@ -0,0 +1,243 @@
+use std::{sync::{Arc, atomic::{AtomicBool, AtomicUsize, Ordering}}, marker::PhantomData, future::Future};
+use crossbeam::{deque::{Injector, Stealer, Worker, Steal}, sync::{Parker, Unparker}, utils::Backoff};
+use futures::channel::oneshot;
+use crate::{Result, Error, ErrorKind};
+
+type Job = Box<dyn FnOnce() + Send>;
+
+struct PoolInner {
+    injector: Injector<Job>,
+    stealers: Vec<Stealer<Job>>,
+    unparkers: Vec<Unparker>,
+    shutdown: AtomicBool
+}
+
+impl PoolInner {
+    #[inline]
+    fn push (&self, job: Job) {
+        self.injector.push(job);
+        for unparker in self.unparkers.iter() {
+            unparker.unpark()
+        }
+    }
+
+    /// Finds a job in the local queue, the global queue or the queues of other workers, in that order
+    fn find_job (&self, local: Option<&Worker<Job>>) -> Option<Job> {
+        if let Some(job) = local.and_then(Worker::pop) {
+            return Some(job)
+        }
+
+        loop {
+            let steal = match local {
+                Some(local) => self.injector.steal_batch_and_pop(local),
+                None => self.injector.steal()
+            };
+
+            let steal = steal.or_else(|| self.stealers.iter().map(Stealer::steal).collect());
+            match steal {
+                Steal::Success(job) => return Some(job),
+                Steal::Empty => return None,
+                Steal::Retry => continue
+            }
+        }
+    }
+}
+
+/// A pool of worker threads, that run jobs from a shared work-stealing queue.
+///
+/// Jobs can be [spawned](ThreadPool::spawn) into the pool, or run within a [scope](ThreadPool::scope), which allows them to
+/// borrow from the calling thread. Since WebAssembly threads share the same memory (a `SharedArrayBuffer`), slices can be
+/// split across workers without copying them.
+///
+/// When droped, the workers exit once they finish their current job.
+///
+/// On `wasm32`, panics don't unwind, so a panicking job permanently kills the worker it ran on.
+/// ```ignore
+/// let pool = ThreadPool::new(4)?;
+/// let mut pixels = vec![0u32; 1920 * 1080];
+/// pool.for_each_slice(&mut pixels, 1920, |row, pixels| shade(row, pixels));
+/// ```
+pub struct ThreadPool {
+    inner: Arc<PoolInner>
+}
+
+impl ThreadPool {
+    /// Creates a new pool with `threads` workers
+    pub fn new (threads: usize) -> Result<Self> {
+        if threads == 0 {
+            return Err(Error::new(ErrorKind::NotSupported, "thread pools must have at least one thread"))
+        }
+
+        let workers = (0..threads).map(|_| Worker::new_fifo()).collect::<Vec<_>>();
+        let parkers = (0..threads).map(|_| Parker::new()).collect::<Vec<_>>();
+
+        let inner = Arc::new(PoolInner {
+            injector: Injector::new(),
+            stealers: workers.iter().map(Worker::stealer).collect(),
+            unparkers: parkers.iter().map(|x| x.unparker().clone()).collect(),
+            shutdown: AtomicBool::new(false)
+        });
+
+        for (i, (local, parker)) in workers.into_iter().zip(parkers).enumerate() {
+            let inner = inner.clone();
+            let _ = super::Builder::new()
+                .name(format!("rustww-pool-{i}"))
+                .spawn(move || run_worker(inner, local, parker))?;
+        }
+
+        return Ok(Self { inner })
+    }
+
+    /// Returns the number of workers of the pool
+    #[inline]
+    pub fn threads (&self) -> usize {
+        return self.inner.stealers.len()
+    }
+
+    /// Runs `f` on one of the workers
+    #[inline]
+    pub fn spawn<F: 'static + Send + FnOnce()> (&self, f: F) {
+        self.inner.push(Box::new(f))
+    }
+
+    /// Runs `f` on one of the workers, returning a future that resolves with it's result.
+    ///
+    /// Unlike [`scope`](ThreadPool::scope), awaiting the result doesn't block the current thread, so it's suitable for the main thread.
+    /// It resolves to an error if the pool is droped before the job runs.
+    ///
+    /// # Panics
+    /// On `wasm32`, panics don't unwind: a panicking job traps it's worker, which is permanently lost, and the returned future
+    /// never resolves.
+    pub fn run<T: 'static + Send, F: 'static + Send + FnOnce() -> T> (&self, f: F) -> impl Future<Output = Result<T>> {
+        let (send, recv) = oneshot::channel();
+        self.spawn(move || { let _ = send.send(f()); });
+        return async move {
+            recv.await.map_err(|_| Error::new(ErrorKind::Abort, "the pool was dropped before the job ran"))
+        }
+    }
+
+    /// Creates a scope in which jobs borrowing from the current thread can be spawned, returning once all of them have completed.
+    ///
+    /// While waiting, the current thread runs pending jobs itself, and spins when there are none left, so the scope
+    /// can also be used from the main thread, where blocking isn't allowed. The jobs are waited for even if `f` panics.
+    ///
+    /// # Panics
+    /// On `wasm32`, panics don't unwind: a panicking job traps it's worker, which is permanently lost, and the scope
+    /// it was spawned in never returns. Jobs spawned within a scope must not panic.
+    pub fn scope<'scope, R, F: FnOnce(&Scope<'scope>) -> R> (&'scope self, f: F) -> R {
+        /// Waits for the jobs of the scope when droped, so that they never outlive their borrows
+        struct WaitPending<'a> {
+            inner: &'a PoolInner,
+            pending: &'a AtomicUsize
+        }
+
+        impl Drop for WaitPending<'_> {
+            fn drop(&mut self) {
+                let backoff = Backoff::new();
+                while self.pending.load(Ordering::Acquire) > 0 {
+                    match self.inner.find_job(None) {
+                        Some(job) => {
+                            job();
+                            backoff.reset()
+                        },
+                        None => backoff.snooze()
+                    }
+                }
+            }
+        }
+
+        let scope = Scope {
+            inner: &self.inner,
+            pending: Arc::new(AtomicUsize::new(0)),
+            _phtm: PhantomData
+        };
+
+        let _wait = WaitPending { inner: &self.inner, pending: &scope.pending };
+        return f(&scope)
+    }
+
+    /// Splits `slice` into chunks of `chunk_size` elements (the last one may be shorter), and calls `f` with the index
+    /// and the contents of each chunk, across the workers
+    ///
+    /// # Panics
+    /// This method panics if `chunk_size` is zero.
+    pub fn for_each_slice<T: Send, F: Sync + Fn(usize, &mut [T])> (&self, slice: &mut [T], chunk_size: usize, f: F) {
+        let f = &f;
+        self.scope(|s| {
+            for (i, chunk) in slice.chunks_mut(chunk_size).enumerate() {
+                s.spawn(move || f(i, chunk))
+            }
+        })
+    }
+
+    /// Splits `slice` into chunks of `chunk_size` elements (the last one may be shorter), and maps each chunk with `f`
+    /// across the workers, returning the results in order.
+    ///
+    /// # Panics
+    /// This method panics if `chunk_size` is zero.
+    pub fn map_chunks<T: Sync, U: Send, F: Sync + Fn(&[T]) -> U> (&self, slice: &[T], chunk_size: usize, f: F) -> Vec<U> {
+        let mut results = (0..slice.len().div_ceil(chunk_size)).map(|_| None).collect::<Vec<_>>();
+
+        let f = &f;
+        self.scope(|s| {
+            for (chunk, result) in slice.chunks(chunk_size).zip(results.iter_mut()) {
+                s.spawn(move || *result = Some(f(chunk)))
+            }
+        });
+
+        return results.into_iter().map(|x| x.expect("every chunk has been mapped")).collect()
+    }
+}
+
+impl Drop for ThreadPool {
+    #[inline]
+    fn drop(&mut self) {
+        self.inner.shutdown.store(true, Ordering::Release);
+        for unparker in self.inner.unparkers.iter() {
+            unparker.unpark()
+        }
+    }
+}
+
+/// A scope of a [`ThreadPool`], whose jobs may borrow from the thread that created it
+pub struct Scope<'scope> {
+    inner: &'scope PoolInner,
+    pending: Arc<AtomicUsize>,
+    _phtm: PhantomData<&'scope mut &'scope ()>
+}
+
+impl<'scope> Scope<'scope> {
+    /// Runs `f` on one of the workers, before the scope ends
+    pub fn spawn<F: 'scope + Send + FnOnce()> (&self, f: F) {
+        /// Marks the job as completed when droped, even if it panics on targets that unwind.
+        /// On `wasm32`, a panic traps the worker without unwinding, so the job is never marked as completed.
+        struct Completion (Arc<AtomicUsize>);
+
+        impl Drop for Completion {
+            #[inline]
+            fn drop(&mut self) {
+                self.0.fetch_sub(1, Ordering::AcqRel);
+            }
+        }
+
+        self.pending.fetch_add(1, Ordering::AcqRel);
+        let completion = Completion(self.pending.clone());
+        let job = Box::new(move || {
+            let _completion = completion;
+            f()
+        }) as Box<dyn 'scope + Send + FnOnce()>;
+
+        // SAFETY: The scope doesn't end until every one of it's jobs has completed, so they never outlive their borrows
+        let job = unsafe { core::mem::transmute::<Box<dyn 'scope + Send + FnOnce()>, Job>(job) };
+        self.inner.push(job)
+    }
+}
+
+fn run_worker (inner: Arc<PoolInner>, local: Worker<Job>, parker: Parker) {
+    while !inner.shutdown.load(Ordering::Acquire) {
+        match inner.find_job(Some(&local)) {
+            Some(job) => job(),
+            None => parker.park()
+        }
+    }
+}
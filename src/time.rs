@@ -1,8 +1,8 @@
-use std::{time::Duration, intrinsics::unlikely, fmt::Debug, mem::ManuallyDrop, marker::PhantomData, task::Poll};
+use std::{time::Duration, intrinsics::unlikely, fmt::Debug, mem::ManuallyDrop, marker::PhantomData, task::{Poll, Waker}, rc::Rc, cell::Cell};
 use futures::{Stream, StreamExt, Future, FutureExt};
 use js_sys::{Function};
-use wasm_bindgen::{JsValue, prelude::Closure, JsCast, closure::WasmClosureFnOnce};
-use crate::{Result, utils::{LocalReceiver, local_channel}, sync::{ShotReceiver, one_shot}};
+use wasm_bindgen::{prelude::Closure, JsCast, closure::WasmClosureFnOnce};
+use crate::{Result, Error, ErrorKind, utils::{LocalReceiver, local_channel}, sync::{ShotReceiver, one_shot}, diagnostics::Tracked};
 use crate::scope::*;
 const MAX_MILLIS: u128 = i32::MAX as u128;
 
@@ -24,6 +24,7 @@ pub struct Interval<'a, T> {
     _closure: DropHandle,
     #[cfg(not(target_feature = "atomics"))]
     _closure: Closure<dyn FnMut()>,
+    _tracked: Tracked,
     _phtm: PhantomData<&'a mut &'a dyn FnMut()>
 }
 
@@ -34,7 +35,7 @@ impl<'a, T: 'a> Interval<'a, T> {
     pub fn new<F: 'a + FnMut() -> T> (timeout: Duration, mut f: F) -> Result<Self> {
         let millis = timeout.as_millis();
         if unlikely(millis > MAX_MILLIS) {
-            return Err(JsValue::from_str("timeout overflow"))
+            return Err(Error::new(ErrorKind::NotSupported, "timeout overflow"))
         }
     
         #[cfg(target_feature = "atomics")]
@@ -69,6 +70,7 @@ impl<'a, T: 'a> Interval<'a, T> {
             id,
             recv,
             _closure: closure,
+            _tracked: Tracked::new("time::Interval"),
             _phtm: PhantomData
         })
     }
@@ -117,6 +119,95 @@ impl<T> Drop for Interval<'_, T> {
     }
 }
 
+impl Interval<'static, ()> {
+    /// Returns a [`Stream`] that yields every time the specified period elapses.
+    /// 
+    /// Unlike [`Interval::new`], no user closure is called and no value is sent through a channel on each tick:
+    /// ticks are counted and the stream's task is woken, which makes it better suited for render and update loops.
+    /// Ticks that elapse while the stream isn't polled are yielded in a row when it is.
+    pub fn ticks (period: Duration) -> Result<Ticks> {
+        let millis = period.as_millis();
+        if unlikely(millis > MAX_MILLIS) {
+            return Err(Error::new(ErrorKind::NotSupported, "timeout overflow"))
+        }
+
+        let state = Rc::new(TickState::default());
+        let my_state = state.clone();
+        let closure = Closure::<dyn FnMut()>::new(move || {
+            my_state.pending.set(my_state.pending.get().saturating_add(1));
+            if let Some(waker) = my_state.waker.take() {
+                waker.wake()
+            }
+        });
+
+        let id = set_interval(closure.as_ref().unchecked_ref(), millis as i32)?;
+        return Ok(Ticks { id, state, _closure: closure, _tracked: Tracked::new("time::Ticks") })
+    }
+}
+
+#[derive(Default)]
+struct TickState {
+    pending: Cell<u32>,
+    waker: Cell<Option<Waker>>
+}
+
+/// A [`Stream`] of interval ticks, created with [`Interval::ticks`].
+/// 
+/// When dropped, the interval will be cleared.
+pub struct Ticks {
+    id: i32,
+    state: Rc<TickState>,
+    _closure: Closure<dyn FnMut()>,
+    _tracked: Tracked
+}
+
+impl Ticks {
+    /// Returns the id of the interval
+    #[inline]
+    pub fn id (&self) -> i32 {
+        return self.id
+    }
+
+    /// Returns the number of ticks that have elapsed but haven't been yielded yet
+    #[inline]
+    pub fn pending (&self) -> u32 {
+        return self.state.pending.get()
+    }
+}
+
+impl Stream for Ticks {
+    type Item = ();
+
+    #[inline]
+    fn poll_next(self: std::pin::Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Option<Self::Item>> {
+        let pending = self.state.pending.get();
+        if pending > 0 {
+            self.state.pending.set(pending - 1);
+            return Poll::Ready(Some(()))
+        }
+
+        self.state.waker.set(Some(cx.waker().clone()));
+        return Poll::Pending
+    }
+}
+
+impl Debug for Ticks {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Ticks")
+            .field("id", &self.id)
+            .field("pending", &self.state.pending.get())
+            .finish()
+    }
+}
+
+impl Drop for Ticks {
+    #[inline]
+    fn drop (&mut self) {
+        clear_interval(self.id);
+    }
+}
+
 /// Spawns an interval directly into JavaScript memory management, leaking any Rust memory related to it,
 /// and returning a [`Stream`] that returns the result of each interval.
 #[inline]
@@ -140,6 +231,7 @@ pub struct Timeout<'a, T> {
     _closure: DropHandle,
     #[cfg(not(target_feature = "atomics"))]
     _closure: Closure<dyn FnMut()>,
+    _tracked: Tracked,
     _phtm: PhantomData<&'a mut &'a dyn FnMut()>
 }
 
@@ -148,7 +240,7 @@ impl<'a, T: 'a> Timeout<'a, T> {
     pub fn new<F: 'a + FnOnce() -> T> (timeout: Duration, f: F) -> Result<Self> {
         let millis = timeout.as_millis();
         if unlikely(millis > MAX_MILLIS) {
-            return Err(JsValue::from_str("timeout overflow"))
+            return Err(Error::new(ErrorKind::NotSupported, "timeout overflow"))
         }
 
         let (send, recv) = one_shot::<T>();
@@ -170,6 +262,7 @@ impl<'a, T: 'a> Timeout<'a, T> {
             id,
             recv,
             _closure: closure,
+            _tracked: Tracked::new("time::Timeout"),
             _phtm: PhantomData
         })
     }
@@ -234,6 +327,443 @@ impl Future for Sleep {
     }
 }
 
+/// Awaits the future for up to `dur`, returning `None` if it doesn't complete in time.
+pub async fn timeout<F: Future> (dur: Duration, fut: F) -> Result<Option<F::Output>> {
+    let sleep = sleep(dur)?;
+    futures::pin_mut!(fut);
+
+    return Ok(match futures::future::select(fut, sleep).await {
+        futures::future::Either::Left((value, _)) => Some(value),
+        futures::future::Either::Right(_) => None
+    })
+}
+
+/// Wraps the stream, so that it yields an error of kind [`NotSupported`](ErrorKind::NotSupported) (and ends) if it's first item
+/// isn't received within `dur`.
+///
+/// This is useful to detect watchers that never produce any item, as sensor watchers do on devices without the sensor.
+#[inline]
+pub fn first_event_timeout<S: Unpin + Stream> (stream: S, dur: Duration) -> Result<FirstEventTimeout<S>> {
+    return Ok(FirstEventTimeout {
+        stream,
+        timeout: Some(sleep(dur)?),
+        failed: false
+    })
+}
+
+/// Stream returned by [`first_event_timeout`]
+pub struct FirstEventTimeout<S> {
+    stream: S,
+    timeout: Option<Sleep>,
+    failed: bool
+}
+
+impl<S> FirstEventTimeout<S> {
+    /// Returns the inner stream
+    #[inline]
+    pub fn into_inner (self) -> S {
+        return self.stream
+    }
+}
+
+impl<S: Unpin + Stream> Stream for FirstEventTimeout<S> {
+    type Item = Result<S::Item>;
+
+    fn poll_next(mut self: std::pin::Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.failed {
+            return Poll::Ready(None)
+        }
+
+        match self.stream.poll_next_unpin(cx) {
+            Poll::Ready(Some(item)) => {
+                self.timeout = None;
+                return Poll::Ready(Some(Ok(item)))
+            },
+            Poll::Ready(None) => return Poll::Ready(None),
+            Poll::Pending => {}
+        }
+
+        if let Some(ref mut timeout) = self.timeout && timeout.poll_unpin(cx).is_ready() {
+            self.timeout = None;
+            self.failed = true;
+            return Poll::Ready(Some(Err(Error::new(ErrorKind::NotSupported, "no event was received before the timeout (the device may lack the required sensor)"))))
+        }
+        return Poll::Pending
+    }
+}
+
+/// A point in time, either relative to the present or absolute
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Deadline {
+    /// The specified amount of time after the present
+    After (Duration),
+    /// The specified date
+    At (chrono::DateTime<chrono::Utc>)
+}
+
+impl Deadline {
+    /// Returns the deadline as milliseconds since the UNIX epoch
+    #[inline]
+    fn as_epoch_millis (self) -> f64 {
+        return match self {
+            Self::After(dur) => js_sys::Date::now() + dur.as_secs_f64() * 1000.,
+            Self::At(date) => date.timestamp_millis() as f64
+        }
+    }
+}
+
+impl From<Duration> for Deadline {
+    #[inline]
+    fn from(value: Duration) -> Self {
+        return Self::After(value)
+    }
+}
+
+impl<Tz: chrono::TimeZone> From<chrono::DateTime<Tz>> for Deadline {
+    #[inline]
+    fn from(value: chrono::DateTime<Tz>) -> Self {
+        return Self::At(value.with_timezone(&chrono::Utc))
+    }
+}
+
+/// Returns a [`Future`] that resolves once the deadline is reached.
+/// 
+/// Unlike [`sleep`], the delay isn't limited to `i32::MAX` milliseconds (~24.8 days), since multiple timeouts
+/// are chained internally. Deadlines in the past resolve immediately.
+#[inline]
+pub fn long_timeout (deadline: impl Into<Deadline>) -> Result<LongTimeout> {
+    let target = deadline.into().as_epoch_millis();
+    return Ok(LongTimeout {
+        target,
+        current: LongTimeout::next_chunk(target)?
+    })
+}
+
+/// Future returned by [`long_timeout`]
+pub struct LongTimeout {
+    target: f64,
+    current: Option<Sleep>
+}
+
+impl LongTimeout {
+    #[inline]
+    fn next_chunk (target: f64) -> Result<Option<Sleep>> {
+        let remaining = target - js_sys::Date::now();
+        if remaining <= 0. {
+            return Ok(None)
+        }
+
+        let millis = f64::min(remaining.ceil(), MAX_MILLIS as f64) as u64;
+        return sleep(Duration::from_millis(millis)).map(Some)
+    }
+}
+
+impl Future for LongTimeout {
+    type Output = Result<()>;
+
+    fn poll(mut self: std::pin::Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> std::task::Poll<Self::Output> {
+        loop {
+            match self.current {
+                Some(ref mut current) => match current.poll_unpin(cx) {
+                    Poll::Ready(()) => {
+                        let target = self.target;
+                        self.current = match Self::next_chunk(target) {
+                            Ok(Some(next)) => Some(next),
+                            Ok(None) => return Poll::Ready(Ok(())),
+                            Err(e) => return Poll::Ready(Err(e))
+                        };
+                    },
+                    Poll::Pending => return Poll::Pending
+                },
+                None => return Poll::Ready(Ok(()))
+            }
+        }
+    }
+}
+
+#[wasm_bindgen::prelude::wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = performance, js_name = now)]
+    fn performance_now () -> f64;
+}
+
+/// Returns the time elapsed since the [time origin](https://developer.mozilla.org/en-US/docs/Web/API/Performance/timeOrigin),
+/// with sub-millisecond precision
+#[inline]
+pub fn now () -> Duration {
+    return Duration::from_secs_f64(performance_now() / 1000.)
+}
+
+/// A monotonic stopwatch, based on [`now`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stopwatch {
+    start: f64,
+    lap: f64
+}
+
+impl Stopwatch {
+    /// Creates and starts a new stopwatch
+    #[inline]
+    pub fn start () -> Self {
+        let now = performance_now();
+        return Self { start: now, lap: now }
+    }
+
+    /// Returns the time elapsed since the stopwatch was started
+    #[inline]
+    pub fn elapsed (&self) -> Duration {
+        return millis_to_duration(performance_now() - self.start)
+    }
+
+    /// Returns the time elapsed since the last lap (or since the stopwatch was started, if this is the first lap), starting a new lap
+    #[inline]
+    pub fn lap (&mut self) -> Duration {
+        let now = performance_now();
+        let lap = core::mem::replace(&mut self.lap, now);
+        return millis_to_duration(now - lap)
+    }
+
+    /// Restarts the stopwatch, returning the time elapsed since it was started
+    #[inline]
+    pub fn restart (&mut self) -> Duration {
+        let now = performance_now();
+        let start = core::mem::replace(&mut self.start, now);
+        self.lap = now;
+        return millis_to_duration(now - start)
+    }
+}
+
+/// Accumulator of frame times over a sliding window, for game and render loops
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrameStats {
+    frames: std::collections::VecDeque<f64>,
+    capacity: usize,
+    last: Option<f64>
+}
+
+impl FrameStats {
+    /// Creates a new accumulator, that keeps track of the last `capacity` frames
+    #[inline]
+    pub fn new (capacity: usize) -> Self {
+        return Self {
+            frames: std::collections::VecDeque::with_capacity(capacity),
+            capacity,
+            last: None
+        }
+    }
+
+    /// Records a new frame, with `timestamp` being the time (in milliseconds) at which the frame started,
+    /// like the one received by [`render_loop`](crate::gl::render_loop) callbacks.
+    /// 
+    /// The first call only establishes the reference timestamp.
+    pub fn tick (&mut self, timestamp: f64) {
+        if let Some(last) = self.last.replace(timestamp) {
+            self.record(millis_to_duration(timestamp - last));
+        }
+    }
+
+    /// Records a new frame, using [`now`] as it's timestamp
+    #[inline]
+    pub fn tick_now (&mut self) {
+        self.tick(performance_now())
+    }
+
+    /// Records the duration of a frame
+    pub fn record (&mut self, frame_time: Duration) {
+        if self.capacity == 0 { return }
+        if self.frames.len() == self.capacity {
+            let _ = self.frames.pop_front();
+        }
+        self.frames.push_back(frame_time.as_secs_f64() * 1000.);
+    }
+
+    /// Returns the number of frames in the window
+    #[inline]
+    pub fn len (&self) -> usize {
+        return self.frames.len()
+    }
+
+    /// Returns `true` if no frames have been recorded yet
+    #[inline]
+    pub fn is_empty (&self) -> bool {
+        return self.frames.is_empty()
+    }
+
+    /// Returns the average frame time
+    #[inline]
+    pub fn average (&self) -> Option<Duration> {
+        if self.frames.is_empty() { return None }
+        return Some(millis_to_duration(self.frames.iter().sum::<f64>() / self.frames.len() as f64))
+    }
+
+    /// Returns the average frames per second
+    #[inline]
+    pub fn fps (&self) -> Option<f64> {
+        return self.average().map(|x| 1. / x.as_secs_f64())
+    }
+
+    /// Returns the frame time below which `p` percent of the frames fall (e.g. `99.` for the 99th percentile)
+    pub fn percentile (&self, p: f64) -> Option<Duration> {
+        if self.frames.is_empty() { return None }
+
+        let mut frames = self.frames.iter().copied().collect::<Vec<_>>();
+        frames.sort_unstable_by(f64::total_cmp);
+
+        let idx = ((p.clamp(0., 100.) / 100.) * (frames.len() - 1) as f64).round() as usize;
+        return Some(millis_to_duration(frames[idx]))
+    }
+
+    /// Returns the longest frame time in the window
+    #[inline]
+    pub fn max (&self) -> Option<Duration> {
+        return self.frames.iter().copied().reduce(f64::max).map(millis_to_duration)
+    }
+
+    /// Removes every recorded frame
+    #[inline]
+    pub fn clear (&mut self) {
+        self.frames.clear();
+        self.last = None;
+    }
+}
+
+/// An exponential backoff policy, which yields the delays to wait between consecutive retries of an operation.
+///
+/// The `n`-th delay is `initial * multiplier^n`, capped at the maximum delay. With full jitter enabled (the default),
+/// each delay is instead a random duration between zero and that value, which spreads out retries of many clients failing at the same time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Backoff {
+    initial: Duration,
+    multiplier: f64,
+    max: Duration,
+    jitter: bool,
+    max_retries: Option<u32>,
+    attempt: u32
+}
+
+impl Backoff {
+    /// Creates a new backoff policy starting at the specified delay, with a multiplier of 2, a maximum delay of 30 seconds,
+    /// full jitter and unlimited retries.
+    #[inline]
+    pub fn new (initial: Duration) -> Self {
+        return Self {
+            initial,
+            multiplier: 2.,
+            max: Duration::from_secs(30),
+            jitter: true,
+            max_retries: None,
+            attempt: 0
+        }
+    }
+
+    /// Sets the factor each delay is multiplied by
+    #[inline]
+    pub fn multiplier (mut self, multiplier: f64) -> Self {
+        self.multiplier = multiplier;
+        self
+    }
+
+    /// Sets the maximum delay
+    #[inline]
+    pub fn max_delay (mut self, max: Duration) -> Self {
+        self.max = max;
+        self
+    }
+
+    /// Enables or disables full jitter
+    #[inline]
+    pub fn jitter (mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Sets the maximum number of delays the policy yields
+    #[inline]
+    pub fn max_retries (mut self, max_retries: u32) -> Self {
+        self.max_retries = Some(max_retries);
+        self
+    }
+
+    /// Returns the number of delays yielded so far
+    #[inline]
+    pub fn attempt (&self) -> u32 {
+        return self.attempt
+    }
+
+    /// Restarts the policy from it's initial delay
+    #[inline]
+    pub fn reset (&mut self) {
+        self.attempt = 0
+    }
+
+    /// Returns a stream that waits for each delay of the policy before yielding the number of the attempt
+    pub fn into_stream (self) -> impl Stream<Item = Result<u32>> {
+        return futures::stream::unfold(self, |mut this| async move {
+            let delay = this.next()?;
+            let result = match sleep(delay) {
+                Ok(sleep) => {
+                    sleep.await;
+                    Ok(this.attempt)
+                },
+                Err(e) => Err(e)
+            };
+            Some((result, this))
+        })
+    }
+}
+
+impl Iterator for Backoff {
+    type Item = Duration;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.max_retries.is_some_and(|max| self.attempt >= max) {
+            return None
+        }
+
+        let exp = self.multiplier.powi(i32::try_from(self.attempt).unwrap_or(i32::MAX));
+        let delay = f64::min(self.initial.as_secs_f64() * exp, self.max.as_secs_f64());
+        let delay = match self.jitter {
+            true => delay * rand::random::<f64>(),
+            false => delay
+        };
+
+        self.attempt = self.attempt.saturating_add(1);
+        return Some(Duration::from_secs_f64(f64::max(delay, 0.)))
+    }
+}
+
+/// Calls `f` until it succeeds, waiting for the policy's delays between attempts.
+/// When the policy runs out of delays, the last error is returned.
+#[inline]
+pub async fn retry<T, E, F: FnMut() -> Fut, Fut: Future<Output = ::core::result::Result<T, E>>> (policy: Backoff, f: F) -> ::core::result::Result<T, E> {
+    return retry_if(policy, |_| true, f).await
+}
+
+/// Calls `f` until it succeeds or fails with an error for which `retryable` returns `false`, waiting for the policy's delays between attempts.
+/// When the policy runs out of delays, the last error is returned.
+pub async fn retry_if<T, E, F: FnMut() -> Fut, Fut: Future<Output = ::core::result::Result<T, E>>> (mut policy: Backoff, mut retryable: impl FnMut(&E) -> bool, mut f: F) -> ::core::result::Result<T, E> {
+    loop {
+        let e = match f().await {
+            Ok(x) => return Ok(x),
+            Err(e) => e
+        };
+
+        if !retryable(&e) {
+            return Err(e)
+        }
+
+        match policy.next().map(sleep) {
+            Some(Ok(sleep)) => sleep.await,
+            _ => return Err(e)
+        }
+    }
+}
+
+#[inline]
+fn millis_to_duration (millis: f64) -> Duration {
+    return Duration::from_secs_f64(f64::max(millis, 0.) / 1000.)
+}
+
 /*
 /// Returns a [`Future`] that resolves after a specified delay.
 #[cfg(not(target_feature = "atomics"))]
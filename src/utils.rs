@@ -1,7 +1,7 @@
 #![allow(unused)]
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
-use std::{cell::{UnsafeCell, Cell}, mem::{MaybeUninit}, rc::{Rc, Weak}, task::{Waker, Poll, Context}, future::Future, ops::{Deref, DerefMut}, collections::VecDeque, fmt::{Debug, Display}, pin::Pin, io::ErrorKind, marker::{PhantomPinned, PhantomData}, any::{Any, TypeId}};
+use std::{cell::{UnsafeCell, Cell, RefCell}, mem::{MaybeUninit}, rc::{Rc, Weak}, task::{Waker, Poll, Context}, future::Future, ops::{Deref, DerefMut}, collections::VecDeque, fmt::{Debug, Display}, pin::Pin, io::ErrorKind, marker::{PhantomPinned, PhantomData}, any::{Any, TypeId}};
 use futures::{Stream, AsyncRead, stream::Aborted, FutureExt};
 use js_sys::*;
 use serde::{Deserialize, Serialize, de::DeserializeOwned};
@@ -124,12 +124,60 @@ impl Default for AbortHandle {
 
 struct ChannelInner<T> {
     buffer: VecDeque<T>,
-    waker: Option<Waker>
+    waker: Option<Waker>,
+    capacity: Option<(usize, Overflow)>,
+    closed: bool
+}
+
+impl<T> ChannelInner<T> {
+    #[inline]
+    fn new (capacity: Option<(usize, Overflow)>) -> Self {
+        return Self { buffer: VecDeque::new(), waker: None, capacity, closed: false }
+    }
+}
+
+/// Behaviour of a bounded local channel when a value is sent while it's full
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Overflow {
+    /// The oldest value of the channel is dropped to make room for the new one
+    DropOldest,
+    /// The new value is rejected, and returned to the sender
+    Reject
 }
 
 /// A local channel's receiver, designed to receive values from or inside JavaScript callbacks.
+///
+/// The channel's state is never borrowed while a waker is woken, so values may be sent or received
+/// re-entrantly (for example, from inside a waker, or from the `poll_next` of a wrapping stream).
 pub struct LocalReceiver<T> {
-    inner: Rc<UnsafeCell<ChannelInner<T>>>
+    inner: Rc<RefCell<ChannelInner<T>>>
+}
+
+impl<T> LocalReceiver<T> {
+    /// Returns the number of values waiting in the channel
+    #[inline]
+    pub fn len (&self) -> usize {
+        return self.inner.borrow().buffer.len()
+    }
+
+    /// Returns `true` if there are no values waiting in the channel
+    #[inline]
+    pub fn is_empty (&self) -> bool {
+        return self.len() == 0
+    }
+
+    /// Returns `true` if the channel has been closed, or all of it's senders have been dropped.
+    /// Values sent before closing may still be waiting in the channel.
+    #[inline]
+    pub fn is_closed (&self) -> bool {
+        return self.inner.borrow().closed || Rc::weak_count(&self.inner) == 0
+    }
+
+    /// Returns the next value waiting in the channel, if any, without waiting for one
+    #[inline]
+    pub fn try_recv (&mut self) -> Option<T> {
+        return self.inner.borrow_mut().buffer.pop_front()
+    }
 }
 
 impl<T> Stream for LocalReceiver<T> {
@@ -137,24 +185,25 @@ impl<T> Stream for LocalReceiver<T> {
 
     #[inline]
     fn poll_next(self: std::pin::Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Option<Self::Item>> {
-        let mut inner = unsafe { &mut *self.inner.get() };
+        let mut inner = self.inner.borrow_mut();
 
         if let Some(value) = inner.buffer.pop_front() {
             return Poll::Ready(Some(value))
-        } else if Rc::weak_count(&self.inner) == 0 {
+        } else if inner.closed || Rc::weak_count(&self.inner) == 0 {
             return Poll::Ready(None)
         }
 
-        inner.waker = Some(cx.waker().clone());
+        // the previous waker is dropped after the borrow is released, since dropping it may run arbitrary code
+        let prev = inner.waker.replace(cx.waker().clone());
+        drop(inner);
+        drop(prev);
         return Poll::Pending
     }
 
     #[inline]
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let inner = unsafe { &*self.inner.get() };
-        let size = inner.buffer.len();
-
-        if Rc::weak_count(&self.inner) == 0 {
+        let size = self.len();
+        if self.is_closed() {
             return (size, Some(size))
         } else {
             return (size, None)
@@ -164,7 +213,7 @@ impl<T> Stream for LocalReceiver<T> {
 
 /// A local channel's sender, designed to send values from or into JavaScript callbacks.
 pub struct LocalSender<T> {
-    inner: Weak<UnsafeCell<ChannelInner<T>>>
+    inner: Weak<RefCell<ChannelInner<T>>>
 }
 
 impl<T> LocalSender<T> {
@@ -176,19 +225,57 @@ impl<T> LocalSender<T> {
     
     /// Attempts to send the value through the channel, returning it if it fails.
     /// 
-    /// A send through the channel will fail if there are no receivers left.
+    /// A send through the channel will fail if there are no receivers left, if the channel has been closed,
+    /// or if the channel is full and [rejects](Overflow::Reject) new values.
     #[inline]
     pub fn try_send (&self, v: T) -> ::core::result::Result<(), T> {
         if let Some(inner) = self.inner.upgrade() {
-            let mut inner = unsafe { &mut *inner.get() };
-            inner.buffer.push_back(v);
-            if let Some(waker) = inner.waker.take() { waker.wake() }
+            let mut borrow = inner.borrow_mut();
+            if borrow.closed {
+                return Err(v)
+            }
+
+            let mut dropped = None;
+            if let Some((capacity, overflow)) = borrow.capacity && borrow.buffer.len() >= capacity {
+                match overflow {
+                    Overflow::DropOldest => dropped = borrow.buffer.pop_front(),
+                    Overflow::Reject => return Err(v)
+                }
+            }
+
+            borrow.buffer.push_back(v);
+            let waker = borrow.waker.take();
+            // the borrow must be released before waking (or dropping values), since either may re-enter the channel
+            drop(borrow);
+            drop(dropped);
+            if let Some(waker) = waker { waker.wake() }
             return Ok(())
         }
 
         // There are no more recievers
         return Err(v)
     }
+
+    /// Closes the channel for every sender. The receiver still yields the values sent before closing, and then ends.
+    #[inline]
+    pub fn close (&self) {
+        if let Some(inner) = self.inner.upgrade() {
+            let mut borrow = inner.borrow_mut();
+            borrow.closed = true;
+            let waker = borrow.waker.take();
+            drop(borrow);
+            if let Some(waker) = waker { waker.wake() }
+        }
+    }
+
+    /// Returns `true` if the channel has been closed, or it's receiver has been dropped
+    #[inline]
+    pub fn is_closed (&self) -> bool {
+        return match self.inner.upgrade() {
+            Some(inner) => inner.borrow().closed,
+            None => true
+        }
+    }
 }
 
 impl<T> Clone for LocalSender<T> {
@@ -202,14 +289,115 @@ impl<T> Clone for LocalSender<T> {
 /// which are known to be executed on a single thread, but aren't attached to a specific scope. 
 #[inline]
 pub fn local_channel<T> () -> (LocalSender<T>, LocalReceiver<T>) {
-    let inner = Rc::new(UnsafeCell::new(ChannelInner {
-        buffer: VecDeque::new(),
-        waker: None
-    }));
+    let inner = Rc::new(RefCell::new(ChannelInner::new(None)));
+    return (LocalSender { inner: Rc::downgrade(&inner) }, LocalReceiver { inner });
+}
 
+/// Creates a new local channel that holds up to `capacity` values, handling any value sent while it's full as specified by `overflow`.
+///
+/// Bounded channels keep memory usage in check when the receiver (for example, a watcher stream) isn't polled.
+///
+/// # Panics
+/// This method panics if `capacity` is zero.
+#[inline]
+pub fn bounded_local_channel<T> (capacity: usize, overflow: Overflow) -> (LocalSender<T>, LocalReceiver<T>) {
+    assert!(capacity > 0, "capacity must be greater than zero");
+    let inner = Rc::new(RefCell::new(ChannelInner::new(Some((capacity, overflow)))));
     return (LocalSender { inner: Rc::downgrade(&inner) }, LocalReceiver { inner });
 }
 
+/// Stream that yields the items of multiple streams as they become available, ending once all of them have ended.
+///
+/// Streams are polled in a round-robin fashion, so a busy stream (like a motion watcher) can't starve the others.
+/// It's usually created with [`merge_streams!`](crate::merge_streams), which tags each item with it's source.
+pub struct MergeStreams<'a, T> {
+    streams: Vec<futures::stream::LocalBoxStream<'a, T>>,
+    next: usize
+}
+
+impl<'a, T> MergeStreams<'a, T> {
+    /// Creates a new, empty, merged stream
+    #[inline]
+    pub fn new () -> Self {
+        return Self { streams: Vec::new(), next: 0 }
+    }
+
+    /// Adds a stream to the merged stream
+    #[inline]
+    pub fn push<S: 'a + Stream<Item = T>> (&mut self, stream: S) {
+        self.streams.push(Box::pin(stream));
+    }
+
+    /// Returns the number of streams that haven't ended yet
+    #[inline]
+    pub fn len (&self) -> usize {
+        return self.streams.len()
+    }
+
+    /// Returns `true` if all the streams have ended
+    #[inline]
+    pub fn is_empty (&self) -> bool {
+        return self.streams.is_empty()
+    }
+}
+
+impl<'a, T> Default for MergeStreams<'a, T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, T, S: 'a + Stream<Item = T>> FromIterator<S> for MergeStreams<'a, T> {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = S>>(iter: I) -> Self {
+        let mut this = Self::new();
+        iter.into_iter().for_each(|stream| this.push(stream));
+        return this
+    }
+}
+
+impl<'a, T> Stream for MergeStreams<'a, T> {
+    type Item = T;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        let mut remaining = this.streams.len();
+
+        while remaining > 0 {
+            if this.next >= this.streams.len() {
+                this.next = 0;
+            }
+
+            match this.streams[this.next].as_mut().poll_next(cx) {
+                Poll::Ready(Some(item)) => {
+                    this.next += 1;
+                    return Poll::Ready(Some(item))
+                },
+                // the last stream is moved into this index, so it's polled next
+                Poll::Ready(None) => { let _ = this.streams.swap_remove(this.next); },
+                Poll::Pending => this.next += 1
+            }
+            remaining -= 1;
+        }
+
+        if this.streams.is_empty() {
+            return Poll::Ready(None)
+        }
+        return Poll::Pending
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        return self.streams.iter().fold((0, Some(0)), |(low, high), stream| {
+            let (stream_low, stream_high) = stream.size_hint();
+            (low.saturating_add(stream_low), high.zip(stream_high).and_then(|(a, b)| a.checked_add(b)))
+        })
+    }
+}
+
+pub use crate::merge_streams;
+
 #[wasm_bindgen]
 extern "C" {
     #[derive(Debug, Clone)]
@@ -222,8 +410,263 @@ extern "C" {
 
     #[wasm_bindgen(method)]
     fn abort (this: &AbortControllerExt, reason: &JsValue);
-    #[wasm_bindgen(method)]
+    #[wasm_bindgen(method, getter)]
     fn reason (this: &AbortSignalExt) -> JsValue;
+    #[wasm_bindgen(static_method_of = AbortSignalExt, js_name = timeout, catch)]
+    fn timeout_native (millis: f64) -> ::core::result::Result<web_sys::AbortSignal, JsValue>;
+    #[wasm_bindgen(static_method_of = AbortSignalExt, js_name = any, catch)]
+    fn any_native (signals: &Array) -> ::core::result::Result<web_sys::AbortSignal, JsValue>;
+}
+
+/// Serializes the value into a [`JsValue`].
+///
+/// Maps are serialized as plain objects (or as `Map`s, if some of their keys can't be object keys), and byte buffers (like the fields annotated with
+/// [`serde_bytes`](https://docs.rs/serde_bytes)) as `Uint8Array`s, without going element by element.
+#[inline]
+pub fn to_js<T: ?Sized + Serialize> (value: &T) -> Result<JsValue> {
+    let serializer = serde_wasm_bindgen::Serializer::new().serialize_maps_as_objects(true);
+    return match value.serialize(&serializer) {
+        Ok(value) => Ok(value),
+        Err(_) => Ok(value.serialize(&serde_wasm_bindgen::Serializer::new())?)
+    }
+}
+
+/// Deserializes the value from a [`JsValue`].
+///
+/// Byte buffers (like the fields annotated with [`serde_bytes`](https://docs.rs/serde_bytes)) are read directly from
+/// `Uint8Array`s and `ArrayBuffer`s.
+#[inline]
+pub fn from_js<T: DeserializeOwned> (value: JsValue) -> Result<T> {
+    return Ok(serde_wasm_bindgen::from_value(value)?)
+}
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_name = postMessage, structural, method, catch)]
+    fn post_message_with_transfer (this: &js_sys::Object, msg: &JsValue, transfer: &Array) -> ::core::result::Result<(), JsValue>;
+}
+
+/// Sends `msg` through a `postMessage` call on `target` (a `Worker`, `MessagePort`, `DedicatedWorkerGlobalScope`, ...),
+/// moving the objects of `transfer` to the receiving context.
+#[inline]
+pub(crate) fn post_message (target: &js_sys::Object, msg: &JsValue, transfer: &Array) -> Result<()> {
+    return Ok(post_message_with_transfer(target, msg, transfer)?)
+}
+
+/// An object that can be moved to another context with `postMessage`, instead of being cloned.
+///
+/// Once transfered, the object is unusable (detached) in the sending context.
+pub trait Transferable: sealed::Sealed {
+    /// Returns the object that is added to the transfer list (the buffer, for typed arrays)
+    fn transferable (&self) -> JsValue;
+}
+
+macro_rules! impl_transferable {
+    ($($name:ty),+) => {
+        $(
+            impl Transferable for $name {
+                #[inline]
+                fn transferable (&self) -> JsValue {
+                    return AsRef::<JsValue>::as_ref(self).clone()
+                }
+            }
+
+            impl sealed::Sealed for $name {}
+        )+
+    };
+}
+
+impl_transferable! {
+    ArrayBuffer,
+    web_sys::MessagePort,
+    web_sys::ImageBitmap,
+    web_sys::OffscreenCanvas
+}
+
+/// A message that moves it's transferable objects ([`ArrayBuffer`]s, `MessagePort`s, `ImageBitmap`s, ...) to the receiving
+/// context through the transfer list of `postMessage`, instead of structurally cloning them.
+/// This avoids copying large binary payloads when talking to workers.
+///
+/// `BroadcastChannel` doesn't support transfer lists, so messages sent through it are always cloned.
+/// ```ignore
+/// let bytes = Uint8Array::from(&pixels[..]);
+/// Transfer::new(bytes.clone()).with(&bytes).post(&worker)?;
+/// // `bytes` is now detached
+/// ```
+#[derive(Debug, Clone)]
+pub struct Transfer<T> {
+    value: T,
+    transfer: Array
+}
+
+impl<T> Transfer<T> {
+    /// Creates a new message without transferable objects
+    #[inline]
+    pub fn new (value: T) -> Self {
+        return Self { value, transfer: Array::new() }
+    }
+
+    /// Adds an object to the transfer list
+    #[inline]
+    pub fn with<O: ?Sized + Transferable> (mut self, object: &O) -> Self {
+        self.push(object);
+        return self
+    }
+
+    /// Adds an object to the transfer list
+    #[inline]
+    pub fn push<O: ?Sized + Transferable> (&mut self, object: &O) {
+        self.transfer.push(&object.transferable());
+    }
+
+    /// Returns the value of the message
+    #[inline]
+    pub fn value (&self) -> &T {
+        return &self.value
+    }
+
+    /// Returns the transfer list of the message
+    #[inline]
+    pub fn transfer_list (&self) -> &Array {
+        return &self.transfer
+    }
+
+    /// Returns the value of the message and it's transfer list
+    #[inline]
+    pub fn into_parts (self) -> (T, Array) {
+        return (self.value, self.transfer)
+    }
+
+    /// Returns the value of the message, discarding the transfer list
+    #[inline]
+    pub fn into_inner (self) -> T {
+        return self.value
+    }
+}
+
+impl<T: Transferable> Transfer<T> {
+    /// Creates a new message that transfers the value itself
+    #[inline]
+    pub fn object (value: T) -> Self {
+        return Self::new(value).with_self()
+    }
+
+    #[inline]
+    fn with_self (mut self) -> Self {
+        self.transfer.push(&self.value.transferable());
+        return self
+    }
+}
+
+impl Transfer<Uint8Array> {
+    /// Creates a new message with a copy of `bytes`, whose buffer is transfered instead of copied again
+    #[inline]
+    pub fn bytes (bytes: &[u8]) -> Self {
+        return Self::object(Uint8Array::from(bytes))
+    }
+}
+
+impl<T: AsRef<JsValue>> Transfer<T> {
+    /// Sends the message through a `postMessage` call on `target` (a `Worker`, `MessagePort`, `DedicatedWorkerGlobalScope`, ...)
+    #[inline]
+    pub fn post (&self, target: &js_sys::Object) -> Result<()> {
+        return post_message(target, self.value.as_ref(), &self.transfer)
+    }
+}
+
+/// Returns a raw signal that aborts with a `TimeoutError` after `dur`.
+pub(crate) fn timeout_signal (dur: std::time::Duration) -> Result<web_sys::AbortSignal> {
+    if let Ok(signal) = timeout_native(dur.as_millis() as f64) {
+        return Ok(signal)
+    }
+
+    let millis = i32::try_from(dur.as_millis())
+        .map_err(|_| crate::Error::new(crate::ErrorKind::NotSupported, "timeout overflow"))?;
+
+    let con = web_sys::AbortController::new()?.unchecked_into::<AbortControllerExt>();
+    let signal = con.signal();
+    let f = Closure::once_into_js(move || {
+        let reason = js_sys::Error::new("signal timed out");
+        reason.set_name("TimeoutError");
+        con.abort(reason.as_ref())
+    });
+
+    crate::scope::set_timeout(f.unchecked_ref(), millis)?;
+    return Ok(signal)
+}
+
+/// Returns a raw signal that aborts as soon as any of `signals` does, with it's reason.
+pub(crate) fn any_signal (signals: &[web_sys::AbortSignal]) -> Result<web_sys::AbortSignal> {
+    let array = signals.iter().collect::<Array>();
+    if let Ok(signal) = any_native(&array) {
+        return Ok(signal)
+    }
+
+    let con = web_sys::AbortController::new()?.unchecked_into::<AbortControllerExt>();
+    for signal in signals {
+        let signal = signal.unchecked_ref::<AbortSignalExt>();
+        if signal.aborted() {
+            con.abort(&signal.reason());
+            break
+        }
+
+        forward_abort(signal, &con)?;
+    }
+
+    return Ok(con.signal())
+}
+
+thread_local! {
+    /// Aborts the controller bound as it's first argument, with the reason of the signal that dispatched the event
+    static FORWARD_ABORT: Closure<dyn FnMut(AbortControllerExt, web_sys::Event)> = Closure::new(|con: AbortControllerExt, evt: web_sys::Event| {
+        if let Some(signal) = evt.target() && !con.signal().aborted() {
+            con.abort(&signal.unchecked_into::<AbortSignalExt>().reason())
+        }
+    });
+}
+
+/// Aborts `con` when `signal` aborts.
+/// 
+/// The listener is a bound JavaScript function, so it doesn't hold any WASM memory, and it's removed once `con` aborts.
+fn forward_abort (signal: &web_sys::AbortSignal, con: &AbortControllerExt) -> Result<()> {
+    let listener = FORWARD_ABORT.with(|f| f.as_ref().unchecked_ref::<Function>().bind1(&JsValue::NULL, con));
+
+    let mut options = web_sys::AddEventListenerOptions::new();
+    options.once(true);
+    Reflect::set(&options, &JsValue::from_str("signal"), &con.signal())?;
+
+    signal.add_event_listener_with_callback_and_add_event_listener_options("abort", &listener, &options)?;
+    return Ok(())
+}
+
+/// Runs a callback when the signal is aborted, for as long as the link is alive.
+pub(crate) struct SignalLink {
+    signal: web_sys::AbortSignal,
+    f: Closure<dyn FnMut()>
+}
+
+impl SignalLink {
+    pub fn new<F: 'static + FnMut()> (signal: web_sys::AbortSignal, f: F) -> Result<Self> {
+        let f = Closure::<dyn FnMut()>::new(f);
+        signal.add_event_listener_with_callback("abort", f.as_ref().unchecked_ref())?;
+        return Ok(Self { signal, f })
+    }
+}
+
+impl Debug for SignalLink {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SignalLink")
+            .field("signal", &self.signal)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Drop for SignalLink {
+    #[inline]
+    fn drop(&mut self) {
+        let _ = self.signal.remove_event_listener_with_callback("abort", self.f.as_ref().unchecked_ref());
+    }
 }
 
 /// Creates a new abortion flag
@@ -254,7 +697,7 @@ impl<T> AbortController<T> {
     /// Sends the abortion signal with the specified reason
     #[inline]
     pub fn abort_with (&self, reason: &T) -> Result<()> where T: Serialize {
-        let reason = serde_wasm_bindgen::to_value(reason)?;
+        let reason = to_js(reason)?;
         self.inner.abort(reason.as_ref());
         Ok(())
     }
@@ -277,6 +720,22 @@ impl<T> AbortController<T> {
         return AbortSignal::new(self.inner.signal())
     }
 
+    /// Creates a new controller that is aborted (with the same reason) when this one is.
+    ///
+    /// Aborting the child doesn't affect the parent.
+    pub fn child (&self) -> Result<Self> {
+        let child = Self::new()?;
+        let signal = self.raw_signal().unchecked_into::<AbortSignalExt>();
+
+        if signal.aborted() {
+            child.inner.abort(&signal.reason());
+            return Ok(child)
+        }
+
+        forward_abort(&signal, &child.inner)?;
+        return Ok(child)
+    }
+
     /// Creates a [`Promise`] that resolves when the controller aborts. 
     pub fn signal_promise (&self) -> Promise {
         let signal = self.raw_signal();
@@ -319,70 +778,83 @@ impl<T> From<web_sys::AbortController> for AbortController<T> {
 
 /// Represents a signal object that allows you to communicate with a DOM request (such as a fetch request) and abort it if required via an [`AbortController`].
 pub struct AbortSignal<T> {
-    inner: AbortSignalExt,
-    _list: Closure<dyn FnMut()>, 
-    waker: Pin<Box<Cell<Option<Waker>>>>,
+    inner: Rc<SignalInner>,
     _phtm: PhantomData<T>
 }
 
+/// State shared between all the clones of an [`AbortSignal`]
+struct SignalInner {
+    signal: AbortSignalExt,
+    wakers: Rc<RefCell<Vec<Waker>>>,
+    listener: Closure<dyn FnMut()>
+}
+
 impl<T> AbortSignal<T> {
     pub fn new (inner: web_sys::AbortSignal) -> Result<Self> {
-        let waker = Box::pin(Cell::new(None::<Waker>));
-        let f = || if let Some(waker) = waker.take() {
-            waker.wake();
-        };
-
-        let f = unsafe {
-            core::mem::transmute::<
-                Box<dyn FnOnce()>,
-                Box<dyn 'static + FnOnce()>
-            >(Box::new(f))
-        };
-
-        let _list = Closure::wrap(f.into_fn_mut());
-        
-        let fun = _list.as_ref();
-        debug_assert!(fun.is_instance_of::<js_sys::Function>());
-        inner.add_event_listener_with_callback("abort", fun.unchecked_ref())?;
+        let wakers = Rc::new(RefCell::new(Vec::<Waker>::new()));
+        let my_wakers = wakers.clone();
+        let listener = Closure::<dyn FnMut()>::new(move || {
+            let wakers = core::mem::take(&mut *my_wakers.borrow_mut());
+            for waker in wakers {
+                waker.wake()
+            }
+        });
 
+        inner.add_event_listener_with_callback("abort", listener.as_ref().unchecked_ref())?;
         return Ok(Self {
-            inner: inner.unchecked_into(),
-            _list,
-            waker,
+            inner: Rc::new(SignalInner {
+                signal: inner.unchecked_into(),
+                wakers,
+                listener
+            }),
             _phtm: PhantomData
         })
     }
 
+    /// Creates a new signal that will abort with a `TimeoutError` after the specified duration.
+    #[inline]
+    pub fn timeout (dur: std::time::Duration) -> Result<Self> {
+        return Self::new(timeout_signal(dur)?)
+    }
+
+    /// Creates a new signal that aborts as soon as any of the specified signals does, with it's reason.
+    pub fn any<'a, I: IntoIterator<Item = &'a AbortSignal<T>>> (signals: I) -> Result<Self> where T: 'a {
+        let signals = signals.into_iter()
+            .map(|x| x.as_ref().clone())
+            .collect::<Vec<_>>();
+
+        return Self::new(any_signal(&signals)?)
+    }
+
     /// Returns `true` if the signal is aborted, `false` otherwise
     #[inline]
     pub fn is_aborted (&self) -> bool {
-        return self.inner.aborted()
+        return self.inner.signal.aborted()
     }
 
     #[inline]
     pub fn reason (&self) -> Result<Option<T>> where T: DeserializeOwned {
-        let reason = self.inner.reason();
+        let reason = self.inner.signal.reason();
         if reason.is_undefined() {
             return Ok(None)
         }
 
-        let v = serde_wasm_bindgen::from_value(reason)?;
-        return Ok(v)
+        return from_js(reason)
     }
 
     #[inline]
     pub fn reason_cast (&self) -> Result<Option<T>> where T: JsCast {
-        let reason = self.inner.reason();
+        let reason = self.inner.signal.reason();
         if reason.is_undefined() {
             return Ok(None)
         }
-        return JsCast::dyn_into::<T>(reason).map(Some)
+        return JsCast::dyn_into::<T>(reason).map(Some).map_err(Into::into)
     }
 
     /// Creates a [`Promise`] that resolves when the signal is aborted. 
     pub fn promise (&self) -> Promise {
         return Promise::new(&mut |resolve, reject| {
-            if let Err(e) = self.inner.add_event_listener_with_callback("abort", &resolve) {
+            if let Err(e) = self.inner.signal.add_event_listener_with_callback("abort", &resolve) {
                 if let Err(e) = reject.call1(&JsValue::UNDEFINED, &e) {
                     ::wasm_bindgen::throw_val(e);
                 }
@@ -390,17 +862,19 @@ impl<T> AbortSignal<T> {
         })
     }
 
-    /// Attempts to clone the signal
+    /// Attempts to clone the signal.
+    /// 
+    /// Clones share the same listener, so this never fails.
     #[inline]
     pub fn try_clone (&self) -> Result<Self> {
-        return Self::new(self.inner.clone().unchecked_into())
+        return Ok(self.clone())
     }
 }
 
 impl<T> AsRef<web_sys::AbortSignal> for AbortSignal<T> {
     #[inline]
     fn as_ref(&self) -> &web_sys::AbortSignal {
-        &self.inner
+        &self.inner.signal
     }
 }
 
@@ -410,11 +884,14 @@ impl<T: DeserializeOwned> Future for AbortSignal<T> {
     #[inline]
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         if self.is_aborted() {
-            let v = serde_wasm_bindgen::from_value::<T>(self.inner.reason())?;
+            let v = from_js::<T>(self.inner.signal.reason())?;
             return Poll::Ready(Ok(v))
         }
 
-        Cell::set(&self.waker, Some(cx.waker().clone()));
+        let mut wakers = self.inner.wakers.borrow_mut();
+        if !wakers.iter().any(|x| x.will_wake(cx.waker())) {
+            wakers.push(cx.waker().clone());
+        }
         return Poll::Pending
     }
 }
@@ -431,16 +908,17 @@ impl<T> Debug for AbortSignal<T> {
 impl<T> Clone for AbortSignal<T> {
     #[inline]
     fn clone(&self) -> Self {
-        self.try_clone().unwrap()
+        Self {
+            inner: self.inner.clone(),
+            _phtm: PhantomData
+        }
     }
 }
 
-impl<T> Drop for AbortSignal<T> {
+impl Drop for SignalInner {
     #[inline]
     fn drop(&mut self) {
-        let fun = self._list.as_ref();
-        debug_assert!(fun.is_instance_of::<js_sys::Function>());
-        self.inner.remove_event_listener_with_callback("abort", fun.unchecked_ref()).unwrap();
+        let _ = self.signal.remove_event_listener_with_callback("abort", self.listener.as_ref().unchecked_ref());
     }
 }
 
@@ -552,6 +1030,13 @@ macro_rules! impl_typed_array {
                 }
             }
 
+            impl Transferable for $name {
+                #[inline]
+                fn transferable (&self) -> JsValue {
+                    return <$name>::buffer(self).into()
+                }
+            }
+
             impl sealed::Sealed for $name {}
         )+
 
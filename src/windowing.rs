@@ -0,0 +1,235 @@
+use std::{fmt::{Display, Write}, time::Duration};
+use js_sys::{Promise, Array};
+use serde::Serialize;
+use wasm_bindgen::{JsCast, JsValue, prelude::wasm_bindgen};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::Window;
+use crate::{Result, Error, ErrorKind, scope::window, time::sleep, utils::Transfer};
+
+/// Interval at which [`WindowHandle::closed`] checks the state of the window
+const CLOSE_POLL: Duration = Duration::from_millis(250);
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_name = getScreenDetails, catch)]
+    fn get_screen_details () -> ::core::result::Result<Promise, JsValue>;
+}
+
+/// Features of a window opened with [`open`]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct WindowFeatures {
+    width: Option<i32>,
+    height: Option<i32>,
+    left: Option<i32>,
+    top: Option<i32>,
+    popup: bool,
+    noopener: bool,
+    noreferrer: bool
+}
+
+impl WindowFeatures {
+    /// Creates a new set of features, with every value set to the browser's default
+    #[inline]
+    pub fn new () -> Self {
+        return Default::default()
+    }
+
+    /// Sets the width of the window's content area, in pixels
+    #[inline]
+    pub fn width (&mut self, width: i32) -> &mut Self {
+        self.width = Some(width);
+        self
+    }
+
+    /// Sets the height of the window's content area, in pixels
+    #[inline]
+    pub fn height (&mut self, height: i32) -> &mut Self {
+        self.height = Some(height);
+        self
+    }
+
+    /// Sets the distance from the left side of the work area to the window, in pixels
+    #[inline]
+    pub fn left (&mut self, left: i32) -> &mut Self {
+        self.left = Some(left);
+        self
+    }
+
+    /// Sets the distance from the top side of the work area to the window, in pixels
+    #[inline]
+    pub fn top (&mut self, top: i32) -> &mut Self {
+        self.top = Some(top);
+        self
+    }
+
+    /// Places the window on the top-left corner of the specified screen
+    #[inline]
+    pub fn on_screen (&mut self, screen: &ScreenInfo) -> &mut Self {
+        self.left = Some(screen.avail_left);
+        self.top = Some(screen.avail_top);
+        self
+    }
+
+    /// If `true`, a minimal popup window is requested, instead of a new tab
+    #[inline]
+    pub fn popup (&mut self, popup: bool) -> &mut Self {
+        self.popup = popup;
+        self
+    }
+
+    /// If `true`, the new window won't have access to the current one.
+    ///
+    /// When set, [`open`] will always return `None`
+    #[inline]
+    pub fn noopener (&mut self, noopener: bool) -> &mut Self {
+        self.noopener = noopener;
+        self
+    }
+
+    /// If `true`, the `Referer` header is omitted, and [`noopener`](WindowFeatures::noopener) is implied
+    #[inline]
+    pub fn noreferrer (&mut self, noreferrer: bool) -> &mut Self {
+        self.noreferrer = noreferrer;
+        self
+    }
+}
+
+impl Display for WindowFeatures {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut first = true;
+        let mut push = |name: &str, value: &dyn Display| -> std::fmt::Result {
+            if !core::mem::take(&mut first) { f.write_char(',')? }
+            write!(f, "{name}={value}")
+        };
+
+        if let Some(width) = self.width { push("width", &width)? }
+        if let Some(height) = self.height { push("height", &height)? }
+        if let Some(left) = self.left { push("left", &left)? }
+        if let Some(top) = self.top { push("top", &top)? }
+        if self.popup { push("popup", &1)? }
+        if self.noopener { push("noopener", &1)? }
+        if self.noreferrer { push("noreferrer", &1)? }
+        return Ok(())
+    }
+}
+
+/// Opens `url` in a new window (or tab) named `target`.
+///
+/// Returns `None` if the new window isn't accessible (as when [`noopener`](WindowFeatures::noopener) is set),
+/// and fails if the window couldn't be opened (as when popups are blocked).
+pub fn open (url: &str, target: &str, features: &WindowFeatures) -> Result<Option<WindowHandle>> {
+    let inner = window()?.open_with_url_and_target_and_features(url, target, &features.to_string())?;
+    return match inner {
+        Some(inner) => Ok(Some(WindowHandle { inner })),
+        None if features.noopener || features.noreferrer => Ok(None),
+        None => Err(Error::new(ErrorKind::Permission, "the window couldn't be opened"))
+    }
+}
+
+/// A handle to a window opened with [`open`]
+#[derive(Debug, Clone)]
+pub struct WindowHandle {
+    inner: Window
+}
+
+impl WindowHandle {
+    /// Returns the underlying [`Window`]
+    #[inline]
+    pub fn raw (&self) -> &Window {
+        return &self.inner
+    }
+
+    /// Serializes `msg` and sends it to the window, if it's origin matches `target_origin` (`"*"` matches any origin)
+    #[inline]
+    pub fn post_message<T: ?Sized + Serialize> (&self, msg: &T, target_origin: &str) -> Result<()> {
+        let msg = crate::utils::to_js(msg)?;
+        return self.post_message_js(&msg, target_origin)
+    }
+
+    /// Sends `msg` to the window, if it's origin matches `target_origin` (`"*"` matches any origin)
+    #[inline]
+    pub fn post_message_js (&self, msg: &JsValue, target_origin: &str) -> Result<()> {
+        return Ok(self.inner.post_message(msg, target_origin)?)
+    }
+
+    /// Sends `msg` to the window, if it's origin matches `target_origin` (`"*"` matches any origin), moving it's transferable objects
+    #[inline]
+    pub fn post_message_transfer<T: AsRef<JsValue>> (&self, msg: &Transfer<T>, target_origin: &str) -> Result<()> {
+        return Ok(self.inner.post_message_with_transfer(msg.value().as_ref(), target_origin, msg.transfer_list())?)
+    }
+
+    /// Returns `true` if the window has been closed, `false` otherwise
+    #[inline]
+    pub fn is_closed (&self) -> bool {
+        return self.inner.closed().unwrap_or(true)
+    }
+
+    /// Closes the window
+    #[inline]
+    pub fn close (&self) -> Result<()> {
+        return Ok(self.inner.close()?)
+    }
+
+    /// Resolves when the window is closed
+    pub async fn closed (&self) -> Result<()> {
+        while !self.is_closed() {
+            sleep(CLOSE_POLL)?.await;
+        }
+        return Ok(())
+    }
+}
+
+/// Information about one of the device's screens
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScreenInfo {
+    /// Human-readable name of the screen
+    pub label: String,
+    pub left: i32,
+    pub top: i32,
+    pub width: i32,
+    pub height: i32,
+    /// Left coordinate of the area available to windows (excluding taskbars, ...)
+    pub avail_left: i32,
+    /// Top coordinate of the area available to windows (excluding taskbars, ...)
+    pub avail_top: i32,
+    pub avail_width: i32,
+    pub avail_height: i32,
+    pub device_pixel_ratio: f64,
+    pub is_primary: bool,
+    pub is_internal: bool
+}
+
+impl From<JsValue> for ScreenInfo {
+    fn from(value: JsValue) -> Self {
+        let get = |key: &str| js_sys::Reflect::get(&value, &JsValue::from_str(key)).unwrap_or(JsValue::UNDEFINED);
+        let int = |key: &str| get(key).as_f64().unwrap_or_default() as i32;
+
+        return Self {
+            label: get("label").as_string().unwrap_or_default(),
+            left: int("left"),
+            top: int("top"),
+            width: int("width"),
+            height: int("height"),
+            avail_left: int("availLeft"),
+            avail_top: int("availTop"),
+            avail_width: int("availWidth"),
+            avail_height: int("availHeight"),
+            device_pixel_ratio: get("devicePixelRatio").as_f64().unwrap_or(1.),
+            is_primary: get("isPrimary").as_bool().unwrap_or_default(),
+            is_internal: get("isInternal").as_bool().unwrap_or_default()
+        }
+    }
+}
+
+/// Returns every screen connected to the device, via the [Window Management API](https://developer.mozilla.org/en-US/docs/Web/API/Window_Management_API).
+///
+/// The user will be asked for permission if it hasn't been granted yet.
+pub async fn screens () -> Result<Vec<ScreenInfo>> {
+    if !js_sys::Reflect::has(&window()?, &JsValue::from_str("getScreenDetails"))? {
+        return Err(Error::new(ErrorKind::NotSupported, "Window Management API is not supported"))
+    }
+
+    let details = JsFuture::from(get_screen_details()?).await?;
+    let screens = js_sys::Reflect::get(&details, &JsValue::from_str("screens"))?;
+    return Ok(screens.unchecked_into::<Array>().iter().map(ScreenInfo::from).collect())
+}
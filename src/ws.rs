@@ -0,0 +1,373 @@
+use std::{rc::Rc, cell::{Cell, RefCell}, collections::VecDeque, pin::Pin, task::{Context, Poll}};
+use futures::{Stream, StreamExt};
+use js_sys::{ArrayBuffer, Uint8Array};
+use wasm_bindgen::{JsCast, JsValue, prelude::Closure};
+use web_sys::{BinaryType, MessageEvent};
+use crate::{Result, Error, ErrorKind, sync::one_shot, time::{Backoff, sleep}, utils::{LocalSender, LocalReceiver, local_channel}};
+
+/// A message sent or received through a WebSocket
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Message {
+    Text (String),
+    Binary (Vec<u8>)
+}
+
+impl Message {
+    #[inline]
+    fn from_js (data: JsValue) -> Option<Self> {
+        if let Some(text) = data.as_string() {
+            return Some(Self::Text(text))
+        }
+        return match data.dyn_into::<ArrayBuffer>() {
+            Ok(buffer) => Some(Self::Binary(Uint8Array::new(&buffer).to_vec())),
+            Err(_) => None
+        }
+    }
+}
+
+impl From<String> for Message {
+    #[inline]
+    fn from(value: String) -> Self {
+        return Self::Text(value)
+    }
+}
+
+impl From<&str> for Message {
+    #[inline]
+    fn from(value: &str) -> Self {
+        return Self::Text(value.to_string())
+    }
+}
+
+impl From<Vec<u8>> for Message {
+    #[inline]
+    fn from(value: Vec<u8>) -> Self {
+        return Self::Binary(value)
+    }
+}
+
+#[inline]
+fn send_raw (socket: &web_sys::WebSocket, msg: &Message) -> Result<()> {
+    match msg {
+        Message::Text(text) => socket.send_with_str(text)?,
+        Message::Binary(bytes) => socket.send_with_u8_array(bytes)?
+    }
+    return Ok(())
+}
+
+/// A WebSocket connection, yielding the messages it receives until it's closed.
+///
+/// The connection is closed when dropped.
+pub struct WebSocket {
+    inner: web_sys::WebSocket,
+    _on_message: Closure<dyn FnMut(MessageEvent)>,
+    _on_close: Closure<dyn FnMut(web_sys::Event)>,
+    recv: LocalReceiver<Option<Message>>,
+    done: bool
+}
+
+impl WebSocket {
+    /// Opens a new connection to the specified url, resolving once it's established
+    pub async fn connect (url: &str) -> Result<Self> {
+        let inner = web_sys::WebSocket::new(url)?;
+        inner.set_binary_type(BinaryType::Arraybuffer);
+
+        let (send, recv) = local_channel();
+        let on_message_send = send.clone();
+        let on_message = Closure::<dyn FnMut(MessageEvent)>::new(move |evt: MessageEvent| {
+            if let Some(msg) = Message::from_js(evt.data()) {
+                let _ = on_message_send.try_send(Some(msg));
+            }
+        });
+        let on_close = Closure::<dyn FnMut(web_sys::Event)>::new(move |_| {
+            let _ = send.try_send(None);
+        });
+
+        inner.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+        inner.set_onclose(Some(on_close.as_ref().unchecked_ref()));
+
+        let (open_send, open_recv) = one_shot();
+        let open_send = Rc::new(open_send);
+        let error_send = open_send.clone();
+        let on_open = Closure::<dyn FnMut(web_sys::Event)>::new(move |_| { let _ = open_send.try_send(true); });
+        let on_error = Closure::<dyn FnMut(web_sys::Event)>::new(move |_| { let _ = error_send.try_send(false); });
+        inner.set_onopen(Some(on_open.as_ref().unchecked_ref()));
+        inner.set_onerror(Some(on_error.as_ref().unchecked_ref()));
+
+        let this = Self { inner, _on_message: on_message, _on_close: on_close, recv, done: false };
+        let opened = open_recv.await;
+        this.inner.set_onopen(None);
+        this.inner.set_onerror(None);
+
+        return match opened {
+            Some(true) => Ok(this),
+            _ => Err(Error::new(ErrorKind::Io, "WebSocket connection failed"))
+        }
+    }
+
+    /// Sends a message through the connection
+    #[inline]
+    pub fn send (&self, msg: &Message) -> Result<()> {
+        return send_raw(&self.inner, msg)
+    }
+
+    /// Closes the connection
+    #[inline]
+    pub fn close (&self) -> Result<()> {
+        return Ok(self.inner.close()?)
+    }
+
+    /// Returns the url of the connection
+    #[inline]
+    pub fn url (&self) -> String {
+        return self.inner.url()
+    }
+
+    /// Returns the underlying [`WebSocket`](web_sys::WebSocket)
+    #[inline]
+    pub fn raw (&self) -> &web_sys::WebSocket {
+        return &self.inner
+    }
+}
+
+impl Stream for WebSocket {
+    type Item = Message;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.done {
+            return Poll::Ready(None)
+        }
+
+        return match self.recv.poll_next_unpin(cx) {
+            Poll::Ready(Some(Some(msg))) => Poll::Ready(Some(msg)),
+            Poll::Ready(_) => {
+                self.done = true;
+                Poll::Ready(None)
+            },
+            Poll::Pending => Poll::Pending
+        }
+    }
+}
+
+impl Drop for WebSocket {
+    #[inline]
+    fn drop(&mut self) {
+        self.inner.set_onmessage(None);
+        self.inner.set_onclose(None);
+        let _ = self.inner.close();
+    }
+}
+
+/// State of a [`Resilient`] connection
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ConnectionState {
+    /// The first connection is being established
+    Connecting,
+    /// The connection is open
+    Open,
+    /// The connection was lost, and the specified reconnection attempt is in progress
+    Reconnecting (u32),
+    /// The connection was closed by the user, or the backoff policy ran out of retries
+    Closed
+}
+
+/// Builder of a [`Resilient`] connection
+pub struct ResilientBuilder {
+    url: String,
+    backoff: Backoff,
+    handshake: Vec<Message>,
+    buffer: usize
+}
+
+impl ResilientBuilder {
+    /// Sets the backoff policy used between reconnection attempts.
+    /// By default, an unlimited exponential backoff starting at 500 milliseconds is used.
+    #[inline]
+    pub fn backoff (mut self, backoff: Backoff) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Adds a message that is sent every time the connection is established, before any buffered message
+    /// (for example, an authentication or subscription request)
+    #[inline]
+    pub fn handshake (mut self, msg: impl Into<Message>) -> Self {
+        self.handshake.push(msg.into());
+        self
+    }
+
+    /// Sets the maximum number of outgoing messages buffered while disconnected. By default, up to 64 messages are buffered.
+    #[inline]
+    pub fn buffer (mut self, capacity: usize) -> Self {
+        self.buffer = capacity;
+        self
+    }
+
+    /// Starts the connection
+    pub fn connect (self) -> Resilient {
+        let (msg_send, messages) = local_channel();
+        let (state_send, states) = local_channel();
+
+        let shared = Rc::new(Shared {
+            socket: RefCell::new(None),
+            buffer: RefCell::new(VecDeque::new()),
+            capacity: self.buffer,
+            state: Cell::new(ConnectionState::Connecting),
+            closed: Cell::new(false),
+            state_send
+        });
+
+        wasm_bindgen_futures::spawn_local(run(shared.clone(), self, msg_send));
+        return Resilient { shared, messages, states: Some(states) }
+    }
+}
+
+struct Shared {
+    socket: RefCell<Option<web_sys::WebSocket>>,
+    buffer: RefCell<VecDeque<Message>>,
+    capacity: usize,
+    state: Cell<ConnectionState>,
+    closed: Cell<bool>,
+    state_send: LocalSender<ConnectionState>
+}
+
+impl Shared {
+    #[inline]
+    fn set_state (&self, state: ConnectionState) {
+        if self.state.replace(state) != state {
+            let _ = self.state_send.try_send(state);
+        }
+    }
+}
+
+async fn run (shared: Rc<Shared>, builder: ResilientBuilder, messages: LocalSender<Message>) {
+    let ResilientBuilder { url, mut backoff, handshake, .. } = builder;
+
+    while !shared.closed.get() {
+        if let Ok(mut socket) = WebSocket::connect(&url).await {
+            backoff.reset();
+
+            let handshake = handshake.iter().try_for_each(|msg| socket.send(msg));
+            if handshake.is_ok() && !shared.closed.get() {
+                *shared.socket.borrow_mut() = Some(socket.raw().clone());
+                shared.set_state(ConnectionState::Open);
+
+                let buffered = core::mem::take(&mut *shared.buffer.borrow_mut());
+                for msg in buffered.iter() {
+                    let _ = socket.send(msg);
+                }
+
+                while let Some(msg) = socket.next().await {
+                    let _ = messages.try_send(msg);
+                }
+
+                *shared.socket.borrow_mut() = None;
+            }
+        }
+
+        if shared.closed.get() { break }
+        let delay = match backoff.next() {
+            Some(delay) => delay,
+            None => break
+        };
+
+        shared.set_state(ConnectionState::Reconnecting(backoff.attempt()));
+        match sleep(delay) {
+            Ok(sleep) => sleep.await,
+            Err(_) => break
+        }
+    }
+
+    shared.closed.set(true);
+    shared.set_state(ConnectionState::Closed);
+}
+
+/// A WebSocket connection that automatically reconnects when it's lost.
+///
+/// Every time the connection is established, the handshake messages are sent, followed by the messages buffered while it was down.
+/// The connection is closed when dropped.
+pub struct Resilient {
+    shared: Rc<Shared>,
+    messages: LocalReceiver<Message>,
+    states: Option<LocalReceiver<ConnectionState>>
+}
+
+impl Resilient {
+    /// Creates a builder for a connection to the specified url
+    #[inline]
+    pub fn builder (url: impl Into<String>) -> ResilientBuilder {
+        return ResilientBuilder {
+            url: url.into(),
+            backoff: Backoff::new(std::time::Duration::from_millis(500)),
+            handshake: Vec::new(),
+            buffer: 64
+        }
+    }
+
+    /// Starts a connection to the specified url, with the default options
+    #[inline]
+    pub fn connect (url: impl Into<String>) -> Self {
+        return Self::builder(url).connect()
+    }
+
+    /// Returns the current state of the connection
+    #[inline]
+    pub fn state (&self) -> ConnectionState {
+        return self.shared.state.get()
+    }
+
+    /// Returns a stream of the connection's state changes.
+    ///
+    /// Only the first call returns a stream, with the following ones returning `None`.
+    #[inline]
+    pub fn states (&mut self) -> Option<LocalReceiver<ConnectionState>> {
+        return self.states.take()
+    }
+
+    /// Sends the message if the connection is open, or buffers it until the connection is reestablished.
+    ///
+    /// Returns an error if the connection is closed, or if the buffer is full.
+    pub fn send (&self, msg: impl Into<Message>) -> Result<()> {
+        let msg = msg.into();
+        if self.shared.closed.get() {
+            return Err(Error::new(ErrorKind::Io, "WebSocket connection is closed"))
+        }
+
+        if let Some(ref socket) = *self.shared.socket.borrow() {
+            return send_raw(socket, &msg)
+        }
+
+        let mut buffer = self.shared.buffer.borrow_mut();
+        if buffer.len() >= self.shared.capacity {
+            return Err(Error::new(ErrorKind::Io, "WebSocket outgoing buffer is full"))
+        }
+
+        buffer.push_back(msg);
+        return Ok(())
+    }
+
+    /// Closes the connection, without reconnecting
+    pub fn close (&self) {
+        self.shared.closed.set(true);
+        self.shared.buffer.borrow_mut().clear();
+        if let Some(ref socket) = *self.shared.socket.borrow() {
+            let _ = socket.close();
+        }
+    }
+}
+
+impl Stream for Resilient {
+    type Item = Message;
+
+    #[inline]
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.messages.poll_next_unpin(cx)
+    }
+}
+
+impl Drop for Resilient {
+    #[inline]
+    fn drop(&mut self) {
+        self.close()
+    }
+}
@@ -0,0 +1,27 @@
+use std::time::Duration;
+use rustww::{diagnostics, geo::Geolocation, time::timeout};
+use wasm_bindgen_test::{wasm_bindgen_test};
+
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+fn live_requests () -> usize {
+    return diagnostics::dump().into_iter()
+        .find(|x| x.name == "geo::current")
+        .map_or(0, |x| x.live)
+}
+
+/// Every answered request must release it's callbacks, whether it succeeds or fails
+#[wasm_bindgen_test]
+async fn current_releases_closures () {
+    let before = live_requests();
+
+    for _ in 0..200 {
+        let current = Geolocation::current().unwrap();
+        // the browser may be waiting for the user to grant the permission
+        if timeout(Duration::from_secs(5), current).await.unwrap().is_none() {
+            return
+        }
+    }
+
+    assert_eq!(live_requests(), before);
+}
@@ -34,6 +34,33 @@ async fn ip_text () -> Result<()> {
     Ok(())
 }
 
+fn bytes_stream (bytes: &mut [u8]) -> Result<JsReadStream<'static, js_sys::Uint8Array>> {
+    let body = rustww::web_sys::Response::new_with_opt_u8_array(Some(bytes))?.body().unwrap();
+    return JsReadStream::new(body)
+}
+
+#[wasm_bindgen_test]
+async fn stream_adapters () -> Result<()> {
+    let mut bytes = (0..10).collect::<Vec<u8>>();
+
+    let mut chunks = bytes_stream(&mut bytes)?.chunk_exact(4)?;
+    let mut lens = Vec::new();
+    while let Some(chunk) = chunks.read_chunk().await? {
+        lens.push(chunk.length())
+    }
+    assert_eq!(lens, [4, 4, 2]);
+
+    let mut taken = bytes_stream(&mut bytes)?.take_bytes(3)?;
+    assert_eq!(taken.read_remaining_bytes().await?, [0, 1, 2]);
+
+    let mut doubled = bytes_stream(&mut bytes)?.map_chunk(|chunk: js_sys::Uint8Array| {
+        let doubled = chunk.to_vec().into_iter().map(|x| 2 * x).collect::<Vec<_>>();
+        Ok(js_sys::Uint8Array::from(&doubled as &[u8]))
+    })?;
+    assert_eq!(doubled.read_remaining_bytes().await?, bytes.iter().map(|x| 2 * x).collect::<Vec<_>>());
+    Ok(())
+}
+
 /*#[cfg(web_sys_unstable_apis)]
 #[wasm_bindgen_test]
 async fn custom_read () -> Result<()> {
@@ -170,6 +197,41 @@ async fn write_async_lt () -> Result<()> {
     Ok(())
 }
 
+#[cfg(web_sys_unstable_apis)]
+#[wasm_bindgen_test]
+async fn sink_order () -> Result<()> {
+    use std::time::Duration;
+    use futures::SinkExt;
+    use js_sys::Uint8Array;
+    use wasm_bindgen::__rt::WasmRefCell;
+
+    let vec = WasmRefCell::new(Vec::<u8>::new());
+    let writer: JsWriteStream<'_, Uint8Array> = JsWriteStream::custom()
+        .write_async(|chunk: Uint8Array, con| {
+            let vec = &vec;
+            return async move {
+                // Slower chunks first
+                sleep(Duration::from_millis(10 * chunk.get_index(0) as u64)).await?;
+                vec.borrow_mut().extend(chunk.to_vec());
+                Ok(())
+            }
+        })
+        .build()?;
+
+    let mut sink = writer.into_sink().buffered(2);
+    for i in [3u8, 1, 2] {
+        sink.feed(Uint8Array::from(&[i][..])).await?;
+        assert!(sink.in_flight() <= 2);
+    }
+
+    sink.flush().await?;
+    assert_eq!(sink.in_flight(), 0);
+    drop(sink);
+
+    assert_eq!(&vec.borrow() as &[u8], &[3, 1, 2]);
+    Ok(())
+}
+
 #[cfg(web_sys_unstable_apis)]
 #[wasm_bindgen_test]
 fn write_drop () {
@@ -127,3 +127,95 @@ impl_tests! {
     //Vec3d as [f64; 3] => (x, y, z)
     //Vec4d as [f64; 4] => (x, y, z, w)
 }
+
+#[wasm_bindgen_test]
+fn batch_kernels () {
+    use rustww::math::batch;
+
+    let a = (0..37).map(|i| i as f32).collect::<Vec<_>>();
+    let mut b = vec![2f32; 37];
+
+    assert_eq!(batch::sum(&a), 666.);
+    assert_eq!(batch::dot(&a, &b), 1332.);
+
+    batch::axpy(0.5, &a, &mut b);
+    assert!(b.iter().enumerate().all(|(i, x)| *x == 2. + 0.5 * i as f32));
+
+    batch::scale(&mut b, 2.);
+    assert!(b.iter().enumerate().all(|(i, x)| *x == 4. + i as f32));
+}
+
+#[wasm_bindgen_test]
+fn mat4_transforms () {
+    use rustww::math::{Mat4f, batch};
+
+    let m = Mat4f::from_translation(Vec3f::new(1., 2., 3.)) * Mat4f::from_scale(Vec3f::new(2., 2., 2.));
+    assert_eq!(m.transform_point(Vec3f::new(1., 1., 1.)), Vec3f::new(3., 4., 5.));
+    assert_eq!(m.transform_vector(Vec3f::new(1., 1., 1.)), Vec3f::new(2., 2., 2.));
+    assert_eq!(m.transpose().transpose(), m);
+
+    let mut points = vec![Vec4f::new(1., 0., 0., 1.); 9];
+    batch::transform_slice(&m, &mut points);
+    assert!(points.iter().all(|p| *p == Vec4f::new(3., 2., 3., 1.)));
+}
+
+#[wasm_bindgen_test]
+fn approximate_equality () {
+    use rustww::math::{Mat4f, Quatd};
+
+    let alpha = Vec3f::new(0.1, 0.2, 0.3);
+    let beta = Vec3f::new(0.1 + 1e-7, 0.2, 0.3 - 1e-7);
+    assert!(alpha.abs_diff_eq(beta, 1e-6));
+    assert!(!alpha.abs_diff_eq(beta, 1e-8));
+    assert!(alpha.ulps_eq(beta, 16));
+
+    let big = Vec2d::new(1e12, -1e12);
+    assert!(big.approx_eq(big + Vec2d::new(1., 0.), 1e-9));
+    assert!(!big.abs_diff_eq(big + Vec2d::new(1., 0.), 1e-9));
+    assert!(!Vec2d::new(f64::NAN, 0.).ulps_eq(Vec2d::new(f64::NAN, 0.), u32::MAX));
+
+    let m = Mat4f::from_scale(Vec3f::new(3., 3., 3.));
+    assert!((m * Mat4f::from_scale(Vec3f::new(1. / 3., 1. / 3., 1. / 3.))).approx_eq(Mat4f::IDENTITY, f32::EPSILON));
+    assert!(Quatd::IDENTITY.abs_diff_eq(Quatd::new(0., 0., 1e-12, 1.), 1e-9));
+}
+
+#[wasm_bindgen_test]
+fn serde_layouts () {
+    use serde::{Serialize, Deserialize};
+
+    macro_rules! roundtrip {
+        ($($name:ty),+) => {
+            $(
+                let alpha: $name = random();
+                let json = serde_json::to_string(&alpha).unwrap();
+                assert_eq!(serde_json::from_str::<$name>(&json).unwrap(), alpha);
+            )+
+        };
+    }
+    roundtrip!(Vec2f, Vec3f, Vec4f, Vec2d, Vec3d, Vec4d);
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Mesh {
+        #[serde(with = "rustww::math::soa")]
+        positions: Vec<Vec3f>
+    }
+
+    let mesh = Mesh { positions: vec![Vec3f::new(1., 2., 3.), Vec3f::new(4., 5., 6.)] };
+    let json = serde_json::to_string(&mesh).unwrap();
+    assert_eq!(json, r#"{"positions":{"x":[1.0,4.0],"y":[2.0,5.0],"z":[3.0,6.0]}}"#);
+    assert_eq!(serde_json::from_str::<Mesh>(&json).unwrap(), mesh);
+    assert!(serde_json::from_str::<Mesh>(r#"{"positions":{"x":[1.0],"y":[],"z":[3.0]}}"#).is_err());
+}
+
+#[wasm_bindgen_test]
+fn checked_units () {
+    assert_eq!(Vec3f::default().try_unit(), None);
+    assert_eq!(Vec3f::default().normalize_or_zero(), Vec3f::default());
+    assert_eq!(Vec2d::new(3., 4.).try_unit(), Some(Vec2d::new(0.6, 0.8)));
+    assert_eq!(Vec4d::new(f64::INFINITY, 0., 0., 0.).normalize_or_zero(), Vec4d::default());
+
+    assert!(Vec3d::new(1., 2., 3.).is_finite());
+    assert!(!Vec3d::new(1., f64::NAN, 3.).is_finite());
+    assert!(Vec4f::new(0., 0., 0., f32::NAN).is_nan());
+    assert!(!Vec2f::new(f32::INFINITY, 0.).is_nan());
+}
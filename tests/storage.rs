@@ -0,0 +1,53 @@
+use rustww::{Error, ErrorKind, idb::IdbStore, storage::{KvStore, MemoryStore, Storage, Migrations}};
+use wasm_bindgen_test::{wasm_bindgen_test};
+
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+async fn roundtrip<S: KvStore> (store: &S) {
+    store.set("rustww:test", &vec![1u32, 2, 3]).await.unwrap();
+    assert_eq!(store.get::<Vec<u32>>("rustww:test").await.unwrap(), Some(vec![1, 2, 3]));
+    assert!(store.iter::<serde_json::Value>().await.unwrap().any(|(key, _)| key == "rustww:test"));
+
+    store.remove("rustww:test").await.unwrap();
+    assert_eq!(store.get::<Vec<u32>>("rustww:test").await.unwrap(), None);
+}
+
+#[wasm_bindgen_test]
+async fn kv_stores () {
+    roundtrip(&MemoryStore::new()).await;
+
+    // values stored by other scripts aren't necessarily JSON
+    let local = Storage::local().unwrap().unwrap();
+    rustww::web_sys::window().unwrap().local_storage().unwrap().unwrap().set_item("rustww:foreign", "not json").unwrap();
+    roundtrip(&local).await;
+    local.remove("rustww:foreign").unwrap();
+
+    roundtrip(&IdbStore::open("rustww-test", "kv").await.unwrap()).await;
+}
+
+#[wasm_bindgen_test]
+async fn migrations () {
+    let store = MemoryStore::new();
+    let migrations = || Migrations::<MemoryStore>::new("version")
+        .add(2, |store| async move { store.set("b", &2).await })
+        .add(1, |store| async move { store.set("a", &1).await });
+
+    let report = migrations().dry_run(true).run(&store).await.unwrap();
+    assert_eq!(report.applied, [1, 2]);
+    assert!(store.is_empty());
+
+    let report = migrations().run(&store).await.unwrap();
+    assert_eq!((report.from, report.to), (0, 2));
+    assert_eq!(store.get::<u32>("b").await.unwrap(), Some(2));
+
+    // a failing migration leaves the store untouched
+    let result = migrations()
+        .add(3, |store| async move { store.remove("a").await })
+        .add(4, |_| async move { Err(Error::new(ErrorKind::Js, "failed")) })
+        .run(&store)
+        .await;
+
+    assert!(result.is_err());
+    assert_eq!(store.get::<u32>("a").await.unwrap(), Some(1));
+    assert_eq!(store.get::<u32>("version").await.unwrap(), Some(2));
+}
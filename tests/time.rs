@@ -0,0 +1,15 @@
+use std::time::Duration;
+use futures::StreamExt;
+use rustww::time::Interval;
+use wasm_bindgen_test::{wasm_bindgen_test};
+
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+async fn ticks () {
+    let mut ticks = Interval::ticks(Duration::from_millis(1)).unwrap();
+    assert_eq!(ticks.pending(), 0);
+
+    assert_eq!(ticks.by_ref().take(3).count().await, 3);
+    drop(ticks);
+}
@@ -0,0 +1,181 @@
+use std::{cell::RefCell, pin::Pin, task::{Context, Poll, Waker, RawWaker, RawWakerVTable}};
+use futures::{Stream, StreamExt, FutureExt};
+use rustww::{sync::Mutex, utils::{LocalSender, LocalReceiver, local_channel, Transfer}, diagnostics, time::Interval};
+use wasm_bindgen_test::{wasm_bindgen_test};
+
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+thread_local! {
+    static SENDER: RefCell<Option<LocalSender<u32>>> = RefCell::new(None);
+    static RECEIVER: RefCell<Option<LocalReceiver<u32>>> = RefCell::new(None);
+    static RECEIVED: RefCell<Vec<Poll<Option<u32>>>> = RefCell::new(Vec::new());
+}
+
+/// Builds a waker that calls `f` every time it's woken
+fn waker (f: fn ()) -> Waker {
+    unsafe fn clone_raw (ptr: *const ()) -> RawWaker { RawWaker::new(ptr, &VTABLE) }
+    unsafe fn wake_raw (ptr: *const ()) { core::mem::transmute::<*const (), fn ()>(ptr)() }
+    unsafe fn drop_raw (_: *const ()) {}
+    static VTABLE: RawWakerVTable = RawWakerVTable::new(clone_raw, wake_raw, wake_raw, drop_raw);
+
+    return unsafe { Waker::from_raw(RawWaker::new(f as *const (), &VTABLE)) }
+}
+
+/// Re-entrant send: the receiver's waker sends another value through the channel
+#[wasm_bindgen_test]
+fn send_from_waker () {
+    let (send, mut recv) = local_channel::<u32>();
+    SENDER.with(|x| *x.borrow_mut() = Some(send.clone()));
+
+    let waker = waker(|| SENDER.with(|x| {
+        if let Some(send) = x.borrow_mut().take() {
+            send.try_send(2).unwrap();
+        }
+    }));
+    let mut cx = Context::from_waker(&waker);
+
+    assert_eq!(recv.poll_next_unpin(&mut cx), Poll::Pending);
+    send.try_send(1).unwrap();
+
+    assert_eq!(recv.poll_next_unpin(&mut cx), Poll::Ready(Some(1)));
+    assert_eq!(recv.poll_next_unpin(&mut cx), Poll::Ready(Some(2)));
+    drop(send);
+    assert_eq!(recv.poll_next_unpin(&mut cx), Poll::Ready(None));
+}
+
+/// Re-entrant receive: the receiver is polled from inside it's own waker, while a value is being sent
+#[wasm_bindgen_test]
+fn poll_from_waker () {
+    let (send, mut recv) = local_channel::<u32>();
+
+    let waker = waker(|| RECEIVER.with(|x| {
+        if let Some(ref mut recv) = *x.borrow_mut() {
+            let mut cx = Context::from_waker(futures::task::noop_waker_ref());
+            let result = recv.poll_next_unpin(&mut cx);
+            RECEIVED.with(|x| x.borrow_mut().push(result));
+        }
+    }));
+
+    assert_eq!(recv.poll_next_unpin(&mut Context::from_waker(&waker)), Poll::Pending);
+    RECEIVER.with(|x| *x.borrow_mut() = Some(recv));
+
+    send.try_send(1).unwrap();
+    RECEIVED.with(|x| assert_eq!(*x.borrow(), [Poll::Ready(Some(1))]));
+    RECEIVER.with(|x| x.borrow_mut().take());
+}
+
+/// A stream that sends a value through the channel from inside it's `poll_next`, whenever the channel is empty
+struct Refill {
+    send: LocalSender<u32>,
+    recv: LocalReceiver<u32>,
+    next: u32
+}
+
+impl Stream for Refill {
+    type Item = u32;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if let Poll::Ready(x) = self.recv.poll_next_unpin(cx) {
+            return Poll::Ready(x)
+        }
+
+        // wakes the waker registered just above, while still inside `poll_next`
+        let next = self.next;
+        self.next += 1;
+        self.send.try_send(next).unwrap();
+        return self.recv.poll_next_unpin(cx)
+    }
+}
+
+#[wasm_bindgen_test]
+async fn send_from_poll_next () {
+    let (send, recv) = local_channel::<u32>();
+    let stream = Refill { send, recv, next: 0 };
+    assert_eq!(stream.take(3).collect::<Vec<_>>().await, [0, 1, 2]);
+}
+
+#[wasm_bindgen_test]
+fn mutex_wakes_from_guard_drop () {
+    let mutex = Mutex::new(0);
+    let mut guard = mutex.try_lock().unwrap();
+    assert!(mutex.try_lock().is_none());
+
+    let waker = waker(|| {});
+    let mut lock = mutex.lock();
+    assert!(lock.poll_unpin(&mut Context::from_waker(&waker)).is_pending());
+
+    *guard += 1;
+    drop(guard);
+
+    match lock.poll_unpin(&mut Context::from_waker(&waker)) {
+        Poll::Ready(guard) => assert_eq!(*guard, 1),
+        Poll::Pending => panic!("the mutex should be unlocked")
+    }
+}
+
+#[wasm_bindgen_test]
+fn diagnostics_track_intervals () {
+    let live = || diagnostics::dump().into_iter()
+        .find(|x| x.name == "time::Interval")
+        .map_or(0, |x| x.live);
+
+    let before = live();
+    let interval = Interval::new(std::time::Duration::from_secs(60), || {}).unwrap();
+    assert_eq!(live(), before + 1);
+
+    drop(interval);
+    assert_eq!(live(), before);
+}
+
+#[wasm_bindgen_test]
+fn transfer_detaches_buffer () {
+    let channel = rustww::web_sys::MessageChannel::new().unwrap();
+    let msg = Transfer::bytes(&[1, 2, 3, 4]);
+    assert_eq!(msg.value().byte_length(), 4);
+
+    msg.post(&channel.port1()).unwrap();
+    assert_eq!(msg.value().byte_length(), 0);
+}
+
+#[wasm_bindgen_test]
+fn uuid_v4 () {
+    let uuid = rustww::random::uuid_v4().unwrap();
+    assert_eq!(uuid.len(), 36);
+    assert_eq!(uuid.as_bytes()[14], b'4');
+    assert!(matches!(uuid.as_bytes()[19], b'8' | b'9' | b'a' | b'b'));
+    assert_ne!(uuid, rustww::random::uuid_v4().unwrap());
+}
+
+#[wasm_bindgen_test]
+fn ordered_ids () {
+    use rustww::ids;
+
+    let uuids = (0..100).map(|_| ids::uuid_v7().unwrap()).collect::<Vec<_>>();
+    assert!(uuids.windows(2).all(|x| x[0] < x[1]));
+    assert!(uuids.iter().all(|x| x.version() == 7));
+
+    let ulids = (0..100).map(|_| ids::ulid().unwrap()).collect::<Vec<_>>();
+    assert!(ulids.windows(2).all(|x| x[0] < x[1] && x[0].to_string() < x[1].to_string()));
+    assert_eq!(ulids[0].to_string().len(), 26);
+}
+
+#[wasm_bindgen_test]
+async fn signals_coalesce () {
+    use rustww::reactive::{Signal, Memo, Reactive};
+
+    let signal = Signal::new(1u32);
+    let parity = Memo::new(&signal, |x| x % 2);
+    let mut changes = signal.subscribe();
+    let mut parities = parity.subscribe();
+
+    signal.set(2);
+    signal.update(|x| *x += 2);
+    assert_eq!(changes.next().await, Some(4));
+    assert!(changes.is_empty());
+    assert_eq!(parities.next().await, Some(0));
+
+    signal.set(6);
+    assert_eq!(changes.next().await, Some(6));
+    assert_eq!(parity.get(), 0);
+    assert!(parities.is_empty());
+}